@@ -0,0 +1,95 @@
+// capture saves datagrams as golden packets for tests/golden.rs
+// usage: cargo run --example capture -- <f1_2020|f1_2022|dirt_rally2> [address]
+// F1: the first datagram of every packet type is saved, Dirt: a single datagram is saved
+// then add a test checking the fields of each new capture to tests/golden.rs
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cm_telemetry::dirt::rally2::DirtRally2;
+use cm_telemetry::f1::{f1_2020::F1_2020, f1_2022::F1_2022};
+use cm_telemetry::schema::TelemetrySchema;
+use cm_telemetry::{TelemetryEvent, TelemetryPacket, TelemetryServer};
+
+// Raw keeps the datagram as received
+struct Raw(Vec<u8>);
+
+impl TelemetryEvent for Raw {
+    fn from_packet(packet: &TelemetryPacket) -> Result<Raw, Box<dyn Error>> {
+        Ok(Raw(packet.to_vec()))
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let format = args.next().unwrap_or_default();
+    let address = args.next().unwrap_or_else(|| "127.0.0.1:20777".to_string());
+
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data")
+        .join(&format);
+    fs::create_dir_all(&directory).expect("failed to create the captures directory");
+
+    let server = TelemetryServer::<Raw>::new(&address).expect("failed to bind to address");
+    println!("listening on {}, saving {} captures...", address, format);
+
+    let mut saved = HashSet::new();
+    loop {
+        let Raw(packet) = match server.next() {
+            Ok(packet) => packet,
+            Err(e) => {
+                println!("error: {:?}", e);
+                continue;
+            }
+        };
+
+        let capture = match format.as_str() {
+            "f1_2020" => f1_capture::<F1_2020>(&packet, &mut saved, |e| e.header().packet_id),
+            "f1_2022" => f1_capture::<F1_2022>(&packet, &mut saved, |e| e.header().packet_id),
+            "dirt_rally2" => DirtRally2::from_packet(&packet)
+                .ok()
+                .map(|_| "stage".to_string()),
+            _ => {
+                println!(
+                    "unknown format {:?}, use f1_2020, f1_2022 or dirt_rally2",
+                    format
+                );
+                return;
+            }
+        };
+
+        if let Some(name) = capture {
+            let path: PathBuf = directory.join(&name);
+            fs::write(path.with_extension("bin"), &packet).expect("failed to save capture");
+            println!("saved {}", path.display());
+            if format == "dirt_rally2" {
+                return;
+            }
+        }
+    }
+}
+
+// f1_capture names the first datagram of each packet id, ie: "06_car_telemetry"
+fn f1_capture<T: TelemetryEvent + TelemetrySchema>(
+    packet: &[u8],
+    saved: &mut HashSet<u8>,
+    packet_id: impl Fn(&T) -> u8,
+) -> Option<String> {
+    let event = T::from_packet(packet).ok()?;
+    let id = packet_id(&event);
+    if !saved.insert(id) {
+        return None;
+    }
+
+    let packet_name = T::schema().packet(id)?.name;
+    let mut name = format!("{:02}_", id);
+    for (i, c) in packet_name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            name.push('_');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    Some(name)
+}
@@ -17,7 +17,7 @@ fn main() {
         match event.unwrap() {
             F1_2020::Motion(data) => println!(
                 "Motion packet received: {:?}",
-                data.player_data().world_position
+                data.player_data().map(|car| &car.world_position)
             ),
             F1_2020::Session(data) => println!(
                 "Session packet received: {:?}, {:?}, {:?}, {:?}",
@@ -25,20 +25,20 @@ fn main() {
             ),
             F1_2020::LapData(data) => println!(
                 "LapData packet received: {:?}, {:?}, {:?}",
-                data.player_data().current_lap_time,
-                data.player_data().pit_status,
-                data.player_data().driver_status,
+                data.player_data().map(|car| &car.current_lap_time),
+                data.player_data().map(|car| &car.pit_status),
+                data.player_data().map(|car| &car.driver_status),
             ),
             F1_2020::Event(data) => {
                 println!("Event packet received: {:?}", data.event_data_details)
             }
             F1_2020::Participants(data) => println!(
                 "Participants packet received: {:?}",
-                data.player_data().name
+                data.player_data().map(|car| &car.name)
             ),
             F1_2020::CarSetup(data) => println!(
                 "CarSetups packet received: {:?}",
-                data.player_data().fuel_load
+                data.player_data().map(|car| &car.fuel_load)
             ),
             F1_2020::CarTelemetry(data) => println!(
                 "CarTelemtry packet received: {:?}, {:?}",
@@ -47,7 +47,7 @@ fn main() {
             ),
             F1_2020::CarStatus(data) => println!(
                 "CarStatus packet received: {:?}",
-                data.player_data().drs_activation_distance
+                data.player_data().map(|car| &car.drs_activation_distance)
             ),
             F1_2020::FinalClassification(data) => println!(
                 "FinalClassification packet received: {:?}",
@@ -0,0 +1,62 @@
+#ifndef CM_TELEMETRY_H
+#define CM_TELEMETRY_H
+
+/* Generated with cbindgen from src/capi.rs, do not edit by hand */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// CmGame selects the decoder of a CmTelemetry handle, its values are the game argument of
+// cm_telemetry_open (taken as an integer: any other value gets NULL back)
+typedef enum CmGame {
+  CM_GAME_F1_2020 = 0,
+  CM_GAME_F1_2022 = 1,
+  CM_GAME_DIRT_RALLY2 = 2,
+} CmGame;
+
+// CmTelemetry is the opaque handle returned by cm_telemetry_open
+typedef struct CmTelemetry CmTelemetry;
+
+// CmEventCallback receives every decoded event as a NUL terminated JSON document (and its
+// length), the document is only valid during the call. Returning false stops cm_telemetry_run
+typedef bool (*CmEventCallback)(const char *json, size_t len, void *user_data);
+
+// cm_telemetry_open listens for UDP packets of the given game (a CmGame value) on address
+// (ie: "0.0.0.0:20777"), returns NULL when the game is unknown or the address is invalid
+// or can not be bound
+//
+// # Safety
+// address must be NULL or a NUL terminated string
+CmTelemetry *cm_telemetry_open(const char *address, uint32_t game);
+
+// cm_telemetry_next_json blocks until the next event decodes and copies it as a NUL terminated
+// JSON document into buffer. Returns the length of the document (without the NUL), -1 on error
+// (see cm_telemetry_last_error). When the length is len or more nothing was copied: the event
+// is kept for the next call, retry with a buffer of at least the returned length + 1
+//
+// # Safety
+// handle must come from cm_telemetry_open, buffer must be valid for len bytes
+intptr_t cm_telemetry_next_json(CmTelemetry *handle, char *buffer, size_t len);
+
+// cm_telemetry_run blocks calling callback with every event that decodes (and user_data) until
+// the callback returns false (returns 0) or the socket fails (returns -1)
+//
+// # Safety
+// handle must come from cm_telemetry_open, callback must be safe to call with user_data
+int cm_telemetry_run(CmTelemetry *handle, CmEventCallback callback, void *user_data);
+
+// cm_telemetry_last_error returns the message of the last error of handle, NULL when none.
+// The message is owned by handle and valid until the next call using it
+//
+// # Safety
+// handle must be NULL or come from cm_telemetry_open
+const char *cm_telemetry_last_error(const CmTelemetry *handle);
+
+// cm_telemetry_close closes the socket and frees handle
+//
+// # Safety
+// handle must be NULL or come from cm_telemetry_open, and not be used afterwards
+void cm_telemetry_close(CmTelemetry *handle);
+
+#endif /* CM_TELEMETRY_H */
@@ -0,0 +1,4 @@
+fn main() {
+    // node addons leave the N-API symbols undefined, resolved when loaded by node
+    napi_build::setup();
+}
@@ -0,0 +1,30 @@
+// EventEmitter wrapper of the addon built by the node crate, emits every packet under the name
+// of its type (ie: "CarTelemetry") and as "packet", socket errors are emitted as "error"
+const { EventEmitter } = require('events');
+
+const addon = require(process.env.CM_TELEMETRY_ADDON || './cm_telemetry.node');
+
+class TelemetryServer extends EventEmitter {
+  constructor(address, game) {
+    super();
+    this.server = new addon.TelemetryServer(address, game);
+  }
+
+  listen() {
+    this.server.listen((err, name, event) => {
+      if (err) {
+        this.emit('error', err);
+        return;
+      }
+      this.emit(name, event);
+      this.emit('packet', name, event);
+    });
+    return this;
+  }
+
+  close() {
+    this.server.close();
+  }
+}
+
+module.exports = { TelemetryServer };
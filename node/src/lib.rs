@@ -0,0 +1,111 @@
+use std::sync::Mutex;
+
+use napi::bindgen_prelude::spawn;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::tokio::task::JoinHandle;
+use napi::JsFunction;
+use napi_derive::napi;
+use serde::Serialize;
+use serde_json::Value;
+
+use cm_telemetry::dirt::rally2::DirtRally2;
+use cm_telemetry::f1::{f1_2020::F1_2020, f1_2022::F1_2022};
+use cm_telemetry::{AsyncTelemetryServer, TelemetryEvent};
+
+// receives the name of the packet type and the packet as a plain object, or an error
+type Listener = ThreadsafeFunction<(String, Value), ErrorStrategy::CalleeHandled>;
+
+/// TelemetryServer is the Node.js class receiving the packets of a game, listen calls a callback
+/// with (err, name, event) for every packet, name being the packet type (ie: "CarTelemetry").
+/// node/index.js wraps it into an EventEmitter
+#[napi(js_name = "TelemetryServer")]
+pub struct NodeTelemetryServer {
+    address: String,
+    game: String,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+#[napi]
+impl NodeTelemetryServer {
+    /// new receives the packets of game ("f1_2020", "f1_2022" or "dirt_rally2") on address,
+    /// nothing is bound before listen
+    #[napi(constructor)]
+    pub fn new(address: String, game: String) -> napi::Result<NodeTelemetryServer> {
+        match game.as_str() {
+            "f1_2020" | "f1_2022" | "dirt_rally2" => Ok(NodeTelemetryServer {
+                address,
+                game,
+                task: Mutex::new(None),
+            }),
+            _ => Err(napi::Error::from_reason(format!("Unknown game: {}", game))),
+        }
+    }
+
+    /// listen binds the address and calls callback with every packet that decodes,
+    /// a bind or socket error is passed to callback and stops the server
+    #[napi]
+    pub fn listen(&self, callback: JsFunction) -> napi::Result<()> {
+        let listener: Listener = callback.create_threadsafe_function(0, |ctx| {
+            let (name, event) = ctx.value;
+            Ok(vec![Value::String(name), event])
+        })?;
+
+        let address = self.address.clone();
+        let task = match self.game.as_str() {
+            "f1_2020" => spawn(serve::<F1_2020>(address, listener, None)),
+            "f1_2022" => spawn(serve::<F1_2022>(address, listener, None)),
+            _ => spawn(serve::<DirtRally2>(address, listener, Some("DirtRally2"))),
+        };
+        if let Some(previous) = self.task.lock().unwrap().replace(task) {
+            previous.abort();
+        }
+        Ok(())
+    }
+
+    /// close stops the server and releases the callback
+    #[napi]
+    pub fn close(&self) {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+}
+
+// serve sends the packets of T to listener, named after their variant (serialized externally
+// tagged: {"CarTelemetry": {...}}) or after name for games sending a single packet type
+async fn serve<T: TelemetryEvent + Serialize + Send + 'static>(
+    address: String,
+    listener: Listener,
+    name: Option<&'static str>,
+) {
+    let server = match AsyncTelemetryServer::<T>::new(&address).await {
+        Ok(server) => server,
+        Err(e) => {
+            listener.call(Err(error(e)), ThreadsafeFunctionCallMode::NonBlocking);
+            return;
+        }
+    };
+
+    loop {
+        let event = match server.next().await {
+            Ok(event) => event,
+            Err(e) if e.is::<std::io::Error>() => {
+                listener.call(Err(error(e)), ThreadsafeFunctionCallMode::NonBlocking);
+                return;
+            }
+            Err(_) => continue, // packets that do not decode are skipped
+        };
+        let named = match (serde_json::to_value(&event), name) {
+            (Ok(event), Some(name)) => Some((String::from(name), event)),
+            (Ok(Value::Object(object)), None) => object.into_iter().next(),
+            _ => None,
+        };
+        if let Some(named) = named {
+            listener.call(Ok(named), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+    }
+}
+
+fn error<E: std::fmt::Display>(e: E) -> napi::Error {
+    napi::Error::from_reason(e.to_string())
+}
@@ -0,0 +1,171 @@
+use alloc::{collections::VecDeque, vec::Vec};
+
+use crate::f1::f1_2022::{LapData, ResultStatus};
+use crate::f1::util::CarIndex;
+
+// how long the distance history of every car is kept, gaps above it can't be computed
+const HISTORY_SECONDS: f32 = 10.0;
+
+/// BattleConfig sets what counts as a battle
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BattleConfig {
+    pub max_gap: f32, // seconds between the two cars
+    pub min_laps: u8, // laps the cars have to stay within max_gap
+    pub drs_gap: f32, // seconds under which the attacker is in DRS range
+}
+
+impl Default for BattleConfig {
+    fn default() -> Self {
+        BattleConfig {
+            max_gap: 1.5,
+            min_laps: 2,
+            drs_gap: 1.0,
+        }
+    }
+}
+
+/// Battle is a car (attacker) fighting the one right ahead of it on track (defender)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Battle {
+    pub attacker: CarIndex,
+    pub defender: CarIndex,
+    pub position: u8, // position fought for (the defender's)
+    pub gap: f32,     // seconds
+    pub start_lap: u8,
+    pub laps: u8,
+    pub drs_range: bool,
+}
+
+/// DirectorHint suggests a car to put the camera on, higher scores are more interesting
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DirectorHint {
+    pub car: CarIndex,
+    pub score: f32,
+    pub battle: Battle,
+}
+
+/// BattleDetector follows the gaps between consecutive cars over "F1 22" LapData packets
+/// gaps are measured as the time since the car ahead went through the same point of the track
+#[derive(Debug, Default, Clone)]
+pub struct BattleDetector {
+    config: BattleConfig,
+    session_uid: u64,
+    history: Vec<VecDeque<(f32, f32)>>, // total distance and session time of every car
+    battles: Vec<Battle>,
+}
+
+impl BattleDetector {
+    pub fn new(config: BattleConfig) -> BattleDetector {
+        BattleDetector {
+            config,
+            ..BattleDetector::default()
+        }
+    }
+
+    /// observe feeds a LapData packet, a packet from another session resets the detector
+    pub fn observe(&mut self, data: &LapData) {
+        let now = data.header.session_time;
+        if data.header.session_uid != self.session_uid {
+            self.session_uid = data.header.session_uid;
+            self.history.clear();
+            self.battles.clear();
+        }
+        self.history.resize(data.laps.len(), VecDeque::new());
+
+        let mut running = Vec::new();
+        for (car, lap) in data.laps.iter().enumerate() {
+            let history = &mut self.history[car];
+            if lap.result_status != ResultStatus::Active {
+                history.clear();
+                continue;
+            }
+            // a flashback rewinds the distances, forget what happened after it
+            while matches!(history.back(), Some((distance, _)) if *distance >= lap.total_distance) {
+                history.pop_back();
+            }
+            history.push_back((lap.total_distance, now));
+            while matches!(history.front(), Some((_, time)) if *time < now - HISTORY_SECONDS) {
+                history.pop_front();
+            }
+            running.push((lap.car_position, CarIndex(car as u8)));
+        }
+        running.sort_unstable();
+
+        let mut battles = Vec::new();
+        for pair in running.windows(2) {
+            let ((position, defender), (_, attacker)) = (pair[0], pair[1]);
+            let (attacker_lap, history) = match (
+                attacker.index().and_then(|index| data.laps.get(index)),
+                defender.index().and_then(|index| self.history.get(index)),
+            ) {
+                (Some(lap), Some(history)) => (lap, history),
+                _ => continue,
+            };
+            let gap = match passed_at(history, attacker_lap.total_distance) {
+                Some(time) if now - time <= self.config.max_gap => now - time,
+                _ => continue,
+            };
+
+            let start_lap = self
+                .battles
+                .iter()
+                .find(|b| b.attacker == attacker && b.defender == defender)
+                .map(|b| b.start_lap)
+                .unwrap_or(attacker_lap.current_lap_number);
+
+            battles.push(Battle {
+                attacker,
+                defender,
+                position,
+                gap,
+                start_lap,
+                laps: attacker_lap.current_lap_number.saturating_sub(start_lap),
+                drs_range: gap <= self.config.drs_gap,
+            });
+        }
+        self.battles = battles;
+    }
+
+    /// battles returns the battles that lasted at least min_laps, ordered by position
+    pub fn battles(&self) -> impl Iterator<Item = &Battle> {
+        let min_laps = self.config.min_laps;
+        self.battles.iter().filter(move |b| b.laps >= min_laps)
+    }
+
+    /// director ranks the attackers of the current battles, most interesting first
+    /// closer gaps, positions near the front, longer battles and DRS range score higher
+    pub fn director(&self) -> Vec<DirectorHint> {
+        let mut hints: Vec<DirectorHint> = self
+            .battles()
+            .map(|battle| DirectorHint {
+                car: battle.attacker,
+                score: score(battle, &self.config),
+                battle: *battle,
+            })
+            .collect();
+        hints.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hints
+    }
+}
+
+fn score(battle: &Battle, config: &BattleConfig) -> f32 {
+    let closeness = 1.0 - battle.gap / config.max_gap; // 0..1
+    let position = 22u8.saturating_sub(battle.position) as f32 / 21.0; // ~1 for the lead
+    let duration = battle.laps.min(10) as f32 / 10.0;
+    let drs = if battle.drs_range { 1.0 } else { 0.0 };
+
+    4.0 * closeness + 3.0 * position + 2.0 * duration + drs
+}
+
+/// passed_at returns the session time a car went through the given total distance
+fn passed_at(history: &VecDeque<(f32, f32)>, distance: f32) -> Option<f32> {
+    let next = history.partition_point(|(d, _)| *d < distance);
+    let (after_distance, after_time) = *history.get(next)?;
+    let (before_distance, before_time) = *history.get(next.checked_sub(1)?)?;
+
+    let ratio = (distance - before_distance) / (after_distance - before_distance);
+    Some(before_time + ratio * (after_time - before_time))
+}
@@ -0,0 +1,254 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::analysis::haptics::WheelPosition;
+use crate::f1::f1_2022::{CarDamageData, F1_2022};
+use crate::f1::util::CarIndex;
+
+/// DamageComponent is a part of the car reporting damage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum DamageComponent {
+    Tyre(WheelPosition),
+    Brake(WheelPosition),
+    FrontLeftWing,
+    FrontRightWing,
+    RearWing,
+    Floor,
+    Diffuser,
+    Sidepod,
+    Gearbox,
+    Engine,
+}
+
+impl fmt::Display for DamageComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wheel = |wheel: &WheelPosition| match wheel {
+            WheelPosition::RearLeft => "rear left",
+            WheelPosition::RearRight => "rear right",
+            WheelPosition::FrontLeft => "front left",
+            WheelPosition::FrontRight => "front right",
+        };
+        match self {
+            DamageComponent::Tyre(position) => write!(f, "{} tyre", wheel(position)),
+            DamageComponent::Brake(position) => write!(f, "{} brake", wheel(position)),
+            DamageComponent::FrontLeftWing => f.write_str("front left wing"),
+            DamageComponent::FrontRightWing => f.write_str("front right wing"),
+            DamageComponent::RearWing => f.write_str("rear wing"),
+            DamageComponent::Floor => f.write_str("floor"),
+            DamageComponent::Diffuser => f.write_str("diffuser"),
+            DamageComponent::Sidepod => f.write_str("sidepod"),
+            DamageComponent::Gearbox => f.write_str("gearbox"),
+            DamageComponent::Engine => f.write_str("engine"),
+        }
+    }
+}
+
+/// Fault is an on/off failure of a car system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Fault {
+    Drs,
+    Ers,
+    EngineBlown,
+    EngineSeized,
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Fault::Drs => "DRS fault",
+            Fault::Ers => "ERS fault",
+            Fault::EngineBlown => "engine blown",
+            Fault::EngineSeized => "engine seized",
+        };
+        f.write_str(name)
+    }
+}
+
+/// DamageEvent is a change of the damage of a car
+/// displayed as eg: "front left wing +15% car 4", "DRS fault car 11"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum DamageEvent {
+    Damage {
+        car: CarIndex,
+        component: DamageComponent,
+        from: u8, // percentage
+        to: u8,   // percentage
+    },
+    Fault {
+        car: CarIndex,
+        fault: Fault,
+        active: bool, // false when repaired
+    },
+}
+
+impl fmt::Display for DamageEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DamageEvent::Damage {
+                car,
+                component,
+                from,
+                to,
+            } => write!(
+                f,
+                "{} {:+}% car {}",
+                component,
+                *to as i16 - *from as i16,
+                car
+            ),
+            DamageEvent::Fault { car, fault, active } if *active => {
+                write!(f, "{} car {}", fault, car)
+            }
+            DamageEvent::Fault { car, fault, .. } => write!(f, "{} cleared car {}", fault, car),
+        }
+    }
+}
+
+const COMPONENTS: [DamageComponent; 16] = [
+    DamageComponent::Tyre(WheelPosition::RearLeft),
+    DamageComponent::Tyre(WheelPosition::RearRight),
+    DamageComponent::Tyre(WheelPosition::FrontLeft),
+    DamageComponent::Tyre(WheelPosition::FrontRight),
+    DamageComponent::Brake(WheelPosition::RearLeft),
+    DamageComponent::Brake(WheelPosition::RearRight),
+    DamageComponent::Brake(WheelPosition::FrontLeft),
+    DamageComponent::Brake(WheelPosition::FrontRight),
+    DamageComponent::FrontLeftWing,
+    DamageComponent::FrontRightWing,
+    DamageComponent::RearWing,
+    DamageComponent::Floor,
+    DamageComponent::Diffuser,
+    DamageComponent::Sidepod,
+    DamageComponent::Gearbox,
+    DamageComponent::Engine,
+];
+
+const FAULTS: [Fault; 4] = [
+    Fault::Drs,
+    Fault::Ers,
+    Fault::EngineBlown,
+    Fault::EngineSeized,
+];
+
+fn damage_values(damage: &CarDamageData) -> [u8; 16] {
+    let (tyres, brakes, wings) = (
+        &damage.tyres_damage,
+        &damage.brakes_damage,
+        &damage.wing_damage,
+    );
+    [
+        tyres.rear_left,
+        tyres.rear_right,
+        tyres.front_left,
+        tyres.front_right,
+        brakes.rear_left,
+        brakes.rear_right,
+        brakes.front_left,
+        brakes.front_right,
+        wings.front_left,
+        wings.front_right,
+        wings.rear,
+        damage.floor_damage,
+        damage.diffuser_damage,
+        damage.sidepod_damage,
+        damage.gear_box_damage,
+        damage.engine_damage,
+    ]
+}
+
+fn fault_values(damage: &CarDamageData) -> [bool; 4] {
+    [
+        damage.drs_fault,
+        damage.ers_fault,
+        damage.engine_blown,
+        damage.engine_seized,
+    ]
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CarDamage {
+    reported: [u8; 16],
+    faults: [bool; 4],
+}
+
+/// DamageTracker diffs "F1 22" CarDamage packets and emits what changed for every car
+/// damage is reported once it moved at least min_change points since the last report,
+/// so slow changes (ie: brakes) are not lost; tyre and engine wear are left out
+#[derive(Debug, Clone)]
+pub struct DamageTracker {
+    min_change: u8,
+    cars: Vec<Option<CarDamage>>,
+}
+
+impl DamageTracker {
+    pub fn new(min_change: u8) -> DamageTracker {
+        DamageTracker {
+            min_change: min_change.max(1),
+            cars: Vec::new(),
+        }
+    }
+
+    /// observe feeds a packet and returns the changes of CarDamage packets
+    /// the first packet only sets the initial damage
+    pub fn observe(&mut self, event: &F1_2022) -> Vec<DamageEvent> {
+        let data = match event {
+            F1_2022::CarDamage(data) => data,
+            _ => return Vec::new(),
+        };
+
+        self.cars.resize(data.car_damage_data.len(), None);
+        let mut events = Vec::new();
+        for (car, (damage, state)) in data.car_damage_data.iter().zip(&mut self.cars).enumerate() {
+            let values = damage_values(damage);
+            let faults = fault_values(damage);
+            let state = match state {
+                Some(state) => state,
+                None => {
+                    *state = Some(CarDamage {
+                        reported: values,
+                        faults,
+                    });
+                    continue;
+                }
+            };
+
+            for (i, &value) in values.iter().enumerate() {
+                let reported = state.reported[i];
+                if value.abs_diff(reported) >= self.min_change {
+                    events.push(DamageEvent::Damage {
+                        car: CarIndex(car as u8),
+                        component: COMPONENTS[i],
+                        from: reported,
+                        to: value,
+                    });
+                    state.reported[i] = value;
+                }
+            }
+            for (i, &active) in faults.iter().enumerate() {
+                if active != state.faults[i] {
+                    events.push(DamageEvent::Fault {
+                        car: CarIndex(car as u8),
+                        fault: FAULTS[i],
+                        active,
+                    });
+                }
+            }
+            state.faults = faults;
+        }
+        events
+    }
+
+    /// reset forgets the damage of every car (ie: when a new session starts)
+    pub fn reset(&mut self) {
+        self.cars.clear();
+    }
+}
+
+impl Default for DamageTracker {
+    fn default() -> Self {
+        DamageTracker::new(1)
+    }
+}
@@ -0,0 +1,131 @@
+use alloc::vec::Vec;
+
+use crate::f1::f1_2022::{CarStatusData, F1_2022};
+use crate::f1::util::CarIndex;
+
+/// LapEnergy is the fuel and ERS usage of a car over one completed lap
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LapEnergy {
+    pub car: CarIndex,
+    pub lap: u8,
+    pub fuel_start: f32,         // kg
+    pub fuel_end: f32,           // kg
+    pub ers_store_start: f32,    // Joules
+    pub ers_store_end: f32,      // Joules
+    pub ers_deployed: f32,       // Joules
+    pub ers_harvested_mguk: f32, // Joules
+    pub ers_harvested_mguh: f32, // Joules
+}
+
+impl LapEnergy {
+    /// fuel_used returns the fuel burnt during the lap (kg)
+    pub fn fuel_used(&self) -> f32 {
+        self.fuel_start - self.fuel_end
+    }
+
+    /// ers_harvested returns the energy harvested by both motor generator units (Joules)
+    pub fn ers_harvested(&self) -> f32 {
+        self.ers_harvested_mguk + self.ers_harvested_mguh
+    }
+
+    /// ers_balance returns the energy harvested minus the energy deployed (Joules)
+    pub fn ers_balance(&self) -> f32 {
+        self.ers_harvested() - self.ers_deployed
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct CarLedger {
+    lap: u8,
+    current: Option<LapEnergy>, // lap in progress, updated with every CarStatus
+    laps: Vec<LapEnergy>,
+}
+
+/// EnergyLedger snapshots the fuel and ERS usage of every car at each lap boundary
+/// from "F1 22" LapData and CarStatus packets, oldest lap first
+#[derive(Debug, Default, Clone)]
+pub struct EnergyLedger {
+    session_uid: u64,
+    cars: Vec<CarLedger>,
+}
+
+impl EnergyLedger {
+    pub fn new() -> EnergyLedger {
+        EnergyLedger::default()
+    }
+
+    /// observe feeds a packet, returns the laps completed by any car with this packet
+    pub fn observe(&mut self, event: &F1_2022) -> Vec<LapEnergy> {
+        let header = event.header();
+        if header.session_uid != self.session_uid {
+            self.session_uid = header.session_uid;
+            self.cars.clear();
+        }
+
+        let mut completed = Vec::new();
+        match event {
+            F1_2022::CarStatus(data) => {
+                self.cars
+                    .resize(data.car_status_data.len(), CarLedger::default());
+                for (car, (status, ledger)) in
+                    data.car_status_data.iter().zip(&mut self.cars).enumerate()
+                {
+                    update(CarIndex(car as u8), ledger, status);
+                }
+            }
+            F1_2022::LapData(data) => {
+                self.cars.resize(data.laps.len(), CarLedger::default());
+                for (lap, ledger) in data.laps.iter().zip(&mut self.cars) {
+                    let previous = core::mem::replace(&mut ledger.lap, lap.current_lap_number);
+                    if previous == lap.current_lap_number {
+                        continue;
+                    }
+
+                    // a flashback to a previous lap drops the laps after it
+                    ledger
+                        .laps
+                        .retain(|energy| energy.lap < lap.current_lap_number);
+                    let current = ledger.current.take();
+                    if let Some(energy) = current {
+                        if previous > 0 && lap.current_lap_number == previous + 1 {
+                            ledger.laps.push(energy);
+                            completed.push(energy);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        completed
+    }
+
+    /// laps returns the completed laps of a car, oldest first
+    pub fn laps(&self, car: CarIndex) -> &[LapEnergy] {
+        car.index()
+            .and_then(|index| self.cars.get(index))
+            .map(|ledger| ledger.laps.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// current returns the usage of a car so far on the lap in progress
+    pub fn current(&self, car: CarIndex) -> Option<&LapEnergy> {
+        self.cars.get(car.index()?)?.current.as_ref()
+    }
+}
+
+fn update(car: CarIndex, ledger: &mut CarLedger, status: &CarStatusData) {
+    let ers = &status.ers_data;
+    let energy = ledger.current.get_or_insert(LapEnergy {
+        car,
+        lap: ledger.lap,
+        fuel_start: status.fuel_in_tank,
+        ers_store_start: ers.stored_energy,
+        ..LapEnergy::default()
+    });
+    energy.fuel_end = status.fuel_in_tank;
+    energy.ers_store_end = ers.stored_energy;
+    energy.ers_deployed = ers.deployed_this_lap;
+    energy.ers_harvested_mguk = ers.harvested_this_lap_mguk;
+    energy.ers_harvested_mguh = ers.harvested_this_lap_mguh;
+}
@@ -0,0 +1,173 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::f1::f1_2022::{CarTelemetryData, F1_2022};
+use crate::f1::util::CarIndex;
+
+// below this speed (kph) the clutch slips and rpm / speed is not the gear ratio
+const MIN_SPEED: f32 = 20.0;
+// throttle (0..1) from which the car is considered accelerating flat out
+const FULL_THROTTLE: f32 = 0.95;
+// width (kph) of the speed buckets the acceleration is averaged over
+const SPEED_BUCKET: u16 = 5;
+
+/// GearRatio is the effective ratio of a gear, engine rpm per kph of road speed
+/// (gearbox, final drive and tyre size together)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GearRatio {
+    pub gear: u8,
+    pub rpm_per_kph: f32,
+    pub samples: u32,
+}
+
+/// ShiftPoint is the best moment to shift up from a gear: from this speed the next gear
+/// accelerates the car harder than the current one
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ShiftPoint {
+    pub gear: u8,   // gear to shift up from
+    pub speed: f32, // kph
+    pub rpm: f32,   // engine rpm in gear at that speed
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Average {
+    sum: f32,
+    samples: u32,
+}
+
+impl Average {
+    fn add(&mut self, value: f32) {
+        self.sum += value;
+        self.samples += 1;
+    }
+
+    fn value(&self) -> f32 {
+        self.sum / self.samples as f32
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    session_time: f32,
+    gear: i8,
+    speed: f32,
+}
+
+/// GearingAnalysis infers the gear ratios and shift points of a car from the gear, rpm and speed
+/// of "F1 22" CarTelemetry packets: ratios from rpm / speed, shift points from the full throttle
+/// acceleration of consecutive gears at the same speed. Both get better over a session
+#[derive(Debug, Clone)]
+pub struct GearingAnalysis {
+    car: CarIndex,
+    ratios: BTreeMap<u8, Average>,
+    acceleration: BTreeMap<(u8, u16), Average>, // kph/s by gear and speed bucket
+    previous: Option<Sample>,
+}
+
+impl GearingAnalysis {
+    /// new analyses the car at the given index (see Header::player_car_index)
+    pub fn new(car: CarIndex) -> GearingAnalysis {
+        GearingAnalysis {
+            car,
+            ratios: BTreeMap::new(),
+            acceleration: BTreeMap::new(),
+            previous: None,
+        }
+    }
+
+    /// observe feeds a packet, only CarTelemetry packets are used
+    pub fn observe(&mut self, event: &F1_2022) {
+        if let F1_2022::CarTelemetry(data) = event {
+            if let Some(telemetry) = data.car_data(self.car) {
+                self.add(data.header.session_time, telemetry);
+            }
+        }
+    }
+
+    fn add(&mut self, session_time: f32, telemetry: &CarTelemetryData) {
+        let gear = telemetry.gear.as_number();
+        let speed = telemetry.speed as f32;
+        let sample = Sample {
+            session_time,
+            gear,
+            speed,
+        };
+        let previous = self.previous.replace(sample);
+        if gear < 1 || speed < MIN_SPEED {
+            return;
+        }
+
+        self.ratios
+            .entry(gear as u8)
+            .or_default()
+            .add(telemetry.engine_rpm as f32 / speed);
+
+        let previous = match previous {
+            Some(previous) if previous.gear == gear => previous,
+            _ => return,
+        };
+        let elapsed = session_time - previous.session_time;
+        if telemetry.throttle < FULL_THROTTLE || elapsed <= 0.0 {
+            return;
+        }
+        let bucket = speed as u16 / SPEED_BUCKET;
+        self.acceleration
+            .entry((gear as u8, bucket))
+            .or_default()
+            .add((speed - previous.speed) / elapsed);
+    }
+
+    /// ratio returns the effective ratio of a gear, None before it was driven in
+    pub fn ratio(&self, gear: u8) -> Option<GearRatio> {
+        self.ratios.get(&gear).map(|average| GearRatio {
+            gear,
+            rpm_per_kph: average.value(),
+            samples: average.samples,
+        })
+    }
+
+    /// ratios returns the effective ratio of every gear driven in, lowest gear first
+    pub fn ratios(&self) -> Vec<GearRatio> {
+        self.ratios
+            .keys()
+            .filter_map(|&gear| self.ratio(gear))
+            .collect()
+    }
+
+    /// shift_points returns the best shift up point of every gear whose acceleration
+    /// and the one of the next gear were seen over a common speed range
+    pub fn shift_points(&self) -> Vec<ShiftPoint> {
+        self.ratios
+            .keys()
+            .filter_map(|&gear| self.shift_point(gear))
+            .collect()
+    }
+
+    /// shift_point returns the best shift up point from the given gear
+    pub fn shift_point(&self, gear: u8) -> Option<ShiftPoint> {
+        let ratio = self.ratio(gear)?;
+        let bucket = self
+            .acceleration
+            .range((gear, 0)..=(gear, u16::MAX))
+            .find(|&(&(_, bucket), current)| {
+                self.acceleration
+                    .get(&(gear + 1, bucket))
+                    .is_some_and(|next| next.value() >= current.value())
+            })
+            .map(|(&(_, bucket), _)| bucket)?;
+
+        let speed = (bucket * SPEED_BUCKET) as f32;
+        Some(ShiftPoint {
+            gear,
+            speed,
+            rpm: speed * ratio.rpm_per_kph,
+        })
+    }
+
+    /// reset forgets everything, ie: after a setup change
+    pub fn reset(&mut self) {
+        *self = GearingAnalysis::new(self.car);
+    }
+}
@@ -0,0 +1,224 @@
+use alloc::vec::Vec;
+
+use crate::dirt::rally2::DirtRally2;
+use crate::f1::f1_2022::{Surface, F1_2022};
+use crate::f1::util::WheelValue;
+
+/// WheelPosition identifies a corner of the car, in the order used by the games (RL, RR, FL, FR)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum WheelPosition {
+    RearLeft,
+    RearRight,
+    FrontLeft,
+    FrontRight,
+}
+
+const WHEELS: [WheelPosition; 4] = [
+    WheelPosition::RearLeft,
+    WheelPosition::RearRight,
+    WheelPosition::FrontLeft,
+    WheelPosition::FrontRight,
+];
+
+/// CueKind is what a haptic cue should make the driver feel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CueKind {
+    WheelSpin,
+    WheelLock,
+    Bump,             // a spike of suspension acceleration, ie: kerb strikes
+    Surface(Surface), // rolling over anything but tarmac (F1 only)
+}
+
+/// HapticCue is a single cue for a wheel, intensity is normalized between 0 and 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HapticCue {
+    pub wheel: WheelPosition,
+    pub kind: CueKind,
+    pub intensity: f32,
+}
+
+/// HapticsConfig holds the thresholds where cues start (intensity 0) and saturate (intensity 1)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HapticsConfig {
+    pub slip_threshold: f32, // slip ratio
+    pub slip_full_scale: f32,
+    pub bump_threshold: f32, // suspension acceleration, in the unit sent by the game
+    pub bump_full_scale: f32,
+    pub surface_full_speed: f32, // kph where surface cues reach their full intensity
+}
+
+impl Default for HapticsConfig {
+    fn default() -> Self {
+        HapticsConfig {
+            slip_threshold: 0.1,
+            slip_full_scale: 0.5,
+            bump_threshold: 2000.0,
+            bump_full_scale: 10000.0,
+            surface_full_speed: 100.0,
+        }
+    }
+}
+
+/// surface_roughness returns how much a surface should be felt compared to a rumble strip
+pub fn surface_roughness(surface: Surface) -> f32 {
+    match surface {
+        Surface::Tarmac | Surface::Unknown => 0.0,
+        Surface::RumbleStrip => 1.0,
+        Surface::Ridged => 0.8,
+        Surface::Cobblestone => 0.7,
+        Surface::Rock | Surface::Gravel => 0.6,
+        Surface::Mud | Surface::Sand => 0.4,
+        Surface::Grass => 0.3,
+        Surface::Water | Surface::Metal => 0.2,
+        Surface::Concrete => 0.1,
+    }
+}
+
+/// Haptics extracts haptic cues (slip, bumps and surfaces) from "F1 22" or "Dirt Rally 2.0"
+/// packets of the player car, meant to drive bass shakers
+#[derive(Debug, Default, Clone)]
+pub struct Haptics {
+    config: HapticsConfig,
+    speed: f32,                                   // kph
+    surfaces: [Surface; 4],                       // F1 only
+    suspension_velocity: Option<([f32; 4], f32)>, // Dirt only, with the packet time
+}
+
+impl Haptics {
+    pub fn new(config: HapticsConfig) -> Haptics {
+        Haptics {
+            config,
+            ..Haptics::default()
+        }
+    }
+
+    /// observe_f1 feeds a "F1 22" packet, returns the cues active on Motion packets
+    /// (CarTelemetry packets only update the speed and surfaces under each wheel)
+    pub fn observe_f1(&mut self, event: &F1_2022) -> Vec<HapticCue> {
+        match event {
+            F1_2022::CarTelemetry(data) => {
+                if let Some(telemetry) = data.player_data() {
+                    self.speed = telemetry.speed as f32;
+                    self.surfaces = wheels(&telemetry.surface_type);
+                }
+                Vec::new()
+            }
+            F1_2022::Motion(data) => {
+                let slip = wheels(&data.wheel_slip);
+                let acceleration = wheels(&data.suspension_acceleration);
+
+                let mut cues = self.slip_cues(slip);
+                cues.extend(self.bump_cues(acceleration));
+                cues.extend(self.surface_cues());
+                cues
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// observe_dirt feeds a "Dirt Rally 2.0" packet and returns the active cues
+    /// the game sends no slip ratio nor suspension acceleration, they are derived from wheel
+    /// speeds and from the suspension velocity of consecutive packets
+    pub fn observe_dirt(&mut self, event: &DirtRally2) -> Vec<HapticCue> {
+        let car = &event.car;
+        let time = event.session.track.time;
+        let wheels = car.wheels.as_array();
+
+        self.speed = car.speed_kph();
+        let speed = car.speed.abs().max(1.0);
+        let slip = wheels.map(|wheel| (wheel.wheel_velocity - car.speed) / speed);
+        let velocity = wheels.map(|wheel| wheel.suspension_velocity);
+
+        let mut cues = self.slip_cues(slip);
+        if let Some((previous, previous_time)) = self.suspension_velocity {
+            let elapsed = time - previous_time;
+            if elapsed > 0.0 {
+                let mut acceleration = [0.0; 4];
+                for (i, value) in acceleration.iter_mut().enumerate() {
+                    *value = (velocity[i] - previous[i]) / elapsed;
+                }
+                cues.extend(self.bump_cues(acceleration));
+            }
+        }
+        self.suspension_velocity = Some((velocity, time));
+
+        cues
+    }
+
+    fn slip_cues(&self, slip: [f32; 4]) -> Vec<HapticCue> {
+        let config = &self.config;
+        WHEELS
+            .iter()
+            .zip(slip)
+            .filter_map(|(&wheel, slip)| {
+                let kind = if slip > 0.0 {
+                    CueKind::WheelSpin
+                } else {
+                    CueKind::WheelLock
+                };
+                let intensity =
+                    normalize(slip.abs(), config.slip_threshold, config.slip_full_scale);
+                cue(wheel, kind, intensity)
+            })
+            .collect()
+    }
+
+    fn bump_cues(&self, acceleration: [f32; 4]) -> Vec<HapticCue> {
+        let config = &self.config;
+        WHEELS
+            .iter()
+            .zip(acceleration)
+            .filter_map(|(&wheel, acceleration)| {
+                let intensity = normalize(
+                    acceleration.abs(),
+                    config.bump_threshold,
+                    config.bump_full_scale,
+                );
+                cue(wheel, CueKind::Bump, intensity)
+            })
+            .collect()
+    }
+
+    fn surface_cues(&self) -> Vec<HapticCue> {
+        let speed = (self.speed / self.config.surface_full_speed).min(1.0);
+        WHEELS
+            .iter()
+            .zip(self.surfaces)
+            .filter_map(|(&wheel, surface)| {
+                let intensity = surface_roughness(surface) * speed;
+                cue(wheel, CueKind::Surface(surface), intensity)
+            })
+            .collect()
+    }
+}
+
+fn wheels<T: binread::BinRead<Args = ()> + Copy>(value: &WheelValue<T>) -> [T; 4] {
+    [
+        value.rear_left,
+        value.rear_right,
+        value.front_left,
+        value.front_right,
+    ]
+}
+
+fn normalize(value: f32, threshold: f32, full_scale: f32) -> f32 {
+    if value <= threshold || !value.is_finite() {
+        return 0.0;
+    }
+    ((value - threshold) / (full_scale - threshold)).min(1.0)
+}
+
+fn cue(wheel: WheelPosition, kind: CueKind, intensity: f32) -> Option<HapticCue> {
+    if intensity <= 0.0 {
+        return None;
+    }
+    Some(HapticCue {
+        wheel,
+        kind,
+        intensity,
+    })
+}
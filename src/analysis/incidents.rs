@@ -0,0 +1,208 @@
+use alloc::vec::Vec;
+
+use crate::f1::f1_2022::{CarDamageData, ResultStatus, F1_2022};
+use crate::f1::util::CarIndex;
+
+/// IncidentConfig sets the thresholds of the contact heuristics
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct IncidentConfig {
+    pub spike_g: f32,    // change of any g-force component between two Motion packets
+    pub window: f32,     // seconds between a spike and the damage it caused
+    pub proximity: f32,  // metres between cars spiking together to consider them involved
+    pub min_damage: u16, // increase of the summed impact damage percentages
+}
+
+impl Default for IncidentConfig {
+    fn default() -> Self {
+        IncidentConfig {
+            spike_g: 3.0,
+            window: 1.0,
+            proximity: 15.0,
+            min_damage: 1,
+        }
+    }
+}
+
+/// Incident is a likely contact: g-force spikes followed by new damage
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Incident {
+    pub session_time: f32,      // of the first spike
+    pub cars: Vec<CarIndex>,    // every car involved, the damaged ones first
+    pub damaged: Vec<CarIndex>, // cars that took damage
+    pub peak_g: f32,            // biggest g-force change seen
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct CarState {
+    g_force: Option<[f32; 3]>,
+    spike: Option<(f32, f32)>, // session time and g-force change
+    position: [f32; 3],
+    damage: Option<u16>,
+}
+
+/// IncidentDetector flags likely contacts from "F1 22" Motion and CarDamage packets
+/// a car whose impact damage (tyres, wings, floor, diffuser, sidepods) increases shortly after
+/// a g-force spike is in an incident, with the nearby cars that spiked at the same time
+#[derive(Debug, Default, Clone)]
+pub struct IncidentDetector {
+    config: IncidentConfig,
+    active: Vec<bool>,
+    cars: Vec<CarState>,
+    incidents: Vec<Incident>,
+}
+
+impl IncidentDetector {
+    pub fn new(config: IncidentConfig) -> IncidentDetector {
+        IncidentDetector {
+            config,
+            ..IncidentDetector::default()
+        }
+    }
+
+    /// observe feeds a packet and returns the incidents it revealed
+    /// LapData packets are used to leave out cars that are not on track
+    pub fn observe(&mut self, event: &F1_2022) -> Vec<Incident> {
+        let now = event.header().session_time;
+
+        match event {
+            F1_2022::LapData(data) => {
+                self.active = data
+                    .laps
+                    .iter()
+                    .map(|lap| lap.result_status == ResultStatus::Active)
+                    .collect();
+                Vec::new()
+            }
+            F1_2022::Motion(data) => {
+                self.cars
+                    .resize(data.car_motion_data.len(), CarState::default());
+                for (car, (motion, state)) in
+                    data.car_motion_data.iter().zip(&mut self.cars).enumerate()
+                {
+                    let g_force = [
+                        motion.g_force_lateral,
+                        motion.g_force_longitudinal,
+                        motion.g_force_vertical,
+                    ];
+                    let previous = state.g_force.replace(g_force);
+                    let position = &motion.world_position;
+                    state.position = [position.x, position.y, position.z];
+
+                    if !self.active.get(car).copied().unwrap_or(false) {
+                        continue;
+                    }
+                    if let Some(previous) = previous {
+                        let change = (0..3)
+                            .map(|i| (g_force[i] - previous[i]).abs())
+                            .fold(0.0, f32::max);
+                        if change >= self.config.spike_g {
+                            state.spike = Some((now, change));
+                        }
+                    }
+                }
+                Vec::new()
+            }
+            F1_2022::CarDamage(data) => {
+                self.cars
+                    .resize(data.car_damage_data.len(), CarState::default());
+                let mut incidents = Vec::new();
+                for (car, damage) in data.car_damage_data.iter().enumerate() {
+                    let damage = impact_damage(damage);
+                    let previous = self.cars[car].damage.replace(damage);
+                    if previous.is_none_or(|p| damage < p + self.config.min_damage) {
+                        continue;
+                    }
+                    if let Some(incident) = self.damaged(car, now) {
+                        incidents.push(incident);
+                    }
+                }
+                self.incidents.extend_from_slice(&incidents);
+                incidents
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// damaged handles new damage on a car, returns the incident if it is a new one
+    fn damaged(&mut self, car: usize, now: f32) -> Option<Incident> {
+        let window = self.config.window;
+        let index = CarIndex(car as u8);
+
+        // the other car of a contact reported a moment ago
+        if let Some(incident) = self
+            .incidents
+            .iter_mut()
+            .rev()
+            .take_while(|incident| now - incident.session_time <= window)
+            .find(|incident| incident.cars.contains(&index))
+        {
+            if !incident.damaged.contains(&index) {
+                incident.damaged.push(index);
+            }
+            return None;
+        }
+
+        let (time, change) = self.cars[car]
+            .spike
+            .filter(|(time, _)| now - time <= window)?;
+
+        let mut incident = Incident {
+            session_time: time,
+            cars: alloc::vec![index],
+            damaged: alloc::vec![index],
+            peak_g: change,
+        };
+        let proximity = self.config.proximity * self.config.proximity;
+        let position = self.cars[car].position;
+        for (other, state) in self.cars.iter().enumerate() {
+            let (time, change) = match state.spike {
+                Some(spike) if other != car && now - spike.0 <= window => spike,
+                _ => continue,
+            };
+            if distance_squared(position, state.position) <= proximity {
+                incident.cars.push(CarIndex(other as u8));
+                incident.session_time = incident.session_time.min(time);
+                incident.peak_g = incident.peak_g.max(change);
+            }
+        }
+
+        // every spike belongs to this incident now
+        for involved in &incident.cars {
+            if let Some(state) = involved.index().and_then(|index| self.cars.get_mut(index)) {
+                state.spike = None;
+            }
+        }
+        Some(incident)
+    }
+
+    /// incidents returns every incident of the session, oldest first
+    pub fn incidents(&self) -> &[Incident] {
+        &self.incidents
+    }
+}
+
+fn impact_damage(damage: &CarDamageData) -> u16 {
+    let tyres = &damage.tyres_damage;
+    let wings = &damage.wing_damage;
+    [
+        tyres.rear_left,
+        tyres.rear_right,
+        tyres.front_left,
+        tyres.front_right,
+        wings.front_left,
+        wings.front_right,
+        wings.rear,
+        damage.floor_damage,
+        damage.diffuser_damage,
+        damage.sidepod_damage,
+    ]
+    .iter()
+    .map(|&value| value as u16)
+    .sum()
+}
+
+fn distance_squared(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (0..3).map(|i| (a[i] - b[i]) * (a[i] - b[i])).sum()
+}
@@ -0,0 +1,238 @@
+use alloc::{boxed::Box, format, vec::Vec};
+use core::error::Error;
+
+use crate::f1::f1_2022::F1_2022;
+use crate::f1::util::{read_packet, CarIndex};
+
+use binread::BinRead;
+
+/// REFERENCE_LAP_MAGIC starts every saved reference lap
+pub const REFERENCE_LAP_MAGIC: &[u8; 4] = b"CMRL";
+
+/// REFERENCE_LAP_VERSION is the version of the saved reference lap format written by to_bytes
+/// version 1: magic, version (u8), lap_time_ms (u32), sample count (u32),
+/// then per sample distance (f32), time_ms (u32), speed (u16), all little-endian
+pub const REFERENCE_LAP_VERSION: u8 = 1;
+
+/// LapSample is the state of a car at a given distance into the lap
+#[derive(Debug, Default, Clone, Copy, PartialEq, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LapSample {
+    pub distance: f32, // metres from the line
+    pub time_ms: u32,  // time since the start of the lap
+    pub speed: u16,    // kph
+}
+
+/// ReferenceLap is a completed lap recorded as a distance indexed curve
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ReferenceLap {
+    pub lap_time_ms: u32,
+    pub samples: Vec<LapSample>, // sorted by distance
+}
+
+#[derive(BinRead)]
+struct ReferenceLapHeader {
+    magic: [u8; 4],
+    version: u8,
+}
+
+#[derive(BinRead)]
+struct ReferenceLapV1 {
+    lap_time_ms: u32,
+    #[allow(dead_code)] // only needed to know how many samples to read
+    sample_count: u32,
+    #[br(count = sample_count)]
+    samples: Vec<LapSample>,
+}
+
+impl ReferenceLap {
+    /// to_bytes serializes the lap in the compact versioned format (see REFERENCE_LAP_VERSION)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(13 + self.samples.len() * 10);
+        bytes.extend_from_slice(REFERENCE_LAP_MAGIC);
+        bytes.push(REFERENCE_LAP_VERSION);
+        bytes.extend_from_slice(&self.lap_time_ms.to_le_bytes());
+        bytes.extend_from_slice(&(self.samples.len() as u32).to_le_bytes());
+        for sample in &self.samples {
+            bytes.extend_from_slice(&sample.distance.to_le_bytes());
+            bytes.extend_from_slice(&sample.time_ms.to_le_bytes());
+            bytes.extend_from_slice(&sample.speed.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// from_bytes deserializes a lap written by to_bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<ReferenceLap, Box<dyn Error>> {
+        let header: ReferenceLapHeader = read_packet(bytes, binread::Endian::Little)?;
+        if &header.magic != REFERENCE_LAP_MAGIC {
+            return Err(Box::from("Not a reference lap"));
+        }
+
+        match header.version {
+            1 => {
+                let lap: ReferenceLapV1 = read_packet(&bytes[5..], binread::Endian::Little)?;
+                Ok(ReferenceLap {
+                    lap_time_ms: lap.lap_time_ms,
+                    samples: lap.samples,
+                })
+            }
+            version => Err(Box::from(format!(
+                "Unsupported reference lap version {}",
+                version
+            ))),
+        }
+    }
+
+    /// save writes the lap to the given file
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        std::fs::write(path, self.to_bytes())
+    }
+
+    /// load reads a lap saved with save
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<ReferenceLap, Box<dyn Error>> {
+        ReferenceLap::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// time_at returns the time (ms) the lap reached the given distance, linearly interpolated
+    /// None outside of the recorded distances
+    pub fn time_at(&self, distance: f32) -> Option<f32> {
+        let next = self.samples.partition_point(|s| s.distance < distance);
+        let after = self.samples.get(next)?;
+        if after.distance == distance {
+            return Some(after.time_ms as f32);
+        }
+
+        let before = self.samples.get(next.checked_sub(1)?)?;
+        let ratio = (distance - before.distance) / (after.distance - before.distance);
+        Some(before.time_ms as f32 + ratio * (after.time_ms as f32 - before.time_ms as f32))
+    }
+}
+
+/// LapComparison records the laps of one car and computes a live delta against a reference lap
+/// the reference is the best valid lap of the session unless one was set explicitly
+#[derive(Debug, Clone)]
+pub struct LapComparison {
+    car_index: CarIndex,
+    lap_number: u8,
+    lap_invalid: bool,
+    speed: u16,
+    current: Vec<LapSample>,
+    best: Option<ReferenceLap>,
+    reference: Option<ReferenceLap>,
+    delta: Option<f32>,
+}
+
+impl LapComparison {
+    /// new compares the laps of the car at car_index (see Header::player_car_index)
+    pub fn new(car_index: CarIndex) -> LapComparison {
+        LapComparison {
+            car_index,
+            lap_number: 0,
+            lap_invalid: false,
+            speed: 0,
+            current: Vec::new(),
+            best: None,
+            reference: None,
+            delta: None,
+        }
+    }
+
+    /// with_reference compares against the given lap (ie: loaded from a previous session)
+    /// instead of the best lap of the session
+    pub fn with_reference(mut self, reference: ReferenceLap) -> Self {
+        self.reference = Some(reference);
+        self
+    }
+
+    /// observe feeds a packet into the comparison, returns the lap just completed when it is a new best
+    /// only LapData and CarTelemetry packets are used
+    pub fn observe(&mut self, event: &F1_2022) -> Option<&ReferenceLap> {
+        match event {
+            F1_2022::CarTelemetry(data) => {
+                if let Some(telemetry) = data.car_data(self.car_index) {
+                    self.speed = telemetry.speed;
+                }
+                None
+            }
+            F1_2022::LapData(data) => {
+                let lap = data.car_data(self.car_index)?;
+
+                let mut new_best = false;
+                if lap.current_lap_number != self.lap_number {
+                    new_best = self.complete_lap(lap.last_lap_time_ms);
+                    self.lap_number = lap.current_lap_number;
+                }
+                self.lap_invalid = lap.current_lap_invalid;
+                self.record(lap.lap_distance, lap.current_lap_time_ms);
+
+                self.best.as_ref().filter(|_| new_best)
+            }
+            _ => None,
+        }
+    }
+
+    fn complete_lap(&mut self, lap_time_ms: u32) -> bool {
+        let samples = core::mem::take(&mut self.current);
+        let valid = !self.lap_invalid && lap_time_ms > 0 && !samples.is_empty();
+        self.delta = None;
+
+        let faster = match &self.best {
+            Some(best) => lap_time_ms < best.lap_time_ms,
+            None => true,
+        };
+        if !valid || !faster {
+            return false;
+        }
+
+        self.best = Some(ReferenceLap {
+            lap_time_ms,
+            samples,
+        });
+        true
+    }
+
+    fn record(&mut self, distance: f32, time_ms: u32) {
+        if distance < 0.0 {
+            return; // the line hasn't been crossed yet
+        }
+
+        // a flashback rewinds the lap, forget what was driven after it
+        while matches!(self.current.last(), Some(last) if last.distance >= distance) {
+            self.current.pop();
+        }
+        self.current.push(LapSample {
+            distance,
+            time_ms,
+            speed: self.speed,
+        });
+
+        self.delta = self
+            .reference()
+            .and_then(|reference| reference.time_at(distance))
+            .map(|reference_ms| (time_ms as f32 - reference_ms) / 1000.0);
+    }
+
+    /// delta returns the seconds the current lap is behind (positive) or ahead (negative)
+    /// of the reference lap at the same distance
+    pub fn delta(&self) -> Option<f32> {
+        self.delta
+    }
+
+    /// reference returns the lap the current one is compared against
+    pub fn reference(&self) -> Option<&ReferenceLap> {
+        self.reference.as_ref().or(self.best.as_ref())
+    }
+
+    /// best returns the best valid lap recorded in this session
+    pub fn best(&self) -> Option<&ReferenceLap> {
+        self.best.as_ref()
+    }
+
+    /// current returns the samples recorded so far on the current lap
+    pub fn current(&self) -> &[LapSample] {
+        &self.current
+    }
+}
@@ -0,0 +1,22 @@
+pub mod battles;
+pub mod damage;
+pub mod energy;
+pub mod gearing;
+pub mod haptics;
+pub mod incidents;
+pub mod lap_comparison;
+pub mod overtakes;
+pub mod pit_stops;
+pub mod race_start;
+pub mod radar;
+pub mod rewind;
+pub mod segments;
+pub mod smoothing;
+pub mod spotter;
+pub mod standings;
+pub mod stints;
+pub mod summary;
+pub mod weather;
+
+pub use lap_comparison::LapComparison;
+pub use radar::Radar;
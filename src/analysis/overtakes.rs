@@ -0,0 +1,126 @@
+use alloc::vec::Vec;
+
+use crate::f1::f1_2022::{Lap, PitStatus, ResultStatus, F1_2022};
+use crate::f1::util::CarIndex;
+
+// two cars further apart than this (metres) swapped positions through the pits, not on track
+const MAX_PASS_DISTANCE: f32 = 200.0;
+
+/// Overtake is an on track pass between two cars
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Overtake {
+    pub overtaker: CarIndex,
+    pub overtaken: CarIndex,
+    pub lap: u8,                    // overtaker's lap
+    pub approx_track_fraction: f32, // 0..1 around the lap, 0 until a Session packet is seen
+    pub session_time: f32,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct CarState {
+    position: u8,
+    in_pits: bool,
+}
+
+/// OvertakeDetector finds the position swaps of "F1 22" LapData packets that happened on track
+/// swaps where one of the cars is (or just was) in the pit lane and swaps between cars far
+/// apart (a pit cycle, a retirement) are left out
+#[derive(Debug, Default, Clone)]
+pub struct OvertakeDetector {
+    session_uid: u64,
+    track_length: f32,
+    cars: Vec<CarState>,
+    overtakes: Vec<Overtake>,
+}
+
+impl OvertakeDetector {
+    pub fn new() -> OvertakeDetector {
+        OvertakeDetector::default()
+    }
+
+    /// observe feeds a packet, returns the overtakes it revealed
+    /// Session packets provide the track length used for approx_track_fraction
+    pub fn observe(&mut self, event: &F1_2022) -> Vec<Overtake> {
+        match event {
+            F1_2022::Session(data) => {
+                self.track_length = data.track_length as f32;
+                Vec::new()
+            }
+            F1_2022::LapData(data) => {
+                if data.header.session_uid != self.session_uid {
+                    self.session_uid = data.header.session_uid;
+                    self.cars.clear();
+                    self.overtakes.clear();
+                }
+
+                let current: Vec<CarState> = data
+                    .laps
+                    .iter()
+                    .map(|lap| CarState {
+                        position: lap.car_position,
+                        in_pits: lap.pit_status != PitStatus::None || lap.pit_lane_timer_active,
+                    })
+                    .collect();
+
+                let mut overtakes = Vec::new();
+                if self.cars.len() == current.len() {
+                    for (overtaker, lap) in data.laps.iter().enumerate() {
+                        for (overtaken, other) in data.laps.iter().enumerate() {
+                            if self.passed(overtaker, overtaken, &current)
+                                && on_track(lap)
+                                && on_track(other)
+                                && (lap.total_distance - other.total_distance).abs()
+                                    <= MAX_PASS_DISTANCE
+                            {
+                                overtakes.push(Overtake {
+                                    overtaker: CarIndex(overtaker as u8),
+                                    overtaken: CarIndex(overtaken as u8),
+                                    lap: lap.current_lap_number,
+                                    approx_track_fraction: self.track_fraction(lap),
+                                    session_time: data.header.session_time,
+                                });
+                            }
+                        }
+                    }
+                }
+
+                self.cars = current;
+                self.overtakes.extend_from_slice(&overtakes);
+                overtakes
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// overtakes returns every overtake of the session, oldest first
+    pub fn overtakes(&self) -> &[Overtake] {
+        &self.overtakes
+    }
+
+    /// passed tells if a car moved from behind to ahead of another one, neither in the pits
+    fn passed(&self, overtaker: usize, overtaken: usize, current: &[CarState]) -> bool {
+        let (before, after) = (&self.cars, current);
+        let cars = [overtaker, overtaken];
+        if cars
+            .iter()
+            .any(|&car| before[car].position == 0 || before[car].in_pits || after[car].in_pits)
+        {
+            return false;
+        }
+
+        before[overtaker].position > before[overtaken].position
+            && after[overtaker].position < after[overtaken].position
+    }
+
+    fn track_fraction(&self, lap: &Lap) -> f32 {
+        if self.track_length <= 0.0 {
+            return 0.0;
+        }
+        (lap.lap_distance / self.track_length).clamp(0.0, 1.0)
+    }
+}
+
+fn on_track(lap: &Lap) -> bool {
+    lap.result_status == ResultStatus::Active && lap.car_position > 0
+}
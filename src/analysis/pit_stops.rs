@@ -0,0 +1,100 @@
+use alloc::vec::Vec;
+
+use crate::f1::f1_2022::{LapData, PitStatus};
+use crate::f1::util::CarIndex;
+
+/// PitStopCompleted is emitted when a car leaves the pit lane
+/// stop_time_ms is 0 for drive-throughs (the car never stopped in its box)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PitStopCompleted {
+    pub car: CarIndex,
+    pub lane_time_ms: u16,
+    pub stop_time_ms: u16,
+    pub lap: u8,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct PitLaneVisit {
+    lane_time_ms: u16,
+    stop_time_ms: u16,
+    lap: u8,
+}
+
+/// PitStopDetector follows the pit status and pit lane timers of "F1 22" LapData packets
+/// and keeps the pit stop history of every car in the session
+#[derive(Debug, Default, Clone)]
+pub struct PitStopDetector {
+    session_uid: u64,
+    visits: Vec<Option<PitLaneVisit>>,
+    history: Vec<Vec<PitStopCompleted>>,
+}
+
+impl PitStopDetector {
+    pub fn new() -> PitStopDetector {
+        PitStopDetector::default()
+    }
+
+    /// observe feeds a LapData packet and returns the pit stops completed since the last one
+    /// a packet from another session resets the history
+    pub fn observe(&mut self, data: &LapData) -> Vec<PitStopCompleted> {
+        if data.header.session_uid != self.session_uid {
+            self.session_uid = data.header.session_uid;
+            self.visits.clear();
+            self.history.clear();
+        }
+
+        self.visits.resize(data.laps.len(), None);
+        self.history.resize(data.laps.len(), Vec::new());
+
+        let mut completed = Vec::new();
+        for (car, lap) in data.laps.iter().enumerate() {
+            let in_pit_lane = lap.pit_status != PitStatus::None || lap.pit_lane_timer_active;
+
+            match (self.visits[car].as_mut(), in_pit_lane) {
+                (None, true) => {
+                    self.visits[car] = Some(PitLaneVisit {
+                        lane_time_ms: lap.pit_lane_time_in_lane_ms,
+                        stop_time_ms: lap.pit_stop_timer_ms,
+                        lap: lap.current_lap_number,
+                    });
+                }
+                (Some(visit), true) => {
+                    // the timers reset once the car is out, keep the last values seen in the lane
+                    visit.lane_time_ms = visit.lane_time_ms.max(lap.pit_lane_time_in_lane_ms);
+                    visit.stop_time_ms = visit.stop_time_ms.max(lap.pit_stop_timer_ms);
+                }
+                (Some(visit), false) => {
+                    let stop = PitStopCompleted {
+                        car: CarIndex(car as u8),
+                        lane_time_ms: visit.lane_time_ms.max(lap.pit_lane_time_in_lane_ms),
+                        stop_time_ms: visit.stop_time_ms.max(lap.pit_stop_timer_ms),
+                        lap: visit.lap,
+                    };
+                    self.visits[car] = None;
+                    self.history[car].push(stop);
+                    completed.push(stop);
+                }
+                (None, false) => {}
+            }
+        }
+
+        completed
+    }
+
+    /// history returns the pit stops completed by the given car, oldest first
+    pub fn history(&self, car: CarIndex) -> &[PitStopCompleted] {
+        car.index()
+            .and_then(|car| self.history.get(car))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// in_pit_lane returns true if the given car is currently in the pit lane
+    pub fn in_pit_lane(&self, car: CarIndex) -> bool {
+        matches!(
+            car.index().and_then(|car| self.visits.get(car)),
+            Some(Some(_))
+        )
+    }
+}
@@ -0,0 +1,142 @@
+use alloc::vec::Vec;
+
+use crate::f1::f1_2022::{EventDataDetail, F1_2022};
+use crate::f1::util::CarIndex;
+use crate::units;
+
+// throttle (0..1) counted as the driver reacting
+const THROTTLE_THRESHOLD: f32 = 0.5;
+// seconds after lights out the analysis is complete
+const ANALYSIS_WINDOW: f32 = 5.0;
+// seconds after lights out the launch speed is taken
+const LAUNCH_TIME: f32 = 2.0;
+
+/// CarStart is how a car got away from the grid
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CarStart {
+    pub car: CarIndex,
+    pub reaction_time: Option<f32>, // seconds from lights out to moving with the throttle applied
+    pub speed_after_launch: Option<u16>, // kph, 2 seconds after lights out
+    pub time_to_100_kph: Option<f32>, // seconds from lights out
+}
+
+impl CarStart {
+    /// initial_acceleration returns the average acceleration over the launch (m/s²)
+    pub fn initial_acceleration(&self) -> Option<f32> {
+        self.speed_after_launch
+            .map(|speed| units::kph_to_mps(speed as f32) / LAUNCH_TIME)
+    }
+}
+
+/// RaceStartAnalysis is the start of every car, from the lights going out
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RaceStartAnalysis {
+    pub lights_out_time: f32, // session time
+    pub lights_duration: f32, // seconds from the first light to lights out
+    pub cars: Vec<CarStart>,
+}
+
+impl RaceStartAnalysis {
+    /// car returns the start of the car at the given index (ie: Header::player_car_index)
+    pub fn car(&self, car: CarIndex) -> Option<&CarStart> {
+        self.cars.get(car.index()?)
+    }
+
+    /// best_reaction returns the car that reacted first
+    pub fn best_reaction(&self) -> Option<&CarStart> {
+        self.cars
+            .iter()
+            .filter_map(|start| Some((start.reaction_time?, start)))
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, start)| start)
+    }
+}
+
+/// RaceStartAnalyzer times the race start of "F1 22" from the StartLights and LightsOut
+/// events and the CarTelemetry packets that follow
+#[derive(Debug, Default, Clone)]
+pub struct RaceStartAnalyzer {
+    first_light: Option<f32>,
+    analysis: Option<RaceStartAnalysis>,
+    complete: bool,
+}
+
+impl RaceStartAnalyzer {
+    pub fn new() -> RaceStartAnalyzer {
+        RaceStartAnalyzer::default()
+    }
+
+    /// observe feeds a packet, returns the analysis once complete (a few seconds after lights out)
+    pub fn observe(&mut self, event: &F1_2022) -> Option<&RaceStartAnalysis> {
+        let now = event.header().session_time;
+
+        match event {
+            F1_2022::Event(data) => match data.event_data_details {
+                EventDataDetail::StartLights(_) => {
+                    if self.first_light.is_none() || self.complete {
+                        *self = RaceStartAnalyzer {
+                            first_light: Some(now),
+                            ..RaceStartAnalyzer::default()
+                        };
+                    }
+                    None
+                }
+                EventDataDetail::LightsOut => {
+                    self.analysis = Some(RaceStartAnalysis {
+                        lights_out_time: now,
+                        lights_duration: self.first_light.map_or(0.0, |first| now - first),
+                        cars: Vec::new(),
+                    });
+                    self.complete = false;
+                    None
+                }
+                _ => None,
+            },
+            F1_2022::CarTelemetry(data) => {
+                if self.complete {
+                    return None;
+                }
+                let analysis = self.analysis.as_mut()?;
+                let elapsed = now - analysis.lights_out_time;
+                if elapsed < 0.0 {
+                    return None;
+                }
+
+                analysis
+                    .cars
+                    .resize(data.car_telemetry_data.len(), CarStart::default());
+                for (car, (telemetry, start)) in data
+                    .car_telemetry_data
+                    .iter()
+                    .zip(&mut analysis.cars)
+                    .enumerate()
+                {
+                    start.car = CarIndex(car as u8);
+                    if start.reaction_time.is_none()
+                        && telemetry.throttle >= THROTTLE_THRESHOLD
+                        && telemetry.speed > 0
+                    {
+                        start.reaction_time = Some(elapsed);
+                    }
+                    if start.speed_after_launch.is_none() && elapsed >= LAUNCH_TIME {
+                        start.speed_after_launch = Some(telemetry.speed);
+                    }
+                    if start.time_to_100_kph.is_none() && telemetry.speed >= 100 {
+                        start.time_to_100_kph = Some(elapsed);
+                    }
+                }
+
+                self.complete = elapsed >= ANALYSIS_WINDOW;
+                self.result()
+            }
+            _ => None,
+        }
+    }
+
+    /// result returns the analysis once complete
+    pub fn result(&self) -> Option<&RaceStartAnalysis> {
+        self.analysis.as_ref().filter(|_| self.complete)
+    }
+}
@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+use core::f32::consts::PI;
+
+use crate::f1::f1_2022::{CarMotionData, ResultStatus, F1_2022};
+use crate::f1::util::CarIndex;
+
+/// RadarBlip is a car around the player, in the player's local frame (metres)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RadarBlip {
+    pub car: CarIndex,
+    pub lateral: f32,      // negative to the left
+    pub longitudinal: f32, // negative behind
+    pub relative_yaw: f32, // radians between -PI and PI, 0 when facing the same way
+}
+
+impl RadarBlip {
+    /// distance_squared returns the squared distance to the player
+    pub fn distance_squared(&self) -> f32 {
+        self.lateral * self.lateral + self.longitudinal * self.longitudinal
+    }
+
+    /// distance returns the distance to the player in metres
+    #[cfg(feature = "std")]
+    pub fn distance(&self) -> f32 {
+        self.distance_squared().sqrt()
+    }
+}
+
+/// Radar computes where the cars around the player are, updated on every "F1 22" Motion packet
+/// LapData packets are used to leave out cars that are not on track
+#[derive(Debug, Default, Clone)]
+pub struct Radar {
+    radius: f32,
+    active: Vec<bool>,
+    blips: Vec<RadarBlip>,
+}
+
+impl Radar {
+    /// new only reports cars within radius metres of the player
+    pub fn new(radius: f32) -> Radar {
+        Radar {
+            radius,
+            ..Radar::default()
+        }
+    }
+
+    /// observe feeds a packet, returns the cars in range (closest first) on Motion packets
+    pub fn observe(&mut self, event: &F1_2022) -> Option<&[RadarBlip]> {
+        match event {
+            F1_2022::LapData(data) => {
+                self.active = data
+                    .laps
+                    .iter()
+                    .map(|lap| lap.result_status == ResultStatus::Active)
+                    .collect();
+                None
+            }
+            F1_2022::Motion(data) => {
+                let player = data.header.player_car_index.index()?;
+                let me = data.car_motion_data.get(player)?;
+                let radius_squared = self.radius * self.radius;
+
+                self.blips.clear();
+                for (index, car) in data.car_motion_data.iter().enumerate() {
+                    if index == player || !self.active.get(index).copied().unwrap_or(false) {
+                        continue;
+                    }
+                    let (lateral, longitudinal) = local_position(me, car);
+                    let blip = RadarBlip {
+                        car: CarIndex(index as u8),
+                        lateral,
+                        longitudinal,
+                        relative_yaw: wrap_angle(car.yaw - me.yaw),
+                    };
+                    if blip.distance_squared() <= radius_squared {
+                        self.blips.push(blip);
+                    }
+                }
+                self.blips
+                    .sort_by(|a, b| a.distance_squared().total_cmp(&b.distance_squared()));
+
+                Some(&self.blips)
+            }
+            _ => None,
+        }
+    }
+
+    /// blips returns the cars in range as of the latest Motion packet, closest first
+    pub fn blips(&self) -> &[RadarBlip] {
+        &self.blips
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+}
+
+/// local_position returns where a car is relative to another one, in metres:
+/// lateral (negative to the left) and longitudinal (negative behind)
+pub fn local_position(me: &CarMotionData, other: &CarMotionData) -> (f32, f32) {
+    let dx = other.world_position.x - me.world_position.x;
+    let dz = other.world_position.z - me.world_position.z;
+
+    let forward = &me.world_forward_dir;
+    let right = &me.world_right_dir;
+    let lateral = (dx * right.x as f32 + dz * right.z as f32) / i16::MAX as f32;
+    let longitudinal = (dx * forward.x as f32 + dz * forward.z as f32) / i16::MAX as f32;
+    (lateral, longitudinal)
+}
+
+fn wrap_angle(mut angle: f32) -> f32 {
+    if !angle.is_finite() {
+        return 0.0;
+    }
+    while angle > PI {
+        angle -= 2.0 * PI;
+    }
+    while angle < -PI {
+        angle += 2.0 * PI;
+    }
+    angle
+}
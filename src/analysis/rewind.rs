@@ -0,0 +1,124 @@
+use alloc::collections::VecDeque;
+
+use crate::f1::f1_2022::{EventDataDetail, F1_2022};
+
+/// DEFAULT_SNAPSHOTS is the number of snapshots kept: 120 snapshots × 60 frames = 7200 frames
+/// with DEFAULT_SNAPSHOT_INTERVAL, about 2 minutes only when the UDP rate is set to 60 Hz
+pub const DEFAULT_SNAPSHOTS: usize = 120;
+/// DEFAULT_SNAPSHOT_INTERVAL is the number of frames between snapshots
+pub const DEFAULT_SNAPSHOT_INTERVAL: u32 = 60;
+
+/// Rewound notifies that a flashback rewound the session
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Rewound {
+    pub frame_identifier: u32, // frame flashed back to
+    pub session_time: f32,     // session time flashed back to
+    // frame of the restored snapshot, None when the flashback goes further back than the oldest
+    // snapshot (the state is kept as is)
+    pub snapshot_frame: Option<u32>,
+}
+
+/// Rewindable wraps a state accumulated from "F1 22" packets (laps, damage...) that a flashback
+/// makes wrong: it keeps a ring buffer of snapshots keyed by frame_identifier and restores the
+/// latest one taken before the frame flashed back to. What happened between the snapshot and the
+/// flashback frame is lost, a smaller interval narrows that window at the cost of more clones
+#[derive(Debug, Clone)]
+pub struct Rewindable<S> {
+    state: S,
+    snapshots: VecDeque<(u32, S)>, // frame_identifier, state before the packets of that frame
+    capacity: usize,
+    interval: u32,
+    session_uid: u64,
+}
+
+impl<S: Clone> Rewindable<S> {
+    /// new keeps DEFAULT_SNAPSHOTS snapshots of state, one every DEFAULT_SNAPSHOT_INTERVAL frames
+    pub fn new(state: S) -> Rewindable<S> {
+        Rewindable {
+            state,
+            snapshots: VecDeque::new(),
+            capacity: DEFAULT_SNAPSHOTS,
+            interval: DEFAULT_SNAPSHOT_INTERVAL,
+            session_uid: 0,
+        }
+    }
+
+    /// with_snapshots keeps the given number of snapshots, one every interval frames
+    pub fn with_snapshots(mut self, capacity: usize, interval: u32) -> Self {
+        self.capacity = capacity.max(1);
+        self.interval = interval.max(1);
+        self
+    }
+
+    /// observe feeds a packet: a Flashback event restores the state and returns Rewound,
+    /// any other packet is applied to the state with update. A new session_uid drops the
+    /// snapshots, resetting the state itself is left to update
+    pub fn observe<F>(&mut self, event: &F1_2022, update: F) -> Option<Rewound>
+    where
+        F: FnOnce(&mut S, &F1_2022),
+    {
+        let header = event.header();
+        if header.session_uid != self.session_uid {
+            self.session_uid = header.session_uid;
+            self.snapshots.clear();
+        }
+
+        if let F1_2022::Event(data) = event {
+            if let EventDataDetail::Flashback(frame_identifier, session_time) =
+                data.event_data_details
+            {
+                return Some(self.rewind(frame_identifier, session_time));
+            }
+        }
+
+        let frame = header.frame_identifier;
+        let due = match self.snapshots.back() {
+            Some((last, _)) => frame >= last.saturating_add(self.interval) || frame < *last,
+            None => true,
+        };
+        if due {
+            if self.snapshots.len() == self.capacity {
+                self.snapshots.pop_front();
+            }
+            self.snapshots.push_back((frame, self.state.clone()));
+        }
+
+        update(&mut self.state, event);
+        None
+    }
+
+    fn rewind(&mut self, frame_identifier: u32, session_time: f32) -> Rewound {
+        while matches!(self.snapshots.back(), Some((frame, _)) if *frame > frame_identifier) {
+            self.snapshots.pop_back();
+        }
+        // the snapshot is kept, another flashback can go back to it again
+        let snapshot_frame = match self.snapshots.back() {
+            Some((frame, snapshot)) => {
+                self.state = snapshot.clone();
+                Some(*frame)
+            }
+            None => None,
+        };
+        Rewound {
+            frame_identifier,
+            session_time,
+            snapshot_frame,
+        }
+    }
+
+    /// state returns the current state
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// snapshots returns the number of snapshots kept
+    pub fn snapshots(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// into_inner returns the current state
+    pub fn into_inner(self) -> S {
+        self.state
+    }
+}
@@ -0,0 +1,197 @@
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+
+use crate::f1::f1_2022::{Gear, F1_2022};
+use crate::f1::util::CarIndex;
+
+/// BRAKE_THRESHOLD is the brake input above which a sample counts as braking
+pub const BRAKE_THRESHOLD: f32 = 0.1;
+
+/// Segment is a stretch of the lap between two lap distances (in metres)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Segment {
+    pub name: String,
+    pub start: f32,
+    pub end: f32,
+}
+
+/// SegmentLayout splits a lap into segments, either evenly sized micro-sectors or given corners
+#[derive(Debug, Clone, Default)]
+pub struct SegmentLayout {
+    segments: Vec<Segment>,
+}
+
+impl SegmentLayout {
+    /// new builds a layout from the given segments (eg: one per corner)
+    /// segments are sorted by start distance, gaps between them are not recorded
+    pub fn new(mut segments: Vec<Segment>) -> SegmentLayout {
+        segments.sort_by(|a, b| a.start.total_cmp(&b.start));
+        SegmentLayout { segments }
+    }
+
+    /// micro_sectors splits a track of the given length (in metres) into count even segments
+    pub fn micro_sectors(track_length: f32, count: usize) -> SegmentLayout {
+        let size = track_length / count as f32;
+        let segments = (0..count)
+            .map(|index| Segment {
+                name: format!("{}", index + 1),
+                start: index as f32 * size,
+                end: (index + 1) as f32 * size,
+            })
+            .collect();
+        SegmentLayout { segments }
+    }
+
+    /// segments returns the segments of the layout ordered by distance
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// segment_at returns the index of the segment covering the given lap distance
+    pub fn segment_at(&self, lap_distance: f32) -> Option<usize> {
+        self.segments
+            .iter()
+            .position(|segment| segment.start <= lap_distance && lap_distance < segment.end)
+    }
+}
+
+/// SegmentStats is what a car did through a segment on one lap
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SegmentStats {
+    pub segment: usize,
+    pub min_speed: u16,               // kph
+    pub min_speed_gear: Gear,         // gear at the minimum speed
+    pub min_speed_distance: f32,      // lap distance of the minimum speed
+    pub min_speed_position: [f32; 3], // world position of the minimum speed (x, y, z)
+    pub brake_point: Option<f32>,     // lap distance where the brakes were first applied
+    pub max_brake: f32,
+}
+
+/// SegmentRecorder records per segment statistics of one car, lap after lap
+/// lap distance comes from LapData, world position from Motion and inputs from CarTelemetry,
+/// samples are recorded on CarTelemetry packets once the car crossed the line
+#[derive(Debug, Clone)]
+pub struct SegmentRecorder {
+    layout: SegmentLayout,
+    car_index: CarIndex,
+    lap_number: u8,
+    lap_distance: Option<f32>,
+    position: [f32; 3],
+    laps: BTreeMap<u8, Vec<SegmentStats>>,
+}
+
+impl SegmentRecorder {
+    /// new records the car at car_index (see Header::player_car_index) using the given layout
+    pub fn new(layout: SegmentLayout, car_index: CarIndex) -> SegmentRecorder {
+        SegmentRecorder {
+            layout,
+            car_index,
+            lap_number: 0,
+            lap_distance: None,
+            position: [0.0; 3],
+            laps: BTreeMap::new(),
+        }
+    }
+
+    /// observe feeds a packet into the recorder, packets of other types are ignored
+    pub fn observe(&mut self, event: &F1_2022) {
+        match event {
+            F1_2022::LapData(data) => {
+                if let Some(lap) = data.car_data(self.car_index) {
+                    self.lap_number = lap.current_lap_number;
+                    self.lap_distance = Some(lap.lap_distance).filter(|d| *d >= 0.0);
+                }
+            }
+            F1_2022::Motion(data) => {
+                if let Some(motion) = data.car_data(self.car_index) {
+                    let position = &motion.world_position;
+                    self.position = [position.x, position.y, position.z];
+                }
+            }
+            F1_2022::CarTelemetry(data) => {
+                if let Some(telemetry) = data.car_data(self.car_index) {
+                    self.record(telemetry.speed, telemetry.gear, telemetry.brake);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn record(&mut self, speed: u16, gear: Gear, brake: f32) {
+        let distance = match self.lap_distance {
+            Some(distance) => distance,
+            None => return,
+        };
+        let segment = match self.layout.segment_at(distance) {
+            Some(segment) => segment,
+            None => return,
+        };
+
+        let laps = self.laps.entry(self.lap_number).or_default();
+        let index = match laps.iter().position(|stats| stats.segment == segment) {
+            Some(index) => index,
+            None => {
+                laps.push(SegmentStats {
+                    segment,
+                    min_speed: speed,
+                    min_speed_gear: gear,
+                    min_speed_distance: distance,
+                    min_speed_position: self.position,
+                    brake_point: None,
+                    max_brake: 0.0,
+                });
+                laps.len() - 1
+            }
+        };
+
+        let stats = &mut laps[index];
+        if speed < stats.min_speed {
+            stats.min_speed = speed;
+            stats.min_speed_gear = gear;
+            stats.min_speed_distance = distance;
+            stats.min_speed_position = self.position;
+        }
+        if brake > BRAKE_THRESHOLD && stats.brake_point.is_none() {
+            stats.brake_point = Some(distance);
+        }
+        stats.max_brake = stats.max_brake.max(brake);
+    }
+
+    /// layout returns the layout the recorder segments laps with
+    pub fn layout(&self) -> &SegmentLayout {
+        &self.layout
+    }
+
+    /// lap returns the statistics of the given lap in the order the segments were driven
+    pub fn lap(&self, lap_number: u8) -> Option<&[SegmentStats]> {
+        self.laps.get(&lap_number).map(Vec::as_slice)
+    }
+
+    /// laps iterates over the recorded laps, oldest first
+    pub fn laps(&self) -> impl Iterator<Item = (u8, &[SegmentStats])> {
+        self.laps
+            .iter()
+            .map(|(lap, stats)| (*lap, stats.as_slice()))
+    }
+
+    /// min_speed_delta compares the minimum speed of every segment driven on both laps
+    /// yields (segment, speed on lap minus speed on reference) in kph
+    pub fn min_speed_delta(
+        &self,
+        lap_number: u8,
+        reference_lap: u8,
+    ) -> impl Iterator<Item = (usize, i32)> + '_ {
+        let reference = self.lap(reference_lap).unwrap_or_default();
+        self.lap(lap_number)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(move |stats| {
+                let other = reference.iter().find(|r| r.segment == stats.segment)?;
+                Some((
+                    stats.segment,
+                    stats.min_speed as i32 - other.min_speed as i32,
+                ))
+            })
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::{boxed::Box, collections::VecDeque, format, vec::Vec};
+use core::error::Error;
+
+/// Sample is a single value of a telemetry channel stream
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Sample {
+    pub timestamp: f64, // seconds, ie: the session time of the packet
+    pub value: f64,
+}
+
+impl Sample {
+    pub fn new(timestamp: f64, value: f64) -> Sample {
+        Sample { timestamp, value }
+    }
+}
+
+/// Filter conditions a stream of samples (ie: throttle or brake traces before comparing them)
+pub trait Filter {
+    /// push feeds the next sample, returns the filtered sample when one is ready
+    /// (some filters delay their output, see SavitzkyGolay)
+    fn push(&mut self, sample: Sample) -> Option<Sample>;
+
+    /// reset forgets the samples seen so far (ie: on a flashback or a new lap)
+    fn reset(&mut self);
+}
+
+/// smooth runs every sample through the filter and collects the output
+pub fn smooth<F: Filter, I: IntoIterator<Item = Sample>>(
+    filter: &mut F,
+    samples: I,
+) -> Vec<Sample> {
+    samples
+        .into_iter()
+        .filter_map(|sample| filter.push(sample))
+        .collect()
+}
+
+/// MovingAverage averages the latest window samples
+#[derive(Debug, Clone)]
+pub struct MovingAverage {
+    window: usize,
+    samples: VecDeque<f64>,
+    sum: f64,
+}
+
+impl MovingAverage {
+    pub fn new(window: usize) -> MovingAverage {
+        let window = window.max(1);
+        MovingAverage {
+            window,
+            samples: VecDeque::with_capacity(window),
+            sum: 0.0,
+        }
+    }
+}
+
+impl Filter for MovingAverage {
+    fn push(&mut self, sample: Sample) -> Option<Sample> {
+        if self.samples.len() == self.window {
+            self.sum -= self.samples.pop_front().unwrap_or_default();
+        }
+        self.samples.push_back(sample.value);
+        self.sum += sample.value;
+
+        Some(Sample::new(
+            sample.timestamp,
+            self.sum / self.samples.len() as f64,
+        ))
+    }
+
+    fn reset(&mut self) {
+        self.samples.clear();
+        self.sum = 0.0;
+    }
+}
+
+// quadratic/cubic smoothing coefficients and their normalization, by window size
+const SAVITZKY_GOLAY: [(&[f64], f64); 4] = [
+    (&[-3.0, 12.0, 17.0, 12.0, -3.0], 35.0),
+    (&[-2.0, 3.0, 6.0, 7.0, 6.0, 3.0, -2.0], 21.0),
+    (
+        &[-21.0, 14.0, 39.0, 54.0, 59.0, 54.0, 39.0, 14.0, -21.0],
+        231.0,
+    ),
+    (
+        &[
+            -36.0, 9.0, 44.0, 69.0, 84.0, 89.0, 84.0, 69.0, 44.0, 9.0, -36.0,
+        ],
+        429.0,
+    ),
+];
+
+/// SavitzkyGolay fits a quadratic polynomial over a window of samples, smoothing noise
+/// while keeping peaks (ie: the brake point) better than a moving average
+/// the output is the center of the window, so it lags window / 2 samples behind the input
+#[derive(Debug, Clone)]
+pub struct SavitzkyGolay {
+    coefficients: &'static [f64],
+    normalization: f64,
+    samples: VecDeque<Sample>,
+}
+
+impl SavitzkyGolay {
+    /// new builds the filter for a window of 5, 7, 9 or 11 samples
+    pub fn new(window: usize) -> Result<SavitzkyGolay, Box<dyn Error>> {
+        let (coefficients, normalization) = SAVITZKY_GOLAY
+            .iter()
+            .find(|(coefficients, _)| coefficients.len() == window)
+            .ok_or_else(|| format!("Unsupported Savitzky-Golay window {}", window))?;
+
+        Ok(SavitzkyGolay {
+            coefficients,
+            normalization: *normalization,
+            samples: VecDeque::with_capacity(window),
+        })
+    }
+}
+
+impl Filter for SavitzkyGolay {
+    fn push(&mut self, sample: Sample) -> Option<Sample> {
+        if self.samples.len() == self.coefficients.len() {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        if self.samples.len() < self.coefficients.len() {
+            return None;
+        }
+
+        let value: f64 = self
+            .samples
+            .iter()
+            .zip(self.coefficients)
+            .map(|(sample, coefficient)| sample.value * coefficient)
+            .sum();
+        let center = self.samples[self.coefficients.len() / 2];
+        Some(Sample::new(center.timestamp, value / self.normalization))
+    }
+
+    fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
+
+/// OversamplingGuard drops samples arriving closer than min_interval seconds to the previous
+/// one (or going back in time) before they reach the inner filter, so a window always spans
+/// the same duration when the game sends packets faster than expected or duplicates them
+#[derive(Debug, Clone)]
+pub struct OversamplingGuard<F: Filter> {
+    inner: F,
+    min_interval: f64,
+    last: Option<f64>,
+}
+
+impl<F: Filter> OversamplingGuard<F> {
+    pub fn new(inner: F, min_interval: f64) -> OversamplingGuard<F> {
+        OversamplingGuard {
+            inner,
+            min_interval,
+            last: None,
+        }
+    }
+
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+}
+
+impl<F: Filter> Filter for OversamplingGuard<F> {
+    fn push(&mut self, sample: Sample) -> Option<Sample> {
+        if let Some(last) = self.last {
+            if sample.timestamp - last < self.min_interval {
+                return None;
+            }
+        }
+        self.last = Some(sample.timestamp);
+        self.inner.push(sample)
+    }
+
+    fn reset(&mut self) {
+        self.last = None;
+        self.inner.reset();
+    }
+}
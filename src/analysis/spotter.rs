@@ -0,0 +1,183 @@
+use alloc::vec::Vec;
+
+use crate::analysis::radar::local_position;
+use crate::f1::f1_2022::{CarMotionData, ResultStatus, ZoneFlag, F1_2022};
+use crate::f1::util::CarIndex;
+
+/// SpotterEvent is something a spotter would call out to the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum SpotterEvent {
+    CarLeft,
+    CarRight,
+    ThreeWide,
+    ClearLeft,
+    ClearRight,
+    YellowAhead { zone: u8 }, // marshal zone index
+    PitWindowOpen { ideal_lap: u8, latest_lap: u8 },
+}
+
+/// SpotterConfig sets the area around the player where a car counts as alongside (metres)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SpotterConfig {
+    pub car_length: f32,  // overlap needed on the longitudinal axis
+    pub max_lateral: f32, // farthest lateral distance still considered alongside
+}
+
+impl Default for SpotterConfig {
+    fn default() -> Self {
+        SpotterConfig {
+            car_length: 5.5,
+            max_lateral: 4.0,
+        }
+    }
+}
+
+/// Spotter follows "F1 22" packets and emits spotter style events for the player car:
+/// cars alongside (from Motion world positions), yellow flags in the marshal zone ahead
+/// and the pit window opening
+#[derive(Debug, Default, Clone)]
+pub struct Spotter {
+    config: SpotterConfig,
+    active: Vec<bool>,
+    left: bool,
+    right: bool,
+    zones: Vec<(f32, ZoneFlag)>, // start and flag of each marshal zone
+    track_length: f32,
+    lap_fraction: f32,
+    current_lap: u8,
+    warned_zone: Option<usize>,
+    pit_window: (u8, u8),
+    pit_window_announced: u8,
+}
+
+impl Spotter {
+    pub fn new(config: SpotterConfig) -> Spotter {
+        Spotter {
+            config,
+            ..Spotter::default()
+        }
+    }
+
+    /// observe feeds a packet and returns the events it caused
+    pub fn observe(&mut self, event: &F1_2022) -> Vec<SpotterEvent> {
+        let player = event.header().player_car_index;
+        let mut events = Vec::new();
+
+        match event {
+            F1_2022::Motion(data) => {
+                if let Some(me) = data.car_data(player) {
+                    self.alongside(player, me, &data.car_motion_data, &mut events);
+                }
+            }
+            F1_2022::LapData(data) => {
+                self.active = data
+                    .laps
+                    .iter()
+                    .map(|lap| lap.result_status == ResultStatus::Active)
+                    .collect();
+                if let Some(lap) = data.car_data(player) {
+                    if self.track_length > 0.0 {
+                        self.lap_fraction = lap.lap_distance.max(0.0) / self.track_length;
+                    }
+                    self.current_lap = lap.current_lap_number;
+                }
+                self.yellow_ahead(&mut events);
+                self.pit_window_open(&mut events);
+            }
+            F1_2022::Session(data) => {
+                self.track_length = data.track_length as f32;
+                self.zones = data
+                    .active_marshal_zones()
+                    .iter()
+                    .map(|zone| (zone.zone_start, zone.zone_flag))
+                    .collect();
+                self.pit_window = (
+                    data.pit_stop_window_ideal_lap,
+                    data.pit_stop_window_latest_lap,
+                );
+                self.yellow_ahead(&mut events);
+                self.pit_window_open(&mut events);
+            }
+            _ => {}
+        }
+
+        events
+    }
+
+    fn alongside(
+        &mut self,
+        player: CarIndex,
+        me: &CarMotionData,
+        cars: &[CarMotionData],
+        events: &mut Vec<SpotterEvent>,
+    ) {
+        let config = &self.config;
+        let (mut left, mut right) = (false, false);
+        for (index, car) in cars.iter().enumerate() {
+            if Some(index) == player.index() || !self.active.get(index).copied().unwrap_or(false) {
+                continue;
+            }
+            let (lateral, longitudinal) = local_position(me, car);
+            if longitudinal.abs() >= config.car_length || lateral.abs() > config.max_lateral {
+                continue;
+            }
+            if lateral < 0.0 {
+                left = true;
+            } else {
+                right = true;
+            }
+        }
+
+        if left && right && !(self.left && self.right) {
+            events.push(SpotterEvent::ThreeWide);
+        } else {
+            match (self.left, left) {
+                (false, true) => events.push(SpotterEvent::CarLeft),
+                (true, false) => events.push(SpotterEvent::ClearLeft),
+                _ => {}
+            }
+            match (self.right, right) {
+                (false, true) => events.push(SpotterEvent::CarRight),
+                (true, false) => events.push(SpotterEvent::ClearRight),
+                _ => {}
+            }
+        }
+        self.left = left;
+        self.right = right;
+    }
+
+    fn yellow_ahead(&mut self, events: &mut Vec<SpotterEvent>) {
+        if self.zones.is_empty() {
+            return;
+        }
+
+        let current = self
+            .zones
+            .iter()
+            .rposition(|(start, _)| *start <= self.lap_fraction)
+            .unwrap_or(self.zones.len() - 1); // before the first zone we are still in the last one
+        let ahead = (current + 1) % self.zones.len();
+
+        if self.zones[ahead].1 != ZoneFlag::Yellow {
+            self.warned_zone = None;
+        } else if self.warned_zone != Some(ahead) {
+            self.warned_zone = Some(ahead);
+            events.push(SpotterEvent::YellowAhead { zone: ahead as u8 });
+        }
+    }
+
+    fn pit_window_open(&mut self, events: &mut Vec<SpotterEvent>) {
+        let (ideal_lap, latest_lap) = self.pit_window;
+        if ideal_lap == 0 || self.current_lap < ideal_lap || self.pit_window_announced == ideal_lap
+        {
+            return;
+        }
+        self.pit_window_announced = ideal_lap;
+        events.push(SpotterEvent::PitWindowOpen {
+            ideal_lap,
+            latest_lap,
+        });
+    }
+}
@@ -0,0 +1,148 @@
+use alloc::vec::Vec;
+
+use crate::f1::f1_2022::{EventDataDetail, ResultStatus, F1_2022};
+use crate::f1::util::CarIndex;
+
+/// StandingsEvent is a change of the running order
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum StandingsEvent {
+    PositionChange {
+        car: CarIndex,
+        from: u8,
+        to: u8,
+        lap: u8,
+    },
+    FastestLap {
+        car: CarIndex,
+        lap_time: f32, // seconds
+        lap: u8,
+    },
+    Retirement {
+        car: CarIndex,
+        status: ResultStatus,
+        lap: u8,
+    },
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct CarStanding {
+    position: u8,
+    lap: u8,
+    status: ResultStatus,
+}
+
+/// StandingsTracker follows the running order over "F1 22" LapData and Event packets
+/// and emits what changed instead of only exposing the current standings
+#[derive(Debug, Default, Clone)]
+pub struct StandingsTracker {
+    session_uid: u64,
+    cars: Vec<CarStanding>,
+}
+
+impl StandingsTracker {
+    pub fn new() -> StandingsTracker {
+        StandingsTracker::default()
+    }
+
+    /// observe feeds a packet and returns the changes it caused
+    /// the first LapData packet of a session only sets the initial standings
+    pub fn observe(&mut self, event: &F1_2022) -> Vec<StandingsEvent> {
+        let mut events = Vec::new();
+
+        match event {
+            F1_2022::LapData(data) => {
+                let first = data.header.session_uid != self.session_uid || self.cars.is_empty();
+                self.session_uid = data.header.session_uid;
+                self.cars.resize(data.laps.len(), CarStanding::default());
+
+                for (car, (lap, previous)) in data.laps.iter().zip(&mut self.cars).enumerate() {
+                    let current = CarStanding {
+                        position: lap.car_position,
+                        lap: lap.current_lap_number,
+                        status: lap.result_status,
+                    };
+
+                    if !first && previous.status == ResultStatus::Active {
+                        if is_retirement(current.status) {
+                            events.push(StandingsEvent::Retirement {
+                                car: CarIndex(car as u8),
+                                status: current.status,
+                                lap: current.lap,
+                            });
+                        } else if current.status == ResultStatus::Active
+                            && current.position != previous.position
+                            && current.position > 0
+                        {
+                            events.push(StandingsEvent::PositionChange {
+                                car: CarIndex(car as u8),
+                                from: previous.position,
+                                to: current.position,
+                                lap: current.lap,
+                            });
+                        }
+                    }
+                    *previous = current;
+                }
+            }
+            F1_2022::Event(data) => {
+                if let EventDataDetail::FastestLap(car, lap_time) = data.event_data_details {
+                    let lap = car
+                        .index()
+                        .and_then(|index| self.cars.get(index))
+                        .map(|standing| standing.lap.saturating_sub(1))
+                        .unwrap_or_default();
+                    events.push(StandingsEvent::FastestLap { car, lap_time, lap });
+                }
+            }
+            _ => {}
+        }
+
+        events
+    }
+
+    /// standings returns the car indices of the cars still running, in race order
+    pub fn standings(&self) -> Vec<CarIndex> {
+        let mut running: Vec<(u8, CarIndex)> = self
+            .cars
+            .iter()
+            .enumerate()
+            .filter(|(_, standing)| standing.position > 0 && !is_retirement(standing.status))
+            .map(|(car, standing)| (standing.position, CarIndex(car as u8)))
+            .collect();
+        running.sort_unstable();
+        running.into_iter().map(|(_, car)| car).collect()
+    }
+
+    /// positions maps every car index to its current position (None when unknown)
+    pub fn positions(&self) -> Vec<Option<u8>> {
+        (0..self.cars.len() as u8)
+            .map(|car| self.position(CarIndex(car)))
+            .collect()
+    }
+
+    /// car_at returns the index of the car in the given position
+    pub fn car_at(&self, position: u8) -> Option<CarIndex> {
+        self.standings()
+            .into_iter()
+            .find(|&car| self.position(car) == Some(position))
+    }
+
+    /// position returns the current position of a car (None when unknown)
+    pub fn position(&self, car: CarIndex) -> Option<u8> {
+        self.cars
+            .get(car.index()?)
+            .map(|standing| standing.position)
+            .filter(|&position| position > 0)
+    }
+}
+
+fn is_retirement(status: ResultStatus) -> bool {
+    matches!(
+        status,
+        ResultStatus::DidNotFinished
+            | ResultStatus::Disqualified
+            | ResultStatus::NotClassified
+            | ResultStatus::Retired
+    )
+}
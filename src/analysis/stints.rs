@@ -0,0 +1,153 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::f1::f1_2022::{SessionHistory, TyreCompound, TyreVisual};
+
+/// Stint is a run of laps on the same set of tyres
+/// pace figures leave out the out-lap and in-lap (the ones spent partly in the pit lane)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Stint {
+    pub start_lap: u8,
+    pub end_lap: u8, // last completed lap for the current stint
+    pub actual_compound: TyreCompound,
+    pub visual_compound: TyreVisual,
+    pub average_lap_ms: Option<u32>,
+    pub degradation_ms_per_lap: Option<f32>, // None with less than 3 pace laps
+    pub first_pace_laps_ms: Option<u32>, // average of the first 3 pace laps, the fresh tyre pace
+    pub last_pace_laps_ms: Option<u32>,  // average of the last 3 pace laps, the current tyre pace
+    pub current: bool,
+}
+
+impl Stint {
+    /// laps returns how many laps the stint lasted
+    pub fn laps(&self) -> u8 {
+        self.end_lap
+            .saturating_add(1)
+            .saturating_sub(self.start_lap)
+    }
+}
+
+const PACE_SAMPLE: usize = 3;
+
+/// stints returns the tyre stints of the car the SessionHistory relates to, oldest first
+pub fn stints(history: &SessionHistory) -> Vec<Stint> {
+    let completed_laps = history
+        .lap_history_data
+        .iter()
+        .take_while(|lap| lap.lap_time_ms > 0)
+        .count() as u8;
+
+    let mut stints = Vec::with_capacity(history.tyre_stints_history_data.len());
+    let mut start_lap: u8 = 1;
+    for (index, stint) in history.tyre_stints_history_data.iter().enumerate() {
+        let current = index + 1 == history.tyre_stints_history_data.len();
+        let end_lap = if current || stint.end_lap == 255 {
+            completed_laps
+        } else {
+            stint.end_lap
+        };
+
+        // out-lap of every stint but the first and in-lap of every stint but the current
+        let first_pace_lap = if index == 0 {
+            start_lap
+        } else {
+            start_lap.saturating_add(1)
+        };
+        let last_pace_lap = if current {
+            end_lap
+        } else {
+            end_lap.saturating_sub(1)
+        };
+        let pace: Vec<(u8, u32)> = (first_pace_lap..=last_pace_lap)
+            .filter_map(|lap| Some((lap, history.lap(lap)?.lap_time_ms)))
+            .filter(|(_, time)| *time > 0)
+            .collect();
+
+        stints.push(Stint {
+            start_lap,
+            end_lap,
+            actual_compound: stint.tyre_actual_compound,
+            visual_compound: stint.tyre_visual_compound,
+            average_lap_ms: average(&pace),
+            degradation_ms_per_lap: degradation(&pace),
+            first_pace_laps_ms: average(&pace[..pace.len().min(PACE_SAMPLE)]),
+            last_pace_laps_ms: average(&pace[pace.len().saturating_sub(PACE_SAMPLE)..]),
+            current,
+        });
+
+        start_lap = end_lap.saturating_add(1);
+    }
+
+    stints
+}
+
+/// UndercutWindow projects what happens if the attacker pits before the defender
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UndercutWindow {
+    pub gap_ms: u32,
+    pub gain_per_lap_ms: i32, // fresh tyre pace of the attacker vs current pace of the defender
+    pub laps_needed: Option<u8>, // laps the defender must stay out for the undercut to work
+}
+
+impl UndercutWindow {
+    /// is_overcut returns true when the defender is faster on old tyres than the attacker on new ones
+    /// (staying out is the better strategy)
+    pub fn is_overcut(&self) -> bool {
+        self.gain_per_lap_ms < 0
+    }
+}
+
+/// undercut projects the undercut window of the attacker (behind by gap_ms) on the defender
+/// the time lost in the pit lane cancels out since both cars are expected to stop,
+/// returns None when either car has no pace laps to compare
+pub fn undercut(
+    attacker: &SessionHistory,
+    defender: &SessionHistory,
+    gap_ms: u32,
+) -> Option<UndercutWindow> {
+    let fresh_pace = stints(attacker)
+        .iter()
+        .rev()
+        .find_map(|stint| stint.first_pace_laps_ms)?;
+    let current_pace = stints(defender).last()?.last_pace_laps_ms?;
+
+    let gain_per_lap_ms = current_pace as i32 - fresh_pace as i32;
+    let laps_needed = match gain_per_lap_ms {
+        gain if gain > 0 => u8::try_from(gap_ms.div_ceil(gain as u32).max(1)).ok(),
+        _ => None,
+    };
+
+    Some(UndercutWindow {
+        gap_ms,
+        gain_per_lap_ms,
+        laps_needed,
+    })
+}
+
+fn average(pace: &[(u8, u32)]) -> Option<u32> {
+    if pace.is_empty() {
+        return None;
+    }
+    let total: u64 = pace.iter().map(|(_, time)| *time as u64).sum();
+    Some((total / pace.len() as u64) as u32)
+}
+
+/// degradation is the least squares slope of lap time over lap number
+fn degradation(pace: &[(u8, u32)]) -> Option<f32> {
+    if pace.len() < PACE_SAMPLE {
+        return None;
+    }
+
+    let count = pace.len() as f32;
+    let mean_lap = pace.iter().map(|(lap, _)| *lap as f32).sum::<f32>() / count;
+    let mean_time = pace.iter().map(|(_, time)| *time as f32).sum::<f32>() / count;
+
+    let (covariance, variance) = pace.iter().fold((0.0, 0.0), |(cov, var), (lap, time)| {
+        let dx = *lap as f32 - mean_lap;
+        (cov + dx * (*time as f32 - mean_time), var + dx * dx)
+    });
+
+    Some(covariance / variance)
+}
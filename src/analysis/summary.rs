@@ -0,0 +1,165 @@
+use alloc::vec::Vec;
+
+use crate::f1::f1_2022::{LapData, Session, Weather, F1_2022};
+use crate::f1::util::CarIndex;
+
+/// DEFAULT_INTERVAL is how often (session seconds) a standings snapshot is emitted
+pub const DEFAULT_INTERVAL: f32 = 1.0;
+
+/// Standing is the position of a car in a standings snapshot
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Standing {
+    pub car: CarIndex,
+    pub position: u8,
+    pub lap: u8,
+    pub last_lap_time: f32, // seconds, 0 before the first lap is completed
+}
+
+/// SummaryItem is a low frequency update of the session
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum SummaryItem {
+    Standings {
+        session_time: f32,
+        standings: Vec<Standing>, // in race order
+    },
+    LapCompleted {
+        car: CarIndex,
+        lap: u8,       // number of the completed lap
+        lap_time: f32, // seconds
+    },
+    Weather {
+        weather: Weather,
+        track_temperature: i8, // celsius
+        air_temperature: i8,   // celsius
+    },
+}
+
+/// SessionSummarizer consumes every "F1 22" packet and only emits what a lightweight client
+/// (ie: a chat bot) needs: standings every interval, completed laps and weather changes
+#[derive(Debug, Clone)]
+pub struct SessionSummarizer {
+    interval: f32,
+    session_uid: Option<u64>,
+    last_standings: Option<f32>, // session_time of the last snapshot
+    laps: Vec<u8>,               // current lap number, by car index
+    weather: Option<(Weather, i8, i8)>,
+}
+
+impl Default for SessionSummarizer {
+    fn default() -> Self {
+        SessionSummarizer::new(DEFAULT_INTERVAL)
+    }
+}
+
+impl SessionSummarizer {
+    /// new emits a standings snapshot every interval seconds of session time
+    pub fn new(interval: f32) -> SessionSummarizer {
+        SessionSummarizer {
+            interval,
+            session_uid: None,
+            last_standings: None,
+            laps: Vec::new(),
+            weather: None,
+        }
+    }
+
+    /// observe feeds a packet and returns the summary items it caused, usually none
+    /// a new session_uid resets the summarizer, its first Session packet reports the weather
+    pub fn observe(&mut self, event: &F1_2022) -> Vec<SummaryItem> {
+        let header = event.header();
+        if self.session_uid != Some(header.session_uid) {
+            *self = SessionSummarizer::new(self.interval);
+            self.session_uid = Some(header.session_uid);
+        }
+
+        match event {
+            F1_2022::LapData(data) => self.observe_laps(data),
+            F1_2022::Session(session) => self.observe_weather(session).into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn observe_laps(&mut self, data: &LapData) -> Vec<SummaryItem> {
+        let mut items = Vec::new();
+        if self.laps.len() < data.laps.len() {
+            self.laps.resize(data.laps.len(), 0);
+        }
+
+        for (car, lap) in data.active_laps() {
+            let previous = match car.index().and_then(|index| self.laps.get_mut(index)) {
+                Some(previous) => previous,
+                None => continue,
+            };
+            if *previous > 0 && lap.current_lap_number > *previous {
+                items.push(SummaryItem::LapCompleted {
+                    car,
+                    lap: *previous,
+                    lap_time: lap.last_lap_time_ms as f32 / 1000.0,
+                });
+            }
+            *previous = lap.current_lap_number;
+        }
+
+        let session_time = data.header.session_time;
+        let due = match self.last_standings {
+            Some(last) => session_time - last >= self.interval || session_time < last,
+            None => true,
+        };
+        if due {
+            self.last_standings = Some(session_time);
+            let mut standings: Vec<Standing> = data
+                .active_laps()
+                .filter(|(_, lap)| lap.car_position > 0)
+                .map(|(car, lap)| Standing {
+                    car,
+                    position: lap.car_position,
+                    lap: lap.current_lap_number,
+                    last_lap_time: lap.last_lap_time_ms as f32 / 1000.0,
+                })
+                .collect();
+            standings.sort_by_key(|standing| standing.position);
+            items.push(SummaryItem::Standings {
+                session_time,
+                standings,
+            });
+        }
+
+        items
+    }
+
+    fn observe_weather(&mut self, session: &Session) -> Option<SummaryItem> {
+        let weather = (
+            session.weather,
+            session.track_temperature,
+            session.air_temperature,
+        );
+        if self.weather == Some(weather) {
+            return None;
+        }
+        self.weather = Some(weather);
+        Some(SummaryItem::Weather {
+            weather: weather.0,
+            track_temperature: weather.1,
+            air_temperature: weather.2,
+        })
+    }
+}
+
+#[cfg(feature = "net")]
+impl crate::TelemetryServer<F1_2022> {
+    /// next_summary receives packets until the summarizer emits something, for clients that
+    /// only want the summary of the session instead of every packet
+    pub fn next_summary(
+        &self,
+        summarizer: &mut SessionSummarizer,
+    ) -> Result<Vec<SummaryItem>, alloc::boxed::Box<dyn core::error::Error>> {
+        loop {
+            let items = summarizer.observe(&self.next()?);
+            if !items.is_empty() {
+                return Ok(items);
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use alloc::vec::Vec;
+
+use crate::f1::f1_2022::{Session, Weather};
+
+/// WeatherPoint is the weather from a given session time on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WeatherPoint {
+    pub session_time: u32, // seconds
+    pub weather: Weather,
+    pub track_temperature: i8,
+    pub air_temperature: i8,
+    pub rain_percentage: Option<u8>, // from the forecast for now, when the game sends one
+}
+
+/// ForecastPoint is a forecast sample as first received
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ForecastPoint {
+    pub issued_at: u32,   // session time (seconds) the forecast was received
+    pub target_time: u32, // session time (seconds) the forecast is for
+    pub weather: Weather,
+    pub track_temperature: i8,
+    pub rain_percentage: u8,
+}
+
+/// ForecastCheck compares a forecast with the weather seen once its time came
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ForecastCheck {
+    pub forecast: ForecastPoint,
+    pub actual: Weather,
+}
+
+impl ForecastCheck {
+    pub fn is_correct(&self) -> bool {
+        self.forecast.weather == self.actual
+    }
+}
+
+/// WeatherEvent is a change of the conditions or of the forecast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum WeatherEvent {
+    WeatherChanged {
+        from: Weather,
+        to: Weather,
+    },
+    TrackTemperatureChanged {
+        from: i8,
+        to: i8,
+    },
+    AirTemperatureChanged {
+        from: i8,
+        to: i8,
+    },
+    RainChanged {
+        from: u8,
+        to: u8,
+    },
+    ForecastChanged {
+        time_offset: u8,
+        from: Weather,
+        to: Weather,
+    },
+}
+
+/// WeatherTimeline accumulates the weather and forecast samples of "F1 22" Session packets
+/// only changes are stored, so the timeline stays small for a whole session
+#[derive(Debug, Default, Clone)]
+pub struct WeatherTimeline {
+    session_uid: u64,
+    session_time: u32,
+    points: Vec<WeatherPoint>,
+    forecasts: Vec<ForecastPoint>,
+    latest_forecast: Vec<(u8, Weather, i8, u8)>, // offset, weather, track temperature, rain
+}
+
+impl WeatherTimeline {
+    pub fn new() -> WeatherTimeline {
+        WeatherTimeline::default()
+    }
+
+    /// observe feeds a Session packet and returns what changed since the previous one
+    /// a packet from another session starts a new timeline
+    pub fn observe(&mut self, session: &Session) -> Vec<WeatherEvent> {
+        if session.header.session_uid != self.session_uid {
+            *self = WeatherTimeline {
+                session_uid: session.header.session_uid,
+                ..WeatherTimeline::default()
+            };
+        }
+
+        let now = session.header.session_time as u32;
+        self.session_time = now;
+        let mut events = Vec::new();
+
+        let forecast: Vec<(u8, Weather, i8, u8)> = session
+            .forecast_for(session.session_type)
+            .map(|s| {
+                (
+                    s.time_offset,
+                    s.weather,
+                    s.track_temperature,
+                    s.rain_percentage,
+                )
+            })
+            .collect();
+        let point = WeatherPoint {
+            session_time: now,
+            weather: session.weather,
+            track_temperature: session.track_temperature,
+            air_temperature: session.air_temperature,
+            rain_percentage: forecast
+                .iter()
+                .find(|(offset, ..)| *offset == 0)
+                .map(|(.., rain)| *rain),
+        };
+
+        match self.points.last() {
+            None => self.points.push(point),
+            Some(last) => {
+                if last.weather != point.weather {
+                    events.push(WeatherEvent::WeatherChanged {
+                        from: last.weather,
+                        to: point.weather,
+                    });
+                }
+                if last.track_temperature != point.track_temperature {
+                    events.push(WeatherEvent::TrackTemperatureChanged {
+                        from: last.track_temperature,
+                        to: point.track_temperature,
+                    });
+                }
+                if last.air_temperature != point.air_temperature {
+                    events.push(WeatherEvent::AirTemperatureChanged {
+                        from: last.air_temperature,
+                        to: point.air_temperature,
+                    });
+                }
+                if let (Some(from), Some(to)) = (last.rain_percentage, point.rain_percentage) {
+                    if from != to {
+                        events.push(WeatherEvent::RainChanged { from, to });
+                    }
+                }
+                if !events.is_empty() {
+                    self.points.push(point);
+                }
+            }
+        }
+
+        for &(offset, weather, track_temperature, rain) in &forecast {
+            let previous = self
+                .latest_forecast
+                .iter()
+                .find(|(previous_offset, ..)| *previous_offset == offset);
+            if let Some(&(_, previous_weather, ..)) = previous {
+                if previous_weather != weather {
+                    events.push(WeatherEvent::ForecastChanged {
+                        time_offset: offset,
+                        from: previous_weather,
+                        to: weather,
+                    });
+                }
+            }
+            if previous != Some(&(offset, weather, track_temperature, rain)) && offset > 0 {
+                self.forecasts.push(ForecastPoint {
+                    issued_at: now,
+                    target_time: now + offset as u32 * 60,
+                    weather,
+                    track_temperature,
+                    rain_percentage: rain,
+                });
+            }
+        }
+        if !forecast.is_empty() {
+            self.latest_forecast = forecast;
+        }
+
+        events
+    }
+
+    /// points returns the weather timeline, oldest first
+    pub fn points(&self) -> &[WeatherPoint] {
+        &self.points
+    }
+
+    /// forecasts returns every forecast sample received, as issued
+    pub fn forecasts(&self) -> &[ForecastPoint] {
+        &self.forecasts
+    }
+
+    /// weather_at returns the conditions at the given session time (seconds)
+    pub fn weather_at(&self, session_time: u32) -> Option<&WeatherPoint> {
+        let next = self
+            .points
+            .partition_point(|point| point.session_time <= session_time);
+        self.points.get(next.checked_sub(1)?)
+    }
+
+    /// validate compares the forecasts whose time already came with the weather at that time
+    pub fn validate(&self) -> Vec<ForecastCheck> {
+        self.forecasts
+            .iter()
+            .filter(|forecast| forecast.target_time <= self.session_time)
+            .filter_map(|forecast| {
+                let actual = self.weather_at(forecast.target_time)?.weather;
+                Some(ForecastCheck {
+                    forecast: *forecast,
+                    actual,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,80 @@
+use std::net::SocketAddr;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::net::Transport;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// SIGNED_MAGIC starts every packet signed by sign_packet
+pub const SIGNED_MAGIC: &[u8; 4] = b"CMTS";
+
+/// SIGNED_HEADER_SIZE is the size of the signature header:
+/// magic (4 bytes), HMAC-SHA256 of the payload (32 bytes)
+pub const SIGNED_HEADER_SIZE: usize = 36;
+
+/// sign_packet wraps a game packet in a header carrying its HMAC-SHA256 with the given key
+/// (for relays sending to a SignedTransport)
+pub fn sign_packet(key: &[u8], packet: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(packet);
+
+    let mut signed = Vec::with_capacity(SIGNED_HEADER_SIZE + packet.len());
+    signed.extend_from_slice(SIGNED_MAGIC);
+    signed.extend_from_slice(&mac.finalize().into_bytes());
+    signed.extend_from_slice(packet);
+    signed
+}
+
+/// verify_packet returns the game packet if its header was signed with the given key
+pub fn verify_packet<'a>(key: &[u8], packet: &'a [u8]) -> Result<&'a [u8], std::io::Error> {
+    let invalid = |message| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+
+    if packet.len() < SIGNED_HEADER_SIZE || !packet.starts_with(SIGNED_MAGIC) {
+        return Err(invalid("packet is not signed"));
+    }
+
+    let (tag, payload) = packet[SIGNED_MAGIC.len()..].split_at(32);
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    mac.verify_slice(tag)
+        .map_err(|_| invalid("packet signature does not match"))?;
+    Ok(payload)
+}
+
+/// SignedTransport only accepts packets signed with a shared key (see sign_packet),
+/// so collectors open to the internet can reject telemetry from strangers.
+/// It wraps any transport, rejected packets are returned as InvalidData errors.
+/// Signatures do not protect against a captured packet being sent again
+pub struct SignedTransport<R: Transport> {
+    inner: R,
+    key: Vec<u8>,
+}
+
+impl<R: Transport> SignedTransport<R> {
+    pub fn new(inner: R, key: &[u8]) -> SignedTransport<R> {
+        SignedTransport {
+            inner,
+            key: key.to_vec(),
+        }
+    }
+}
+
+impl<R: Transport> Transport for SignedTransport<R> {
+    fn recv_from(&self) -> Result<(Vec<u8>, Option<SocketAddr>), std::io::Error> {
+        let (packet, source) = self.inner.recv_from()?;
+        let payload = verify_packet(&self.key, &packet)?;
+        Ok((payload.to_vec(), source))
+    }
+
+    fn local_addr(&self) -> Option<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    fn recv_on(&self) -> Result<(Vec<u8>, Option<SocketAddr>), std::io::Error> {
+        let (packet, local_addr) = self.inner.recv_on()?;
+        let payload = verify_packet(&self.key, &packet)?;
+        Ok((payload.to_vec(), local_addr))
+    }
+}
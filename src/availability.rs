@@ -0,0 +1,161 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+use crate::TelemetryFrame;
+
+/// DEFAULT_STALE_AFTER is how long (session seconds) the data of a packet type stays fresh
+pub const DEFAULT_STALE_AFTER: f32 = 2.0;
+
+/// Availability is the state of the data carried by a packet type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Availability {
+    Fresh,
+    Stale,       // expected, but not received for longer than stale_after
+    Unavailable, // not expected (disabled on the sender) or never received this session
+}
+
+/// AvailabilityWarning points at a sender that does not match the declared packet types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AvailabilityWarning {
+    Unexpected { packet_id: u8 }, // received but not declared, reported once per session
+    Missing { packet_id: u8 },    // declared but not received after stale_after
+}
+
+/// AvailabilityStats are the counters of a PacketAvailability
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AvailabilityStats {
+    pub received: u64,
+    pub unexpected: u64,  // packets received that were not declared
+    pub missing: Vec<u8>, // declared packet ids not received this session
+}
+
+/// PacketAvailability tells which data can be trusted when the sender only enables a subset of
+/// the packet types (ie: broadcast setups with a restricted spec): data of a packet type that is
+/// not declared, or never arrived, is Unavailable instead of Stale
+#[derive(Debug)]
+pub struct PacketAvailability {
+    expected: Option<BTreeSet<u8>>, // None when every packet type is expected
+    stale_after: f32,
+    session_uid: Option<u64>,
+    session_start: f32,
+    session_time: f32,
+    last_seen: BTreeMap<u8, f32>, // session_time of the last packet, by packet id
+    warned: BTreeSet<u8>,
+    received: u64,
+    unexpected: u64,
+}
+
+impl Default for PacketAvailability {
+    fn default() -> Self {
+        PacketAvailability::new(DEFAULT_STALE_AFTER)
+    }
+}
+
+impl PacketAvailability {
+    /// new expects every packet type, their data becomes stale after stale_after seconds
+    pub fn new(stale_after: f32) -> PacketAvailability {
+        PacketAvailability {
+            expected: None,
+            stale_after,
+            session_uid: None,
+            session_start: 0.0,
+            session_time: 0.0,
+            last_seen: BTreeMap::new(),
+            warned: BTreeSet::new(),
+            received: 0,
+            unexpected: 0,
+        }
+    }
+
+    /// with_expected_packets declares the only packet ids the sender is expected to send
+    pub fn with_expected_packets(mut self, packet_ids: &[u8]) -> PacketAvailability {
+        self.expected = Some(packet_ids.iter().copied().collect());
+        self
+    }
+
+    /// observe records the event and returns a warning the first time (per session)
+    /// a packet type that was not declared is received, a new session_uid resets the state
+    pub fn observe<T: TelemetryFrame>(&mut self, event: &T) -> Option<AvailabilityWarning> {
+        let packet_id = event.packet_id();
+        let session_time = event.session_time();
+        if self.session_uid != Some(event.session_uid()) {
+            self.session_uid = Some(event.session_uid());
+            self.session_start = session_time;
+            self.last_seen.clear();
+            self.warned.clear();
+        }
+
+        self.received += 1;
+        self.session_time = session_time;
+        self.last_seen.insert(packet_id, session_time);
+
+        if self.is_expected(packet_id) {
+            return None;
+        }
+        self.unexpected += 1;
+        if self.warned.insert(packet_id) {
+            return Some(AvailabilityWarning::Unexpected { packet_id });
+        }
+        None
+    }
+
+    /// availability returns the state of the data of the given packet id
+    pub fn availability(&self, packet_id: u8) -> Availability {
+        if !self.is_expected(packet_id) {
+            return Availability::Unavailable;
+        }
+        match self.last_seen.get(&packet_id) {
+            None => Availability::Unavailable,
+            Some(&seen) if self.session_time - seen > self.stale_after => Availability::Stale,
+            Some(_) => Availability::Fresh,
+        }
+    }
+
+    /// is_expected returns true if the given packet id was declared (or none were)
+    pub fn is_expected(&self, packet_id: u8) -> bool {
+        match &self.expected {
+            Some(expected) => expected.contains(&packet_id),
+            None => true,
+        }
+    }
+
+    /// warnings returns the current warnings: declared packet types that did not arrive
+    /// within stale_after of the start of the session, then the unexpected ones received
+    pub fn warnings(&self) -> Vec<AvailabilityWarning> {
+        let mut warnings: Vec<AvailabilityWarning> = self
+            .missing()
+            .into_iter()
+            .map(|packet_id| AvailabilityWarning::Missing { packet_id })
+            .collect();
+        warnings.extend(
+            self.warned
+                .iter()
+                .map(|&packet_id| AvailabilityWarning::Unexpected { packet_id }),
+        );
+        warnings
+    }
+
+    /// stats returns the counters, missing is only filled once stale_after went by
+    pub fn stats(&self) -> AvailabilityStats {
+        AvailabilityStats {
+            received: self.received,
+            unexpected: self.unexpected,
+            missing: self.missing(),
+        }
+    }
+
+    fn missing(&self) -> Vec<u8> {
+        let expected = match &self.expected {
+            Some(expected) if self.session_time - self.session_start > self.stale_after => expected,
+            _ => return Vec::new(),
+        };
+        expected
+            .iter()
+            .copied()
+            .filter(|packet_id| !self.last_seen.contains_key(packet_id))
+            .collect()
+    }
+}
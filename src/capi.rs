@@ -0,0 +1,199 @@
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+
+use serde::Serialize;
+
+use crate::dirt::rally2::DirtRally2;
+use crate::f1::{f1_2020::F1_2020, f1_2022::F1_2022};
+use crate::net::{Transport, UdpTransport};
+use crate::{TelemetryEvent, TelemetryPacket};
+
+/// CmGame selects the decoder of a CmTelemetry handle, its values are the game argument of
+/// cm_telemetry_open (taken as an integer: any other value gets NULL back)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmGame {
+    F1_2020 = 0,
+    F1_2022 = 1,
+    DirtRally2 = 2,
+}
+
+impl TryFrom<u32> for CmGame {
+    type Error = u32;
+
+    fn try_from(game: u32) -> Result<Self, Self::Error> {
+        match game {
+            0 => Ok(CmGame::F1_2020),
+            1 => Ok(CmGame::F1_2022),
+            2 => Ok(CmGame::DirtRally2),
+            _ => Err(game),
+        }
+    }
+}
+
+/// CmEventCallback receives every decoded event as a NUL terminated JSON document (and its
+/// length), the document is only valid during the call. Returning false stops cm_telemetry_run
+pub type CmEventCallback =
+    Option<unsafe extern "C" fn(json: *const c_char, len: usize, user_data: *mut c_void) -> bool>;
+
+/// CmTelemetry is the opaque handle returned by cm_telemetry_open
+pub struct CmTelemetry {
+    transport: UdpTransport,
+    game: CmGame,
+    pending: Option<CString>, // decoded event that did not fit the buffer of the caller
+    last_error: Option<CString>,
+}
+
+impl CmTelemetry {
+    // next_event returns the pending event or receives packets until one decodes,
+    // None on a transport error (kept as last_error)
+    fn next_event(&mut self) -> Option<CString> {
+        if let Some(json) = self.pending.take() {
+            return Some(json);
+        }
+        loop {
+            match self.transport.recv() {
+                Ok(packet) => {
+                    if let Ok(json) = decode_json(self.game, &packet) {
+                        return Some(json);
+                    }
+                }
+                Err(e) => {
+                    self.last_error = CString::new(e.to_string()).ok();
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+fn decode_json(game: CmGame, packet: &TelemetryPacket) -> Result<CString, String> {
+    match game {
+        CmGame::F1_2020 => to_json::<F1_2020>(packet),
+        CmGame::F1_2022 => to_json::<F1_2022>(packet),
+        CmGame::DirtRally2 => to_json::<DirtRally2>(packet),
+    }
+}
+
+fn to_json<T: TelemetryEvent + Serialize>(packet: &TelemetryPacket) -> Result<CString, String> {
+    let event = T::from_packet(packet).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string(&event).map_err(|e| e.to_string())?;
+    CString::new(json).map_err(|e| e.to_string())
+}
+
+/// cm_telemetry_open listens for UDP packets of the given game (a CmGame value) on address
+/// (ie: "0.0.0.0:20777"), returns NULL when the game is unknown or the address is invalid
+/// or can not be bound
+///
+/// # Safety
+/// address must be NULL or a NUL terminated string
+#[no_mangle]
+pub unsafe extern "C" fn cm_telemetry_open(address: *const c_char, game: u32) -> *mut CmTelemetry {
+    let game = match CmGame::try_from(game) {
+        Ok(game) => game,
+        Err(_) => return ptr::null_mut(),
+    };
+    if address.is_null() {
+        return ptr::null_mut();
+    }
+    let address = match CStr::from_ptr(address).to_str() {
+        Ok(address) => address,
+        Err(_) => return ptr::null_mut(),
+    };
+    match UdpTransport::new(address) {
+        Ok(transport) => Box::into_raw(Box::new(CmTelemetry {
+            transport,
+            game,
+            pending: None,
+            last_error: None,
+        })),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// cm_telemetry_next_json blocks until the next event decodes and copies it as a NUL terminated
+/// JSON document into buffer. Returns the length of the document (without the NUL), -1 on error
+/// (see cm_telemetry_last_error). When the length is len or more nothing was copied: the event
+/// is kept for the next call, retry with a buffer of at least the returned length + 1
+///
+/// # Safety
+/// handle must come from cm_telemetry_open, buffer must be valid for len bytes
+#[no_mangle]
+pub unsafe extern "C" fn cm_telemetry_next_json(
+    handle: *mut CmTelemetry,
+    buffer: *mut c_char,
+    len: usize,
+) -> isize {
+    let handle = match handle.as_mut() {
+        Some(handle) => handle,
+        None => return -1,
+    };
+    let json = match handle.next_event() {
+        Some(json) => json,
+        None => return -1,
+    };
+
+    let bytes = json.as_bytes_with_nul();
+    let written = bytes.len() as isize - 1;
+    if buffer.is_null() || bytes.len() > len {
+        handle.pending = Some(json);
+    } else {
+        ptr::copy_nonoverlapping(bytes.as_ptr() as *const c_char, buffer, bytes.len());
+    }
+    written
+}
+
+/// cm_telemetry_run blocks calling callback with every event that decodes (and user_data) until
+/// the callback returns false (returns 0) or the socket fails (returns -1)
+///
+/// # Safety
+/// handle must come from cm_telemetry_open, callback must be safe to call with user_data
+#[no_mangle]
+pub unsafe extern "C" fn cm_telemetry_run(
+    handle: *mut CmTelemetry,
+    callback: CmEventCallback,
+    user_data: *mut c_void,
+) -> c_int {
+    let (handle, callback) = match (handle.as_mut(), callback) {
+        (Some(handle), Some(callback)) => (handle, callback),
+        _ => return -1,
+    };
+    loop {
+        let json = match handle.next_event() {
+            Some(json) => json,
+            None => return -1,
+        };
+        if !callback(json.as_ptr(), json.as_bytes().len(), user_data) {
+            return 0;
+        }
+    }
+}
+
+/// cm_telemetry_last_error returns the message of the last error of handle, NULL when none.
+/// The message is owned by handle and valid until the next call using it
+///
+/// # Safety
+/// handle must be NULL or come from cm_telemetry_open
+#[no_mangle]
+pub unsafe extern "C" fn cm_telemetry_last_error(handle: *const CmTelemetry) -> *const c_char {
+    match handle
+        .as_ref()
+        .and_then(|handle| handle.last_error.as_ref())
+    {
+        Some(error) => error.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// cm_telemetry_close closes the socket and frees handle
+///
+/// # Safety
+/// handle must be NULL or come from cm_telemetry_open, and not be used afterwards
+#[no_mangle]
+pub unsafe extern "C" fn cm_telemetry_close(handle: *mut CmTelemetry) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
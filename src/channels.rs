@@ -0,0 +1,436 @@
+use alloc::vec::Vec;
+
+use crate::analysis::haptics::WheelPosition;
+use crate::analysis::smoothing::Sample;
+use crate::dirt::rally2::DirtRally2;
+use crate::f1::util::{CarIndex, WheelValue};
+use crate::f1::{f1_2020, f1_2022};
+
+/// ChannelKind identifies a telemetry channel, with the same unit for every game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ChannelKind {
+    Speed,
+    Throttle,
+    Brake,
+    Clutch,
+    Steer,
+    Gear,
+    EngineRpm,
+    LapDistance,
+    LapTime,
+    GForceLateral,
+    GForceLongitudinal,
+    BrakeTemperature(WheelPosition),
+    WheelSpeed(WheelPosition),
+}
+
+impl ChannelKind {
+    /// name returns the display name of the channel (ie: "Brake Temp FL")
+    pub fn name(&self) -> &'static str {
+        let wheel = |wheel: &WheelPosition, names: [&'static str; 4]| match wheel {
+            WheelPosition::RearLeft => names[0],
+            WheelPosition::RearRight => names[1],
+            WheelPosition::FrontLeft => names[2],
+            WheelPosition::FrontRight => names[3],
+        };
+        match self {
+            ChannelKind::Speed => "Speed",
+            ChannelKind::Throttle => "Throttle",
+            ChannelKind::Brake => "Brake",
+            ChannelKind::Clutch => "Clutch",
+            ChannelKind::Steer => "Steer",
+            ChannelKind::Gear => "Gear",
+            ChannelKind::EngineRpm => "Engine RPM",
+            ChannelKind::LapDistance => "Lap Distance",
+            ChannelKind::LapTime => "Lap Time",
+            ChannelKind::GForceLateral => "G Force Lat",
+            ChannelKind::GForceLongitudinal => "G Force Long",
+            ChannelKind::BrakeTemperature(position) => wheel(
+                position,
+                [
+                    "Brake Temp RL",
+                    "Brake Temp RR",
+                    "Brake Temp FL",
+                    "Brake Temp FR",
+                ],
+            ),
+            ChannelKind::WheelSpeed(position) => wheel(
+                position,
+                [
+                    "Wheel Speed RL",
+                    "Wheel Speed RR",
+                    "Wheel Speed FL",
+                    "Wheel Speed FR",
+                ],
+            ),
+        }
+    }
+
+    /// unit returns the unit of the channel values
+    pub fn unit(&self) -> &'static str {
+        match self {
+            ChannelKind::Speed => "km/h",
+            ChannelKind::Throttle | ChannelKind::Brake | ChannelKind::Clutch => "%",
+            ChannelKind::Steer => "%", // -100 full lock left, 100 full lock right
+            ChannelKind::Gear => "",   // -1 reverse, 0 neutral
+            ChannelKind::EngineRpm => "rpm",
+            ChannelKind::LapDistance => "m",
+            ChannelKind::LapTime => "s",
+            ChannelKind::GForceLateral | ChannelKind::GForceLongitudinal => "G",
+            ChannelKind::BrakeTemperature(_) => "C",
+            ChannelKind::WheelSpeed(_) => "m/s",
+        }
+    }
+}
+
+/// Channel is a single value of a telemetry channel of a car
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Channel {
+    pub kind: ChannelKind,
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub value: f64,
+    pub car: CarIndex,  // always 0 for "Dirt Rally 2.0"
+    pub timestamp: f64, // seconds, the session time of the packet
+}
+
+impl Channel {
+    pub fn new(kind: ChannelKind, car: CarIndex, timestamp: f64, value: f64) -> Channel {
+        Channel {
+            kind,
+            name: kind.name(),
+            unit: kind.unit(),
+            value,
+            car,
+            timestamp,
+        }
+    }
+
+    /// sample returns the value as a Sample, to be fed to the smoothing filters
+    pub fn sample(&self) -> Sample {
+        Sample::new(self.timestamp, self.value)
+    }
+}
+
+const WHEELS: [WheelPosition; 4] = [
+    WheelPosition::RearLeft,
+    WheelPosition::RearRight,
+    WheelPosition::FrontLeft,
+    WheelPosition::FrontRight,
+];
+
+/// ChannelSet is the configurable set of channels (and cars) to extract from packets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelSet {
+    kinds: Vec<ChannelKind>,
+    all_cars: bool,
+}
+
+impl ChannelSet {
+    /// new extracts the given channels of the player car
+    pub fn new(kinds: &[ChannelKind]) -> ChannelSet {
+        ChannelSet {
+            kinds: kinds.to_vec(),
+            all_cars: false,
+        }
+    }
+
+    /// all extracts every channel of the player car
+    pub fn all() -> ChannelSet {
+        let mut kinds = Vec::from([
+            ChannelKind::Speed,
+            ChannelKind::Throttle,
+            ChannelKind::Brake,
+            ChannelKind::Clutch,
+            ChannelKind::Steer,
+            ChannelKind::Gear,
+            ChannelKind::EngineRpm,
+            ChannelKind::LapDistance,
+            ChannelKind::LapTime,
+            ChannelKind::GForceLateral,
+            ChannelKind::GForceLongitudinal,
+        ]);
+        kinds.extend(WHEELS.map(ChannelKind::BrakeTemperature));
+        kinds.extend(WHEELS.map(ChannelKind::WheelSpeed));
+        ChannelSet {
+            kinds,
+            all_cars: false,
+        }
+    }
+
+    /// with_all_cars extracts the channels of every car instead of the player car only
+    /// (F1 only sends wheel speeds of the player car)
+    pub fn with_all_cars(mut self) -> ChannelSet {
+        self.all_cars = true;
+        self
+    }
+
+    pub fn kinds(&self) -> &[ChannelKind] {
+        &self.kinds
+    }
+
+    pub fn contains(&self, kind: ChannelKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    /// extract returns the channels of the set carried by the packet
+    /// F1 packets only carry some channels each (ie: Throttle on CarTelemetry packets)
+    pub fn extract<S: ChannelSource>(&self, source: &S) -> Vec<Channel> {
+        let mut channels = Channels {
+            set: self,
+            channels: Vec::new(),
+            car: CarIndex(0),
+            timestamp: 0.0,
+        };
+        source.channels(&mut channels);
+        channels.channels
+    }
+}
+
+impl Default for ChannelSet {
+    fn default() -> Self {
+        ChannelSet::all()
+    }
+}
+
+/// ChannelSource is implemented by the packets channels can be extracted from
+pub trait ChannelSource {
+    fn channels(&self, channels: &mut Channels);
+}
+
+/// Channels collects the channels of a packet that belong to a ChannelSet
+pub struct Channels<'a> {
+    set: &'a ChannelSet,
+    channels: Vec<Channel>,
+    car: CarIndex,
+    timestamp: f64,
+}
+
+impl Channels<'_> {
+    /// car sets the car and timestamp of the values pushed next
+    pub fn car(&mut self, car: CarIndex, timestamp: f64) -> &mut Self {
+        self.car = car;
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// push adds a value if its channel is part of the set
+    pub fn push(&mut self, kind: ChannelKind, value: f64) -> &mut Self {
+        if self.set.contains(kind) {
+            let channel = Channel::new(kind, self.car, self.timestamp, value);
+            self.channels.push(channel);
+        }
+        self
+    }
+
+    fn push_wheels<T, F>(
+        &mut self,
+        kind: fn(WheelPosition) -> ChannelKind,
+        values: &WheelValue<T>,
+        f: F,
+    ) where
+        T: binread::BinRead<Args = ()> + Copy,
+        F: Fn(T) -> f64,
+    {
+        let values = [
+            values.rear_left,
+            values.rear_right,
+            values.front_left,
+            values.front_right,
+        ];
+        for (position, value) in WHEELS.iter().zip(values) {
+            self.push(kind(*position), f(value));
+        }
+    }
+
+    /// cars returns the indexes of the cars to extract out of the given number of cars
+    fn cars(&self, count: usize, player_car_index: CarIndex) -> Vec<CarIndex> {
+        if self.set.all_cars {
+            (0..count.min(CarIndex::MAX_CARS) as u8)
+                .map(CarIndex)
+                .collect()
+        } else if player_car_index.index().is_some_and(|index| index < count) {
+            Vec::from([player_car_index])
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl ChannelSource for DirtRally2 {
+    fn channels(&self, channels: &mut Channels) {
+        let car = &self.car;
+        channels
+            .car(CarIndex(0), self.session.track.time as f64)
+            .push(ChannelKind::Speed, car.speed_kph() as f64)
+            .push(ChannelKind::Throttle, car.throttle as f64 * 100.0)
+            .push(ChannelKind::Brake, car.brake as f64 * 100.0)
+            .push(ChannelKind::Clutch, car.clutch as f64 * 100.0)
+            .push(ChannelKind::Steer, car.steer as f64 * 100.0)
+            .push(ChannelKind::Gear, car.gear.as_number() as f64)
+            .push(ChannelKind::EngineRpm, car.rpm() as f64)
+            .push(
+                ChannelKind::LapDistance,
+                self.session.lap_info.current_lap_distance as f64,
+            )
+            .push(
+                ChannelKind::LapTime,
+                self.session.lap_info.current_lap_time as f64,
+            )
+            .push(
+                ChannelKind::GForceLateral,
+                self.motion.g_force_lateral as f64,
+            )
+            .push(
+                ChannelKind::GForceLongitudinal,
+                self.motion.g_force_longitudinal as f64,
+            );
+
+        for (position, wheel) in WHEELS.iter().zip(car.wheels.as_array()) {
+            channels
+                .push(
+                    ChannelKind::BrakeTemperature(*position),
+                    wheel.brake_temperature as f64,
+                )
+                .push(
+                    ChannelKind::WheelSpeed(*position),
+                    wheel.wheel_velocity as f64,
+                );
+        }
+    }
+}
+
+impl ChannelSource for f1_2022::F1_2022 {
+    fn channels(&self, channels: &mut Channels) {
+        let header = self.header();
+        let timestamp = header.session_time as f64;
+        match self {
+            f1_2022::F1_2022::CarTelemetry(data) => {
+                let cars = channels.cars(data.car_telemetry_data.len(), header.player_car_index);
+                for car in cars {
+                    let telemetry = match data.car_data(car) {
+                        Some(telemetry) => telemetry,
+                        None => continue,
+                    };
+                    channels
+                        .car(car, timestamp)
+                        .push(ChannelKind::Speed, telemetry.speed as f64)
+                        .push(ChannelKind::Throttle, telemetry.throttle as f64 * 100.0)
+                        .push(ChannelKind::Brake, telemetry.brake as f64 * 100.0)
+                        .push(ChannelKind::Clutch, telemetry.clutch as f64)
+                        .push(ChannelKind::Steer, telemetry.steer as f64 * 100.0)
+                        .push(ChannelKind::Gear, telemetry.gear.as_number() as f64)
+                        .push(ChannelKind::EngineRpm, telemetry.engine_rpm as f64)
+                        .push_wheels(
+                            ChannelKind::BrakeTemperature,
+                            &telemetry.brake_temp,
+                            |value| value as f64,
+                        );
+                }
+            }
+            f1_2022::F1_2022::LapData(data) => {
+                for car in channels.cars(data.laps.len(), header.player_car_index) {
+                    let lap = match data.car_data(car) {
+                        Some(lap) => lap,
+                        None => continue,
+                    };
+                    channels
+                        .car(car, timestamp)
+                        .push(ChannelKind::LapDistance, lap.lap_distance as f64)
+                        .push(
+                            ChannelKind::LapTime,
+                            lap.current_lap_time_ms as f64 / 1000.0,
+                        );
+                }
+            }
+            f1_2022::F1_2022::Motion(data) => {
+                for car in channels.cars(data.car_motion_data.len(), header.player_car_index) {
+                    let motion = match data.car_data(car) {
+                        Some(motion) => motion,
+                        None => continue,
+                    };
+                    channels
+                        .car(car, timestamp)
+                        .push(ChannelKind::GForceLateral, motion.g_force_lateral as f64)
+                        .push(
+                            ChannelKind::GForceLongitudinal,
+                            motion.g_force_longitudinal as f64,
+                        );
+                }
+                channels
+                    .car(header.player_car_index, timestamp)
+                    .push_wheels(ChannelKind::WheelSpeed, &data.wheel_speed, |value| {
+                        value as f64
+                    });
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ChannelSource for f1_2020::F1_2020 {
+    fn channels(&self, channels: &mut Channels) {
+        let header = self.header();
+        let timestamp = header.session_time as f64;
+        match self {
+            f1_2020::F1_2020::CarTelemetry(data) => {
+                let cars = channels.cars(data.car_telemetry_data.len(), header.player_car_index);
+                for car in cars {
+                    let telemetry = match data.car_data(car) {
+                        Some(telemetry) => telemetry,
+                        None => continue,
+                    };
+                    channels
+                        .car(car, timestamp)
+                        .push(ChannelKind::Speed, telemetry.speed as f64)
+                        .push(ChannelKind::Throttle, telemetry.throttle as f64 * 100.0)
+                        .push(ChannelKind::Brake, telemetry.brake as f64 * 100.0)
+                        .push(ChannelKind::Clutch, telemetry.clutch as f64)
+                        .push(ChannelKind::Steer, telemetry.steer as f64 * 100.0)
+                        .push(ChannelKind::Gear, telemetry.gear.as_number() as f64)
+                        .push(ChannelKind::EngineRpm, telemetry.engine_rpm as f64)
+                        .push_wheels(
+                            ChannelKind::BrakeTemperature,
+                            &telemetry.brake_temp,
+                            |value| value as f64,
+                        );
+                }
+            }
+            f1_2020::F1_2020::LapData(data) => {
+                for car in channels.cars(data.laps.len(), header.player_car_index) {
+                    let lap = match data.car_data(car) {
+                        Some(lap) => lap,
+                        None => continue,
+                    };
+                    channels
+                        .car(car, timestamp)
+                        .push(ChannelKind::LapDistance, lap.lap_distance as f64)
+                        .push(ChannelKind::LapTime, lap.current_lap_time as f64);
+                }
+            }
+            f1_2020::F1_2020::Motion(data) => {
+                for car in channels.cars(data.car_motion_data.len(), header.player_car_index) {
+                    let motion = match data.car_data(car) {
+                        Some(motion) => motion,
+                        None => continue,
+                    };
+                    channels
+                        .car(car, timestamp)
+                        .push(ChannelKind::GForceLateral, motion.g_force_lateral as f64)
+                        .push(
+                            ChannelKind::GForceLongitudinal,
+                            motion.g_force_longitudinal as f64,
+                        );
+                }
+                channels
+                    .car(header.player_car_index, timestamp)
+                    .push_wheels(ChannelKind::WheelSpeed, &data.wheel_speed, |value| {
+                        value as f64
+                    });
+            }
+            _ => {}
+        }
+    }
+}
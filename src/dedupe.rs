@@ -0,0 +1,141 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt::{self, Debug, Write};
+
+use crate::TelemetryFrame;
+
+/// DEFAULT_REORDER_WINDOW is how many frames back a packet can be before
+/// it is considered a restart of the frame counter (ie: a flashback) instead of a late packet
+pub const DEFAULT_REORDER_WINDOW: u32 = 60;
+
+/// ON_DEMAND_PACKETS are the F1 packet ids sent when something happens instead of at a fixed
+/// rate (Event, FinalClassification and LobbyInfo): several of them can share a frame
+/// (ie: a BUTN and a PENA Event), so their frame_identifier does not tell duplicates apart
+pub const ON_DEMAND_PACKETS: [u8; 3] = [3, 8, 9];
+
+/// DuplicateGuard drops packets that UDP delivered twice or out of order
+/// packets are keyed on (session_uid, packet_id) and compared by frame_identifier,
+/// a packet is accepted only if its frame is newer than the last accepted one for that key.
+/// On demand packets (see ON_DEMAND_PACKETS) are keyed on
+/// (session_uid, packet_id, frame_identifier, content) instead, so different packets sharing
+/// a frame are all accepted but the same packet delivered twice is not
+#[derive(Debug)]
+pub struct DuplicateGuard {
+    reorder_window: u32,
+    on_demand: BTreeSet<u8>,
+    last_frames: BTreeMap<(u64, u8), u32>,
+    on_demand_seen: BTreeSet<(u64, u8, u32, u64)>,
+    duplicates: u64,
+    stale: u64,
+}
+
+// ContentHash is a FNV-1a hasher fed by the Debug representation of a packet,
+// which covers every decoded field (floats included) without allocating
+struct ContentHash(u64);
+
+impl ContentHash {
+    fn of<T: Debug>(event: &T) -> u64 {
+        let mut hash = ContentHash(0xcbf2_9ce4_8422_2325);
+        // writing to ContentHash never fails
+        let _ = write!(hash, "{:?}", event);
+        hash.0
+    }
+}
+
+impl Write for ContentHash {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+        Ok(())
+    }
+}
+
+impl Default for DuplicateGuard {
+    fn default() -> Self {
+        DuplicateGuard::new(DEFAULT_REORDER_WINDOW)
+    }
+}
+
+impl DuplicateGuard {
+    /// new initializes a guard, packets older than reorder_window frames are
+    /// treated as a reset of the frame counter and accepted again
+    pub fn new(reorder_window: u32) -> DuplicateGuard {
+        DuplicateGuard {
+            reorder_window,
+            on_demand: ON_DEMAND_PACKETS.iter().copied().collect(),
+            last_frames: BTreeMap::new(),
+            on_demand_seen: BTreeSet::new(),
+            duplicates: 0,
+            stale: 0,
+        }
+    }
+
+    /// with_on_demand_packets replaces the packet ids that are compared by content
+    /// (ON_DEMAND_PACKETS by default)
+    pub fn with_on_demand_packets(mut self, packet_ids: &[u8]) -> DuplicateGuard {
+        self.on_demand = packet_ids.iter().copied().collect();
+        self
+    }
+
+    /// accept returns true if the event should be processed
+    /// and false if it is a duplicate or arrived after a newer packet of the same type
+    pub fn accept<T: TelemetryFrame + Debug>(&mut self, event: &T) -> bool {
+        let key = (event.session_uid(), event.packet_id());
+        let frame = event.frame_identifier();
+
+        if self.on_demand.contains(&event.packet_id()) {
+            return self.accept_on_demand(key, frame, ContentHash::of(event));
+        }
+
+        if let Some(&last_frame) = self.last_frames.get(&key) {
+            if frame == last_frame {
+                self.duplicates += 1;
+                return false;
+            }
+
+            if frame < last_frame && last_frame - frame <= self.reorder_window {
+                self.stale += 1;
+                return false;
+            }
+        }
+
+        self.last_frames.insert(key, frame);
+        true
+    }
+
+    // accept_on_demand remembers the content of on demand packets seen within reorder_window
+    // frames of the current one, older entries are forgotten as new packets arrive
+    fn accept_on_demand(&mut self, key: (u64, u8), frame: u32, content: u64) -> bool {
+        let (session_uid, packet_id) = key;
+        if !self
+            .on_demand_seen
+            .insert((session_uid, packet_id, frame, content))
+        {
+            self.duplicates += 1;
+            return false;
+        }
+
+        let reorder_window = self.reorder_window;
+        self.on_demand_seen.retain(|&(uid, id, seen, _)| {
+            uid != session_uid || id != packet_id || seen.abs_diff(frame) <= reorder_window
+        });
+        true
+    }
+
+    /// duplicates returns how many packets were dropped for being seen already
+    pub fn duplicates(&self) -> u64 {
+        self.duplicates
+    }
+
+    /// stale returns how many packets were dropped for arriving late
+    pub fn stale(&self) -> u64 {
+        self.stale
+    }
+
+    /// reset forgets every frame seen so far
+    pub fn reset(&mut self) {
+        self.last_frames.clear();
+        self.on_demand_seen.clear();
+    }
+}
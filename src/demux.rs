@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use crate::TelemetryFrame;
+
+/// SessionKey identifies one game session sending to a shared port
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionKey {
+    pub session_uid: u64,
+    pub source: Option<SocketAddr>,
+}
+
+/// SessionHandle receives the events of a single session
+pub struct SessionHandle<T> {
+    pub key: SessionKey,
+    pub events: Receiver<T>,
+}
+
+/// SessionDemux routes events from several consoles sending to the same relay port
+/// into one channel per (session_uid, source address).
+/// Sessions whose handle was dropped are forgotten, a new handle is returned if they keep sending.
+pub struct SessionDemux<T> {
+    sessions: HashMap<SessionKey, Sender<T>>,
+}
+
+impl<T> Default for SessionDemux<T> {
+    fn default() -> Self {
+        SessionDemux {
+            sessions: HashMap::new(),
+        }
+    }
+}
+
+impl<T: TelemetryFrame> SessionDemux<T> {
+    pub fn new() -> SessionDemux<T> {
+        SessionDemux::default()
+    }
+
+    /// route sends the event to the channel of its session,
+    /// returns a new SessionHandle the first time a session is seen
+    pub fn route(&mut self, event: T, source: Option<SocketAddr>) -> Option<SessionHandle<T>> {
+        let key = SessionKey {
+            session_uid: event.session_uid(),
+            source,
+        };
+
+        let mut handle = None;
+        let sender = self.sessions.entry(key).or_insert_with(|| {
+            let (sender, events) = channel();
+            handle = Some(SessionHandle { key, events });
+            sender
+        });
+
+        if let Err(returned) = sender.send(event) {
+            // the handle was dropped, start over as a new session
+            self.sessions.remove(&key);
+            return self.route(returned.0, source);
+        }
+
+        handle
+    }
+
+    /// sessions returns the keys of every session currently being routed
+    pub fn sessions(&self) -> impl Iterator<Item = &SessionKey> {
+        self.sessions.keys()
+    }
+
+    /// close stops routing the given session, its handle will see the channel disconnect
+    pub fn close(&mut self, key: &SessionKey) {
+        self.sessions.remove(key);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::dirt::rally2::DirtRally2;
+
+/// StageFinished is emitted once when the car completes a stage, the game sends no such event
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StageFinished {
+    pub stage_time: f32, // seconds, the final time of the stage
+    pub distance: f32,   // metres into the stage when the finish was detected
+    pub length: f32,     // metres, as sent by the game
+}
+
+// how long the stage timer has to stand still (in game clock seconds) to count as finished
+const FREEZE_TIME: f32 = 1.0;
+
+/// FinishDetector tells when a "Dirt Rally 2.0" stage is finished: the distance reaches the stage
+/// length, or the stage timer stops while the game clock keeps running (ie: a stage whose length
+/// is not sent). A restart (the stage timer going back) arms it again
+#[derive(Debug, Clone)]
+pub struct FinishDetector {
+    freeze_time: f32,
+    finished: bool,
+    stage_time: Option<f32>,
+    frozen_since: Option<f32>,
+}
+
+impl Default for FinishDetector {
+    fn default() -> Self {
+        FinishDetector::new()
+    }
+}
+
+impl FinishDetector {
+    pub fn new() -> FinishDetector {
+        FinishDetector {
+            freeze_time: FREEZE_TIME,
+            finished: false,
+            stage_time: None,
+            frozen_since: None,
+        }
+    }
+
+    /// with_freeze_time sets how long (seconds) the stage timer has to stand still, 1 by default
+    pub fn with_freeze_time(mut self, seconds: f32) -> Self {
+        self.freeze_time = seconds;
+        self
+    }
+
+    /// observe feeds a packet, returns the finish of the stage the first time it is detected
+    pub fn observe(&mut self, event: &DirtRally2) -> Option<StageFinished> {
+        let stage_time = event.session.lap_info.current_lap_time;
+        let distance = event.session.lap_info.current_lap_distance;
+        let length = event.session.track.length;
+        let clock = event.session.track.time;
+
+        let previous = self.stage_time.replace(stage_time);
+        if matches!(previous, Some(previous) if stage_time < previous) {
+            self.finished = false; // restarted
+            self.frozen_since = None;
+        }
+        if self.finished {
+            return None;
+        }
+
+        let frozen = match previous {
+            Some(previous) if stage_time == previous && stage_time > 0.0 => {
+                let since = *self.frozen_since.get_or_insert(clock);
+                clock - since >= self.freeze_time
+            }
+            _ => {
+                self.frozen_since = None;
+                false
+            }
+        };
+
+        if (length > 0.0 && distance >= length) || frozen {
+            self.finished = true;
+            return Some(StageFinished {
+                stage_time,
+                distance,
+                length,
+            });
+        }
+        None
+    }
+
+    /// is_finished returns true if the current stage has been finished
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// reset forgets the current stage, ie: when starting a new one
+    pub fn reset(&mut self) {
+        self.finished = false;
+        self.stage_time = None;
+        self.frozen_since = None;
+    }
+}
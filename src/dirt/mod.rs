@@ -1 +1,3 @@
+pub mod finish;
+pub mod pacenotes;
 pub mod rally2;
@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+
+use crate::dirt::rally2::DirtRally2;
+
+/// Milestone is a point of the stage worth a callout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Milestone {
+    Distance(u32),  // metres driven, every configured interval
+    Split(u8),      // split point number, starting at 1
+    Remaining(u32), // metres left to the finish line
+    Finish,
+}
+
+/// ProgressEvent is emitted when the car goes past a milestone
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProgressEvent {
+    pub milestone: Milestone,
+    pub distance: f32,   // metres into the stage where the milestone is
+    pub stage_time: f32, // seconds since the start of the stage when it was crossed
+}
+
+// a distance going back more than this is a restart, not noise
+const RESTART_DISTANCE: f32 = 50.0;
+
+/// StageProgress turns the distance of "Dirt Rally 2.0" packets into progress milestones
+/// (every interval metres, split points and approach to the finish), ie: for co-driver audio
+#[derive(Debug, Clone)]
+pub struct StageProgress {
+    interval: u32,
+    splits: Vec<f32>,
+    finish_approach: Vec<u32>,
+    stage_length: Option<f32>,
+    distance: Option<f32>,
+}
+
+impl StageProgress {
+    /// new emits a milestone every interval metres (0 disables them), the stage length is read
+    /// from the packets
+    pub fn new(interval: u32) -> StageProgress {
+        StageProgress {
+            interval,
+            splits: Vec::new(),
+            finish_approach: Vec::new(),
+            stage_length: None,
+            distance: None,
+        }
+    }
+
+    /// with_splits sets the split points as fractions of the stage (ie: [0.33, 0.66])
+    pub fn with_splits(mut self, splits: Vec<f32>) -> Self {
+        self.splits = splits;
+        self
+    }
+
+    /// with_finish_approach sets the distances to the finish to call (ie: [1000, 500, 200])
+    pub fn with_finish_approach(mut self, remaining: Vec<u32>) -> Self {
+        self.finish_approach = remaining;
+        self
+    }
+
+    /// with_stage_length uses the given length (metres) instead of the one sent by the game
+    pub fn with_stage_length(mut self, length: f32) -> Self {
+        self.stage_length = Some(length);
+        self
+    }
+
+    /// observe feeds a packet, returns the milestones crossed since the previous one, in order
+    pub fn observe(&mut self, event: &DirtRally2) -> Vec<ProgressEvent> {
+        let length = self.stage_length.unwrap_or(event.session.track.length);
+        let distance = event.session.lap_info.current_lap_distance;
+        let stage_time = event.session.lap_info.current_lap_time;
+
+        let previous = match self.distance {
+            Some(previous) if distance >= previous - RESTART_DISTANCE => previous,
+            _ => {
+                // first packet or a restart, only callout what comes next
+                self.distance = Some(distance);
+                return Vec::new();
+            }
+        };
+        if distance <= previous {
+            return Vec::new();
+        }
+        self.distance = Some(distance);
+
+        let mut events = Vec::new();
+        let mut crossed = |milestone: Milestone, at: f32| {
+            if at > previous && at <= distance {
+                events.push(ProgressEvent {
+                    milestone,
+                    distance: at,
+                    stage_time,
+                });
+            }
+        };
+
+        if self.interval > 0 {
+            let interval = self.interval as f32;
+            let mut at = ((previous as u32 / self.interval + 1) * self.interval) as f32;
+            while at <= distance && at < length {
+                crossed(Milestone::Distance(at as u32), at);
+                at += interval;
+            }
+        }
+        for (index, split) in self.splits.iter().enumerate() {
+            crossed(Milestone::Split(index as u8 + 1), split * length);
+        }
+        for &remaining in &self.finish_approach {
+            crossed(Milestone::Remaining(remaining), length - remaining as f32);
+        }
+        crossed(Milestone::Finish, length);
+
+        events.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        events
+    }
+
+    /// reset forgets the progress, ie: when starting a new stage
+    pub fn reset(&mut self) {
+        self.distance = None;
+    }
+}
@@ -1,10 +1,14 @@
-use crate::{TelemetryEvent, TelemetryPacket};
-use std::error::Error;
+use crate::{units, Endianness, TelemetryEvent, TelemetryPacket};
+use alloc::{boxed::Box, format};
+use core::error::Error;
+use core::fmt;
 
-use byteorder::{ByteOrder, LittleEndian};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
 
 /// DirtRally2 implements the codemasters UDP telemetry protocol for "Dirt Rally 2.0"
 /// see: https://docs.google.com/spreadsheets/d/1eA518KHFowYw7tSMa-NxIFYpiWe5JXgVVQ_IMs7BVW0/edit#gid=0 for details on the specification
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DirtRally2 {
     pub car: Car,
     pub session: Session,
@@ -13,17 +17,26 @@ pub struct DirtRally2 {
 
 impl TelemetryEvent for DirtRally2 {
     fn from_packet(packet: &TelemetryPacket) -> Result<DirtRally2, Box<dyn Error>> {
+        Self::from_packet_with_endianness(packet, Endianness::Little)
+    }
+
+    fn from_packet_with_endianness(
+        packet: &TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<DirtRally2, Box<dyn Error>> {
         if packet.len() < 256 {
             return Err(Box::from("Packet size is less than 256 bytes, please set extradata=3 on hardware_settings_config.xml"));
         }
         Ok(DirtRally2 {
-            car: Car::from_packet(&packet)?,
-            session: Session::from_packet(&packet)?,
-            motion: Motion::from_packet(&packet)?,
+            car: Car::from_packet(packet, endianness)?,
+            session: Session::from_packet(packet, endianness)?,
+            motion: Motion::from_packet(packet, endianness)?,
         })
     }
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Session {
     pub position: f32,
     pub location: Coordinate,
@@ -31,10 +44,12 @@ pub struct Session {
     pub lap_info: Lap,
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Car {
     pub speed: f32,
     pub gear: Gear,
-    pub wheels: (Wheel, Wheel, Wheel, Wheel), // Rear-Left, Rear-Right, Front-Left, Front-Right
+    pub wheels: Wheels,
     pub throttle: f32,
     pub steer: f32,
     pub brake: f32,
@@ -42,7 +57,8 @@ pub struct Car {
     pub rpms: f32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Gear {
     Reverse,
     Neutral,
@@ -57,6 +73,8 @@ pub enum Gear {
     Ninth,
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Motion {
     pub velocity: Coordinate,
     pub roll_vector: Coordinate,
@@ -65,6 +83,19 @@ pub struct Motion {
     pub g_force_longitudinal: f32,
 }
 
+/// Wheels are the values of each wheel, the game sends every wheel value rear left first,
+/// then rear right, front left and front right
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Wheels {
+    pub rear_left: Wheel,
+    pub rear_right: Wheel,
+    pub front_left: Wheel,
+    pub front_right: Wheel,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Wheel {
     pub suspension_position: f32,
     pub suspension_velocity: f32,
@@ -72,12 +103,16 @@ pub struct Wheel {
     pub brake_temperature: f32,
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Track {
     pub time: f32,
     pub distance: f32,
     pub length: f32,
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Lap {
     pub current_lap: f32,
     pub total_laps: f32,
@@ -88,157 +123,215 @@ pub struct Lap {
 
 type Coordinate = (f32, f32, f32); // x,y,z coordinates
 
+fn read_f32(bytes: &[u8], endianness: Endianness) -> f32 {
+    match endianness {
+        Endianness::Little => LittleEndian::read_f32(bytes),
+        Endianness::Big => BigEndian::read_f32(bytes),
+    }
+}
+
 impl Car {
-    fn from_packet(packet: &TelemetryPacket) -> Result<Car, Box<dyn Error>> {
+    fn from_packet(
+        packet: &TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<Car, Box<dyn Error>> {
         Ok(Car {
-            speed: LittleEndian::read_f32(&packet[28..32]),
-            throttle: LittleEndian::read_f32(&packet[116..120]),
-            steer: LittleEndian::read_f32(&packet[120..124]),
-            brake: LittleEndian::read_f32(&packet[124..128]),
-            clutch: LittleEndian::read_f32(&packet[128..132]),
-            rpms: LittleEndian::read_f32(&packet[148..152]),
-            gear: Gear::from_f32(LittleEndian::read_f32(&packet[132..136]))?,
-            wheels: (
-                Wheel {
-                    // Rear-Left
-                    suspension_position: LittleEndian::read_f32(&packet[68..72]),
-                    suspension_velocity: LittleEndian::read_f32(&packet[84..88]),
-                    wheel_velocity: LittleEndian::read_f32(&packet[100..104]),
-                    brake_temperature: LittleEndian::read_f32(&packet[204..208]),
-                },
-                Wheel {
-                    // Rear-Right
-                    suspension_position: LittleEndian::read_f32(&packet[72..76]),
-                    suspension_velocity: LittleEndian::read_f32(&packet[88..92]),
-                    wheel_velocity: LittleEndian::read_f32(&packet[104..108]),
-                    brake_temperature: LittleEndian::read_f32(&packet[208..212]),
-                },
-                Wheel {
-                    // Front-Left
-                    suspension_position: LittleEndian::read_f32(&packet[76..80]),
-                    suspension_velocity: LittleEndian::read_f32(&packet[92..96]),
-                    wheel_velocity: LittleEndian::read_f32(&packet[108..112]),
-                    brake_temperature: LittleEndian::read_f32(&packet[212..216]),
-                },
-                Wheel {
-                    // Front-Right
-                    suspension_position: LittleEndian::read_f32(&packet[80..84]),
-                    suspension_velocity: LittleEndian::read_f32(&packet[96..100]),
-                    wheel_velocity: LittleEndian::read_f32(&packet[112..116]),
-                    brake_temperature: LittleEndian::read_f32(&packet[216..220]),
-                },
-            ),
+            speed: read_f32(&packet[28..32], endianness),
+            throttle: read_f32(&packet[116..120], endianness),
+            steer: read_f32(&packet[120..124], endianness),
+            brake: read_f32(&packet[124..128], endianness),
+            clutch: read_f32(&packet[128..132], endianness),
+            rpms: read_f32(&packet[148..152], endianness),
+            gear: Gear::from_f32(read_f32(&packet[132..136], endianness))?,
+            wheels: Wheels {
+                rear_left: Wheel::from_packet(packet, endianness, 0),
+                rear_right: Wheel::from_packet(packet, endianness, 1),
+                front_left: Wheel::from_packet(packet, endianness, 2),
+                front_right: Wheel::from_packet(packet, endianness, 3),
+            },
         })
     }
 }
 
+impl Wheels {
+    /// as_array returns the wheels in the order of the packet: RL, RR, FL, FR
+    pub fn as_array(&self) -> [&Wheel; 4] {
+        [
+            &self.rear_left,
+            &self.rear_right,
+            &self.front_left,
+            &self.front_right,
+        ]
+    }
+}
+
+impl Wheel {
+    // index is the position of the wheel in every wheel value (see Wheels)
+    fn from_packet(packet: &TelemetryPacket, endianness: Endianness, index: usize) -> Wheel {
+        let value = |offset: usize| {
+            let offset = offset + index * 4;
+            read_f32(&packet[offset..offset + 4], endianness)
+        };
+        Wheel {
+            suspension_position: value(68),
+            suspension_velocity: value(84),
+            wheel_velocity: value(100),
+            brake_temperature: value(204),
+        }
+    }
+}
+
+impl Car {
+    /// speed_kph returns the speed in kilometres per hour (speed is in metres per second)
+    pub fn speed_kph(&self) -> f32 {
+        units::mps_to_kph(self.speed)
+    }
+
+    /// speed_mph returns the speed in miles per hour
+    pub fn speed_mph(&self) -> f32 {
+        units::mps_to_mph(self.speed)
+    }
+
+    /// rpm returns the engine speed in revolutions per minute (the game sends rpm / 10)
+    pub fn rpm(&self) -> f32 {
+        self.rpms * 10.0
+    }
+}
+
+impl Wheel {
+    /// wheel_speed_kph returns the speed of the wheel in kilometres per hour
+    pub fn wheel_speed_kph(&self) -> f32 {
+        units::mps_to_kph(self.wheel_velocity)
+    }
+
+    /// wheel_speed_mph returns the speed of the wheel in miles per hour
+    pub fn wheel_speed_mph(&self) -> f32 {
+        units::mps_to_mph(self.wheel_velocity)
+    }
+}
+
 impl Session {
-    fn from_packet(packet: &TelemetryPacket) -> Result<Session, Box<dyn Error>> {
+    fn from_packet(
+        packet: &TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<Session, Box<dyn Error>> {
         Ok(Session {
             location: (
-                LittleEndian::read_f32(&packet[16..20]),
-                LittleEndian::read_f32(&packet[20..24]),
-                LittleEndian::read_f32(&packet[24..28]),
+                read_f32(&packet[16..20], endianness),
+                read_f32(&packet[20..24], endianness),
+                read_f32(&packet[24..28], endianness),
             ),
-            position: LittleEndian::read_f32(&packet[156..160]),
-            track: Track::from_packet(&packet)?,
-            lap_info: Lap::from_packet(&packet)?,
+            position: read_f32(&packet[156..160], endianness),
+            track: Track::from_packet(packet, endianness)?,
+            lap_info: Lap::from_packet(packet, endianness)?,
         })
     }
 }
 
 impl Motion {
-    fn from_packet(packet: &TelemetryPacket) -> Result<Motion, Box<dyn Error>> {
+    fn from_packet(
+        packet: &TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<Motion, Box<dyn Error>> {
         Ok(Motion {
-            g_force_lateral: LittleEndian::read_f32(&packet[136..140]),
-            g_force_longitudinal: LittleEndian::read_f32(&packet[140..144]),
+            g_force_lateral: read_f32(&packet[136..140], endianness),
+            g_force_longitudinal: read_f32(&packet[140..144], endianness),
             pitch_vector: (
-                LittleEndian::read_f32(&packet[56..60]),
-                LittleEndian::read_f32(&packet[60..64]),
-                LittleEndian::read_f32(&packet[64..68]),
+                read_f32(&packet[56..60], endianness),
+                read_f32(&packet[60..64], endianness),
+                read_f32(&packet[64..68], endianness),
             ),
             roll_vector: (
-                LittleEndian::read_f32(&packet[44..48]),
-                LittleEndian::read_f32(&packet[48..52]),
-                LittleEndian::read_f32(&packet[52..56]),
+                read_f32(&packet[44..48], endianness),
+                read_f32(&packet[48..52], endianness),
+                read_f32(&packet[52..56], endianness),
             ),
             velocity: (
-                LittleEndian::read_f32(&packet[32..36]),
-                LittleEndian::read_f32(&packet[36..40]),
-                LittleEndian::read_f32(&packet[40..44]),
+                read_f32(&packet[32..36], endianness),
+                read_f32(&packet[36..40], endianness),
+                read_f32(&packet[40..44], endianness),
             ),
         })
     }
 }
 
 impl Lap {
-    fn from_packet(packet: &TelemetryPacket) -> Result<Lap, Box<dyn Error>> {
+    fn from_packet(
+        packet: &TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<Lap, Box<dyn Error>> {
         Ok(Lap {
-            current_lap_time: LittleEndian::read_f32(&packet[4..8]),
-            current_lap_distance: LittleEndian::read_f32(&packet[8..12]),
-            current_lap: LittleEndian::read_f32(&packet[144..148]),
-            total_laps: LittleEndian::read_f32(&packet[240..244]),
-            last_lap_time: LittleEndian::read_f32(&packet[248..252]),
+            current_lap_time: read_f32(&packet[4..8], endianness),
+            current_lap_distance: read_f32(&packet[8..12], endianness),
+            current_lap: read_f32(&packet[144..148], endianness),
+            total_laps: read_f32(&packet[240..244], endianness),
+            last_lap_time: read_f32(&packet[248..252], endianness),
         })
     }
 }
 
 impl Track {
-    fn from_packet(packet: &TelemetryPacket) -> Result<Track, Box<dyn Error>> {
+    fn from_packet(
+        packet: &TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<Track, Box<dyn Error>> {
         Ok(Track {
-            distance: LittleEndian::read_f32(&packet[12..16]),
-            time: LittleEndian::read_f32(&packet[0..4]),
-            length: LittleEndian::read_f32(&packet[244..248]),
+            distance: read_f32(&packet[12..16], endianness),
+            time: read_f32(&packet[0..4], endianness),
+            length: read_f32(&packet[244..248], endianness),
         })
     }
 }
 
 impl Gear {
+    // the game sends the gear as a float, 10 is reverse (older titles sent -1)
     fn from_f32(f: f32) -> Result<Gear, Box<dyn Error>> {
-        if f < 0.0 {
-            return Ok(Gear::Reverse);
-        }
-
-        if f >= 0.0 && f < 1.0 {
-            return Ok(Gear::Neutral);
+        if f.is_nan() {
+            return Err(Box::from("gear is NaN"));
         }
 
-        if f >= 1.0 && f < 2.0 {
-            return Ok(Gear::First);
-        }
-
-        if f >= 2.0 && f < 3.0 {
-            return Ok(Gear::Second);
-        }
-
-        if f >= 3.0 && f < 4.0 {
-            return Ok(Gear::Third);
-        }
-
-        if f >= 4.0 && f < 5.0 {
-            return Ok(Gear::Fourth);
-        }
-
-        if f >= 5.0 && f < 6.0 {
-            return Ok(Gear::Fifth);
-        }
-
-        if f >= 6.0 && f < 7.0 {
-            return Ok(Gear::Sixth);
-        }
-
-        if f >= 7.0 && f < 8.0 {
-            return Ok(Gear::Seventh);
+        // round half away from zero, f32::round needs std
+        let gear = if f < 0.0 { f - 0.5 } else { f + 0.5 } as i32;
+        match gear {
+            -1 | 10 => Ok(Gear::Reverse),
+            0 => Ok(Gear::Neutral),
+            1 => Ok(Gear::First),
+            2 => Ok(Gear::Second),
+            3 => Ok(Gear::Third),
+            4 => Ok(Gear::Fourth),
+            5 => Ok(Gear::Fifth),
+            6 => Ok(Gear::Sixth),
+            7 => Ok(Gear::Seventh),
+            8 => Ok(Gear::Eigth),
+            9 => Ok(Gear::Ninth),
+            _ => Err(Box::from(format!("gear out of range: {}", f))),
         }
+    }
 
-        if f >= 8.0 && f < 9.0 {
-            return Ok(Gear::Eigth);
+    /// as_number returns the gear as a number, -1 for reverse and 0 for neutral
+    pub fn as_number(&self) -> i8 {
+        match self {
+            Gear::Reverse => -1,
+            Gear::Neutral => 0,
+            Gear::First => 1,
+            Gear::Second => 2,
+            Gear::Third => 3,
+            Gear::Fourth => 4,
+            Gear::Fifth => 5,
+            Gear::Sixth => 6,
+            Gear::Seventh => 7,
+            Gear::Eigth => 8,
+            Gear::Ninth => 9,
         }
+    }
+}
 
-        if f >= 9.0 {
-            return Ok(Gear::Ninth);
+impl fmt::Display for Gear {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Gear::Reverse => f.write_str("R"),
+            Gear::Neutral => f.write_str("N"),
+            gear => write!(f, "{}", gear.as_number()),
         }
-
-        Err(Box::from("unknown gear"))
     }
 }
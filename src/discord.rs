@@ -0,0 +1,163 @@
+use std::error::Error;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use discord_rich_presence::activity::{Activity, Timestamps};
+use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
+
+use crate::f1::f1_2022::F1_2022;
+use crate::f1::util::format_lap_time;
+
+// Discord drops activity updates sent more often than this
+const MIN_UPDATE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// RacePresence is what the profile of the player shows while racing: the track and session
+/// type as details, position, lap and best lap as state
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RacePresence {
+    pub session_uid: u64,
+    pub track: String,        // empty before the first Session packet
+    pub session_type: String, // ie: "Race"
+    pub position: u8,
+    pub lap: u8,
+    pub total_laps: u8,
+    pub best_lap_ms: u32, // 0 before the first lap is completed
+}
+
+impl RacePresence {
+    pub fn new() -> RacePresence {
+        RacePresence::default()
+    }
+
+    /// observe feeds a packet, returns true when the presence changed
+    /// a new session_uid resets the presence
+    pub fn observe(&mut self, event: &F1_2022) -> bool {
+        let previous = self.clone();
+        let session_uid = event.header().session_uid;
+        if session_uid != self.session_uid {
+            *self = RacePresence::new();
+            self.session_uid = session_uid;
+        }
+
+        match event {
+            F1_2022::Session(session) => {
+                self.track = format!("{}", session.track);
+                self.session_type = format!("{}", session.session_type);
+                self.total_laps = session.total_laps;
+            }
+            F1_2022::LapData(data) => {
+                // no player car while spectating
+                if let Some(lap) = data.player_data() {
+                    self.position = lap.car_position;
+                    self.lap = lap.current_lap_number;
+                    let last = lap.last_lap_time_ms;
+                    if last > 0 && (self.best_lap_ms == 0 || last < self.best_lap_ms) {
+                        self.best_lap_ms = last;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        *self != previous
+    }
+
+    /// details returns the first line, ie: "Monza - Race"
+    pub fn details(&self) -> String {
+        match (self.track.is_empty(), self.session_type.is_empty()) {
+            (true, _) => String::from("In a session"),
+            (false, true) => self.track.clone(),
+            (false, false) => format!("{} - {}", self.track, self.session_type),
+        }
+    }
+
+    /// state returns the second line, ie: "P3 - Lap 12/53 - Best 1:23.456"
+    pub fn state(&self) -> String {
+        let mut state = format!("P{} - Lap {}", self.position, self.lap);
+        if self.total_laps > 0 {
+            state.push_str(&format!("/{}", self.total_laps));
+        }
+        if self.best_lap_ms > 0 {
+            state.push_str(&format!(" - Best {}", format_lap_time(self.best_lap_ms)));
+        }
+        state
+    }
+}
+
+/// DiscordPresence keeps the Discord Rich Presence of the player up to date from "F1 22" packets,
+/// it talks to the Discord client running on the same machine. Updates are sent when the
+/// presence changes, at most once every 15 seconds (the Discord rate limit)
+pub struct DiscordPresence {
+    client: DiscordIpcClient,
+    presence: RacePresence,
+    sent: Option<(Instant, RacePresence)>,
+    started: i64, // unix time the session started, shown as elapsed time
+}
+
+impl DiscordPresence {
+    /// connect connects to the Discord client using the application id of a Discord application
+    /// (created on the Discord developer portal, its name is shown as the game being played)
+    pub fn connect(client_id: &str) -> Result<DiscordPresence, Box<dyn Error>> {
+        let mut client = DiscordIpcClient::new(client_id);
+        client.connect()?;
+        Ok(DiscordPresence {
+            client,
+            presence: RacePresence::new(),
+            sent: None,
+            started: 0,
+        })
+    }
+
+    /// observe feeds a packet and sends the presence when it changed and the last update is old
+    /// enough, returns true when an update was sent
+    pub fn observe(&mut self, event: &F1_2022) -> Result<bool, Box<dyn Error>> {
+        let session_uid = self.presence.session_uid;
+        self.presence.observe(event);
+        if self.presence.session_uid != session_uid {
+            self.started = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs() as i64)
+                .unwrap_or_default();
+        }
+
+        match &self.sent {
+            Some((at, sent)) if *sent == self.presence || at.elapsed() < MIN_UPDATE_INTERVAL => {
+                Ok(false)
+            }
+            _ => {
+                self.update()?;
+                Ok(true)
+            }
+        }
+    }
+
+    /// presence returns the latest values, sent or not
+    pub fn presence(&self) -> &RacePresence {
+        &self.presence
+    }
+
+    /// clear removes the presence from the profile of the player
+    pub fn clear(&mut self) -> Result<(), Box<dyn Error>> {
+        self.client.clear_activity()?;
+        self.sent = None;
+        Ok(())
+    }
+
+    fn update(&mut self) -> Result<(), Box<dyn Error>> {
+        let details = self.presence.details();
+        let state = self.presence.state();
+        let activity = Activity::new()
+            .details(details.as_str())
+            .state(state.as_str())
+            .timestamps(Timestamps::new().start(self.started));
+        self.client.set_activity(activity)?;
+        self.sent = Some((Instant::now(), self.presence.clone()));
+        Ok(())
+    }
+}
+
+impl Drop for DiscordPresence {
+    fn drop(&mut self) {
+        let _ = self.client.close();
+    }
+}
@@ -0,0 +1,178 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::f1::f1_2022::{TyreCompound, TyreVisual, F1_2022};
+use crate::f1::util::CarIndex;
+
+/// LAP_CSV_HEADER is the column layout of the per-lap rows, the same used by community
+/// league spreadsheets (times in seconds, fuel in kg, energy in MJ, wear in percentage)
+pub const LAP_CSV_HEADER: &str = "Lap,Lap Time,Sector 1,Sector 2,Sector 3,Valid,Position,\
+Compound,Visual Compound,Tyre Age,Fuel,Fuel Laps,ERS Store,ERS Deployed,\
+ERS Harvested MGU-K,ERS Harvested MGU-H,Wear RL,Wear RR,Wear FL,Wear FR";
+
+/// LapRow is the summary of one completed lap
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LapRow {
+    pub lap: u8,
+    pub lap_time_ms: u32,
+    pub sector_times_ms: (u32, u32, u32),
+    pub valid: bool,
+    pub position: u8,
+    pub tyres_compound: TyreCompound,
+    pub tyres_visual: TyreVisual,
+    pub tyres_age_laps: u8,
+    pub fuel_in_tank: f32,
+    pub fuel_remaining_laps: f32,
+    pub ers_stored: f32,         // Joules
+    pub ers_deployed: f32,       // Joules, this lap
+    pub ers_harvested_mguk: f32, // Joules, this lap
+    pub ers_harvested_mguh: f32, // Joules, this lap
+    pub tyres_wear: [f32; 4],    // RL, RR, FL, FR
+}
+
+impl LapRow {
+    /// to_csv formats the row following LAP_CSV_HEADER (without a trailing new line)
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{:.3},{:.3},{:.3},{:.3},{},{},{},{},{},{:.2},{:.2},{:.3},{:.3},{:.3},{:.3},{:.1},{:.1},{:.1},{:.1}",
+            self.lap,
+            self.lap_time_ms as f32 / 1000.0,
+            self.sector_times_ms.0 as f32 / 1000.0,
+            self.sector_times_ms.1 as f32 / 1000.0,
+            self.sector_times_ms.2 as f32 / 1000.0,
+            self.valid as u8,
+            self.position,
+            self.tyres_compound,
+            self.tyres_visual,
+            self.tyres_age_laps,
+            self.fuel_in_tank,
+            self.fuel_remaining_laps,
+            self.ers_stored / 1_000_000.0,
+            self.ers_deployed / 1_000_000.0,
+            self.ers_harvested_mguk / 1_000_000.0,
+            self.ers_harvested_mguh / 1_000_000.0,
+            self.tyres_wear[0],
+            self.tyres_wear[1],
+            self.tyres_wear[2],
+            self.tyres_wear[3],
+        )
+    }
+}
+
+/// LapCsvRecorder builds one LapRow per lap completed by a car of "F1 22" packets
+/// the row uses the latest LapData, CarStatus and CarDamage seen before the line was crossed
+#[derive(Debug, Clone)]
+pub struct LapCsvRecorder {
+    car_index: Option<CarIndex>,
+    lap_number: u8,
+    pending: LapRow,
+    rows: Vec<LapRow>,
+}
+
+impl LapCsvRecorder {
+    /// new records the laps of the player car
+    pub fn new() -> LapCsvRecorder {
+        LapCsvRecorder {
+            car_index: None,
+            lap_number: 0,
+            pending: LapRow::default(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// for_car records the laps of the car at the given index instead of the player
+    pub fn for_car(car_index: CarIndex) -> LapCsvRecorder {
+        LapCsvRecorder {
+            car_index: Some(car_index),
+            ..LapCsvRecorder::new()
+        }
+    }
+
+    /// observe feeds a packet into the recorder, returns the row of the lap just completed
+    pub fn observe(&mut self, event: &F1_2022) -> Option<&LapRow> {
+        let header = event.header();
+        let index = self.car_index.unwrap_or(header.player_car_index);
+
+        match event {
+            F1_2022::LapData(data) => {
+                let lap = data.car_data(index)?;
+                if lap.current_lap_number != self.lap_number {
+                    let completed = lap.current_lap_number > self.lap_number
+                        && self.lap_number > 0
+                        && lap.last_lap_time_ms > 0;
+                    self.lap_number = lap.current_lap_number;
+
+                    if completed {
+                        let mut row = self.pending.clone();
+                        let (sector1, sector2, _) = row.sector_times_ms;
+                        row.lap_time_ms = lap.last_lap_time_ms;
+                        row.sector_times_ms.2 =
+                            lap.last_lap_time_ms.saturating_sub(sector1 + sector2);
+                        self.rows.push(row);
+                        return self.rows.last();
+                    }
+                }
+
+                let pending = &mut self.pending;
+                pending.lap = lap.current_lap_number;
+                pending.sector_times_ms.0 = lap.sector_time_ms.0 as u32;
+                pending.sector_times_ms.1 = lap.sector_time_ms.1 as u32;
+                pending.valid = !lap.current_lap_invalid;
+                pending.position = lap.car_position;
+            }
+            F1_2022::CarStatus(data) => {
+                let status = data.car_data(index)?;
+                let pending = &mut self.pending;
+                pending.tyres_compound = status.tyres_compound;
+                pending.tyres_visual = status.tyres_visual;
+                pending.tyres_age_laps = status.tyres_ages_lap;
+                pending.fuel_in_tank = status.fuel_in_tank;
+                pending.fuel_remaining_laps = status.fuel_remaining_laps;
+                pending.ers_stored = status.ers_data.stored_energy;
+                pending.ers_deployed = status.ers_data.deployed_this_lap;
+                pending.ers_harvested_mguk = status.ers_data.harvested_this_lap_mguk;
+                pending.ers_harvested_mguh = status.ers_data.harvested_this_lap_mguh;
+            }
+            F1_2022::CarDamage(data) => {
+                let wear = &data.car_data(index)?.tyres_wear;
+                self.pending.tyres_wear = [
+                    wear.rear_left,
+                    wear.rear_right,
+                    wear.front_left,
+                    wear.front_right,
+                ];
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    /// rows returns the completed laps, oldest first
+    pub fn rows(&self) -> &[LapRow] {
+        &self.rows
+    }
+
+    /// to_csv returns the header followed by every completed lap, one per line
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(LAP_CSV_HEADER);
+        csv.push('\n');
+        for row in &self.rows {
+            csv.push_str(&row.to_csv());
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// save writes the csv to the given file
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        std::fs::write(path, self.to_csv())
+    }
+}
+
+impl Default for LapCsvRecorder {
+    fn default() -> Self {
+        LapCsvRecorder::new()
+    }
+}
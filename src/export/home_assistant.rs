@@ -0,0 +1,168 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::export::json_string;
+use crate::f1::f1_2022::{FiaFlag, F1_2022};
+
+/// DEFAULT_DISCOVERY_PREFIX is the topic prefix Home Assistant listens to for discovery messages
+pub const DEFAULT_DISCOVERY_PREFIX: &str = "homeassistant";
+
+// object id, name, unit of measurement (empty for none), icon
+const SENSORS: [(&str, &str, &str, &str); 4] = [
+    ("fuel", "Fuel", "kg", "mdi:gas-station"),
+    ("fuel_laps", "Fuel Laps", "laps", "mdi:gas-station-outline"),
+    ("position", "Position", "", "mdi:podium"),
+    ("flag", "Flag", "", "mdi:flag-checkered"),
+];
+
+/// MqttMessage is a message to publish, this crate does not ship an MQTT client:
+/// hand the messages to the one already in use
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MqttMessage {
+    pub topic: String,
+    pub payload: String,
+    pub retain: bool,
+}
+
+/// HomeAssistantState is the state published for the sensors of the player car
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HomeAssistantState {
+    pub fuel: f32,      // kg
+    pub fuel_laps: f32, // laps left, as shown on the MFD
+    pub position: u8,
+    pub flag: FiaFlag,
+}
+
+/// HomeAssistant maps "F1 22" packets to MQTT messages following the Home Assistant MQTT
+/// discovery convention: once the discovery messages are published the fuel level, position and
+/// flag of the player car show up as sensors of a device, without configuring Home Assistant
+#[derive(Debug, Clone)]
+pub struct HomeAssistant {
+    node_id: String,
+    discovery_prefix: String,
+    state: HomeAssistantState,
+}
+
+impl HomeAssistant {
+    /// new uses node_id (ie: "f1_rig") for the device and its topics,
+    /// it should only contain letters, digits, underscores and dashes
+    pub fn new(node_id: &str) -> HomeAssistant {
+        HomeAssistant {
+            node_id: String::from(node_id),
+            discovery_prefix: String::from(DEFAULT_DISCOVERY_PREFIX),
+            state: HomeAssistantState::default(),
+        }
+    }
+
+    /// with_discovery_prefix changes the discovery prefix configured in Home Assistant
+    pub fn with_discovery_prefix(mut self, prefix: &str) -> Self {
+        self.discovery_prefix = String::from(prefix);
+        self
+    }
+
+    /// state_topic returns the topic the state is published to
+    pub fn state_topic(&self) -> String {
+        format!("cm-telemetry/{}/state", self.node_id)
+    }
+
+    /// discovery returns the retained config message of every sensor,
+    /// publish them once after connecting to the broker
+    pub fn discovery(&self) -> Vec<MqttMessage> {
+        let state_topic = json_string(&self.state_topic());
+        SENSORS
+            .iter()
+            .map(|(object_id, name, unit, icon)| {
+                let unit = if unit.is_empty() {
+                    String::new()
+                } else {
+                    format!(",\"unit_of_measurement\":{}", json_string(unit))
+                };
+                let payload = format!(
+                    concat!(
+                        "{{\"name\":{},\"unique_id\":{},\"state_topic\":{},",
+                        "\"value_template\":\"{{{{ value_json.{} }}}}\",\"icon\":{}{},",
+                        "\"device\":{{\"identifiers\":[{}],\"name\":{},",
+                        "\"manufacturer\":\"cm-telemetry\"}}}}"
+                    ),
+                    json_string(name),
+                    json_string(&format!("{}_{}", self.node_id, object_id)),
+                    state_topic,
+                    object_id,
+                    json_string(icon),
+                    unit,
+                    json_string(&self.node_id),
+                    json_string(&self.node_id),
+                );
+                MqttMessage {
+                    topic: format!(
+                        "{}/sensor/{}/{}/config",
+                        self.discovery_prefix, self.node_id, object_id
+                    ),
+                    payload,
+                    retain: true,
+                }
+            })
+            .collect()
+    }
+
+    /// observe feeds a packet and returns the state message when the state changed
+    pub fn observe(&mut self, event: &F1_2022) -> Option<MqttMessage> {
+        let previous = self.state.clone();
+        // None while spectating, there is no player car
+        let player = event.header().player_car_index.index()?;
+        match event {
+            F1_2022::CarStatus(data) => {
+                let status = data.car_status_data.get(player)?;
+                self.state.fuel = status.fuel_in_tank;
+                self.state.fuel_laps = status.fuel_remaining_laps;
+                self.state.flag = status.vehicle_fia_flag;
+            }
+            F1_2022::LapData(data) => self.state.position = data.laps.get(player)?.car_position,
+            _ => return None,
+        }
+
+        if self.state == previous {
+            None
+        } else {
+            Some(self.state_message())
+        }
+    }
+
+    /// state returns the latest values
+    pub fn state(&self) -> &HomeAssistantState {
+        &self.state
+    }
+
+    /// state_message returns the state as a JSON message for the state topic
+    pub fn state_message(&self) -> MqttMessage {
+        let state = &self.state;
+        let flag = match state.flag {
+            FiaFlag::Green => "green",
+            FiaFlag::Blue => "blue",
+            FiaFlag::Yellow => "yellow",
+            FiaFlag::Red => "red",
+            FiaFlag::None | FiaFlag::Unknown => "none",
+        };
+        MqttMessage {
+            topic: self.state_topic(),
+            payload: format!(
+                "{{\"fuel\":{:.2},\"fuel_laps\":{:.2},\"position\":{},\"flag\":\"{}\"}}",
+                finite(state.fuel),
+                finite(state.fuel_laps),
+                state.position,
+                flag
+            ),
+            retain: false,
+        }
+    }
+}
+
+// JSON has no representation for NaN or infinity
+fn finite(value: f32) -> f32 {
+    if value.is_finite() {
+        value
+    } else {
+        0.0
+    }
+}
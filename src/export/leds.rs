@@ -0,0 +1,164 @@
+use alloc::vec::Vec;
+
+use crate::f1::f1_2022::{FiaFlag, F1_2022};
+use crate::f1::teams::Rgb;
+
+/// REV_LIGHTS is the number of rev lights on the in game steering wheel
+pub const REV_LIGHTS: usize = 15;
+
+/// LedPalette holds the colors used to light the strip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LedPalette {
+    pub low: Rgb,  // first 5 rev lights
+    pub mid: Rgb,  // middle 5 rev lights
+    pub high: Rgb, // last 5 rev lights
+    pub off: Rgb,
+    pub green_flag: Rgb,
+    pub blue_flag: Rgb,
+    pub yellow_flag: Rgb,
+    pub red_flag: Rgb,
+}
+
+impl Default for LedPalette {
+    fn default() -> Self {
+        LedPalette {
+            low: Rgb::from_hex(0x00FF00),
+            mid: Rgb::from_hex(0xFF0000),
+            high: Rgb::from_hex(0x8000FF),
+            off: Rgb::from_hex(0x000000),
+            green_flag: Rgb::from_hex(0x00FF00),
+            blue_flag: Rgb::from_hex(0x0000FF),
+            yellow_flag: Rgb::from_hex(0xFFFF00),
+            red_flag: Rgb::from_hex(0xFF0000),
+        }
+    }
+}
+
+impl LedPalette {
+    /// rev_light returns the color of the rev light at the given position (0 = leftmost)
+    pub fn rev_light(&self, index: usize) -> Rgb {
+        match index * 3 / REV_LIGHTS {
+            0 => self.low,
+            1 => self.mid,
+            _ => self.high,
+        }
+    }
+
+    /// flag returns the color of a flag, None when no flag is shown
+    pub fn flag(&self, flag: FiaFlag) -> Option<Rgb> {
+        match flag {
+            FiaFlag::Green => Some(self.green_flag),
+            FiaFlag::Blue => Some(self.blue_flag),
+            FiaFlag::Yellow => Some(self.yellow_flag),
+            FiaFlag::Red => Some(self.red_flag),
+            FiaFlag::None | FiaFlag::Unknown => None,
+        }
+    }
+}
+
+/// LedFrame is the state of every led of a rev strip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LedFrame {
+    pub rev: [Rgb; REV_LIGHTS], // leftmost first
+    pub flag: Option<Rgb>,
+}
+
+impl LedFrame {
+    /// from_bits lights the rev lights set in rev_lights_bit_value (bit 0 = leftmost)
+    pub fn from_bits(bits: u16, palette: &LedPalette) -> LedFrame {
+        let mut rev = [palette.off; REV_LIGHTS];
+        for (index, led) in rev.iter_mut().enumerate() {
+            if bits & (1 << index) != 0 {
+                *led = palette.rev_light(index);
+            }
+        }
+        LedFrame { rev, flag: None }
+    }
+
+    /// from_percent lights the rev lights from left to right up to rev_lights_percent
+    /// (for games that don't send the bit value, ie: "F1 2020")
+    pub fn from_percent(percent: u8, palette: &LedPalette) -> LedFrame {
+        let lit = (percent.min(100) as usize * REV_LIGHTS + 50) / 100;
+        LedFrame::from_bits(((1u32 << lit) - 1) as u16, palette)
+    }
+
+    /// with_flag sets the color shown on the flag leds
+    pub fn with_flag(mut self, flag: FiaFlag, palette: &LedPalette) -> Self {
+        self.flag = palette.flag(flag);
+        self
+    }
+
+    /// to_adalight encodes the frame with the Adalight serial protocol understood by most
+    /// WS2812 (NeoPixel) controller sketches: rev lights first, then flag_leds leds with the flag
+    pub fn to_adalight(&self, flag_leds: usize, palette: &LedPalette) -> Vec<u8> {
+        let count = REV_LIGHTS + flag_leds;
+        let [hi, lo] = ((count - 1) as u16).to_be_bytes();
+
+        let mut bytes = Vec::with_capacity(6 + count * 3);
+        bytes.extend_from_slice(b"Ada");
+        bytes.extend_from_slice(&[hi, lo, hi ^ lo ^ 0x55]);
+
+        let flag = self.flag.unwrap_or(palette.off);
+        let leds = self
+            .rev
+            .iter()
+            .chain(core::iter::repeat_n(&flag, flag_leds));
+        for led in leds {
+            bytes.extend_from_slice(&[led.r, led.g, led.b]);
+        }
+        bytes
+    }
+}
+
+/// RevStrip turns "F1 22" packets of the player car into led frames
+#[derive(Debug, Default)]
+pub struct RevStrip {
+    palette: LedPalette,
+    flag: FiaFlag,
+    frame: Option<LedFrame>,
+}
+
+impl RevStrip {
+    pub fn new(palette: LedPalette) -> RevStrip {
+        RevStrip {
+            palette,
+            ..RevStrip::default()
+        }
+    }
+
+    /// observe feeds a packet into the strip, returns the new frame when it changed
+    /// CarTelemetry packets drive the rev lights and CarStatus packets the flag
+    pub fn observe(&mut self, event: &F1_2022) -> Option<LedFrame> {
+        let player = event.header().player_car_index;
+
+        let frame = match event {
+            F1_2022::CarTelemetry(data) => {
+                let telemetry = data.car_data(player)?;
+                LedFrame::from_bits(telemetry.rev_lights_bit_value, &self.palette)
+                    .with_flag(self.flag, &self.palette)
+            }
+            F1_2022::CarStatus(data) => {
+                self.flag = data.car_data(player)?.vehicle_fia_flag;
+                self.frame?.with_flag(self.flag, &self.palette)
+            }
+            _ => return None,
+        };
+
+        if self.frame == Some(frame) {
+            return None;
+        }
+        self.frame = Some(frame);
+        self.frame
+    }
+
+    /// frame returns the latest frame
+    pub fn frame(&self) -> Option<&LedFrame> {
+        self.frame.as_ref()
+    }
+
+    pub fn palette(&self) -> &LedPalette {
+        &self.palette
+    }
+}
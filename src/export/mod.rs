@@ -0,0 +1,26 @@
+use alloc::{format, string::String};
+
+pub mod csv;
+pub mod home_assistant;
+pub mod leds;
+pub mod motec;
+pub mod obs;
+pub mod penalties;
+pub mod simhub;
+
+/// json_string quotes and escapes a string as a JSON value
+pub(crate) fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
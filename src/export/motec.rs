@@ -0,0 +1,303 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::f1::f1_2022::F1_2022;
+
+// sizes of the fixed blocks of a MoTeC i2 ".ld" file
+const HEADER_SIZE: usize = 1762;
+const EVENT_SIZE: usize = 1154;
+const VENUE_SIZE: usize = 1100;
+const VEHICLE_SIZE: usize = 260;
+const CHANNEL_SIZE: usize = 124;
+
+/// LdChannel is a single channel of a MoTeC log, sampled at a fixed frequency
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LdChannel {
+    pub name: String,
+    pub short_name: String,
+    pub unit: String,
+    pub frequency: u16, // Hz
+    pub samples: Vec<f32>,
+}
+
+impl LdChannel {
+    pub fn new(name: &str, short_name: &str, unit: &str, frequency: u16) -> LdChannel {
+        LdChannel {
+            name: name.to_string(),
+            short_name: short_name.to_string(),
+            unit: unit.to_string(),
+            frequency,
+            samples: Vec::new(),
+        }
+    }
+}
+
+/// LdMetadata is the session information shown by i2 for a log
+/// date is expected as "dd/mm/yyyy" and time as "hh:mm:ss"
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LdMetadata {
+    pub driver: String,
+    pub vehicle: String,
+    pub venue: String,
+    pub event: String,
+    pub session: String,
+    pub comment: String,
+    pub date: String,
+    pub time: String,
+}
+
+/// LdLog is a MoTeC i2 log (".ld" file) made of float channels
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LdLog {
+    pub metadata: LdMetadata,
+    pub channels: Vec<LdChannel>,
+}
+
+impl LdLog {
+    /// to_bytes serializes the log in the ".ld" format read by MoTeC i2
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let event_ptr = HEADER_SIZE;
+        let venue_ptr = event_ptr + EVENT_SIZE;
+        let vehicle_ptr = venue_ptr + VENUE_SIZE;
+        let meta_ptr = vehicle_ptr + VEHICLE_SIZE;
+        let data_ptr = meta_ptr + CHANNEL_SIZE * self.channels.len();
+
+        let samples: usize = self.channels.iter().map(|c| c.samples.len()).sum();
+        let mut bytes = Vec::with_capacity(data_ptr + samples * 4);
+        let metadata = &self.metadata;
+
+        // header
+        put_u32(&mut bytes, 0x40);
+        put_zeros(&mut bytes, 4);
+        put_u32(&mut bytes, meta_ptr as u32);
+        put_u32(&mut bytes, data_ptr as u32);
+        put_zeros(&mut bytes, 20);
+        put_u32(&mut bytes, event_ptr as u32);
+        put_zeros(&mut bytes, 24);
+        put_u16(&mut bytes, 1);
+        put_u16(&mut bytes, 0x4240);
+        put_u16(&mut bytes, 0xF);
+        put_u32(&mut bytes, 0x1F44); // device serial
+        put_str(&mut bytes, "ADL", 8); // device type
+        put_u16(&mut bytes, 420); // device version
+        put_u16(&mut bytes, 0xADB0);
+        put_u32(&mut bytes, self.channels.len() as u32);
+        put_zeros(&mut bytes, 4);
+        put_str(&mut bytes, &metadata.date, 16);
+        put_zeros(&mut bytes, 16);
+        put_str(&mut bytes, &metadata.time, 16);
+        put_zeros(&mut bytes, 16);
+        put_str(&mut bytes, &metadata.driver, 64);
+        put_str(&mut bytes, &metadata.vehicle, 64);
+        put_zeros(&mut bytes, 64);
+        put_str(&mut bytes, &metadata.venue, 64);
+        put_zeros(&mut bytes, 64 + 1024);
+        put_u32(&mut bytes, 0xC81A4); // enables "pro logging"
+        put_zeros(&mut bytes, 66);
+        put_str(&mut bytes, &metadata.comment, 64);
+        put_zeros(&mut bytes, 126);
+
+        // event, venue and vehicle blocks
+        put_str(&mut bytes, &metadata.event, 64);
+        put_str(&mut bytes, &metadata.session, 64);
+        put_str(&mut bytes, &metadata.comment, 1024);
+        put_u16(&mut bytes, venue_ptr as u16);
+        put_str(&mut bytes, &metadata.venue, 64);
+        put_zeros(&mut bytes, 1034);
+        put_u16(&mut bytes, vehicle_ptr as u16);
+        put_str(&mut bytes, &metadata.vehicle, 64);
+        put_zeros(&mut bytes, 128);
+        put_u32(&mut bytes, 0); // weight
+        put_str(&mut bytes, "", 32); // type
+        put_str(&mut bytes, "", 32); // comment
+
+        // channel metadata, a linked list in file order
+        let mut channel_data_ptr = data_ptr;
+        for (index, channel) in self.channels.iter().enumerate() {
+            let this_ptr = meta_ptr + index * CHANNEL_SIZE;
+            let prev_ptr = if index == 0 {
+                0
+            } else {
+                this_ptr - CHANNEL_SIZE
+            };
+            let next_ptr = if index + 1 == self.channels.len() {
+                0
+            } else {
+                this_ptr + CHANNEL_SIZE
+            };
+
+            put_u32(&mut bytes, prev_ptr as u32);
+            put_u32(&mut bytes, next_ptr as u32);
+            put_u32(&mut bytes, channel_data_ptr as u32);
+            put_u32(&mut bytes, channel.samples.len() as u32);
+            put_u16(&mut bytes, 0x2EE1 + index as u16);
+            put_u16(&mut bytes, 0x07); // float
+            put_u16(&mut bytes, 4); // 32 bits
+            put_u16(&mut bytes, channel.frequency);
+            put_u16(&mut bytes, 0); // shift
+            put_u16(&mut bytes, 1); // multiplier
+            put_u16(&mut bytes, 1); // scale
+            put_u16(&mut bytes, 0); // decimal places
+            put_str(&mut bytes, &channel.name, 32);
+            put_str(&mut bytes, &channel.short_name, 8);
+            put_str(&mut bytes, &channel.unit, 12);
+            put_zeros(&mut bytes, 40);
+
+            channel_data_ptr += channel.samples.len() * 4;
+        }
+
+        // channel data
+        for channel in &self.channels {
+            for sample in &channel.samples {
+                bytes.extend_from_slice(&sample.to_le_bytes());
+            }
+        }
+
+        bytes
+    }
+
+    /// save writes the log to the given ".ld" file
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        std::fs::write(path, self.to_bytes())
+    }
+}
+
+fn put_u16(bytes: &mut Vec<u8>, value: u16) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_zeros(bytes: &mut Vec<u8>, count: usize) {
+    bytes.resize(bytes.len() + count, 0);
+}
+
+/// put_str writes a null padded string of the given width (truncated if longer)
+fn put_str(bytes: &mut Vec<u8>, value: &str, width: usize) {
+    let value = &value.as_bytes()[..value.len().min(width)];
+    bytes.extend_from_slice(value);
+    put_zeros(bytes, width - value.len());
+}
+
+const CHANNELS: [(&str, &str, &str); 13] = [
+    ("Ground Speed", "Speed", "km/h"),
+    ("Throttle Pos", "Throttle", "%"),
+    ("Brake Pos", "Brake", "%"),
+    ("Steering Pos", "Steer", "%"),
+    ("Gear", "Gear", ""),
+    ("Engine RPM", "RPM", "rpm"),
+    ("Lap Distance", "LapDist", "m"),
+    ("Lap Time", "LapTime", "s"),
+    ("Lap Number", "Lap", ""),
+    ("G Force Lat", "GLat", "G"),
+    ("G Force Long", "GLong", "G"),
+    ("Car Pos X", "PosX", "m"),
+    ("Car Pos Z", "PosZ", "m"),
+];
+
+/// MotecRecorder builds a MoTeC log of the player car from "F1 22" packets
+/// a sample of every channel is taken on each CarTelemetry packet, so frequency must match
+/// the "UDP Send Rate" configured in the game
+#[derive(Debug)]
+pub struct MotecRecorder {
+    log: LdLog,
+    lap: [f32; 3],    // distance, time and number of the latest LapData
+    motion: [f32; 4], // lateral G, longitudinal G, x and z of the latest Motion
+}
+
+impl MotecRecorder {
+    pub fn new(frequency: u16) -> MotecRecorder {
+        let channels = CHANNELS
+            .iter()
+            .map(|(name, short_name, unit)| LdChannel::new(name, short_name, unit, frequency))
+            .collect();
+
+        MotecRecorder {
+            log: LdLog {
+                metadata: LdMetadata::default(),
+                channels,
+            },
+            lap: [0.0; 3],
+            motion: [0.0; 4],
+        }
+    }
+
+    /// observe feeds a packet into the recorder, Session and Participants packets fill the metadata
+    pub fn observe(&mut self, event: &F1_2022) {
+        match event {
+            F1_2022::Session(data) => {
+                let metadata = &mut self.log.metadata;
+                metadata.venue = data.track.to_string();
+                metadata.session = data.session_type.to_string();
+            }
+            F1_2022::Participants(data) => {
+                if let Some(player) = data.player_data() {
+                    let metadata = &mut self.log.metadata;
+                    metadata.driver = player.name.clone();
+                    metadata.vehicle = player.team.to_string();
+                }
+            }
+            F1_2022::LapData(data) => {
+                if let Some(lap) = data.player_data() {
+                    self.lap = [
+                        lap.lap_distance,
+                        lap.current_lap_time_ms as f32 / 1000.0,
+                        lap.current_lap_number as f32,
+                    ];
+                }
+            }
+            F1_2022::Motion(data) => {
+                if let Some(motion) = data.player_data() {
+                    self.motion = [
+                        motion.g_force_lateral,
+                        motion.g_force_longitudinal,
+                        motion.world_position.x,
+                        motion.world_position.z,
+                    ];
+                }
+            }
+            F1_2022::CarTelemetry(data) => {
+                if let Some(telemetry) = data.player_data() {
+                    let sample = [
+                        telemetry.speed as f32,
+                        telemetry.throttle * 100.0,
+                        telemetry.brake * 100.0,
+                        telemetry.steer * 100.0,
+                        telemetry.gear.as_number() as f32,
+                        telemetry.engine_rpm as f32,
+                        self.lap[0],
+                        self.lap[1],
+                        self.lap[2],
+                        self.motion[0],
+                        self.motion[1],
+                        self.motion[2],
+                        self.motion[3],
+                    ];
+                    for (channel, value) in self.log.channels.iter_mut().zip(sample) {
+                        channel.samples.push(value);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// metadata_mut gives access to the metadata not found in packets (date, time, event...)
+    pub fn metadata_mut(&mut self) -> &mut LdMetadata {
+        &mut self.log.metadata
+    }
+
+    /// log returns the log recorded so far
+    pub fn log(&self) -> &LdLog {
+        &self.log
+    }
+
+    pub fn into_log(self) -> LdLog {
+        self.log
+    }
+}
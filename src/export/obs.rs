@@ -0,0 +1,207 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::export::json_string;
+use crate::f1::f1_2022::{
+    CarStatus, Lap, LapData, Participants, PitStatus, Session, TyreVisual, F1_2022,
+};
+use crate::f1::util::format_lap_time;
+
+/// DEFAULT_INTERVAL is how often (session seconds) the overlay is refreshed
+pub const DEFAULT_INTERVAL: f32 = 0.5;
+
+/// ObsStanding is a row of the overlay, every value is ready to be shown as is
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ObsStanding {
+    pub position: u8,
+    pub driver: String,
+    pub gap: String, // to the leader, ie: "+1.234" or "+1 LAP", empty for the leader
+    pub interval: String, // to the car ahead, same format as gap
+    pub tyre: &'static str, // S, M, H, I or W, empty before the first CarStatus packet
+    pub tyre_age: u8, // laps
+    pub last_lap: String, // "1:23.456", "-" before the first lap is completed
+    pub in_pit: bool,
+    pub player: bool,
+}
+
+/// ObsOverlay builds the flat JSON document read by OBS browser source overlay templates
+/// (standings with gaps, tyres and last lap) from "F1 22" packets. Serve or write the document
+/// returned by observe, it is rebuilt at most once every interval so the overlay does not redraw
+/// on every packet
+#[derive(Debug)]
+pub struct ObsOverlay {
+    interval: f32,
+    session_uid: u64,
+    last_update: Option<f32>, // session_time of the last document
+    session: Option<Session>,
+    participants: Option<Participants>,
+    status: Option<CarStatus>,
+}
+
+impl Default for ObsOverlay {
+    fn default() -> Self {
+        ObsOverlay::new(DEFAULT_INTERVAL)
+    }
+}
+
+impl ObsOverlay {
+    /// new refreshes the overlay every interval seconds of session time
+    pub fn new(interval: f32) -> ObsOverlay {
+        ObsOverlay {
+            interval,
+            session_uid: 0,
+            last_update: None,
+            session: None,
+            participants: None,
+            status: None,
+        }
+    }
+
+    /// observe feeds a packet and returns the JSON document when it is due,
+    /// LapData packets trigger the updates, a new session_uid resets the overlay
+    pub fn observe(&mut self, event: &F1_2022) -> Option<String> {
+        let header = event.header();
+        if header.session_uid != self.session_uid {
+            *self = ObsOverlay::new(self.interval);
+            self.session_uid = header.session_uid;
+        }
+
+        match event {
+            F1_2022::Session(data) => self.session = Some(data.clone()),
+            F1_2022::Participants(data) => self.participants = Some(data.clone()),
+            F1_2022::CarStatus(data) => self.status = Some(data.clone()),
+            F1_2022::LapData(data) => {
+                let session_time = data.header.session_time;
+                let due = match self.last_update {
+                    Some(last) => session_time - last >= self.interval || session_time < last,
+                    None => true,
+                };
+                if due {
+                    self.last_update = Some(session_time);
+                    return Some(self.to_json(data));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// standings returns the rows of the overlay in race order
+    pub fn standings(&self, laps: &LapData) -> Vec<ObsStanding> {
+        let track_length = self
+            .session
+            .as_ref()
+            .map(|session| session.track_length as f32)
+            .unwrap_or_default();
+        let order = laps.running_order();
+        let leader = order.first().and_then(|&car| laps.car_data(car));
+        let mut ahead: Option<&Lap> = None;
+
+        order
+            .iter()
+            .filter_map(|&car| {
+                let lap = laps.car_data(car)?;
+                let status = self.status.as_ref().and_then(|data| data.car_data(car));
+                let standing = ObsStanding {
+                    position: lap.car_position,
+                    driver: self
+                        .participants
+                        .as_ref()
+                        .and_then(|data| data.car_data(car))
+                        .map(|participant| participant.label())
+                        .unwrap_or_else(|| format!("Car {}", car)),
+                    gap: gap(lap, leader, track_length),
+                    interval: gap(lap, ahead, track_length),
+                    tyre: status.map(|s| tyre_letter(s.tyres_visual)).unwrap_or(""),
+                    tyre_age: status.map(|s| s.tyres_ages_lap).unwrap_or_default(),
+                    last_lap: format_lap_time(lap.last_lap_time_ms),
+                    in_pit: matches!(lap.pit_status, PitStatus::Pitting | PitStatus::InPitArea),
+                    player: car == laps.header.player_car_index,
+                };
+                ahead = Some(lap);
+                Some(standing)
+            })
+            .collect()
+    }
+
+    /// to_json returns the overlay document for the given LapData packet
+    pub fn to_json(&self, laps: &LapData) -> String {
+        let standings: Vec<String> = self
+            .standings(laps)
+            .iter()
+            .map(|standing| {
+                format!(
+                    concat!(
+                        "{{\"position\":{},\"driver\":{},\"gap\":{},\"interval\":{},",
+                        "\"tyre\":{},\"tyre_age\":{},\"last_lap\":{},\"in_pit\":{},",
+                        "\"player\":{}}}"
+                    ),
+                    standing.position,
+                    json_string(&standing.driver),
+                    json_string(&standing.gap),
+                    json_string(&standing.interval),
+                    json_string(standing.tyre),
+                    standing.tyre_age,
+                    json_string(&standing.last_lap),
+                    standing.in_pit,
+                    standing.player,
+                )
+            })
+            .collect();
+
+        let (track, total_laps) = match &self.session {
+            Some(session) => (format!("{}", session.track), session.total_laps),
+            None => (String::new(), 0),
+        };
+        let lap = laps
+            .car_at(1)
+            .and_then(|car| laps.car_data(car))
+            .map(|lap| lap.current_lap_number)
+            .unwrap_or_default();
+
+        format!(
+            "{{\"track\":{},\"lap\":{},\"total_laps\":{},\"standings\":[{}]}}",
+            json_string(&track),
+            lap,
+            total_laps,
+            standings.join(",")
+        )
+    }
+}
+
+// gap formats the distance to the reference car as time, estimated with this car's average
+// speed on its last lap (or on the current lap before the first one is completed)
+fn gap(lap: &Lap, reference: Option<&Lap>, track_length: f32) -> String {
+    let reference = match reference {
+        Some(reference) => reference,
+        None => return String::new(),
+    };
+    let distance = reference.total_distance - lap.total_distance;
+    if distance <= 0.0 {
+        return String::new();
+    }
+    if track_length > 0.0 && distance >= track_length {
+        let laps = (distance / track_length) as u32;
+        return format!("+{} LAP{}", laps, if laps > 1 { "S" } else { "" });
+    }
+
+    let speed = if track_length > 0.0 && lap.last_lap_time_ms > 0 {
+        track_length / (lap.last_lap_time_ms as f32 / 1000.0)
+    } else if lap.current_lap_time_ms > 0 && lap.lap_distance > 0.0 {
+        lap.lap_distance / (lap.current_lap_time_ms as f32 / 1000.0)
+    } else {
+        return String::new();
+    };
+    format!("+{:.3}", distance / speed)
+}
+
+fn tyre_letter(tyre: TyreVisual) -> &'static str {
+    match tyre {
+        TyreVisual::Soft | TyreVisual::F2SuperSoft | TyreVisual::F2Soft => "S",
+        TyreVisual::Medium | TyreVisual::F2Medium | TyreVisual::ClassicDry => "M",
+        TyreVisual::Hard | TyreVisual::F2Hard => "H",
+        TyreVisual::Inter => "I",
+        TyreVisual::Wet | TyreVisual::ClassicWet | TyreVisual::F2Wet => "W",
+        TyreVisual::Unknown => "",
+    }
+}
@@ -0,0 +1,198 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::export::json_string;
+use crate::f1::f1_2022::{
+    EventDataDetail, InfringementType, PenaltyEventDetail, PenaltyType, F1_2022,
+};
+use crate::f1::util::CarIndex;
+
+/// PENALTY_CSV_HEADER is the column layout of PenaltyLog::to_csv
+pub const PENALTY_CSV_HEADER: &str =
+    "Car,Driver,Lap,Session Time,Penalty,Infringement,Time,Places Gained,Other Car,Description";
+
+/// PenaltyRecord is a single penalty (or warning) given to a driver
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PenaltyRecord {
+    pub lap: u8,
+    pub session_time: f32,
+    pub penalty_type: PenaltyType,
+    pub infringement_type: InfringementType,
+    pub time: u8,
+    pub places_gained: u8,
+    pub other_car: Option<CarIndex>,
+    pub description: String,
+}
+
+/// DriverPenalties holds every penalty given to one car in the session, oldest first
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DriverPenalties {
+    pub car: CarIndex,
+    pub driver: String,
+    pub penalties: Vec<PenaltyRecord>,
+}
+
+/// PenaltyLog collects the Penalty events of "F1 22" grouped by driver, for stewards to
+/// review after the session (driver names come from the latest Participants packet)
+#[derive(Debug, Default, Clone)]
+pub struct PenaltyLog {
+    labels: Vec<String>,
+    drivers: Vec<DriverPenalties>, // sorted by car index
+}
+
+impl PenaltyLog {
+    pub fn new() -> PenaltyLog {
+        PenaltyLog::default()
+    }
+
+    /// observe feeds a packet into the log, returns true when the session ended
+    /// (a SessionEnded event or the FinalClassification packet), the time to export it
+    pub fn observe(&mut self, event: &F1_2022) -> bool {
+        match event {
+            F1_2022::Participants(data) => {
+                self.labels = data.participants_data.iter().map(|p| p.label()).collect();
+                let labels = &self.labels;
+                for driver in self.drivers.iter_mut() {
+                    if let Some(label) = driver.car.index().and_then(|index| labels.get(index)) {
+                        driver.driver = label.clone();
+                    }
+                }
+                false
+            }
+            F1_2022::Event(data) => match &data.event_data_details {
+                EventDataDetail::Penalty(penalty) => {
+                    self.push(data.header.session_time, penalty);
+                    false
+                }
+                EventDataDetail::SessionEnded => true,
+                _ => false,
+            },
+            F1_2022::FinalClassification(_) => true,
+            _ => false,
+        }
+    }
+
+    fn push(&mut self, session_time: f32, penalty: &PenaltyEventDetail) {
+        let car = penalty.vehicle_index;
+        let record = PenaltyRecord {
+            lap: penalty.lap_number,
+            session_time,
+            penalty_type: penalty.penalty_type,
+            infringement_type: penalty.infrigement_type,
+            time: penalty.time,
+            places_gained: penalty.places_gained,
+            other_car: Some(penalty.other_vehicle_index).filter(|car| car.is_valid()),
+            description: penalty.description(),
+        };
+
+        match self.drivers.binary_search_by_key(&car, |d| d.car) {
+            Ok(index) => self.drivers[index].penalties.push(record),
+            Err(index) => {
+                let driver = match car.index().and_then(|index| self.labels.get(index)) {
+                    Some(label) if !label.is_empty() => label.clone(),
+                    _ => format!("Car {}", car),
+                };
+                self.drivers.insert(
+                    index,
+                    DriverPenalties {
+                        car,
+                        driver,
+                        penalties: alloc::vec![record],
+                    },
+                );
+            }
+        }
+    }
+
+    /// by_driver returns the penalties grouped by driver, ordered by car index
+    pub fn by_driver(&self) -> &[DriverPenalties] {
+        &self.drivers
+    }
+
+    /// to_csv returns the header followed by one line per penalty, grouped by driver
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(PENALTY_CSV_HEADER);
+        csv.push('\n');
+        for driver in &self.drivers {
+            for penalty in &driver.penalties {
+                csv.push_str(&format!(
+                    "{},{},{},{:.3},{},{},{},{},{},{}\n",
+                    driver.car,
+                    csv_field(&driver.driver),
+                    penalty.lap,
+                    penalty.session_time,
+                    penalty.penalty_type.description(),
+                    penalty.infringement_type.description().unwrap_or(""),
+                    penalty.time,
+                    penalty.places_gained,
+                    penalty
+                        .other_car
+                        .map(|car| format!("{}", car))
+                        .unwrap_or_default(),
+                    csv_field(&penalty.description),
+                ));
+            }
+        }
+        csv
+    }
+
+    /// to_json returns the penalties grouped by driver as a JSON array
+    pub fn to_json(&self) -> String {
+        let drivers: Vec<String> = self
+            .drivers
+            .iter()
+            .map(|driver| {
+                let penalties: Vec<String> = driver
+                    .penalties
+                    .iter()
+                    .map(|penalty| {
+                        format!(
+                            "{{\"lap\":{},\"session_time\":{},\"penalty\":{},\"infringement\":{},\
+                            \"time\":{},\"places_gained\":{},\"other_car\":{},\"description\":{}}}",
+                            penalty.lap,
+                            penalty.session_time,
+                            json_string(penalty.penalty_type.description()),
+                            json_string(penalty.infringement_type.description().unwrap_or("")),
+                            penalty.time,
+                            penalty.places_gained,
+                            penalty
+                                .other_car
+                                .map(|car| format!("{}", car))
+                                .unwrap_or_else(|| String::from("null")),
+                            json_string(&penalty.description),
+                        )
+                    })
+                    .collect();
+                format!(
+                    "{{\"car\":{},\"driver\":{},\"penalties\":[{}]}}",
+                    driver.car,
+                    json_string(&driver.driver),
+                    penalties.join(",")
+                )
+            })
+            .collect();
+        format!("[{}]", drivers.join(","))
+    }
+
+    /// save_csv writes the csv to the given file
+    #[cfg(feature = "std")]
+    pub fn save_csv<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        std::fs::write(path, self.to_csv())
+    }
+
+    /// save_json writes the json to the given file
+    #[cfg(feature = "std")]
+    pub fn save_json<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        std::fs::write(path, self.to_json())
+    }
+}
+
+/// csv_field quotes a field when it contains separators or quotes
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        String::from(value)
+    }
+}
@@ -0,0 +1,177 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use crate::dirt::rally2::DirtRally2;
+use crate::f1::f1_2022::F1_2022;
+
+/// SimHubFrame is the flat set of values SimHub reads from a custom UDP JSON source
+/// names follow the SimHub game data properties so no mapping is needed on its side
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SimHubFrame {
+    pub speed_kmh: f32,
+    pub rpms: f32,
+    pub max_rpm: f32,
+    pub gear: String,  // "R", "N", "1" ... "9"
+    pub throttle: f32, // 0 to 100
+    pub brake: f32,    // 0 to 100
+    pub clutch: f32,   // 0 to 100
+    pub fuel: f32,
+    pub current_lap: u16,
+    pub total_laps: u16,
+    pub position: u16,
+    pub current_lap_time: f32,   // seconds
+    pub last_lap_time: f32,      // seconds
+    pub acceleration_sway: f32,  // lateral G
+    pub acceleration_surge: f32, // longitudinal G
+}
+
+impl SimHubFrame {
+    /// to_json serializes the frame as the JSON object sent to SimHub
+    pub fn to_json(&self) -> String {
+        format!(
+            concat!(
+                "{{\"SpeedKmh\":{},\"Rpms\":{},\"MaxRpm\":{},\"Gear\":\"{}\",",
+                "\"Throttle\":{},\"Brake\":{},\"Clutch\":{},\"Fuel\":{},",
+                "\"CurrentLap\":{},\"TotalLaps\":{},\"Position\":{},",
+                "\"CurrentLapTime\":{},\"LastLapTime\":{},",
+                "\"AccelerationSway\":{},\"AccelerationSurge\":{}}}"
+            ),
+            finite(self.speed_kmh),
+            finite(self.rpms),
+            finite(self.max_rpm),
+            self.gear,
+            finite(self.throttle),
+            finite(self.brake),
+            finite(self.clutch),
+            finite(self.fuel),
+            self.current_lap,
+            self.total_laps,
+            self.position,
+            finite(self.current_lap_time),
+            finite(self.last_lap_time),
+            finite(self.acceleration_sway),
+            finite(self.acceleration_surge),
+        )
+    }
+}
+
+// JSON has no representation for NaN or infinity
+fn finite(value: f32) -> f32 {
+    if value.is_finite() {
+        value
+    } else {
+        0.0
+    }
+}
+
+impl From<&DirtRally2> for SimHubFrame {
+    fn from(event: &DirtRally2) -> Self {
+        let lap = &event.session.lap_info;
+        SimHubFrame {
+            speed_kmh: event.car.speed_kph(),
+            rpms: event.car.rpm(),
+            max_rpm: 0.0,
+            gear: event.car.gear.to_string(),
+            throttle: event.car.throttle * 100.0,
+            brake: event.car.brake * 100.0,
+            clutch: event.car.clutch * 100.0,
+            fuel: 0.0,
+            current_lap: lap.current_lap as u16 + 1,
+            total_laps: lap.total_laps as u16,
+            position: event.session.position as u16,
+            current_lap_time: lap.current_lap_time,
+            last_lap_time: lap.last_lap_time,
+            acceleration_sway: event.motion.g_force_lateral,
+            acceleration_surge: event.motion.g_force_longitudinal,
+        }
+    }
+}
+
+/// SimHubMapper keeps a SimHubFrame of the player car up to date from "F1 22" packets
+/// (the values SimHub needs are spread over several packet types)
+#[derive(Debug, Default)]
+pub struct SimHubMapper {
+    frame: SimHubFrame,
+}
+
+impl SimHubMapper {
+    pub fn new() -> SimHubMapper {
+        SimHubMapper::default()
+    }
+
+    /// observe feeds a packet into the mapper, returns true when the frame changed
+    pub fn observe(&mut self, event: &F1_2022) -> bool {
+        let player = event.header().player_car_index;
+        let frame = &mut self.frame;
+
+        match event {
+            F1_2022::CarTelemetry(data) => match data.car_data(player) {
+                Some(telemetry) => {
+                    frame.speed_kmh = telemetry.speed as f32;
+                    frame.rpms = telemetry.engine_rpm as f32;
+                    frame.gear = telemetry.gear.to_string();
+                    frame.throttle = telemetry.throttle * 100.0;
+                    frame.brake = telemetry.brake * 100.0;
+                    frame.clutch = telemetry.clutch as f32;
+                }
+                None => return false,
+            },
+            F1_2022::CarStatus(data) => match data.car_data(player) {
+                Some(status) => {
+                    frame.max_rpm = status.max_rpm as f32;
+                    frame.fuel = status.fuel_in_tank;
+                }
+                None => return false,
+            },
+            F1_2022::LapData(data) => match data.car_data(player) {
+                Some(lap) => {
+                    frame.current_lap = lap.current_lap_number as u16;
+                    frame.position = lap.car_position as u16;
+                    frame.current_lap_time = lap.current_lap_time_ms as f32 / 1000.0;
+                    frame.last_lap_time = lap.last_lap_time_ms as f32 / 1000.0;
+                }
+                None => return false,
+            },
+            F1_2022::Motion(data) => match data.car_data(player) {
+                Some(motion) => {
+                    frame.acceleration_sway = motion.g_force_lateral;
+                    frame.acceleration_surge = motion.g_force_longitudinal;
+                }
+                None => return false,
+            },
+            F1_2022::Session(data) => frame.total_laps = data.total_laps as u16,
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// frame returns the latest values
+    pub fn frame(&self) -> &SimHubFrame {
+        &self.frame
+    }
+}
+
+/// SimHubEmitter sends frames as JSON datagrams to a SimHub custom UDP source
+#[cfg(feature = "net")]
+pub struct SimHubEmitter {
+    socket: std::net::UdpSocket,
+}
+
+#[cfg(feature = "net")]
+impl SimHubEmitter {
+    /// new sends frames to the given address (ie: "127.0.0.1:20778")
+    pub fn new(target: &str) -> Result<SimHubEmitter, std::io::Error> {
+        let socket = std::net::UdpSocket::bind("0.0.0.0:0")?;
+        socket.connect(target)?;
+        Ok(SimHubEmitter { socket })
+    }
+
+    pub fn send(&self, frame: &SimHubFrame) -> Result<(), std::io::Error> {
+        self.socket.send(frame.to_json().as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use alloc::{collections::BTreeMap, format, string::String};
+
+use crate::f1::{f1_2020, f1_2022};
+
+/// Anonymizer replaces the names (and network ids) of the players before packets are exported
+/// or forwarded, ie: to publish league data. Each sink can use its own Anonymizer, an aliases
+/// map keeps known players recognizable (ie: "Player 1") while the others become "Driver N"
+/// (car index + 1, or the position in the lobby). AI drivers keep their names by default
+#[derive(Debug, Clone, Default)]
+pub struct Anonymizer {
+    aliases: BTreeMap<String, String>, // real name, alias
+    include_ai: bool,
+    keep_network_ids: bool,
+}
+
+impl Anonymizer {
+    pub fn new() -> Anonymizer {
+        Anonymizer::default()
+    }
+
+    /// with_alias replaces the given name by alias instead of "Driver N"
+    pub fn with_alias(mut self, name: &str, alias: &str) -> Self {
+        self.aliases.insert(String::from(name), String::from(alias));
+        self
+    }
+
+    /// with_ai also replaces the names of AI drivers
+    pub fn with_ai(mut self) -> Self {
+        self.include_ai = true;
+        self
+    }
+
+    /// with_network_ids keeps network ids, they are replaced by the car index by default
+    /// (unique in a session, but not across sessions)
+    pub fn with_network_ids(mut self) -> Self {
+        self.keep_network_ids = true;
+        self
+    }
+
+    /// anonymize_2022 replaces the names in "F1 22" Participants and LobbyInfo packets
+    pub fn anonymize_2022(&self, event: &mut f1_2022::F1_2022) {
+        match event {
+            f1_2022::F1_2022::Participants(data) => {
+                for (index, participant) in data.participants_data.iter_mut().enumerate() {
+                    if !self.keep_network_ids {
+                        participant.network_id = index as u8;
+                    }
+                    self.replace(&mut participant.name, participant.ai_controlled, index);
+                }
+            }
+            f1_2022::F1_2022::LobbyInfo(data) => {
+                for (index, player) in data.lobby_players.iter_mut().enumerate() {
+                    self.replace(&mut player.name, player.ai_controlled, index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// anonymize_2020 replaces the names in "F1 2020" Participants and LobbyInfo packets
+    pub fn anonymize_2020(&self, event: &mut f1_2020::F1_2020) {
+        match event {
+            f1_2020::F1_2020::Participants(data) => {
+                for (index, participant) in data.participants_data.iter_mut().enumerate() {
+                    self.replace(&mut participant.name, participant.ai_controlled, index);
+                }
+            }
+            f1_2020::F1_2020::LobbyInfo(data) => {
+                for (index, player) in data.lobby_players.iter_mut().enumerate() {
+                    self.replace(&mut player.name, player.ai_controlled, index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// alias returns the name a player is published under
+    pub fn alias(&self, name: &str, index: usize) -> String {
+        match self.aliases.get(name) {
+            Some(alias) => alias.clone(),
+            None => format!("Driver {}", index + 1),
+        }
+    }
+
+    fn replace(&self, name: &mut String, ai_controlled: bool, index: usize) {
+        if (ai_controlled && !self.include_ai) || name.is_empty() {
+            return;
+        }
+        *name = self.alias(name, index);
+    }
+}
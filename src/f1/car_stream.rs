@@ -0,0 +1,119 @@
+use crate::f1::f1_2022::{
+    CarDamageData, CarMotionData, CarSetupData, CarStatusData, CarTelemetryData,
+    FinalClassificationData, Header, Lap, ParticipantsData, SessionHistory, F1_2022,
+};
+use crate::f1::util::CarIndex;
+
+/// CarSlice is the part of a "F1 22" packet that relates to a single car
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[allow(clippy::large_enum_variant)] // History is one packet per car every few frames, not boxed
+pub enum CarSlice {
+    Motion(CarMotionData),
+    Lap(Lap),
+    Participant(ParticipantsData),
+    Setup(CarSetupData),
+    Telemetry(CarTelemetryData),
+    Status(CarStatusData),
+    Damage(CarDamageData),
+    FinalClassification(FinalClassificationData),
+    History(SessionHistory), // only the SessionHistory packets of the car
+}
+
+/// CarUpdate is the slice of a packet for one car, with the header of the packet
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CarUpdate {
+    pub header: Header,
+    pub slice: CarSlice,
+}
+
+impl F1_2022 {
+    /// car_slice returns the part of the packet for the given car,
+    /// None for packets without per car data (Session, Event, LobbyInfo)
+    /// or SessionHistory packets of other cars
+    pub fn car_slice(&self, car: CarIndex) -> Option<CarSlice> {
+        let index = car.index()?;
+        match self {
+            F1_2022::Motion(data) => data
+                .car_motion_data
+                .get(index)
+                .cloned()
+                .map(CarSlice::Motion),
+            F1_2022::LapData(data) => data.laps.get(index).cloned().map(CarSlice::Lap),
+            F1_2022::Participants(data) => data
+                .participants_data
+                .get(index)
+                .cloned()
+                .map(CarSlice::Participant),
+            F1_2022::CarSetup(data) => data.car_setup_data.get(index).cloned().map(CarSlice::Setup),
+            F1_2022::CarTelemetry(data) => data
+                .car_telemetry_data
+                .get(index)
+                .cloned()
+                .map(CarSlice::Telemetry),
+            F1_2022::CarStatus(data) => data
+                .car_status_data
+                .get(index)
+                .cloned()
+                .map(CarSlice::Status),
+            F1_2022::CarDamage(data) => data
+                .car_damage_data
+                .get(index)
+                .cloned()
+                .map(CarSlice::Damage),
+            F1_2022::FinalClassification(data) => data
+                .final_classification_data
+                .get(index)
+                .cloned()
+                .map(CarSlice::FinalClassification),
+            F1_2022::SessionHistory(data) if data.car_index == car => {
+                Some(CarSlice::History(data.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// car_update returns the car_slice of the packet with its header
+    pub fn car_update(&self, car: CarIndex) -> Option<CarUpdate> {
+        self.car_slice(car).map(|slice| CarUpdate {
+            header: self.header().clone(),
+            slice,
+        })
+    }
+}
+
+/// CarStream receives the packets of a TelemetryServer and only yields the updates of one car,
+/// for consumers that track a single car (ie: a driver dashboard) and do not want to index
+/// the 22 cars of every packet themselves
+#[cfg(feature = "net")]
+pub struct CarStream<'a> {
+    server: &'a crate::TelemetryServer<F1_2022>,
+    car: CarIndex,
+}
+
+#[cfg(feature = "net")]
+impl Iterator for CarStream<'_> {
+    type Item = Result<CarUpdate, alloc::boxed::Box<dyn core::error::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.server.next() {
+                Ok(event) => match event.car_update(self.car) {
+                    Some(update) => return Some(Ok(update)),
+                    None => continue,
+                },
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "net")]
+impl crate::TelemetryServer<F1_2022> {
+    /// car_stream returns an iterator over the updates of the given car,
+    /// packets without data for it are skipped
+    pub fn car_stream(&self, car: CarIndex) -> CarStream<'_> {
+        CarStream { server: self, car }
+    }
+}
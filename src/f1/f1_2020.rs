@@ -1,19 +1,24 @@
-use std::convert::TryFrom;
-use std::error::Error;
-use std::io::Cursor;
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::convert::TryFrom;
+use core::error::Error;
+use core::fmt;
 
 use crate::{
-    TelemetryEvent,
-    TelemetryPacket,
-    f1::util::*,
     f1::macros::*,
+    f1::spec::shared_packets,
+    f1::teams::TeamInfo,
+    f1::tracks::{self, TrackInfo},
+    f1::util::*,
+    Endianness, TelemetryEvent, TelemetryFrame, TelemetryPacket,
 };
 
-use binread::{BinRead, BinReaderExt};
+use binread::BinRead;
 use num_enum::TryFromPrimitive;
 
 /// F1_2020 implements the codemasters UDP telemetry protocol for "F1 2020"
 /// see: https://forums.codemasters.com/topic/50942-f1-2020-udp-specification/
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum F1_2020 {
     Motion(Motion),
     Session(Session),
@@ -27,53 +32,32 @@ pub enum F1_2020 {
     LobbyInfo(LobbyInfo),
 }
 
-#[derive(Debug, Default, BinRead)]
-pub struct Header {
-    pub packet_format: u16,
-    pub game_major_version: u8,
-    pub game_minor_version: u8,
-    pub packet_version: u8,
-    pub packet_id: u8,
-    pub session_uid: u64,
-    pub session_time: f32,
-    pub frame_identifier: u32,
-    pub player_car_index: u8,
-    pub secondary_player_car_index: u8,
+impl F1_2020 {
+    /// header returns the packet header shared by every packet type
+    pub fn header(&self) -> &Header {
+        match self {
+            F1_2020::Motion(data) => &data.header,
+            F1_2020::Session(data) => &data.header,
+            F1_2020::LapData(data) => &data.header,
+            F1_2020::Event(data) => &data.header,
+            F1_2020::Participants(data) => &data.header,
+            F1_2020::CarSetup(data) => &data.header,
+            F1_2020::CarTelemetry(data) => &data.header,
+            F1_2020::CarStatus(data) => &data.header,
+            F1_2020::FinalClassification(data) => &data.header,
+            F1_2020::LobbyInfo(data) => &data.header,
+        }
+    }
 }
 
-#[derive(Debug, BinRead)]
-pub struct Motion {
-    pub header: Header,
-    #[br(count = 22)]
-    pub car_motion_data: Vec<CarMotionData>,
-    pub suspension_position: WheelValue<f32>,
-    pub suspension_velocity: WheelValue<f32>,
-    pub suspension_acceleration: WheelValue<f32>,
-    pub wheel_speed: WheelValue<f32>,
-    pub wheel_slip: WheelValue<f32>,
-    pub local_velocity: Coordinates<f32>,
-    pub angular_velocity: Coordinates<f32>,
-    pub angular_acceleration: Coordinates<f32>,
-    pub front_wheel_angle: f32,
-}
+// packets with the same layout in every year (Header, Motion, CarSetup...), see f1::spec
+shared_packets!();
 
 player_data!(Motion, CarMotionData, car_motion_data);
+active_data!(Motion, CarMotionData, car_motion_data, active_car_motion);
 
-#[derive(Debug, Default, BinRead)]
-pub struct CarMotionData {
-    pub world_position: Coordinates<f32>,
-    pub world_velocity: Coordinates<f32>,
-    pub world_forward_dir: Coordinates<i16>,
-    pub world_right_dir: Coordinates<i16>,
-    pub g_force_lateral: f32,
-    pub g_force_longitudinal: f32,
-    pub g_force_vertical: f32,
-    pub yaw: f32,
-    pub pitch: f32,
-    pub roll: f32,
-}
-
-#[derive(Debug, BinRead)]
+#[derive(Debug, Clone, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Session {
     pub header: Header,
     pub weather: Weather,
@@ -89,7 +73,7 @@ pub struct Session {
     pub pit_speed_limit: u8,
     pub game_paused: u8,
     pub is_spectating: u8,
-    pub spectator_car_index: u8,
+    pub spectator_car_index: CarIndex,
     pub sli_pro_native_support: u8,
     pub number_of_marshal_zones: u8,
     pub marshal_zones: [MarshalZone; 21],
@@ -102,16 +86,49 @@ pub struct Session {
 
 impl Session {
     pub fn current_weather_forecast_sample(&self) -> &WeatherForecastSample {
-        let mut current_weather_forecast_sample_index =
-            self.number_of_weather_forecast_samples as usize;
-        if current_weather_forecast_sample_index > 0 {
-            current_weather_forecast_sample_index -= 1;
-        }
+        let current_weather_forecast_sample_index =
+            self.number_of_weather_forecast_samples.saturating_sub(1) as usize;
         &self.weather_forecast_samples[current_weather_forecast_sample_index]
     }
+
+    /// active_marshal_zones returns the marshal zones in use (number_of_marshal_zones)
+    pub fn active_marshal_zones(&self) -> &[MarshalZone] {
+        let number_of_marshal_zones =
+            (self.number_of_marshal_zones as usize).min(self.marshal_zones.len());
+        &self.marshal_zones[..number_of_marshal_zones]
+    }
+
+    /// active_yellow_sectors iterates over the marshal zones currently showing a yellow flag
+    pub fn active_yellow_sectors(&self) -> impl Iterator<Item = &MarshalZone> {
+        self.active_marshal_zones()
+            .iter()
+            .filter(|zone| zone.zone_flag == ZoneFlag::Yellow)
+    }
+
+    /// flag_at returns the flag of the marshal zone covering the given fraction (0..1) of the lap
+    pub fn flag_at(&self, lap_fraction: f32) -> ZoneFlag {
+        let zones = self.active_marshal_zones();
+        zones
+            .iter()
+            .rev()
+            .find(|zone| zone.zone_start <= lap_fraction)
+            .or_else(|| zones.last()) // before the first zone start we are still in the last zone
+            .map(|zone| zone.zone_flag)
+            .unwrap_or_default()
+    }
+
+    /// spectated_car returns the index of the car being watched when the player is spectating
+    pub fn spectated_car(&self) -> Option<CarIndex> {
+        if self.is_spectating != 0 {
+            Some(self.spectator_car_index)
+        } else {
+            None
+        }
+    }
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Weather {
     Clear,
@@ -126,7 +143,23 @@ pub enum Weather {
 
 binread_enum!(Weather, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl fmt::Display for Weather {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Weather::Clear => "Clear",
+            Weather::LigthCloud => "Light Cloud",
+            Weather::Overcast => "Overcast",
+            Weather::LightRain => "Light Rain",
+            Weather::HeavyRain => "Heavy Rain",
+            Weather::Storm => "Storm",
+            Weather::Unknown => "Unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(i8)]
 pub enum Track {
     #[default]
@@ -162,7 +195,80 @@ pub enum Track {
 
 binread_enum!(Track, i8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl Track {
+    /// info returns the circuit metadata of the track (None when unknown)
+    pub fn info(&self) -> Option<&'static TrackInfo> {
+        match self {
+            Track::Unknown => None,
+            Track::Melbourne => Some(&tracks::MELBOURNE),
+            Track::PaulRicard => Some(&tracks::PAUL_RICARD),
+            Track::Shanghai => Some(&tracks::SHANGHAI),
+            Track::Sakhir => Some(&tracks::SAKHIR),
+            Track::Catalunya => Some(&tracks::CATALUNYA),
+            Track::Monaco => Some(&tracks::MONACO),
+            Track::Montreal => Some(&tracks::MONTREAL),
+            Track::Silverstone => Some(&tracks::SILVERSTONE),
+            Track::Hockenheim => Some(&tracks::HOCKENHEIM),
+            Track::Hungaroring => Some(&tracks::HUNGARORING),
+            Track::Spa => Some(&tracks::SPA),
+            Track::Monza => Some(&tracks::MONZA),
+            Track::Singapore => Some(&tracks::SINGAPORE),
+            Track::Suzuka => Some(&tracks::SUZUKA),
+            Track::AbuDahbi => Some(&tracks::ABU_DHABI),
+            Track::Texas => Some(&tracks::TEXAS),
+            Track::Brazil => Some(&tracks::BRAZIL),
+            Track::Austria => Some(&tracks::AUSTRIA),
+            Track::Sochi => Some(&tracks::SOCHI),
+            Track::Mexico => Some(&tracks::MEXICO),
+            Track::Baku => Some(&tracks::BAKU),
+            Track::SakhirShort => Some(&tracks::SAKHIR_SHORT),
+            Track::SilverstoneShort => Some(&tracks::SILVERSTONE_SHORT),
+            Track::TexasShort => Some(&tracks::TEXAS_SHORT),
+            Track::SuzukaShort => Some(&tracks::SUZUKA_SHORT),
+            Track::Hanoi => Some(&tracks::HANOI),
+            Track::Zandvoort => Some(&tracks::ZANDVOORT),
+        }
+    }
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Track::Unknown => "Unknown",
+            Track::Melbourne => "Melbourne",
+            Track::PaulRicard => "Paul Ricard",
+            Track::Shanghai => "Shanghai",
+            Track::Sakhir => "Sakhir (Bahrain)",
+            Track::Catalunya => "Catalunya",
+            Track::Monaco => "Monaco",
+            Track::Montreal => "Montréal",
+            Track::Silverstone => "Silverstone",
+            Track::Hockenheim => "Hockenheim",
+            Track::Hungaroring => "Hungaroring",
+            Track::Spa => "Spa-Francorchamps",
+            Track::Monza => "Monza",
+            Track::Singapore => "Singapore",
+            Track::Suzuka => "Suzuka",
+            Track::AbuDahbi => "Abu Dhabi",
+            Track::Texas => "Texas (COTA)",
+            Track::Brazil => "Brazil (Interlagos)",
+            Track::Austria => "Austria (Red Bull Ring)",
+            Track::Sochi => "Sochi",
+            Track::Mexico => "Mexico City",
+            Track::Baku => "Baku (Azerbaijan)",
+            Track::SakhirShort => "Sakhir Short",
+            Track::SilverstoneShort => "Silverstone Short",
+            Track::TexasShort => "Texas Short",
+            Track::SuzukaShort => "Suzuka Short",
+            Track::Hanoi => "Hanoi",
+            Track::Zandvoort => "Zandvoort",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Formula {
     F1Modern,
@@ -175,7 +281,8 @@ pub enum Formula {
 
 binread_enum!(Formula, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum SafetyCarStatus {
     NoSafetyCar,
@@ -187,13 +294,8 @@ pub enum SafetyCarStatus {
 
 binread_enum!(SafetyCarStatus, u8);
 
-#[derive(Debug, Default, BinRead)]
-pub struct MarshalZone {
-    pub zone_start: f32,
-    pub zone_flag: ZoneFlag,
-}
-
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(i8)]
 pub enum ZoneFlag {
     #[default]
@@ -207,7 +309,8 @@ pub enum ZoneFlag {
 
 binread_enum!(ZoneFlag, i8);
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct WeatherForecastSample {
     pub session_type: SessionType,
     pub time_offset: u8,
@@ -216,7 +319,8 @@ pub struct WeatherForecastSample {
     pub air_temperature: i8,
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum SessionType {
     #[default]
@@ -237,16 +341,84 @@ pub enum SessionType {
 
 binread_enum!(SessionType, u8);
 
-#[derive(Debug, BinRead)]
+impl fmt::Display for SessionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            SessionType::Unknown => "Unknown",
+            SessionType::Practice1 => "Practice 1",
+            SessionType::Practice2 => "Practice 2",
+            SessionType::Practice3 => "Practice 3",
+            SessionType::ShortPractice => "Short Practice",
+            SessionType::Qualifier1 => "Qualifying 1",
+            SessionType::Qualifier2 => "Qualifying 2",
+            SessionType::Qualifier3 => "Qualifying 3",
+            SessionType::ShortQualifier => "Short Qualifying",
+            SessionType::OSQ => "One-Shot Qualifying",
+            SessionType::Race => "Race",
+            SessionType::R2 => "Race 2",
+            SessionType::TimeTrial => "Time Trial",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct LapData {
     pub header: Header,
-    #[br(count = 22)]
-    pub laps: Vec<Lap>,
+    pub laps: [Lap; 22],
 }
 
 player_data!(LapData, Lap, laps);
 
-#[derive(Debug, Default, BinRead)]
+impl LapData {
+    /// active_laps iterates over the (car index, lap) of the cars on track,
+    /// skipping placeholder entries (result_status invalid or inactive)
+    pub fn active_laps(&self) -> impl Iterator<Item = (CarIndex, &Lap)> {
+        self.laps
+            .iter()
+            .enumerate()
+            .filter(|(_, lap)| {
+                !matches!(
+                    lap.result_status,
+                    ResultStatus::Invalid | ResultStatus::Inactive
+                )
+            })
+            .map(|(index, lap)| (CarIndex(index as u8), lap))
+    }
+
+    /// running_order returns the index of the cars on track ordered by race position
+    pub fn running_order(&self) -> Vec<CarIndex> {
+        let mut running: Vec<(u8, CarIndex)> = self
+            .active_laps()
+            .filter(|(_, lap)| lap.car_position > 0)
+            .map(|(car, lap)| (lap.car_position, car))
+            .collect();
+        running.sort_unstable();
+        running.into_iter().map(|(_, car)| car).collect()
+    }
+
+    /// positions maps every car index to its race position (None for empty slots)
+    pub fn positions(&self) -> [Option<u8>; CarIndex::MAX_CARS] {
+        let mut positions = [None; CarIndex::MAX_CARS];
+        for (car, lap) in self.active_laps() {
+            if let Some(position) = car.index().and_then(|index| positions.get_mut(index)) {
+                *position = Some(lap.car_position).filter(|&position| position > 0);
+            }
+        }
+        positions
+    }
+
+    /// car_at returns the index of the car in the given race position
+    pub fn car_at(&self, position: u8) -> Option<CarIndex> {
+        self.active_laps()
+            .find(|(_, lap)| position > 0 && lap.car_position == position)
+            .map(|(car, _)| car)
+    }
+}
+
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Lap {
     pub last_lap_time: f32,
     pub current_lap_time: f32,
@@ -274,20 +446,23 @@ pub struct Lap {
     pub result_status: ResultStatus,
 }
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BestLapSectorTime {
     pub sector1: u16,
     pub sector2: u16,
     pub sector3: u16,
 }
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BestOverallSectorTime {
     pub sector_time: u16,
     pub lap_number: u8,
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum PitStatus {
     None,
@@ -299,7 +474,8 @@ pub enum PitStatus {
 
 binread_enum!(PitStatus, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Sector {
     Sector1,
@@ -311,7 +487,8 @@ pub enum Sector {
 
 binread_enum!(Sector, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum DriverStatus {
     InGarage,
@@ -325,7 +502,8 @@ pub enum DriverStatus {
 
 binread_enum!(DriverStatus, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum ResultStatus {
     Invalid,
@@ -342,7 +520,8 @@ pub enum ResultStatus {
 
 binread_enum!(ResultStatus, u8);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Event {
     pub header: Header,
     pub event_data_details: EventDataDetail,
@@ -361,29 +540,29 @@ impl binread::BinRead for Event {
 
         // Read next 4 bytes for event string identification
         let event_code_bytes = <[u8; 4]>::read_options(reader, options, args)?;
-        let event_code = std::str::from_utf8(&event_code_bytes).unwrap_or("UNKW");
+        let event_code = core::str::from_utf8(&event_code_bytes).unwrap_or("UNKW");
 
         let event_data_details = match event_code {
             "SSTA" => EventDataDetail::SessionStarted,
             "SEND" => EventDataDetail::SessionEnded,
             "FTLP" => {
-                let idx = <u8>::read_options(reader, options, args)?;
+                let idx = CarIndex::read_options(reader, options, args)?;
                 let time = <f32>::read_options(reader, options, args)?;
                 EventDataDetail::FastestLap(idx, time)
             }
             "RTMT" => {
-                let idx = <u8>::read_options(reader, options, args)?;
+                let idx = CarIndex::read_options(reader, options, args)?;
                 EventDataDetail::Retirement(idx)
             }
             "DRSE" => EventDataDetail::DRSEnabled,
             "DRSD" => EventDataDetail::DRSDisabled,
             "TMPT" => {
-                let idx = <u8>::read_options(reader, options, args)?;
+                let idx = CarIndex::read_options(reader, options, args)?;
                 EventDataDetail::TeamMateInPits(idx)
             }
             "CHQF" => EventDataDetail::ChequeredFlag,
             "RCWN" => {
-                let idx = <u8>::read_options(reader, options, args)?;
+                let idx = CarIndex::read_options(reader, options, args)?;
                 EventDataDetail::RaceWinner(idx)
             }
             "PENA" => {
@@ -391,11 +570,11 @@ impl binread::BinRead for Event {
                 EventDataDetail::Penalty(detail)
             }
             "SPTP" => {
-                let idx = <u8>::read_options(reader, options, args)?;
+                let idx = CarIndex::read_options(reader, options, args)?;
                 let speed = <f32>::read_options(reader, options, args)?;
                 EventDataDetail::SpeedTrap(idx, speed)
             }
-            _ => EventDataDetail::Unknown,
+            _ => EventDataDetail::Unknown(event_code_bytes, read_remaining(reader)?),
         };
 
         Ok(Event {
@@ -405,34 +584,53 @@ impl binread::BinRead for Event {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum EventDataDetail {
     SessionStarted,
     SessionEnded,
-    FastestLap(u8, f32), // time
-    Retirement(u8),      // car_index
+    FastestLap(CarIndex, f32), // time
+    Retirement(CarIndex),      // car_index
     DRSEnabled,
     DRSDisabled,
-    TeamMateInPits(u8), // car_index
+    TeamMateInPits(CarIndex), // car_index
     ChequeredFlag,
-    RaceWinner(u8), // car_index
+    RaceWinner(CarIndex), // car_index
     Penalty(PenaltyEventDetail),
-    SpeedTrap(u8, f32), // car_index, speed
-    Unknown,            // not part of the spec, added to satisfy match
+    SpeedTrap(CarIndex, f32), // car_index, speed
+    Unknown([u8; 4], Vec<u8>), // not part of the spec: event code and payload of events added
+                              // by game patches
 }
 
-#[derive(Debug, Default, BinRead)]
-pub struct PenaltyEventDetail {
-    pub penalty_type: PenaltyType,
-    pub infrigement_type: InfringementType,
-    pub vehicle_index: u8,
-    pub other_vehicle_index: u8,
-    pub time: u8,
-    pub lap_number: u8,
-    pub places_gained: u8,
+impl PenaltyEventDetail {
+    /// description returns a human readable sentence for race control feeds
+    /// eg: "5s time penalty for corner cutting (gained time) on lap 12"
+    pub fn description(&self) -> String {
+        let mut text = match self.penalty_type {
+            PenaltyType::TimePenalty | PenaltyType::StopGo if self.time > 0 => {
+                format!("{}s {}", self.time, self.penalty_type.description())
+            }
+            _ => String::from(self.penalty_type.description()),
+        };
+
+        if let Some(reason) = self.infrigement_type.description() {
+            text.push_str(" for ");
+            text.push_str(reason);
+        }
+
+        if self.lap_number > 0 {
+            text.push_str(&format!(" on lap {}", self.lap_number));
+        }
+
+        if let Some(first) = text.get_mut(..1) {
+            first.make_ascii_uppercase();
+        }
+        text
+    }
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum PenaltyType {
     DriveThrough,
@@ -459,7 +657,37 @@ pub enum PenaltyType {
 
 binread_enum!(PenaltyType, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl PenaltyType {
+    /// description returns the penalty in plain (lowercase) words
+    pub fn description(&self) -> &'static str {
+        match self {
+            PenaltyType::DriveThrough => "drive-through penalty",
+            PenaltyType::StopGo => "stop-go penalty",
+            PenaltyType::GridPenalty => "grid penalty",
+            PenaltyType::PenaltyReminder => "penalty reminder",
+            PenaltyType::TimePenalty => "time penalty",
+            PenaltyType::Warning => "warning",
+            PenaltyType::Disqualified => "disqualification",
+            PenaltyType::RemovedFromFormationLap => "removed from formation lap",
+            PenaltyType::ParkedTooLongTimer => "parked too long timer",
+            PenaltyType::TyreRegulations => "tyre regulations",
+            PenaltyType::ThisLapInvalidated => "lap invalidated",
+            PenaltyType::ThisAndNextLapInvalidated => "this and next lap invalidated",
+            PenaltyType::ThisLapInvalidatedWithNoReason => "lap invalidated",
+            PenaltyType::ThisAndNextLapInvalidatedWithNoReason => "this and next lap invalidated",
+            PenaltyType::ThisAndPreviousLapInvalidated => "this and previous lap invalidated",
+            PenaltyType::ThisAndPreviousLapInvalidatedWithNoReason => {
+                "this and previous lap invalidated"
+            }
+            PenaltyType::Retired => "retired",
+            PenaltyType::BlackFlagTimer => "black flag timer",
+            PenaltyType::Unknown => "penalty",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum InfringementType {
     BlockingBySlowDriving,
@@ -520,17 +748,106 @@ pub enum InfringementType {
 
 binread_enum!(InfringementType, u8);
 
-#[derive(Debug, BinRead)]
-pub struct Participants {
-    pub header: Header,
-    pub num_active_cars: u8,
-    #[br(count = 22)]
-    pub participants_data: Vec<ParticipantsData>,
+impl InfringementType {
+    /// description returns the reason of the infringement in plain (lowercase) words
+    /// (None when unknown)
+    pub fn description(&self) -> Option<&'static str> {
+        let reason = match self {
+            InfringementType::BlockingBySlowDriving => "blocking by slow driving",
+            InfringementType::BlockingByWrongWayDriving => "blocking by driving the wrong way",
+            InfringementType::ReversingOffTheStartLine => "reversing off the start line",
+            InfringementType::BigCollision => "causing a big collision",
+            InfringementType::SmallCollision => "causing a small collision",
+            InfringementType::CollisionFailedToHandBackPositionSingle => {
+                "failing to hand back a position after a collision"
+            }
+            InfringementType::CollisionFailedToHandBackPositionMultiple => {
+                "failing to hand back positions after a collision"
+            }
+            InfringementType::CornerCuttingGainedTime => "corner cutting (gained time)",
+            InfringementType::CornerCuttingOvertakeSingle => "corner cutting (overtake)",
+            InfringementType::CornerCuttingOvertakeMultiple => {
+                "corner cutting (multiple overtakes)"
+            }
+            InfringementType::CrossedPitExitLane => "crossing the pit exit line",
+            InfringementType::IgnoringBlueFlags => "ignoring blue flags",
+            InfringementType::IgnoringYellowFlags => "ignoring yellow flags",
+            InfringementType::IgnoringDriveThrough => "ignoring a drive-through",
+            InfringementType::TooManyDriveThroughs => "too many drive-throughs",
+            InfringementType::DriveThroughReminderServeWithinNLaps => {
+                "a drive-through to serve within the next laps"
+            }
+            InfringementType::DriveThroughReminderServeThisLap => {
+                "a drive-through to serve this lap"
+            }
+            InfringementType::PitLaneSpeeding => "pit lane speeding",
+            InfringementType::ParkedForTooLong => "parking for too long",
+            InfringementType::IgnoringTyreRegulations => "ignoring tyre regulations",
+            InfringementType::TooManyPenalties => "too many penalties",
+            InfringementType::MultipleWarnings => "multiple warnings",
+            InfringementType::ApproachingDisqualification => "approaching disqualification",
+            InfringementType::TyreRegulationsSelectSingle => "tyre regulations (select a compound)",
+            InfringementType::TyreRegulationsSelectMultiple => {
+                "tyre regulations (select compounds)"
+            }
+            InfringementType::LapInvalidatedCornerCutting => "corner cutting",
+            InfringementType::LapInvalidatedRunningWide => "running wide",
+            InfringementType::CornerCuttingRanWideGainedTimeMinor => {
+                "running wide (gained minor time)"
+            }
+            InfringementType::CornerCuttingRanWideGainedTimeSignificant => {
+                "running wide (gained significant time)"
+            }
+            InfringementType::CornerCuttingRanWideGainedTimeExtreme => {
+                "running wide (gained extreme time)"
+            }
+            InfringementType::LapInvalidatedWallRiding => "wall riding",
+            InfringementType::LapInvalidatedFlashbackUsed => "using a flashback",
+            InfringementType::LapInvalidatedResetToTrack => "being reset to track",
+            InfringementType::BlockingThePitlane => "blocking the pit lane",
+            InfringementType::JumpStart => "a jump start",
+            InfringementType::SafetyCarToCarCollision => "a collision with the safety car",
+            InfringementType::SafetyCarIllegalOvertake => {
+                "an illegal overtake under the safety car"
+            }
+            InfringementType::SafetyCarExceedingAllowedPace => "exceeding the safety car pace",
+            InfringementType::VirtualSafetyCarExceedingAllowedPace => {
+                "exceeding the virtual safety car pace"
+            }
+            InfringementType::FormationLapBelowAllowedSpeed => {
+                "driving below the allowed formation lap speed"
+            }
+            InfringementType::RetiredMechanicalFailure => "a mechanical failure",
+            InfringementType::RetiredTerminallyDamaged => "terminal damage",
+            InfringementType::SafetyCarFallingTooFarBack => "falling too far behind the safety car",
+            InfringementType::BlackFlagTimer => "the black flag timer",
+            InfringementType::UnservedStopGoPenalty => "an unserved stop-go penalty",
+            InfringementType::UnservedDriveThroughPenalty => "an unserved drive-through penalty",
+            InfringementType::EngineComponentChange => "an engine component change",
+            InfringementType::GearboxChange => "a gearbox change",
+            InfringementType::LeagueGridPenalty => "a league grid penalty",
+            InfringementType::RetryPenalty => "a retry",
+            InfringementType::IllegalTimeGain => "an illegal time gain",
+            InfringementType::MandatoryPitstop => "a missed mandatory pit stop",
+            InfringementType::Unknown => return None,
+        };
+        Some(reason)
+    }
 }
 
 player_data!(Participants, ParticipantsData, participants_data);
 
-#[derive(Debug, Default, BinRead)]
+impl Participants {
+    /// active_participants returns the participants of the cars on track (num_active_cars),
+    /// indexed by car index
+    pub fn active_participants(&self) -> &[ParticipantsData] {
+        let count = (self.num_active_cars as usize).min(self.participants_data.len());
+        &self.participants_data[..count]
+    }
+}
+
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ParticipantsData {
     #[br(map = |x: u8| x > 0)]
     pub ai_controlled: bool,
@@ -540,8 +857,19 @@ pub struct ParticipantsData {
     pub nationality: Nationality,
     #[br(parse_with = participant_name_parser)]
     pub name: String,
-    #[br(map = |x: u8| x > 1)]
-    pub your_telemetry_restricted: bool,
+    #[br(map = |x: u8| x == 0)]
+    pub your_telemetry_restricted: bool, // The player's UDP setting, 0 = restricted, 1 = public
+}
+
+impl ParticipantsData {
+    /// label returns a short display label for the car, race number and driver code (eg: "33 VER")
+    /// or the participant name for drivers without a code (the player and network humans)
+    pub fn label(&self) -> String {
+        match self.driver.abbreviation() {
+            Some(code) => format!("{} {}", self.race_number, code),
+            None => self.name.clone(),
+        }
+    }
 }
 
 fn participant_name_parser<R: binread::io::Read + binread::io::Seek>(
@@ -552,14 +880,15 @@ fn participant_name_parser<R: binread::io::Read + binread::io::Seek>(
     let mut bytes: [u8; 48] = [0; 48]; // names for participants are 48 bytes wide
     reader.read_exact(&mut bytes)?;
 
-    let driver_name = std::str::from_utf8(&bytes)
+    let driver_name = core::str::from_utf8(&bytes)
         .unwrap_or("UNKW")
         .trim_matches(char::from(0)); // trim any additional null-bytes
 
     Ok(String::from(driver_name))
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Driver {
     CarlosSainz,
@@ -648,7 +977,221 @@ pub enum Driver {
 
 binread_enum!(Driver, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl Driver {
+    /// abbreviation returns the 3-letter code shown on timing screens (eg: "VER")
+    /// None for the player and network humans
+    pub fn abbreviation(&self) -> Option<&'static str> {
+        let code = match self {
+            Driver::CarlosSainz => "SAI",
+            Driver::DaniilKvyat => "KVY",
+            Driver::DanielRicciardo => "RIC",
+            Driver::KimiRaikkonen => "RAI",
+            Driver::LewisHamilton => "HAM",
+            Driver::MaxVerstappen => "VER",
+            Driver::NicoHulkenburg => "HUL",
+            Driver::KevinMagnussen => "MAG",
+            Driver::RomainGrosjean => "GRO",
+            Driver::SebastianVettel => "VET",
+            Driver::SergioPerez => "PER",
+            Driver::ValtteriBottas => "BOT",
+            Driver::EstebanOcon => "OCO",
+            Driver::LanceStroll => "STR",
+            Driver::ArronBarnes => "BAR",
+            Driver::MartinGiles => "GIL",
+            Driver::AlexMurray => "MUR",
+            Driver::LucasRoth => "ROT",
+            Driver::IgorCorreia => "CRR",
+            Driver::SophieLevasseur => "LEV",
+            Driver::JonasSchiffer => "SCH",
+            Driver::AlainForest => "FOR",
+            Driver::JayLetourneau => "LET",
+            Driver::EstoSaari => "SAA",
+            Driver::YasarAtiyeh => "ATI",
+            Driver::CallistoCalabresi => "CLB",
+            Driver::NaotaIzumi => "IZU",
+            Driver::HowardClarke => "CLA",
+            Driver::WilheimKaufmann => "KAU",
+            Driver::MarieLaursen => "LAU",
+            Driver::FlavioNieves => "NIE",
+            Driver::PeterBelousovm => "BEL",
+            Driver::KlimekMichalski => "MIC",
+            Driver::SantiagoMoreno => "MOR",
+            Driver::BenjaminCoppens => "COP",
+            Driver::NoahVisser => "VIS",
+            Driver::GertWaldmuller => "WAL",
+            Driver::JulianQuesada => "QUE",
+            Driver::DanielJones => "JON",
+            Driver::ArtemMarkelov => "MAR",
+            Driver::TadasukeMakino => "MAK",
+            Driver::SeanGelael => "GEL",
+            Driver::NyckDeVries => "DEV",
+            Driver::JackAitken => "AIT",
+            Driver::GeorgeRussell => "RUS",
+            Driver::MaximilianGunther => "GUN",
+            Driver::NireiFukuzumi => "FUK",
+            Driver::LucaGhiotto => "GHI",
+            Driver::LandoNorris => "NOR",
+            Driver::SergioSetteCamara => "SET",
+            Driver::LouisDeletraz => "DEL",
+            Driver::AntonioFuoco => "FUO",
+            Driver::CharlesLeclerc => "LEC",
+            Driver::PierreGasly => "GAS",
+            Driver::AlexanderAlbon => "ALB",
+            Driver::NicholasLatifi => "LAT",
+            Driver::DorianBoccolacci => "BOC",
+            Driver::NikoKari => "KAR",
+            Driver::RobertoMerhi => "MER",
+            Driver::ArjunMaini => "MAI",
+            Driver::AlessioLorandi => "LOR",
+            Driver::RubenMeijer => "MEI",
+            Driver::RashidNair => "NAI",
+            Driver::JackTremblay => "TRE",
+            Driver::AntonioGiovinazzi => "GIO",
+            Driver::RobertKubica => "KUB",
+            Driver::NobuharuMatsushita => "MAT",
+            Driver::NikitaMazepin => "MAZ",
+            Driver::GuanyaZhou => "ZHO",
+            Driver::MickSchumacher => "MSC",
+            Driver::CallumIlott => "ILO",
+            Driver::JuanManuel => "COR",
+            Driver::Correa => "COR",
+            Driver::JordanKing => "KIN",
+            Driver::MahaveerRaghunathan => "RAG",
+            Driver::TatianaCalderon => "CAL",
+            Driver::AnthoineHubert => "HUB",
+            Driver::GuilianoAlesi => "ALE",
+            Driver::RalphBoschung => "BOS",
+            Driver::MyDriver | Driver::Unknown => return None,
+        };
+        Some(code)
+    }
+
+    /// number returns the permanent race number of real world drivers
+    pub fn number(&self) -> Option<u8> {
+        let number = match self {
+            Driver::CarlosSainz => 55,
+            Driver::DaniilKvyat => 26,
+            Driver::DanielRicciardo => 3,
+            Driver::KimiRaikkonen => 7,
+            Driver::LewisHamilton => 44,
+            Driver::MaxVerstappen => 33,
+            Driver::NicoHulkenburg => 27,
+            Driver::KevinMagnussen => 20,
+            Driver::RomainGrosjean => 8,
+            Driver::SebastianVettel => 5,
+            Driver::SergioPerez => 11,
+            Driver::ValtteriBottas => 77,
+            Driver::EstebanOcon => 31,
+            Driver::LanceStroll => 18,
+            Driver::NyckDeVries => 21,
+            Driver::GeorgeRussell => 63,
+            Driver::LandoNorris => 4,
+            Driver::CharlesLeclerc => 16,
+            Driver::PierreGasly => 10,
+            Driver::AlexanderAlbon => 23,
+            Driver::NicholasLatifi => 6,
+            Driver::AntonioGiovinazzi => 99,
+            Driver::RobertKubica => 88,
+            Driver::NikitaMazepin => 9,
+            Driver::GuanyaZhou => 24,
+            Driver::MickSchumacher => 47,
+            _ => return None,
+        };
+        Some(number)
+    }
+}
+
+impl fmt::Display for Driver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Driver::CarlosSainz => "Carlos Sainz",
+            Driver::DaniilKvyat => "Daniil Kvyat",
+            Driver::DanielRicciardo => "Daniel Ricciardo",
+            Driver::KimiRaikkonen => "Kimi Räikkönen",
+            Driver::LewisHamilton => "Lewis Hamilton",
+            Driver::MaxVerstappen => "Max Verstappen",
+            Driver::NicoHulkenburg => "Nico Hülkenberg",
+            Driver::KevinMagnussen => "Kevin Magnussen",
+            Driver::RomainGrosjean => "Romain Grosjean",
+            Driver::SebastianVettel => "Sebastian Vettel",
+            Driver::SergioPerez => "Sergio Pérez",
+            Driver::ValtteriBottas => "Valtteri Bottas",
+            Driver::EstebanOcon => "Esteban Ocon",
+            Driver::LanceStroll => "Lance Stroll",
+            Driver::ArronBarnes => "Arron Barnes",
+            Driver::MartinGiles => "Martin Giles",
+            Driver::AlexMurray => "Alex Murray",
+            Driver::LucasRoth => "Lucas Roth",
+            Driver::IgorCorreia => "Igor Correia",
+            Driver::SophieLevasseur => "Sophie Levasseur",
+            Driver::JonasSchiffer => "Jonas Schiffer",
+            Driver::AlainForest => "Alain Forest",
+            Driver::JayLetourneau => "Jay Letourneau",
+            Driver::EstoSaari => "Esto Saari",
+            Driver::YasarAtiyeh => "Yasar Atiyeh",
+            Driver::CallistoCalabresi => "Callisto Calabresi",
+            Driver::NaotaIzumi => "Naota Izumi",
+            Driver::HowardClarke => "Howard Clarke",
+            Driver::WilheimKaufmann => "Wilheim Kaufmann",
+            Driver::MarieLaursen => "Marie Laursen",
+            Driver::FlavioNieves => "Flavio Nieves",
+            Driver::PeterBelousovm => "Peter Belousov",
+            Driver::KlimekMichalski => "Klimek Michalski",
+            Driver::SantiagoMoreno => "Santiago Moreno",
+            Driver::BenjaminCoppens => "Benjamin Coppens",
+            Driver::NoahVisser => "Noah Visser",
+            Driver::GertWaldmuller => "Gert Waldmüller",
+            Driver::JulianQuesada => "Julian Quesada",
+            Driver::DanielJones => "Daniel Jones",
+            Driver::ArtemMarkelov => "Artem Markelov",
+            Driver::TadasukeMakino => "Tadasuke Makino",
+            Driver::SeanGelael => "Sean Gelael",
+            Driver::NyckDeVries => "Nyck de Vries",
+            Driver::JackAitken => "Jack Aitken",
+            Driver::GeorgeRussell => "George Russell",
+            Driver::MaximilianGunther => "Maximilian Günther",
+            Driver::NireiFukuzumi => "Nirei Fukuzumi",
+            Driver::LucaGhiotto => "Luca Ghiotto",
+            Driver::LandoNorris => "Lando Norris",
+            Driver::SergioSetteCamara => "Sérgio Sette Câmara",
+            Driver::LouisDeletraz => "Louis Delétraz",
+            Driver::AntonioFuoco => "Antonio Fuoco",
+            Driver::CharlesLeclerc => "Charles Leclerc",
+            Driver::PierreGasly => "Pierre Gasly",
+            Driver::AlexanderAlbon => "Alexander Albon",
+            Driver::NicholasLatifi => "Nicholas Latifi",
+            Driver::DorianBoccolacci => "Dorian Boccolacci",
+            Driver::NikoKari => "Niko Kari",
+            Driver::RobertoMerhi => "Roberto Merhi",
+            Driver::ArjunMaini => "Arjun Maini",
+            Driver::AlessioLorandi => "Alessio Lorandi",
+            Driver::RubenMeijer => "Ruben Meijer",
+            Driver::RashidNair => "Rashid Nair",
+            Driver::JackTremblay => "Jack Tremblay",
+            Driver::AntonioGiovinazzi => "Antonio Giovinazzi",
+            Driver::RobertKubica => "Robert Kubica",
+            Driver::NobuharuMatsushita => "Nobuharu Matsushita",
+            Driver::NikitaMazepin => "Nikita Mazepin",
+            Driver::GuanyaZhou => "Guanya Zhou",
+            Driver::MickSchumacher => "Mick Schumacher",
+            Driver::CallumIlott => "Callum Ilott",
+            Driver::JuanManuel => "Juan Manuel",
+            Driver::Correa => "Correa",
+            Driver::JordanKing => "Jordan King",
+            Driver::MahaveerRaghunathan => "Mahaveer Raghunathan",
+            Driver::TatianaCalderon => "Tatiana Calderón",
+            Driver::AnthoineHubert => "Anthoine Hubert",
+            Driver::GuilianoAlesi => "Giuliano Alesi",
+            Driver::RalphBoschung => "Ralph Boschung",
+            Driver::MyDriver => "My Driver",
+            Driver::Unknown => "Unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Team {
     Mercedes,
@@ -717,7 +1260,146 @@ pub enum Team {
 
 binread_enum!(Team, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl Team {
+    /// info returns the short code and livery colors of the team
+    /// (None for generic and player created teams)
+    pub fn info(&self) -> Option<TeamInfo> {
+        let info = match self {
+            Team::Mercedes => TeamInfo::new("MER", 0x00D2BE, 0x000000),
+            Team::Ferrari => TeamInfo::new("FER", 0xDC0000, 0xFFF200),
+            Team::RedBullRacing => TeamInfo::new("RBR", 0x1E41FF, 0xCC1E4A),
+            Team::Williams => TeamInfo::new("WIL", 0x0082FA, 0xFFFFFF),
+            Team::RacingPoint => TeamInfo::new("RPT", 0xF596C8, 0x1E5BC6),
+            Team::Renault => TeamInfo::new("REN", 0xFFF500, 0x000000),
+            Team::AlphaTauri => TeamInfo::new("APT", 0xFFFFFF, 0x2B4562),
+            Team::Haas => TeamInfo::new("HAA", 0x787878, 0xED1C24),
+            Team::McLaren => TeamInfo::new("MCL", 0xFF8700, 0x47C7FC),
+            Team::AlfaRomeo => TeamInfo::new("ALF", 0x960000, 0xFFFFFF),
+            Team::McLaren1988 => TeamInfo::new("MCL", 0xE4002B, 0xFFFFFF),
+            Team::McLaren1991 => TeamInfo::new("MCL", 0xE4002B, 0xFFFFFF),
+            Team::Williams1992 => TeamInfo::new("WIL", 0x1E3F8F, 0xFFF200),
+            Team::Ferrari1995 => TeamInfo::new("FER", 0xDC0000, 0xFFFFFF),
+            Team::Williams1996 => TeamInfo::new("WIL", 0x1E3F8F, 0xFFF200),
+            Team::McLaren1998 => TeamInfo::new("MCL", 0xC0C0C0, 0x000000),
+            Team::Ferrari2002 => TeamInfo::new("FER", 0xDC0000, 0xFFFFFF),
+            Team::Ferrari2004 => TeamInfo::new("FER", 0xDC0000, 0xFFFFFF),
+            Team::Renault2006 => TeamInfo::new("REN", 0x0055A4, 0xFFD200),
+            Team::Ferrari2007 => TeamInfo::new("FER", 0xDC0000, 0xFFFFFF),
+            Team::McLaren2008 => TeamInfo::new("MCL", 0xC0C0C0, 0xE4002B),
+            Team::RedBull2010 => TeamInfo::new("RBR", 0x1E2A5E, 0xCC1E4A),
+            Team::Ferrari1976 => TeamInfo::new("FER", 0xDC0000, 0xFFFFFF),
+            Team::ARTGrandPrix => TeamInfo::new("ART", 0x5A5A5A, 0xFFFFFF),
+            Team::CamposVexatecRacing => TeamInfo::new("CAM", 0x00205B, 0xFFD200),
+            Team::Carlin => TeamInfo::new("CAR", 0x0A2240, 0xFFFFFF),
+            Team::CharouzRacingSystem => TeamInfo::new("CHA", 0x9B0000, 0xFFFFFF),
+            Team::DAMS => TeamInfo::new("DAM", 0x00AEEF, 0xFFFFFF),
+            Team::RussianTime => TeamInfo::new("RTM", 0xE4002B, 0x003DA5),
+            Team::MPMotorsport => TeamInfo::new("MPM", 0xFF5F00, 0x000000),
+            Team::Pertamina => TeamInfo::new("PRE", 0xE4002B, 0xFFFFFF),
+            Team::McLaren1990 => TeamInfo::new("MCL", 0xE4002B, 0xFFFFFF),
+            Team::Trident => TeamInfo::new("TRI", 0x00205B, 0xC0C0C0),
+            Team::BWTArden => TeamInfo::new("ARD", 0xF596C8, 0xFFFFFF),
+            Team::McLaren1976 => TeamInfo::new("MCL", 0xE4002B, 0xFFFFFF),
+            Team::Lotus1972 => TeamInfo::new("LOT", 0x000000, 0xC9A227),
+            Team::Ferrari1979 => TeamInfo::new("FER", 0xDC0000, 0xFFFFFF),
+            Team::McLaren1982 => TeamInfo::new("MCL", 0xE4002B, 0xFFFFFF),
+            Team::Williams2003 => TeamInfo::new("WIL", 0x1E3F8F, 0xFFFFFF),
+            Team::Brawn2009 => TeamInfo::new("BGP", 0xFFFFFF, 0xB8FD6E),
+            Team::Lotus1978 => TeamInfo::new("LOT", 0x000000, 0xC9A227),
+            Team::ArtGP19 => TeamInfo::new("ART", 0x5A5A5A, 0xFFFFFF),
+            Team::Campos19 => TeamInfo::new("CAM", 0x00205B, 0xFFD200),
+            Team::Carlin19 => TeamInfo::new("CAR", 0x0A2240, 0xFFFFFF),
+            Team::SauberJuniorCharouz19 => TeamInfo::new("CHA", 0x9B0000, 0xFFFFFF),
+            Team::Dams19 => TeamInfo::new("DAM", 0x00AEEF, 0xFFFFFF),
+            Team::UniVirtuosi19 => TeamInfo::new("VIR", 0xFFD700, 0x000000),
+            Team::MPMotorsport19 => TeamInfo::new("MPM", 0xFF5F00, 0x000000),
+            Team::Prema19 => TeamInfo::new("PRE", 0xE4002B, 0xFFFFFF),
+            Team::Trident19 => TeamInfo::new("TRI", 0x00205B, 0xC0C0C0),
+            Team::Arden19 => TeamInfo::new("ARD", 0xF596C8, 0xFFFFFF),
+            Team::Benetton1994 => TeamInfo::new("BEN", 0x00A651, 0x3FA9F5),
+            Team::Benetton1995 => TeamInfo::new("BEN", 0x3FA9F5, 0x00A651),
+            Team::Ferrari2000 => TeamInfo::new("FER", 0xDC0000, 0xFFFFFF),
+            Team::Jordan1991 => TeamInfo::new("JOR", 0x008A47, 0x0055A4),
+            Team::Ferrari1990 => TeamInfo::new("FER", 0xDC0000, 0xFFFFFF),
+            Team::McLaren2010 => TeamInfo::new("MCL", 0xC0C0C0, 0xE4002B),
+            Team::Ferrari2010 => TeamInfo::new("FER", 0xDC0000, 0xFFFFFF),
+            Team::F1GenericCar | Team::Unknown | Team::MyTeam => return None,
+        };
+        Some(info)
+    }
+}
+
+impl fmt::Display for Team {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Team::Mercedes => "Mercedes",
+            Team::Ferrari => "Ferrari",
+            Team::RedBullRacing => "Red Bull Racing",
+            Team::Williams => "Williams",
+            Team::RacingPoint => "Racing Point",
+            Team::Renault => "Renault",
+            Team::AlphaTauri => "AlphaTauri",
+            Team::Haas => "Haas",
+            Team::McLaren => "McLaren",
+            Team::AlfaRomeo => "Alfa Romeo",
+            Team::McLaren1988 => "McLaren 1988",
+            Team::McLaren1991 => "McLaren 1991",
+            Team::Williams1992 => "Williams 1992",
+            Team::Ferrari1995 => "Ferrari 1995",
+            Team::Williams1996 => "Williams 1996",
+            Team::McLaren1998 => "McLaren 1998",
+            Team::Ferrari2002 => "Ferrari 2002",
+            Team::Ferrari2004 => "Ferrari 2004",
+            Team::Renault2006 => "Renault 2006",
+            Team::Ferrari2007 => "Ferrari 2007",
+            Team::McLaren2008 => "McLaren 2008",
+            Team::RedBull2010 => "Red Bull 2010",
+            Team::Ferrari1976 => "Ferrari 1976",
+            Team::ARTGrandPrix => "ART Grand Prix",
+            Team::CamposVexatecRacing => "Campos Vexatec Racing",
+            Team::Carlin => "Carlin",
+            Team::CharouzRacingSystem => "Charouz Racing System",
+            Team::DAMS => "DAMS",
+            Team::RussianTime => "Russian Time",
+            Team::MPMotorsport => "MP Motorsport",
+            Team::Pertamina => "Pertamina",
+            Team::McLaren1990 => "McLaren 1990",
+            Team::Trident => "Trident",
+            Team::BWTArden => "BWT Arden",
+            Team::McLaren1976 => "McLaren 1976",
+            Team::Lotus1972 => "Lotus 1972",
+            Team::Ferrari1979 => "Ferrari 1979",
+            Team::McLaren1982 => "McLaren 1982",
+            Team::Williams2003 => "Williams 2003",
+            Team::Brawn2009 => "Brawn 2009",
+            Team::Lotus1978 => "Lotus 1978",
+            Team::F1GenericCar => "F1 Generic Car",
+            Team::ArtGP19 => "ART GP '19",
+            Team::Campos19 => "Campos '19",
+            Team::Carlin19 => "Carlin '19",
+            Team::SauberJuniorCharouz19 => "Sauber Junior Charouz '19",
+            Team::Dams19 => "DAMS '19",
+            Team::UniVirtuosi19 => "UNI-Virtuosi '19",
+            Team::MPMotorsport19 => "MP Motorsport '19",
+            Team::Prema19 => "Prema '19",
+            Team::Trident19 => "Trident '19",
+            Team::Arden19 => "Arden '19",
+            Team::Benetton1994 => "Benetton 1994",
+            Team::Benetton1995 => "Benetton 1995",
+            Team::Ferrari2000 => "Ferrari 2000",
+            Team::Jordan1991 => "Jordan 1991",
+            Team::Ferrari1990 => "Ferrari 1990",
+            Team::McLaren2010 => "McLaren 2010",
+            Team::Ferrari2010 => "Ferrari 2010",
+            Team::Unknown => "Unknown",
+            Team::MyTeam => "My Team",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Nationality {
     #[default]
@@ -814,54 +1496,39 @@ pub enum Nationality {
 
 binread_enum!(Nationality, u8);
 
-#[derive(Debug, BinRead)]
-pub struct CarSetup {
-    pub header: Header,
-    #[br(count = 22)]
-    pub car_setup_data: Vec<CarSetupData>,
-}
-
-#[derive(Debug, Default, BinRead)]
-pub struct CarSetupData {
-    pub wing: FrontRearValue<u8>,
-    pub on_throttle: u8,
-    pub off_throttle: u8,
-    pub camber: FrontRearValue<f32>,
-    pub toe: FrontRearValue<f32>,
-    pub suspension: FrontRearValue<u8>,
-    pub anti_roll_bar: FrontRearValue<u8>,
-    pub suspension_height: FrontRearValue<u8>,
-    pub brake_pressure: u8,
-    pub brake_bias: u8,
-    pub type_pressure: WheelValue<f32>,
-    pub ballast: u8,
-    pub fuel_load: f32,
-}
-
 player_data!(CarSetup, CarSetupData, car_setup_data);
+active_data!(CarSetup, CarSetupData, car_setup_data, active_car_setups);
 
-#[derive(Debug, BinRead)]
+#[derive(Debug, Clone, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CarTelemetry {
     pub header: Header,
-    #[br(count = 22)]
-    pub car_telemetry_data: Vec<CarTelemetryData>,
-    pub button_status: u32,
+    pub car_telemetry_data: [CarTelemetryData; 22],
+    #[br(map = ButtonFlags::from_bits_retain)]
+    pub button_status: ButtonFlags,
     pub mfd_panel: MFDPanel,
     pub mfd_panel_secondary_player: MFDPanel,
-    #[br(map = |x: i8| if x == 0 { Gear::Unknown } else { Gear::try_from(x).unwrap() })]
+    #[br(map = |x: i8| if x == 0 { Gear::Unknown } else { Gear::try_from(x).unwrap_or_default() })]
     pub suggested_gear: Gear,
 }
 
 player_data!(CarTelemetry, CarTelemetryData, car_telemetry_data);
+active_data!(
+    CarTelemetry,
+    CarTelemetryData,
+    car_telemetry_data,
+    active_car_telemetry
+);
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CarTelemetryData {
     pub speed: u16,
     pub throttle: f32,
     pub steer: f32,
     pub brake: f32,
     pub clutch: u8,
-    #[br(map = |x: i8| Gear::try_from(x).unwrap())]
+    #[br(map = |x: i8| Gear::try_from(x).unwrap_or_default())]
     pub gear: Gear,
     pub engine_rpm: u16,
     #[br(map = |x: u8| x > 0)]
@@ -892,7 +1559,8 @@ fn surface_type_parser<R: binread::io::Read + binread::io::Seek>(
     })
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(i8)]
 pub enum Gear {
     Reverse = -1,
@@ -911,7 +1579,28 @@ pub enum Gear {
 
 binread_enum!(Gear, i8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl Gear {
+    /// as_number returns the gear as a number, -1 for reverse and 0 for neutral (or unknown)
+    pub fn as_number(&self) -> i8 {
+        match self {
+            Gear::Unknown => 0,
+            gear => *gear as i8,
+        }
+    }
+}
+
+impl fmt::Display for Gear {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Gear::Reverse => f.write_str("R"),
+            Gear::Neutral | Gear::Unknown => f.write_str("N"),
+            gear => write!(f, "{}", gear.as_number()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Surface {
     Tarmac,
@@ -932,7 +1621,8 @@ pub enum Surface {
 
 binread_enum!(Surface, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum MFDPanel {
     CarSetup,
@@ -947,16 +1637,11 @@ pub enum MFDPanel {
 
 binread_enum!(MFDPanel, u8);
 
-#[derive(Debug, BinRead)]
-pub struct CarStatus {
-    pub header: Header,
-    #[br(count = 22)]
-    pub car_status_data: Vec<CarStatusData>,
-}
-
 player_data!(CarStatus, CarStatusData, car_status_data);
+active_data!(CarStatus, CarStatusData, car_status_data, active_car_status);
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CarStatusData {
     pub traction_control: u8,
     #[br(map = |x: u8| x > 0)]
@@ -988,7 +1673,8 @@ pub struct CarStatusData {
     pub ers_data: ERS,
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum FuelMix {
     Lean,
@@ -1001,7 +1687,8 @@ pub enum FuelMix {
 
 binread_enum!(FuelMix, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum DRSAllowed {
     NotAllowed,
@@ -1012,7 +1699,8 @@ pub enum DRSAllowed {
 
 binread_enum!(DRSAllowed, u8);
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u16)]
 pub enum DRSActivationDistance {
     #[default]
@@ -1020,7 +1708,8 @@ pub enum DRSActivationDistance {
     Distance(u16),
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum TyreCompound {
     Inter = 7,
@@ -1043,7 +1732,31 @@ pub enum TyreCompound {
 
 binread_enum!(TyreCompound, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl fmt::Display for TyreCompound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TyreCompound::Inter => "Intermediate",
+            TyreCompound::Wet => "Wet",
+            TyreCompound::F1ClassicDry => "Classic Dry",
+            TyreCompound::F1ClassicWet => "Classic Wet",
+            TyreCompound::F2SuperSoft => "F2 Super Soft",
+            TyreCompound::F2Soft => "F2 Soft",
+            TyreCompound::F2Medium => "F2 Medium",
+            TyreCompound::F2Hard => "F2 Hard",
+            TyreCompound::F2Wet => "F2 Wet",
+            TyreCompound::C5 => "C5",
+            TyreCompound::C4 => "C4",
+            TyreCompound::C3 => "C3",
+            TyreCompound::C2 => "C2",
+            TyreCompound::C1 => "C1",
+            TyreCompound::Unknown => "Unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum TyreVisual {
     Inter = 7,
@@ -1057,7 +1770,22 @@ pub enum TyreVisual {
 
 binread_enum!(TyreVisual, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl fmt::Display for TyreVisual {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TyreVisual::Inter => "Intermediate",
+            TyreVisual::Wet => "Wet",
+            TyreVisual::Soft => "Soft",
+            TyreVisual::Medium => "Medium",
+            TyreVisual::Hard => "Hard",
+            TyreVisual::Unknown => "Unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(i8)]
 pub enum FiaFlag {
     #[default]
@@ -1071,16 +1799,8 @@ pub enum FiaFlag {
 
 binread_enum!(FiaFlag, i8);
 
-#[derive(Debug, Default, BinRead)]
-pub struct ERS {
-    pub stored_energy: f32,
-    pub deploy_mode: ERSDeployMode,
-    pub harvested_this_lap_mguk: f32,
-    pub harvested_this_lap_mguh: f32,
-    pub deployed_this_lap: f32,
-}
-
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum ERSDeployMode {
     None,
@@ -1093,21 +1813,55 @@ pub enum ERSDeployMode {
 
 binread_enum!(ERSDeployMode, u8);
 
-#[derive(Debug, BinRead)]
-pub struct FinalClassification {
-    pub header: Header,
-    pub number_of_cars: u8,
-    #[br(count = 22)]
-    pub final_classification_data: Vec<FinalClassificationData>,
-}
-
 player_data!(
     FinalClassification,
     FinalClassificationData,
     final_classification_data
 );
 
-#[derive(Debug, Default, BinRead)]
+impl FinalClassification {
+    /// classified returns the (car index, data) of the classified cars ordered by finishing position
+    pub fn classified(&self) -> Vec<(CarIndex, &FinalClassificationData)> {
+        let number_of_cars = self.number_of_cars as usize;
+        let mut classified: Vec<(CarIndex, &FinalClassificationData)> = self
+            .final_classification_data
+            .iter()
+            .take(number_of_cars)
+            .enumerate()
+            .map(|(index, data)| (CarIndex(index as u8), data))
+            .collect();
+        classified.sort_by_key(|(_, data)| data.position);
+        classified
+    }
+
+    /// car returns the classification of the given car index
+    pub fn car(&self, car_index: CarIndex) -> Option<&FinalClassificationData> {
+        self.final_classification_data.get(car_index.index()?)
+    }
+
+    /// position returns the (car index, data) of the car that finished in the given position
+    pub fn position(&self, position: u8) -> Option<(CarIndex, &FinalClassificationData)> {
+        self.classified()
+            .into_iter()
+            .find(|(_, data)| data.position == position)
+    }
+
+    /// driver returns the classification of the given driver, using participants to find its car
+    pub fn driver(
+        &self,
+        participants: &Participants,
+        driver: Driver,
+    ) -> Option<&FinalClassificationData> {
+        let car_index = participants
+            .participants_data
+            .iter()
+            .position(|participant| participant.driver == driver)?;
+        self.final_classification_data.get(car_index)
+    }
+}
+
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct FinalClassificationData {
     pub position: u8,
     pub number_of_laps: u8,
@@ -1120,33 +1874,28 @@ pub struct FinalClassificationData {
     pub penalties_time: u8,
     pub number_of_penalties: u8,
     pub number_of_tyre_stints: u8,
-    #[br(count = 8)]
-    pub tyre_stints_actual: Vec<TyreCompound>,
-    #[br(count = 8)]
-    pub tyre_stints_visual: Vec<TyreVisual>,
-}
-
-#[derive(Debug, BinRead)]
-pub struct LobbyInfo {
-    pub header: Header,
-    pub number_of_players: u8,
-    #[br(count = 22)]
-    pub lobby_players: Vec<LobbyInfoData>,
+    pub tyre_stints_actual: [TyreCompound; 8],
+    pub tyre_stints_visual: [TyreVisual; 8],
 }
 
 player_data!(LobbyInfo, LobbyInfoData, lobby_players);
 
 impl LobbyInfo {
+    /// active_players returns the players in the lobby (number_of_players)
+    pub fn active_players(&self) -> &[LobbyInfoData] {
+        let count = (self.number_of_players as usize).min(self.lobby_players.len());
+        &self.lobby_players[..count]
+    }
+
     pub fn players(self) -> Vec<LobbyInfoData> {
-        let number_of_players = self.number_of_players as usize;
-        self.lobby_players
-            .into_iter()
-            .take(number_of_players)
-            .collect()
+        let mut players = Vec::from(self.lobby_players);
+        players.truncate(self.number_of_players as usize);
+        players
     }
 }
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct LobbyInfoData {
     #[br(map = |x: u8| x > 0)]
     pub ai_controlled: bool,
@@ -1157,7 +1906,8 @@ pub struct LobbyInfoData {
     pub status: LobbyStatus,
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum LobbyStatus {
     NotReady,
@@ -1171,54 +1921,79 @@ binread_enum!(LobbyStatus, u8);
 
 impl TelemetryEvent for F1_2020 {
     fn from_packet(packet: &TelemetryPacket) -> Result<F1_2020, Box<dyn Error>> {
+        Self::from_packet_with_endianness(packet, Endianness::Little)
+    }
+
+    fn from_packet_with_endianness(
+        packet: &TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<F1_2020, Box<dyn Error>> {
         if packet.len() < 24 {
             return Err(Box::from("Packet is too small to contain a header"));
         }
 
         let packet_id = packet[5]; // packet_id
-        let mut reader = Cursor::new(packet);
+        let endian = binread::Endian::from(endianness);
         match packet_id {
             0 => {
-                let data: Motion = reader.read_le()?;
+                let data: Motion = read_packet(packet, endian)?;
                 Ok(F1_2020::Motion(data))
             }
             1 => {
-                let data: Session = reader.read_le()?;
+                let data: Session = read_packet(packet, endian)?;
                 Ok(F1_2020::Session(data))
             }
             2 => {
-                let data: LapData = reader.read_le()?;
+                let data: LapData = read_packet(packet, endian)?;
                 Ok(F1_2020::LapData(data))
             }
             3 => {
-                let data: Event = reader.read_le()?;
+                let data: Event = read_packet(packet, endian)?;
                 Ok(F1_2020::Event(data))
             }
             4 => {
-                let data: Participants = reader.read_le()?;
+                let data: Participants = read_packet(packet, endian)?;
                 Ok(F1_2020::Participants(data))
             }
             5 => {
-                let data: CarSetup = reader.read_le()?;
+                let data: CarSetup = read_packet(packet, endian)?;
                 Ok(F1_2020::CarSetup(data))
             }
             6 => {
-                let data: CarTelemetry = reader.read_le()?;
+                let data: CarTelemetry = read_packet(packet, endian)?;
                 Ok(F1_2020::CarTelemetry(data))
             }
             7 => {
-                let data: CarStatus = reader.read_le()?;
+                let data: CarStatus = read_packet(packet, endian)?;
                 Ok(F1_2020::CarStatus(data))
             }
             8 => {
-                let data: FinalClassification = reader.read_le()?;
+                let data: FinalClassification = read_packet(packet, endian)?;
                 Ok(F1_2020::FinalClassification(data))
             }
             9 => {
-                let data: LobbyInfo = reader.read_le()?;
+                let data: LobbyInfo = read_packet(packet, endian)?;
                 Ok(F1_2020::LobbyInfo(data))
             }
             id => Err(Box::from(format!("Unknown packet type: {}", id))),
         }
     }
 }
+
+impl TelemetryFrame for F1_2020 {
+    fn session_uid(&self) -> u64 {
+        self.header().session_uid
+    }
+
+    fn frame_identifier(&self) -> u32 {
+        self.header().frame_identifier
+    }
+
+    fn packet_id(&self) -> u8 {
+        self.header().packet_id
+    }
+
+    fn session_time(&self) -> f32 {
+        self.header().session_time
+    }
+}
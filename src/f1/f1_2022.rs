@@ -1,17 +1,28 @@
-use std::convert::TryFrom;
-use std::error::Error;
-use std::io::Cursor;
-
-use crate::{f1::macros::*, f1::util::*, TelemetryEvent, TelemetryPacket};
-
-use binread::{BinRead, BinReaderExt};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::convert::TryFrom;
+use core::error::Error;
+use core::fmt;
+
+use crate::{
+    f1::macros::*,
+    f1::spec::shared_packets,
+    f1::teams::TeamInfo,
+    f1::tracks::{self, TrackInfo},
+    f1::util::*,
+    Endianness, TelemetryEvent, TelemetryFrame, TelemetryPacket,
+};
+
+use binread::BinRead;
 use bitflags::bitflags;
 use num_enum::TryFromPrimitive;
 
+pub use crate::f1::util::ButtonFlags; // shared with "F1 2020", was defined here
+
 /// F1_2022 implements the codemasters UDP telemetry protocol for "F1 22"
 /// See: https://answers.ea.com/t5/General-Discussion/F1-22-UDP-Specification/td-p/11551274
 /// Or: https://answers.ea.com/t5/General-Discussion/F1-22-UDP-Specification/td-p/11551274?attachment-id=657933
-
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum F1_2022 {
     Motion(Motion),
     Session(Session),
@@ -27,60 +38,36 @@ pub enum F1_2022 {
     SessionHistory(SessionHistory),
 }
 
-// HEADER
-#[derive(Debug, Default, BinRead)]
-pub struct Header {
-    pub packet_format: u16,     // 2022
-    pub game_major_version: u8, // Game major version - "X.00"
-    pub game_minor_version: u8, // Game minor version - "1.XX"
-    pub packet_version: u8,     // Version of this packet type, all start from 1
-    pub packet_id: u8,          // Identifier for the packet type, see below
-    pub session_uid: u64,       // Unique identifier for the session
-    pub session_time: f32,      // Session timestamp
-    pub frame_identifier: u32,  // Identifier for the frame the data was retrieved on
-    pub player_car_index: u8,   // Index of player's car in the array
-    pub secondary_player_car_index: u8, // Index of secondary player's car in the array (splitscreen)
-                                        // 255 if no second player
+impl F1_2022 {
+    /// header returns the packet header shared by every packet type
+    pub fn header(&self) -> &Header {
+        match self {
+            F1_2022::Motion(data) => &data.header,
+            F1_2022::Session(data) => &data.header,
+            F1_2022::LapData(data) => &data.header,
+            F1_2022::Event(data) => &data.header,
+            F1_2022::Participants(data) => &data.header,
+            F1_2022::CarSetup(data) => &data.header,
+            F1_2022::CarTelemetry(data) => &data.header,
+            F1_2022::CarStatus(data) => &data.header,
+            F1_2022::FinalClassification(data) => &data.header,
+            F1_2022::LobbyInfo(data) => &data.header,
+            F1_2022::CarDamage(data) => &data.header,
+            F1_2022::SessionHistory(data) => &data.header,
+        }
+    }
 }
 
-// MOTION
-#[derive(Debug, BinRead)]
-pub struct Motion {
-    pub header: Header,
-
-    #[br(count = 22)]
-    pub car_motion_data: Vec<CarMotionData>, // Data for all cars on track (22)
-
-    // Extra player car ONLY data
-    pub suspension_position: WheelValue<f32>, // Note: All wheel arrays have the following order:
-    pub suspension_velocity: WheelValue<f32>, // RL, RR, FL, FR
-    pub suspension_acceleration: WheelValue<f32>, // RL, RR, FL, FR
-    pub wheel_speed: WheelValue<f32>,         // Speed of each wheel
-    pub wheel_slip: WheelValue<f32>,          // Slip ratio for each wheel
-    pub local_velocity: Coordinates<f32>,     // Velocity in local space
-    pub angular_velocity: Coordinates<f32>,   // Angular velocity
-    pub angular_acceleration: Coordinates<f32>, // Angular acceleration
-    pub front_wheel_angle: f32,               // Current front wheels angle in radians
-}
+// packets with the same layout in every year (Header, Motion, CarSetup...), see f1::spec
+shared_packets!();
 
+// MOTION
 player_data!(Motion, CarMotionData, car_motion_data);
-
-#[derive(Debug, Default, BinRead)]
-pub struct CarMotionData {
-    pub world_position: Coordinates<f32>,    // World space position
-    pub world_velocity: Coordinates<f32>,    // Velocity in world space
-    pub world_forward_dir: Coordinates<i16>, // World space forward direction (normalised)
-    pub world_right_dir: Coordinates<i16>,   // World space right direction (normalised)
-    pub g_force_lateral: f32,                // Lateral G-Force component
-    pub g_force_longitudinal: f32,           // Longitudinal G-Force component
-    pub g_force_vertical: f32,               // Vertical G-Force component
-    pub yaw: f32,                            // Yaw angle in radians
-    pub pitch: f32,                          // Pitch angle in radians
-    pub roll: f32,                           // Roll angle in radians
-}
+active_data!(Motion, CarMotionData, car_motion_data, active_car_motion);
 
 // SESSION
-#[derive(Debug, BinRead)]
+#[derive(Debug, Clone, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Session {
     pub header: Header,
     pub weather: Weather, // Weather - 0 = clear, 1 = light cloud, 2 = overcast
@@ -96,23 +83,23 @@ pub struct Session {
     pub formula: Formula, // Formula, 0 = F1 Modern, 1 = F1 Classic, 2 = F2,
     // 3 = F1 Generic, 4 = Beta, 5 = Supercars
     // 6 = Esports, 7 = F2 2021
-    pub session_time_left: u16,      // Time left in session in seconds
-    pub session_duration: u16,       // Session duration in seconds
-    pub pit_speed_limit: u8,         // Pit speed limit in kilometres per hour
-    pub game_paused: u8,             // Whether the game is paused – network game only
-    pub is_spectating: u8,           // Whether the player is spectating
-    pub spectator_car_index: u8,     // Index of the car being spectated
-    pub sli_pro_native_support: u8,  // SLI Pro support, 0 = inactive, 1 = active
-    pub number_of_marshal_zones: u8, // Number of marshal zones to follow
-    #[br(count = 21)]
-    pub marshal_zones: Vec<MarshalZone>, // List of marshal zones – max 21
+    pub session_time_left: u16,        // Time left in session in seconds
+    pub session_duration: u16,         // Session duration in seconds
+    pub pit_speed_limit: u8,           // Pit speed limit in kilometres per hour
+    pub game_paused: u8,               // Whether the game is paused – network game only
+    pub is_spectating: u8,             // Whether the player is spectating
+    pub spectator_car_index: CarIndex, // Index of the car being spectated
+    pub sli_pro_native_support: u8,    // SLI Pro support, 0 = inactive, 1 = active
+    pub number_of_marshal_zones: u8,   // Number of marshal zones to follow
+    #[br(args(number_of_marshal_zones))]
+    pub marshal_zones: TruncatedArray<MarshalZone, 21>, // List of marshal zones – max 21
     pub safety_car_status: SafetyCarStatus, // 0 = no safety car, 1 = full
     // 2 = virtual, 3 = formation lap
     #[br(map = |x: u8| x > 0)]
     pub network_game: bool, // 0 = offline, 1 = online
     pub number_of_weather_forecast_samples: u8, // Number of weather samples to follow
-    #[br(count = 56)] //Array does not work due to size being > 32
-    pub weather_forecast_samples: Vec<WeatherForecastSample>, // Array of weather forecast samples
+    #[br(args(number_of_weather_forecast_samples))]
+    pub weather_forecast_samples: TruncatedArray<WeatherForecastSample, 56>, // Forecast samples
     pub forecast_accuracy: ForecastAccuracy,    // 0 = Perfect, 1 = Approximate
     pub ai_difficulty: u8,                      // AI Difficulty rating – 0-110
     pub season_link_identifier: u32,            // Identifier for season - persists across saves
@@ -142,7 +129,71 @@ pub struct Session {
                                         // 5 = Medium Long, 6 = Long, 7 = Full
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl Session {
+    /// current_weather_forecast_sample returns the forecast sample of the current session at the
+    /// closest time offset (ie: the weather right now). When the forecast has no sample for the
+    /// current session type it returns the last sample sent, like "F1 2020" does, and None only
+    /// when there are no samples at all
+    pub fn current_weather_forecast_sample(&self) -> Option<&WeatherForecastSample> {
+        self.forecast_for(self.session_type)
+            .min_by_key(|sample| sample.time_offset)
+            .or_else(|| self.weather_forecast_samples.last())
+    }
+
+    /// forecast_for iterates over the forecast samples of the given session type
+    pub fn forecast_for(
+        &self,
+        session_type: SessionType,
+    ) -> impl Iterator<Item = &WeatherForecastSample> {
+        self.weather_forecast_samples
+            .iter()
+            .filter(move |sample| sample.session_type == session_type)
+    }
+
+    /// next_rain_window returns the first forecast sample of the current session with rain
+    /// use time_offset to know in how many minutes it starts
+    pub fn next_rain_window(&self) -> Option<&WeatherForecastSample> {
+        self.forecast_for(self.session_type)
+            .filter(|sample| sample.is_rain())
+            .min_by_key(|sample| sample.time_offset)
+    }
+
+    /// active_marshal_zones returns the marshal zones in use (already truncated to number_of_marshal_zones)
+    pub fn active_marshal_zones(&self) -> &[MarshalZone] {
+        &self.marshal_zones
+    }
+
+    /// active_yellow_sectors iterates over the marshal zones currently showing a yellow flag
+    pub fn active_yellow_sectors(&self) -> impl Iterator<Item = &MarshalZone> {
+        self.active_marshal_zones()
+            .iter()
+            .filter(|zone| zone.zone_flag == ZoneFlag::Yellow)
+    }
+
+    /// flag_at returns the flag of the marshal zone covering the given fraction (0..1) of the lap
+    pub fn flag_at(&self, lap_fraction: f32) -> ZoneFlag {
+        let zones = self.active_marshal_zones();
+        zones
+            .iter()
+            .rev()
+            .find(|zone| zone.zone_start <= lap_fraction)
+            .or_else(|| zones.last()) // before the first zone start we are still in the last zone
+            .map(|zone| zone.zone_flag)
+            .unwrap_or_default()
+    }
+
+    /// spectated_car returns the index of the car being watched when the player is spectating
+    pub fn spectated_car(&self) -> Option<CarIndex> {
+        if self.is_spectating != 0 {
+            Some(self.spectator_car_index)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Weather {
     #[default]
@@ -157,7 +208,23 @@ pub enum Weather {
 
 binread_enum!(Weather, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl fmt::Display for Weather {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Weather::Clear => "Clear",
+            Weather::LigthCloud => "Light Cloud",
+            Weather::Overcast => "Overcast",
+            Weather::LightRain => "Light Rain",
+            Weather::HeavyRain => "Heavy Rain",
+            Weather::Storm => "Storm",
+            Weather::Unknown => "Unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum SessionType {
     #[default]
@@ -179,7 +246,30 @@ pub enum SessionType {
 
 binread_enum!(SessionType, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl fmt::Display for SessionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            SessionType::Unknown => "Unknown",
+            SessionType::Practice1 => "Practice 1",
+            SessionType::Practice2 => "Practice 2",
+            SessionType::Practice3 => "Practice 3",
+            SessionType::ShortPractice => "Short Practice",
+            SessionType::Qualifier1 => "Qualifying 1",
+            SessionType::Qualifier2 => "Qualifying 2",
+            SessionType::Qualifier3 => "Qualifying 3",
+            SessionType::ShortQualifier => "Short Qualifying",
+            SessionType::OSQ => "One-Shot Qualifying",
+            SessionType::Race => "Race",
+            SessionType::R2 => "Race 2",
+            SessionType::R3 => "Race 3",
+            SessionType::TimeTrial => "Time Trial",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(i8)]
 pub enum Track {
     #[default]
@@ -219,7 +309,88 @@ pub enum Track {
 
 binread_enum!(Track, i8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl Track {
+    /// info returns the circuit metadata of the track (None when unknown)
+    pub fn info(&self) -> Option<&'static TrackInfo> {
+        match self {
+            Track::Unknown => None,
+            Track::Melbourne => Some(&tracks::MELBOURNE),
+            Track::PaulRicard => Some(&tracks::PAUL_RICARD),
+            Track::Shanghai => Some(&tracks::SHANGHAI),
+            Track::Sakhir => Some(&tracks::SAKHIR),
+            Track::Catalunya => Some(&tracks::CATALUNYA),
+            Track::Monaco => Some(&tracks::MONACO),
+            Track::Montreal => Some(&tracks::MONTREAL),
+            Track::Silverstone => Some(&tracks::SILVERSTONE),
+            Track::Hockenheim => Some(&tracks::HOCKENHEIM),
+            Track::Hungaroring => Some(&tracks::HUNGARORING),
+            Track::Spa => Some(&tracks::SPA),
+            Track::Monza => Some(&tracks::MONZA),
+            Track::Singapore => Some(&tracks::SINGAPORE),
+            Track::Suzuka => Some(&tracks::SUZUKA),
+            Track::AbuDahbi => Some(&tracks::ABU_DHABI),
+            Track::Texas => Some(&tracks::TEXAS),
+            Track::Brazil => Some(&tracks::BRAZIL),
+            Track::Austria => Some(&tracks::AUSTRIA),
+            Track::Sochi => Some(&tracks::SOCHI),
+            Track::Mexico => Some(&tracks::MEXICO),
+            Track::Baku => Some(&tracks::BAKU),
+            Track::SakhirShort => Some(&tracks::SAKHIR_SHORT),
+            Track::SilverstoneShort => Some(&tracks::SILVERSTONE_SHORT),
+            Track::TexasShort => Some(&tracks::TEXAS_SHORT),
+            Track::SuzukaShort => Some(&tracks::SUZUKA_SHORT),
+            Track::Hanoi => Some(&tracks::HANOI),
+            Track::Zandvoort => Some(&tracks::ZANDVOORT),
+            Track::Imola => Some(&tracks::IMOLA),
+            Track::Portimao => Some(&tracks::PORTIMAO),
+            Track::Jeddah => Some(&tracks::JEDDAH),
+            Track::Miami => Some(&tracks::MIAMI),
+        }
+    }
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Track::Unknown => "Unknown",
+            Track::Melbourne => "Melbourne",
+            Track::PaulRicard => "Paul Ricard",
+            Track::Shanghai => "Shanghai",
+            Track::Sakhir => "Sakhir (Bahrain)",
+            Track::Catalunya => "Catalunya",
+            Track::Monaco => "Monaco",
+            Track::Montreal => "Montréal",
+            Track::Silverstone => "Silverstone",
+            Track::Hockenheim => "Hockenheim",
+            Track::Hungaroring => "Hungaroring",
+            Track::Spa => "Spa-Francorchamps",
+            Track::Monza => "Monza",
+            Track::Singapore => "Singapore",
+            Track::Suzuka => "Suzuka",
+            Track::AbuDahbi => "Abu Dhabi",
+            Track::Texas => "Texas (COTA)",
+            Track::Brazil => "Brazil (Interlagos)",
+            Track::Austria => "Austria (Red Bull Ring)",
+            Track::Sochi => "Sochi",
+            Track::Mexico => "Mexico City",
+            Track::Baku => "Baku (Azerbaijan)",
+            Track::SakhirShort => "Sakhir Short",
+            Track::SilverstoneShort => "Silverstone Short",
+            Track::TexasShort => "Texas Short",
+            Track::SuzukaShort => "Suzuka Short",
+            Track::Hanoi => "Hanoi",
+            Track::Zandvoort => "Zandvoort",
+            Track::Imola => "Imola",
+            Track::Portimao => "Portimão",
+            Track::Jeddah => "Jeddah",
+            Track::Miami => "Miami",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Formula {
     #[default]
@@ -235,13 +406,8 @@ pub enum Formula {
 
 binread_enum!(Formula, u8);
 
-#[derive(Debug, Default, BinRead)]
-pub struct MarshalZone {
-    pub zone_start: f32, // Fraction (0..1) of way through the lap the marshal zone starts
-    pub zone_flag: ZoneFlag, // -1 = invalid/unknown, 0 = none, 1 = green, 2 = blue, 3 = yellow, 4 = red
-}
-
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(i8)]
 pub enum ZoneFlag {
     #[default]
@@ -255,7 +421,8 @@ pub enum ZoneFlag {
 
 binread_enum!(ZoneFlag, i8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum SafetyCarStatus {
     #[default]
@@ -268,7 +435,8 @@ pub enum SafetyCarStatus {
 
 binread_enum!(SafetyCarStatus, u8);
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct WeatherForecastSample {
     pub session_type: SessionType, // 0 = unknown, 1 = P1, 2 = P2, 3 = P3, 4 = Short P, 5 = Q1
     // 6 = Q2, 7 = Q3, 8 = Short Q, 9 = OSQ, 10 = R, 11 = R2
@@ -283,7 +451,18 @@ pub struct WeatherForecastSample {
     pub rain_percentage: u8,                             // Rain percentage (0-100)
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl WeatherForecastSample {
+    /// is_rain returns whether rain is forecasted for this sample
+    pub fn is_rain(&self) -> bool {
+        matches!(
+            self.weather,
+            Weather::LightRain | Weather::HeavyRain | Weather::Storm
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(i8)]
 pub enum WeatherTemperatureTrend {
     #[default]
@@ -295,7 +474,8 @@ pub enum WeatherTemperatureTrend {
 
 binread_enum!(WeatherTemperatureTrend, i8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum ForecastAccuracy {
     #[default]
@@ -306,7 +486,8 @@ pub enum ForecastAccuracy {
 
 binread_enum!(ForecastAccuracy, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum BrakingAssist {
     #[default]
@@ -319,7 +500,8 @@ pub enum BrakingAssist {
 
 binread_enum!(BrakingAssist, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum GearboxAssist {
     #[default]
@@ -331,7 +513,8 @@ pub enum GearboxAssist {
 
 binread_enum!(GearboxAssist, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum RacingLine {
     #[default]
@@ -343,7 +526,8 @@ pub enum RacingLine {
 
 binread_enum!(RacingLine, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum RacingLineType {
     #[default]
@@ -354,7 +538,8 @@ pub enum RacingLineType {
 
 binread_enum!(RacingLineType, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum GameMode {
     #[default]
@@ -377,7 +562,8 @@ pub enum GameMode {
 
 binread_enum!(GameMode, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum RuleSet {
     #[default]
@@ -395,7 +581,8 @@ pub enum RuleSet {
 
 binread_enum!(RuleSet, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum SessionLength {
     #[default]
@@ -412,18 +599,101 @@ pub enum SessionLength {
 binread_enum!(SessionLength, u8);
 
 // LAP
-#[derive(Debug, BinRead)]
+#[derive(Debug, Clone, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct LapData {
     pub header: Header,
-    #[br(count = 22)]
-    pub laps: Vec<Lap>, // Lap data for all cars on track
-    pub time_trial_pb_car_idx: u8, // Index of Personal Best car in time trial (255 if invalid)
-    pub time_trial_rival_car_idx: u8, // Index of Rival car in time trial (255 if invalid)
+    pub laps: [Lap; 22],                    // Lap data for all cars on track
+    pub time_trial_pb_car_idx: CarIndex, // Index of Personal Best car in time trial (255 if invalid)
+    pub time_trial_rival_car_idx: CarIndex, // Index of Rival car in time trial (255 if invalid)
 }
 
 player_data!(LapData, Lap, laps);
 
-#[derive(Debug, Default, BinRead)]
+impl LapData {
+    /// active_laps iterates over the (car index, lap) of the cars on track,
+    /// skipping placeholder entries (result_status invalid or inactive)
+    pub fn active_laps(&self) -> impl Iterator<Item = (CarIndex, &Lap)> {
+        self.laps
+            .iter()
+            .enumerate()
+            .filter(|(_, lap)| {
+                !matches!(
+                    lap.result_status,
+                    ResultStatus::Invalid | ResultStatus::Inactive
+                )
+            })
+            .map(|(index, lap)| (CarIndex(index as u8), lap))
+    }
+
+    /// running_order returns the index of the cars on track ordered by race position
+    pub fn running_order(&self) -> Vec<CarIndex> {
+        let mut running: Vec<(u8, CarIndex)> = self
+            .active_laps()
+            .filter(|(_, lap)| lap.car_position > 0)
+            .map(|(car, lap)| (lap.car_position, car))
+            .collect();
+        running.sort_unstable();
+        running.into_iter().map(|(_, car)| car).collect()
+    }
+
+    /// positions maps every car index to its race position (None for empty slots)
+    pub fn positions(&self) -> [Option<u8>; CarIndex::MAX_CARS] {
+        let mut positions = [None; CarIndex::MAX_CARS];
+        for (car, lap) in self.active_laps() {
+            if let Some(position) = car.index().and_then(|index| positions.get_mut(index)) {
+                *position = Some(lap.car_position).filter(|&position| position > 0);
+            }
+        }
+        positions
+    }
+
+    /// car_at returns the index of the car in the given race position
+    pub fn car_at(&self, position: u8) -> Option<CarIndex> {
+        self.active_laps()
+            .find(|(_, lap)| position > 0 && lap.car_position == position)
+            .map(|(car, _)| car)
+    }
+
+    /// time_trial_pb_data returns the lap data of the personal best ghost (time trial only)
+    pub fn time_trial_pb_data(&self) -> Option<&Lap> {
+        self.laps.get(self.time_trial_pb_car_idx.index()?)
+    }
+
+    /// time_trial_rival_data returns the lap data of the rival ghost (time trial only)
+    pub fn time_trial_rival_data(&self) -> Option<&Lap> {
+        self.laps.get(self.time_trial_rival_car_idx.index()?)
+    }
+
+    /// time_trial_pb_delta returns the seconds the player is behind (positive) or ahead (negative)
+    /// of the personal best ghost on the current lap
+    pub fn time_trial_pb_delta(&self) -> Option<f32> {
+        self.player_data()?.delta_to(self.time_trial_pb_data()?)
+    }
+
+    /// time_trial_rival_delta returns the seconds the player is behind (positive) or ahead (negative)
+    /// of the rival ghost on the current lap
+    pub fn time_trial_rival_delta(&self) -> Option<f32> {
+        self.player_data()?.delta_to(self.time_trial_rival_data()?)
+    }
+}
+
+impl Lap {
+    /// delta_to estimates the seconds this car is behind (positive) or ahead (negative) of
+    /// the reference car, both cars have to be on the same lap (ie: time trial ghosts).
+    /// The distance between them is converted to time with this car's average speed on the lap.
+    pub fn delta_to(&self, reference: &Lap) -> Option<f32> {
+        if self.current_lap_time_ms == 0 || self.lap_distance <= 0.0 {
+            return None;
+        }
+
+        let average_speed = self.lap_distance / (self.current_lap_time_ms as f32 / 1000.0); // m/s
+        Some((reference.lap_distance - self.lap_distance) / average_speed)
+    }
+}
+
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Lap {
     pub last_lap_time_ms: u32,      // Last lap time in milliseconds
     pub current_lap_time_ms: u32,   // Current time around the lap in milliseconds
@@ -457,7 +727,8 @@ pub struct Lap {
     pub pit_stop_should_serve_penalty: u8, // Whether the car should serve a penalty at this stop
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum PitStatus {
     #[default]
@@ -469,7 +740,8 @@ pub enum PitStatus {
 
 binread_enum!(PitStatus, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Sector {
     Sector1,
@@ -481,7 +753,8 @@ pub enum Sector {
 
 binread_enum!(Sector, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum DriverStatus {
     InGarage,
@@ -495,7 +768,8 @@ pub enum DriverStatus {
 
 binread_enum!(DriverStatus, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum ResultStatus {
     Invalid,
@@ -513,7 +787,8 @@ pub enum ResultStatus {
 binread_enum!(ResultStatus, u8);
 
 // EVENT
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Event {
     pub header: Header,
     pub event_data_details: EventDataDetail, // Event details - should be interpreted differently
@@ -533,29 +808,29 @@ impl binread::BinRead for Event {
 
         // Read next 4 bytes for event string identification
         let event_code_bytes = <[u8; 4]>::read_options(reader, options, args)?;
-        let event_code = std::str::from_utf8(&event_code_bytes).unwrap_or("UNKW");
+        let event_code = core::str::from_utf8(&event_code_bytes).unwrap_or("UNKW");
 
         let event_data_details = match event_code {
             "SSTA" => EventDataDetail::SessionStarted,
             "SEND" => EventDataDetail::SessionEnded,
             "FTLP" => {
-                let idx = <u8>::read_options(reader, options, args)?;
+                let idx = CarIndex::read_options(reader, options, args)?;
                 let time = <f32>::read_options(reader, options, args)?;
                 EventDataDetail::FastestLap(idx, time)
             }
             "RTMT" => {
-                let idx = <u8>::read_options(reader, options, args)?;
+                let idx = CarIndex::read_options(reader, options, args)?;
                 EventDataDetail::Retirement(idx)
             }
             "DRSE" => EventDataDetail::DRSEnabled,
             "DRSD" => EventDataDetail::DRSDisabled,
             "TMPT" => {
-                let idx = <u8>::read_options(reader, options, args)?;
+                let idx = CarIndex::read_options(reader, options, args)?;
                 EventDataDetail::TeamMateInPits(idx)
             }
             "CHQF" => EventDataDetail::ChequeredFlag,
             "RCWN" => {
-                let idx = <u8>::read_options(reader, options, args)?;
+                let idx = CarIndex::read_options(reader, options, args)?;
                 EventDataDetail::RaceWinner(idx)
             }
             "PENA" => {
@@ -572,11 +847,11 @@ impl binread::BinRead for Event {
             }
             "LGOT" => EventDataDetail::LightsOut,
             "DTSV" => {
-                let idx = <u8>::read_options(reader, options, args)?;
+                let idx = CarIndex::read_options(reader, options, args)?;
                 EventDataDetail::DriveThroughServed(idx)
             }
             "SGSV" => {
-                let idx = <u8>::read_options(reader, options, args)?;
+                let idx = CarIndex::read_options(reader, options, args)?;
                 EventDataDetail::StopGoServed(idx)
             }
             "FLBK" => {
@@ -590,7 +865,15 @@ impl binread::BinRead for Event {
                         .unwrap_or_default();
                 EventDataDetail::ButtonStatus(button_status)
             }
-            _ => EventDataDetail::Unknown,
+            // codes added by "F1 23", seen when it is set to the "2022" UDP format. "F1 24" only
+            // sends the 2023 and 2024 formats, its codes (ie: COLL, SCAR) end up as Unknown
+            "OVTK" => {
+                let overtaking = CarIndex::read_options(reader, options, args)?;
+                let overtaken = CarIndex::read_options(reader, options, args)?;
+                EventDataDetail::Overtake(overtaking, overtaken)
+            }
+            "RDFL" => EventDataDetail::RedFlag,
+            _ => EventDataDetail::Unknown(event_code_bytes, read_remaining(reader)?),
         };
 
         Ok(Event {
@@ -600,87 +883,64 @@ impl binread::BinRead for Event {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum EventDataDetail {
     SessionStarted,
     SessionEnded,
-    FastestLap(u8, f32), // vehicleIdx; Vehicle index of car achieving fastest lap
+    FastestLap(CarIndex, f32), // vehicleIdx; Vehicle index of car achieving fastest lap
     // lapTime; Lap time is in seconds
-    Retirement(u8), // vehicleIdx; Vehicle index of car retiring
+    Retirement(CarIndex), // vehicleIdx; Vehicle index of car retiring
     DRSEnabled,
     DRSDisabled,
-    TeamMateInPits(u8), // vehicleIdx; Vehicle index of team mate
+    TeamMateInPits(CarIndex), // vehicleIdx; Vehicle index of team mate
     ChequeredFlag,
-    RaceWinner(u8), // vehicleIdx; Vehicle index of the race winner
+    RaceWinner(CarIndex), // vehicleIdx; Vehicle index of the race winner
     Penalty(PenaltyEventDetail),
     SpeedTrap(SpeedTrapDetail),
     StartLights(u8), // numLights; Number of lights showing
     LightsOut,
-    DriveThroughServed(u8), // vehicleIdx; Vehicle index of the vehicle serving drive through
-    StopGoServed(u8),       // vehicleIdx; Vehicle index of the vehicle serving stop go
-    Flashback(u32, f32),    // flashbackFrameIdentifier; Frame identifier flashed back to
+    DriveThroughServed(CarIndex), // vehicleIdx; Vehicle index of the vehicle serving drive through
+    StopGoServed(CarIndex),       // vehicleIdx; Vehicle index of the vehicle serving stop go
+    Flashback(u32, f32),          // flashbackFrameIdentifier; Frame identifier flashed back to
     // flashbackSessionTime; Session time flashed back to
     ButtonStatus(ButtonFlags), // buttonStatus; Bit flags specifying which buttons are being pressed
     // currently - see appendices
-    Unknown, // not part of the spec, added to satisfy match
-}
+    Overtake(CarIndex, CarIndex), // overtakingVehicleIdx, beingOvertakenVehicleIdx ("F1 23")
+    RedFlag,                      // ("F1 23")
+    Unknown([u8; 4], Vec<u8>),    // not part of the spec: event code and payload of events added
+                                  // by game patches
+}
+
+impl PenaltyEventDetail {
+    /// description returns a human readable sentence for race control feeds
+    /// eg: "5s time penalty for corner cutting (gained time) on lap 12"
+    pub fn description(&self) -> String {
+        let mut text = match self.penalty_type {
+            PenaltyType::TimePenalty | PenaltyType::StopGo if self.time > 0 => {
+                format!("{}s {}", self.time, self.penalty_type.description())
+            }
+            _ => String::from(self.penalty_type.description()),
+        };
 
-bitflags! {
-    #[derive(Debug)]
-    pub struct ButtonFlags: u32 {
-        const CROSS_OR_A        = 0x00000001;
-        const TRIANGLE_OR_Y     = 0x00000002;
-        const CIRCLE_OR_B       = 0x00000004;
-        const SQUARE_OR_X       = 0x00000008;
-        const D_PAD_LEFT        = 0x00000010;
-        const D_PAD_RIGHT       = 0x00000020;
-        const D_PAD_UP          = 0x00000040;
-        const D_PAD_DOWN        = 0x00000080;
-        const OPTIONS_OR_MENU   = 0x00000100;
-        const L1_OR_LB          = 0x00000200;
-        const R1_OR_RB          = 0x00000400;
-        const L2_OR_LT          = 0x00000800;
-        const R2_OR_RT          = 0x00001000;
-        const LEFT_STICK_CLICK  = 0x00002000;
-        const RIGHT_STICK_CLICK = 0x00004000;
-        const RIGHT_STICK_LEFT  = 0x00008000;
-        const RIGHT_STICK_RIGHT = 0x00010000;
-        const RIGHT_STICK_UP    = 0x00020000;
-        const RIGHT_STICK_DOWN  = 0x00040000;
-        const SPECIAL           = 0x00080000;
-        const UDP_ACTION_1      = 0x00100000;
-        const UDP_ACTION_2      = 0x00200000;
-        const UDP_ACTION_3      = 0x00400000;
-        const UDP_ACTION_4      = 0x00800000;
-        const UDP_ACTION_5      = 0x01000000;
-        const UDP_ACTION_6      = 0x02000000;
-        const UDP_ACTION_7      = 0x04000000;
-        const UDP_ACTION_8      = 0x08000000;
-        const UDP_ACTION_9      = 0x10000000;
-        const UDP_ACTION_10     = 0x20000000;
-        const UDP_ACTION_11     = 0x40000000;
-        const UDP_ACTION_12     = 0x80000000;
-    }
-}
+        if let Some(reason) = self.infrigement_type.description() {
+            text.push_str(" for ");
+            text.push_str(reason);
+        }
 
-impl Default for ButtonFlags {
-    fn default() -> Self {
-        ButtonFlags::empty()
-    }
-}
+        if self.lap_number > 0 {
+            text.push_str(&format!(" on lap {}", self.lap_number));
+        }
 
-#[derive(Debug, Default, BinRead)]
-pub struct PenaltyEventDetail {
-    pub penalty_type: PenaltyType,          // Penalty type – see Appendices
-    pub infrigement_type: InfringementType, // Infringement type – see Appendices
-    pub vehicle_index: u8,                  // Vehicle index of the car the penalty is applied to
-    pub other_vehicle_index: u8,            // Vehicle index of the other car involved
-    pub time: u8,                           // Time gained, or time spent doing action in seconds
-    pub lap_number: u8,                     // Lap the penalty occurred on
-    pub places_gained: u8,                  // Number of places gained by this
+        if let Some(first) = text.get_mut(..1) {
+            first.make_ascii_uppercase();
+        }
+        text
+    }
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum PenaltyType {
     DriveThrough,
@@ -707,7 +967,37 @@ pub enum PenaltyType {
 
 binread_enum!(PenaltyType, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl PenaltyType {
+    /// description returns the penalty in plain (lowercase) words
+    pub fn description(&self) -> &'static str {
+        match self {
+            PenaltyType::DriveThrough => "drive-through penalty",
+            PenaltyType::StopGo => "stop-go penalty",
+            PenaltyType::GridPenalty => "grid penalty",
+            PenaltyType::PenaltyReminder => "penalty reminder",
+            PenaltyType::TimePenalty => "time penalty",
+            PenaltyType::Warning => "warning",
+            PenaltyType::Disqualified => "disqualification",
+            PenaltyType::RemovedFromFormationLap => "removed from formation lap",
+            PenaltyType::ParkedTooLongTimer => "parked too long timer",
+            PenaltyType::TyreRegulations => "tyre regulations",
+            PenaltyType::ThisLapInvalidated => "lap invalidated",
+            PenaltyType::ThisAndNextLapInvalidated => "this and next lap invalidated",
+            PenaltyType::ThisLapInvalidatedWithNoReason => "lap invalidated",
+            PenaltyType::ThisAndNextLapInvalidatedWithNoReason => "this and next lap invalidated",
+            PenaltyType::ThisAndPreviousLapInvalidated => "this and previous lap invalidated",
+            PenaltyType::ThisAndPreviousLapInvalidatedWithNoReason => {
+                "this and previous lap invalidated"
+            }
+            PenaltyType::Retired => "retired",
+            PenaltyType::BlackFlagTimer => "black flag timer",
+            PenaltyType::Unknown => "penalty",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum InfringementType {
     BlockingBySlowDriving,
@@ -771,50 +1061,153 @@ pub enum InfringementType {
 
 binread_enum!(InfringementType, u8);
 
-#[derive(Debug, Default, BinRead)]
+impl InfringementType {
+    /// description returns the reason of the infringement in plain (lowercase) words
+    /// (None when unknown)
+    pub fn description(&self) -> Option<&'static str> {
+        let reason = match self {
+            InfringementType::BlockingBySlowDriving => "blocking by slow driving",
+            InfringementType::BlockingByWrongWayDriving => "blocking by driving the wrong way",
+            InfringementType::ReversingOffTheStartLine => "reversing off the start line",
+            InfringementType::BigCollision => "causing a big collision",
+            InfringementType::SmallCollision => "causing a small collision",
+            InfringementType::CollisionFailedToHandBackPositionSingle => {
+                "failing to hand back a position after a collision"
+            }
+            InfringementType::CollisionFailedToHandBackPositionMultiple => {
+                "failing to hand back positions after a collision"
+            }
+            InfringementType::CornerCuttingGainedTime => "corner cutting (gained time)",
+            InfringementType::CornerCuttingOvertakeSingle => "corner cutting (overtake)",
+            InfringementType::CornerCuttingOvertakeMultiple => {
+                "corner cutting (multiple overtakes)"
+            }
+            InfringementType::CrossedPitExitLane => "crossing the pit exit line",
+            InfringementType::IgnoringBlueFlags => "ignoring blue flags",
+            InfringementType::IgnoringYellowFlags => "ignoring yellow flags",
+            InfringementType::IgnoringDriveThrough => "ignoring a drive-through",
+            InfringementType::TooManyDriveThroughs => "too many drive-throughs",
+            InfringementType::DriveThroughReminderServeWithinNLaps => {
+                "a drive-through to serve within the next laps"
+            }
+            InfringementType::DriveThroughReminderServeThisLap => {
+                "a drive-through to serve this lap"
+            }
+            InfringementType::PitLaneSpeeding => "pit lane speeding",
+            InfringementType::ParkedForTooLong => "parking for too long",
+            InfringementType::IgnoringTyreRegulations => "ignoring tyre regulations",
+            InfringementType::TooManyPenalties => "too many penalties",
+            InfringementType::MultipleWarnings => "multiple warnings",
+            InfringementType::ApproachingDisqualification => "approaching disqualification",
+            InfringementType::TyreRegulationsSelectSingle => "tyre regulations (select a compound)",
+            InfringementType::TyreRegulationsSelectMultiple => {
+                "tyre regulations (select compounds)"
+            }
+            InfringementType::LapInvalidatedCornerCutting => "corner cutting",
+            InfringementType::LapInvalidatedRunningWide => "running wide",
+            InfringementType::CornerCuttingRanWideGainedTimeMinor => {
+                "running wide (gained minor time)"
+            }
+            InfringementType::CornerCuttingRanWideGainedTimeSignificant => {
+                "running wide (gained significant time)"
+            }
+            InfringementType::CornerCuttingRanWideGainedTimeExtreme => {
+                "running wide (gained extreme time)"
+            }
+            InfringementType::LapInvalidatedWallRiding => "wall riding",
+            InfringementType::LapInvalidatedFlashbackUsed => "using a flashback",
+            InfringementType::LapInvalidatedResetToTrack => "being reset to track",
+            InfringementType::BlockingThePitlane => "blocking the pit lane",
+            InfringementType::JumpStart => "a jump start",
+            InfringementType::SafetyCarToCarCollision => "a collision with the safety car",
+            InfringementType::SafetyCarIllegalOvertake => {
+                "an illegal overtake under the safety car"
+            }
+            InfringementType::SafetyCarExceedingAllowedPace => "exceeding the safety car pace",
+            InfringementType::VirtualSafetyCarExceedingAllowedPace => {
+                "exceeding the virtual safety car pace"
+            }
+            InfringementType::FormationLapBelowAllowedSpeed => {
+                "driving below the allowed formation lap speed"
+            }
+            InfringementType::FormationLapParking => "parking on the formation lap",
+            InfringementType::RetiredMechanicalFailure => "a mechanical failure",
+            InfringementType::RetiredTerminallyDamaged => "terminal damage",
+            InfringementType::SafetyCarFallingTooFarBack => "falling too far behind the safety car",
+            InfringementType::BlackFlagTimer => "the black flag timer",
+            InfringementType::UnservedStopGoPenalty => "an unserved stop-go penalty",
+            InfringementType::UnservedDriveThroughPenalty => "an unserved drive-through penalty",
+            InfringementType::EngineComponentChange => "an engine component change",
+            InfringementType::GearboxChange => "a gearbox change",
+            InfringementType::ParcFermeChange => "a parc fermé change",
+            InfringementType::LeagueGridPenalty => "a league grid penalty",
+            InfringementType::RetryPenalty => "a retry",
+            InfringementType::IllegalTimeGain => "an illegal time gain",
+            InfringementType::MandatoryPitstop => "a missed mandatory pit stop",
+            InfringementType::AttributeAssigned => "an attribute assignment",
+            InfringementType::Unknown => return None,
+        };
+        Some(reason)
+    }
+}
+
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SpeedTrapDetail {
-    pub vehicle_index: u8, // Vehicle index of the vehicle triggering speed trap
-    pub speed: f32,        // Top speed achieved in kilometres per hour
+    pub vehicle_index: CarIndex, // Vehicle index of the vehicle triggering speed trap
+    pub speed: f32,              // Top speed achieved in kilometres per hour
     #[br(map = |x: u8| x > 0)]
     pub is_overall_fastest_in_session: bool, // Overall fastest speed in session = 1, otherwise 0
     #[br(map = |x: u8| x > 0)]
     pub is_driver_fastest_in_session: bool, // Fastest speed for driver in session = 1, otherwise 0
-    pub fastest_vehicle_index_in_session: u8, // Vehicle index of the vehicle that is the fastest
+    pub fastest_vehicle_index_in_session: CarIndex, // Vehicle index of the vehicle that is the fastest
     // in this session
     pub fastest_speed_in_session: f32, // Speed of the vehicle that is the fastest
                                        // in this session
 }
 
 // PARTICIPANTS
-#[derive(Debug, BinRead)]
-pub struct Participants {
-    pub header: Header,
-    pub num_active_cars: u8, // Number of active cars in the data – should match number of
-    // cars on HUD
-    #[br(count = 22)]
-    pub participants_data: Vec<ParticipantsData>,
-}
-
 player_data!(Participants, ParticipantsData, participants_data);
 
-#[derive(Debug, Default, BinRead)]
+impl Participants {
+    /// active_participants returns the participants of the cars on track (num_active_cars),
+    /// indexed by car index
+    pub fn active_participants(&self) -> &[ParticipantsData] {
+        let count = (self.num_active_cars as usize).min(self.participants_data.len());
+        &self.participants_data[..count]
+    }
+}
+
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ParticipantsData {
     #[br(map = |x: u8| x > 0)]
     pub ai_controlled: bool, // Whether the vehicle is AI (1) or Human (0) controlled
     pub driver: Driver, // Driver id - see appendix, 255 if network human
     pub network_id: u8, // Network id – unique identifier for network players
     pub team: Team,     // Team id - see appendix
-    #[br(map = |x: u8| x > 1)]
+    #[br(map = |x: u8| x > 0)]
     pub my_team: bool, // My team flag – 1 = My Team, 0 = otherwise
     pub race_number: u8, // Race number of the car
     pub nationality: Nationality, // Nationality of the driver
     #[br(parse_with = participant_name_parser)]
     pub name: String, // Name of participant in UTF-8 format – null terminated
     // Will be truncated with … (U+2026) if too long
-    #[br(map = |x: u8| x > 1)]
+    #[br(map = |x: u8| x > 0)]
     pub your_telemetry_public: bool, // The player's UDP setting, 0 = restricted, 1 = public
 }
 
+impl ParticipantsData {
+    /// label returns a short display label for the car, race number and driver code (eg: "33 VER")
+    /// or the participant name for drivers without a code (the player and network humans)
+    pub fn label(&self) -> String {
+        match self.driver.abbreviation() {
+            Some(code) => format!("{} {}", self.race_number, code),
+            None => self.name.clone(),
+        }
+    }
+}
+
 fn participant_name_parser<R: binread::io::Read + binread::io::Seek>(
     reader: &mut R,
     _: &binread::ReadOptions,
@@ -823,14 +1216,15 @@ fn participant_name_parser<R: binread::io::Read + binread::io::Seek>(
     let mut bytes: [u8; 48] = [0; 48]; // names for participants are 48 bytes wide
     reader.read_exact(&mut bytes)?;
 
-    let driver_name = std::str::from_utf8(&bytes)
+    let driver_name = core::str::from_utf8(&bytes)
         .unwrap_or("UNKW")
         .trim_matches(char::from(0)); // trim any additional null-bytes
 
     Ok(String::from(driver_name))
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Driver {
     CarlosSainz,
@@ -966,7 +1360,321 @@ pub enum Driver {
 
 binread_enum!(Driver, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl Driver {
+    /// abbreviation returns the 3-letter code shown on timing screens (eg: "VER")
+    /// None for the player and network humans
+    pub fn abbreviation(&self) -> Option<&'static str> {
+        let code = match self {
+            Driver::CarlosSainz => "SAI",
+            Driver::DaniilKvyat => "KVY",
+            Driver::DanielRicciardo => "RIC",
+            Driver::FernandoAlonso => "ALO",
+            Driver::FelipeMassa => "MAS",
+            Driver::KimiRaikkonen => "RAI",
+            Driver::LewisHamilton => "HAM",
+            Driver::MaxVerstappen => "VER",
+            Driver::NicoHulkenburg => "HUL",
+            Driver::KevinMagnussen => "MAG",
+            Driver::RomainGrosjean => "GRO",
+            Driver::SebastianVettel => "VET",
+            Driver::SergioPerez => "PER",
+            Driver::ValtteriBottas => "BOT",
+            Driver::EstebanOcon => "OCO",
+            Driver::LanceStroll => "STR",
+            Driver::ArronBarnes => "BAR",
+            Driver::MartinGiles => "GIL",
+            Driver::AlexMurray => "MUR",
+            Driver::LucasRoth => "ROT",
+            Driver::IgorCorreia => "CRR",
+            Driver::SophieLevasseur => "LEV",
+            Driver::JonasSchiffer => "SCH",
+            Driver::AlainForest => "FOR",
+            Driver::JayLetourneau => "LET",
+            Driver::EstoSaari => "SAA",
+            Driver::YasarAtiyeh => "ATI",
+            Driver::CallistoCalabresi => "CLB",
+            Driver::NaotaIzum => "IZU",
+            Driver::HowardClarke => "CLA",
+            Driver::WilheimKaufmann => "KAU",
+            Driver::MarieLaursen => "LAU",
+            Driver::FlavioNieves => "NIE",
+            Driver::PeterBelousov => "BEL",
+            Driver::KlimekMichalski => "MIC",
+            Driver::SantiagoMoreno => "MOR",
+            Driver::BenjaminCoppens => "COP",
+            Driver::NoahVisser => "VIS",
+            Driver::GertWaldmuller => "WAL",
+            Driver::JulianQuesada => "QUE",
+            Driver::DanielJones => "JON",
+            Driver::ArtemMarkelov => "MAR",
+            Driver::TadasukeMakino => "MAK",
+            Driver::SeanGelael => "GEL",
+            Driver::NyckDeVries => "DEV",
+            Driver::JackAitken => "AIT",
+            Driver::GeorgeRussell => "RUS",
+            Driver::MaximilianGunther => "GUN",
+            Driver::NireiFukuzumi => "FUK",
+            Driver::LucaGhiotto => "GHI",
+            Driver::LandoNorris => "NOR",
+            Driver::SergioSetteCamara => "SET",
+            Driver::LouisDeletraz => "DEL",
+            Driver::AntonioFuoco => "FUO",
+            Driver::CharlesLeclerc => "LEC",
+            Driver::PierreGasly => "GAS",
+            Driver::AlexanderAlbon => "ALB",
+            Driver::NicholasLatifi => "LAT",
+            Driver::DorianBoccolacci => "BOC",
+            Driver::NikoKari => "KAR",
+            Driver::RobertoMerhi => "MER",
+            Driver::ArjunMaini => "MAI",
+            Driver::AlessioLorandi => "LOR",
+            Driver::RubenMeijer => "MEI",
+            Driver::RashidNair => "NAI",
+            Driver::JackTremblay => "TRE",
+            Driver::DevonButler => "BTL",
+            Driver::LukasWeber => "WBR",
+            Driver::AntonioGiovinazzi => "GIO",
+            Driver::RobertKubica => "KUB",
+            Driver::AlainProst => "PRO",
+            Driver::AyrtonSenna => "SEN",
+            Driver::NobuharuMatsushita => "MAT",
+            Driver::NikitaMazepin => "MAZ",
+            Driver::GuanyaZhou => "ZHO",
+            Driver::MickSchumacher => "MSC",
+            Driver::CallumIlott => "ILO",
+            Driver::JuanManuelCorrea => "COR",
+            Driver::JordanKing => "KIN",
+            Driver::MahaveerRaghunathan => "RAG",
+            Driver::TatianaCalderon => "CAL",
+            Driver::AnthoineHubert => "HUB",
+            Driver::GuilianoAlesi => "ALE",
+            Driver::RalphBoschung => "BOS",
+            Driver::MichaelSchumacher => "MSC",
+            Driver::DanTicktum => "TIC",
+            Driver::MarcusArmstrong => "ARM",
+            Driver::ChristianLundgaard => "LUN",
+            Driver::YukiTsunoda => "TSU",
+            Driver::JehanDaruvala => "DAR",
+            Driver::GulhermeSamaia => "SAM",
+            Driver::PedroPiquet => "PIQ",
+            Driver::FelipeDrugovich => "DRU",
+            Driver::RobertSchwartzman => "SHW",
+            Driver::RoyNissany => "NIS",
+            Driver::MarinoSato => "SAT",
+            Driver::AidanJackson => "JAC",
+            Driver::CasperAkkerman => "AKK",
+            Driver::JensonButton => "BUT",
+            Driver::DavidCoulthard => "COU",
+            Driver::NicoRosberg => "ROS",
+            Driver::OscarPiastri => "PIA",
+            Driver::LiamLawson => "LAW",
+            Driver::JuriVips => "VIP",
+            Driver::TheoPourchaire => "POU",
+            Driver::RichardVerschoor => "VSC",
+            Driver::LirimZendeli => "ZEN",
+            Driver::DavidBeckmann => "BEC",
+            Driver::AlessioDeledda => "DED",
+            Driver::BentViscaal => "VCA",
+            Driver::EnzoFittipaldi => "FIT",
+            Driver::MarkWebber => "WEB",
+            Driver::JacquesVilleneuve => "VIL",
+            Driver::JakeHughes => "HUG",
+            Driver::FrederikVesti => "VES",
+            Driver::OlliCaldwell => "CDW",
+            Driver::LoganSargeant => "SAR",
+            Driver::CemBolukbasi => "BOL",
+            Driver::AyumaIwasa => "IWA",
+            Driver::ClementNovolak => "NOV",
+            Driver::DennisHauger => "HAU",
+            Driver::CalanWilliams => "WIL",
+            Driver::JackDoohan => "DOO",
+            Driver::AmauryCordeel => "CRD",
+            Driver::MikaHakkinen => "HAK",
+            Driver::Unknown | Driver::Human => return None,
+        };
+        Some(code)
+    }
+
+    /// number returns the permanent race number of real world drivers
+    pub fn number(&self) -> Option<u8> {
+        let number = match self {
+            Driver::CarlosSainz => 55,
+            Driver::DaniilKvyat => 26,
+            Driver::DanielRicciardo => 3,
+            Driver::FernandoAlonso => 14,
+            Driver::FelipeMassa => 19,
+            Driver::KimiRaikkonen => 7,
+            Driver::LewisHamilton => 44,
+            Driver::MaxVerstappen => 33,
+            Driver::NicoHulkenburg => 27,
+            Driver::KevinMagnussen => 20,
+            Driver::RomainGrosjean => 8,
+            Driver::SebastianVettel => 5,
+            Driver::SergioPerez => 11,
+            Driver::ValtteriBottas => 77,
+            Driver::EstebanOcon => 31,
+            Driver::LanceStroll => 18,
+            Driver::NyckDeVries => 21,
+            Driver::GeorgeRussell => 63,
+            Driver::LandoNorris => 4,
+            Driver::CharlesLeclerc => 16,
+            Driver::PierreGasly => 10,
+            Driver::AlexanderAlbon => 23,
+            Driver::NicholasLatifi => 6,
+            Driver::AntonioGiovinazzi => 99,
+            Driver::RobertKubica => 88,
+            Driver::NikitaMazepin => 9,
+            Driver::GuanyaZhou => 24,
+            Driver::MickSchumacher => 47,
+            Driver::YukiTsunoda => 22,
+            Driver::JensonButton => 22,
+            Driver::NicoRosberg => 6,
+            Driver::OscarPiastri => 81,
+            Driver::LiamLawson => 40,
+            Driver::LoganSargeant => 2,
+            _ => return None,
+        };
+        Some(number)
+    }
+}
+
+impl fmt::Display for Driver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Driver::CarlosSainz => "Carlos Sainz",
+            Driver::DaniilKvyat => "Daniil Kvyat",
+            Driver::DanielRicciardo => "Daniel Ricciardo",
+            Driver::FernandoAlonso => "Fernando Alonso",
+            Driver::FelipeMassa => "Felipe Massa",
+            Driver::KimiRaikkonen => "Kimi Räikkönen",
+            Driver::LewisHamilton => "Lewis Hamilton",
+            Driver::MaxVerstappen => "Max Verstappen",
+            Driver::NicoHulkenburg => "Nico Hülkenberg",
+            Driver::KevinMagnussen => "Kevin Magnussen",
+            Driver::RomainGrosjean => "Romain Grosjean",
+            Driver::SebastianVettel => "Sebastian Vettel",
+            Driver::SergioPerez => "Sergio Pérez",
+            Driver::ValtteriBottas => "Valtteri Bottas",
+            Driver::EstebanOcon => "Esteban Ocon",
+            Driver::LanceStroll => "Lance Stroll",
+            Driver::ArronBarnes => "Arron Barnes",
+            Driver::MartinGiles => "Martin Giles",
+            Driver::AlexMurray => "Alex Murray",
+            Driver::LucasRoth => "Lucas Roth",
+            Driver::IgorCorreia => "Igor Correia",
+            Driver::SophieLevasseur => "Sophie Levasseur",
+            Driver::JonasSchiffer => "Jonas Schiffer",
+            Driver::AlainForest => "Alain Forest",
+            Driver::JayLetourneau => "Jay Letourneau",
+            Driver::EstoSaari => "Esto Saari",
+            Driver::YasarAtiyeh => "Yasar Atiyeh",
+            Driver::CallistoCalabresi => "Callisto Calabresi",
+            Driver::NaotaIzum => "Naota Izumi",
+            Driver::HowardClarke => "Howard Clarke",
+            Driver::WilheimKaufmann => "Wilheim Kaufmann",
+            Driver::MarieLaursen => "Marie Laursen",
+            Driver::FlavioNieves => "Flavio Nieves",
+            Driver::PeterBelousov => "Peter Belousov",
+            Driver::KlimekMichalski => "Klimek Michalski",
+            Driver::SantiagoMoreno => "Santiago Moreno",
+            Driver::BenjaminCoppens => "Benjamin Coppens",
+            Driver::NoahVisser => "Noah Visser",
+            Driver::GertWaldmuller => "Gert Waldmüller",
+            Driver::JulianQuesada => "Julian Quesada",
+            Driver::DanielJones => "Daniel Jones",
+            Driver::ArtemMarkelov => "Artem Markelov",
+            Driver::TadasukeMakino => "Tadasuke Makino",
+            Driver::SeanGelael => "Sean Gelael",
+            Driver::NyckDeVries => "Nyck de Vries",
+            Driver::JackAitken => "Jack Aitken",
+            Driver::GeorgeRussell => "George Russell",
+            Driver::MaximilianGunther => "Maximilian Günther",
+            Driver::NireiFukuzumi => "Nirei Fukuzumi",
+            Driver::LucaGhiotto => "Luca Ghiotto",
+            Driver::LandoNorris => "Lando Norris",
+            Driver::SergioSetteCamara => "Sérgio Sette Câmara",
+            Driver::LouisDeletraz => "Louis Delétraz",
+            Driver::AntonioFuoco => "Antonio Fuoco",
+            Driver::CharlesLeclerc => "Charles Leclerc",
+            Driver::PierreGasly => "Pierre Gasly",
+            Driver::AlexanderAlbon => "Alexander Albon",
+            Driver::NicholasLatifi => "Nicholas Latifi",
+            Driver::DorianBoccolacci => "Dorian Boccolacci",
+            Driver::NikoKari => "Niko Kari",
+            Driver::RobertoMerhi => "Roberto Merhi",
+            Driver::ArjunMaini => "Arjun Maini",
+            Driver::AlessioLorandi => "Alessio Lorandi",
+            Driver::RubenMeijer => "Ruben Meijer",
+            Driver::RashidNair => "Rashid Nair",
+            Driver::JackTremblay => "Jack Tremblay",
+            Driver::DevonButler => "Devon Butler",
+            Driver::LukasWeber => "Lukas Weber",
+            Driver::AntonioGiovinazzi => "Antonio Giovinazzi",
+            Driver::RobertKubica => "Robert Kubica",
+            Driver::AlainProst => "Alain Prost",
+            Driver::AyrtonSenna => "Ayrton Senna",
+            Driver::NobuharuMatsushita => "Nobuharu Matsushita",
+            Driver::NikitaMazepin => "Nikita Mazepin",
+            Driver::GuanyaZhou => "Guanya Zhou",
+            Driver::MickSchumacher => "Mick Schumacher",
+            Driver::CallumIlott => "Callum Ilott",
+            Driver::JuanManuelCorrea => "Juan Manuel Correa",
+            Driver::JordanKing => "Jordan King",
+            Driver::MahaveerRaghunathan => "Mahaveer Raghunathan",
+            Driver::TatianaCalderon => "Tatiana Calderón",
+            Driver::AnthoineHubert => "Anthoine Hubert",
+            Driver::GuilianoAlesi => "Giuliano Alesi",
+            Driver::RalphBoschung => "Ralph Boschung",
+            Driver::MichaelSchumacher => "Michael Schumacher",
+            Driver::DanTicktum => "Dan Ticktum",
+            Driver::MarcusArmstrong => "Marcus Armstrong",
+            Driver::ChristianLundgaard => "Christian Lundgaard",
+            Driver::YukiTsunoda => "Yuki Tsunoda",
+            Driver::JehanDaruvala => "Jehan Daruvala",
+            Driver::GulhermeSamaia => "Guilherme Samaia",
+            Driver::PedroPiquet => "Pedro Piquet",
+            Driver::FelipeDrugovich => "Felipe Drugovich",
+            Driver::RobertSchwartzman => "Robert Schwartzman",
+            Driver::RoyNissany => "Roy Nissany",
+            Driver::MarinoSato => "Marino Sato",
+            Driver::AidanJackson => "Aidan Jackson",
+            Driver::CasperAkkerman => "Casper Akkerman",
+            Driver::JensonButton => "Jenson Button",
+            Driver::DavidCoulthard => "David Coulthard",
+            Driver::NicoRosberg => "Nico Rosberg",
+            Driver::OscarPiastri => "Oscar Piastri",
+            Driver::LiamLawson => "Liam Lawson",
+            Driver::JuriVips => "Jüri Vips",
+            Driver::TheoPourchaire => "Théo Pourchaire",
+            Driver::RichardVerschoor => "Richard Verschoor",
+            Driver::LirimZendeli => "Lirim Zendeli",
+            Driver::DavidBeckmann => "David Beckmann",
+            Driver::AlessioDeledda => "Alessio Deledda",
+            Driver::BentViscaal => "Bent Viscaal",
+            Driver::EnzoFittipaldi => "Enzo Fittipaldi",
+            Driver::MarkWebber => "Mark Webber",
+            Driver::JacquesVilleneuve => "Jacques Villeneuve",
+            Driver::JakeHughes => "Jake Hughes",
+            Driver::FrederikVesti => "Frederik Vesti",
+            Driver::OlliCaldwell => "Olli Caldwell",
+            Driver::LoganSargeant => "Logan Sargeant",
+            Driver::CemBolukbasi => "Cem Bölükbaşı",
+            Driver::AyumaIwasa => "Ayumu Iwasa",
+            Driver::ClementNovolak => "Clément Novalak",
+            Driver::DennisHauger => "Dennis Hauger",
+            Driver::CalanWilliams => "Calan Williams",
+            Driver::JackDoohan => "Jack Doohan",
+            Driver::AmauryCordeel => "Amaury Cordeel",
+            Driver::MikaHakkinen => "Mika Häkkinen",
+            Driver::Unknown => "Unknown",
+            Driver::Human => "Human",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Team {
     Mercedes,
@@ -1028,7 +1736,133 @@ pub enum Team {
 
 binread_enum!(Team, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl Team {
+    /// info returns the short code and livery colors of the team
+    /// (None for generic and player created teams)
+    pub fn info(&self) -> Option<TeamInfo> {
+        let info = match self {
+            Team::Mercedes => TeamInfo::new("MER", 0x6CD3BF, 0x000000),
+            Team::Ferrari => TeamInfo::new("FER", 0xF91536, 0xFFF200),
+            Team::RedBullRacing => TeamInfo::new("RBR", 0x3671C6, 0xCC1E4A),
+            Team::Williams => TeamInfo::new("WIL", 0x37BEDD, 0x00205B),
+            Team::AstonMartin => TeamInfo::new("AMR", 0x358C75, 0xCEDC00),
+            Team::Alpine => TeamInfo::new("ALP", 0x2293D1, 0xFD4BC7),
+            Team::AlphaTauri => TeamInfo::new("APT", 0x5E8FAA, 0xFFFFFF),
+            Team::Haas => TeamInfo::new("HAA", 0xB6BABD, 0xED1C24),
+            Team::McLaren => TeamInfo::new("MCL", 0xF58020, 0x47C7FC),
+            Team::AlfaRomeo => TeamInfo::new("ALF", 0xC92D4B, 0xFFFFFF),
+            Team::Mercedes2020 => TeamInfo::new("MER", 0x00D2BE, 0x000000),
+            Team::Ferrari2020 => TeamInfo::new("FER", 0xDC0000, 0xFFF200),
+            Team::RedBull2020 => TeamInfo::new("RBR", 0x1E41FF, 0xCC1E4A),
+            Team::Williams2020 => TeamInfo::new("WIL", 0x0082FA, 0xFFFFFF),
+            Team::RacingPoint2020 => TeamInfo::new("RPT", 0xF596C8, 0x1E5BC6),
+            Team::Renault2020 => TeamInfo::new("REN", 0xFFF500, 0x000000),
+            Team::AlphaTauri2020 => TeamInfo::new("APT", 0xFFFFFF, 0x2B4562),
+            Team::Haas2020 => TeamInfo::new("HAA", 0x787878, 0xED1C24),
+            Team::McLaren2020 => TeamInfo::new("MCL", 0xFF8700, 0x47C7FC),
+            Team::AlfaRomeo2020 => TeamInfo::new("ALF", 0x960000, 0xFFFFFF),
+            Team::AstonMartinDB11V12 => TeamInfo::new("AMR", 0x006F62, 0xFFFFFF),
+            Team::AstonMartinVantageF1Edition => TeamInfo::new("AMR", 0x006F62, 0xFFFFFF),
+            Team::AstonMartinVantageSafetyCar => TeamInfo::new("AMR", 0x006F62, 0xFFFFFF),
+            Team::FerrariF8Tributo => TeamInfo::new("FER", 0xDC0000, 0xFFF200),
+            Team::FerrariRoma => TeamInfo::new("FER", 0xDC0000, 0xFFF200),
+            Team::McLaren720S => TeamInfo::new("MCL", 0xFF8000, 0x000000),
+            Team::McLarenArtura => TeamInfo::new("MCL", 0xFF8000, 0x000000),
+            Team::MercedesAMGGTBlackSeriesSafetyCar => TeamInfo::new("MER", 0xC0C0C0, 0x000000),
+            Team::MercedesAMGGTRPro => TeamInfo::new("MER", 0xC0C0C0, 0x000000),
+            Team::Prema2021 => TeamInfo::new("PRE", 0xE4002B, 0xFFFFFF),
+            Team::UniVirtuosi2021 => TeamInfo::new("VIR", 0xFFD700, 0x000000),
+            Team::Carlin2021 => TeamInfo::new("CAR", 0x0A2240, 0xFFFFFF),
+            Team::Hitech2021 => TeamInfo::new("HIT", 0xC0C0C0, 0xE4002B),
+            Team::ArtGP2021 => TeamInfo::new("ART", 0x5A5A5A, 0xFFFFFF),
+            Team::MPMotorsport2021 => TeamInfo::new("MPM", 0xFF5F00, 0x000000),
+            Team::Charouz2021 => TeamInfo::new("CHA", 0x9B0000, 0xFFFFFF),
+            Team::Dams2021 => TeamInfo::new("DAM", 0x00AEEF, 0xFFFFFF),
+            Team::Campos2021 => TeamInfo::new("CAM", 0x00205B, 0xFFD200),
+            Team::BWT2021 => TeamInfo::new("HWA", 0xF596C8, 0xFFFFFF),
+            Team::Trident2021 => TeamInfo::new("TRI", 0x00205B, 0xC0C0C0),
+            Team::MercedesAMGGTBlackSeries => TeamInfo::new("MER", 0xC0C0C0, 0x000000),
+            Team::Prema2022 => TeamInfo::new("PRE", 0xE4002B, 0xFFFFFF),
+            Team::Virtuosi2022 => TeamInfo::new("VIR", 0xFFD700, 0x000000),
+            Team::Carlin2022 => TeamInfo::new("CAR", 0x0A2240, 0xFFFFFF),
+            Team::Hitech2022 => TeamInfo::new("HIT", 0xC0C0C0, 0xE4002B),
+            Team::ArtGP2022 => TeamInfo::new("ART", 0x5A5A5A, 0xFFFFFF),
+            Team::MPMotorsport2022 => TeamInfo::new("MPM", 0xFF5F00, 0x000000),
+            Team::Charouz2022 => TeamInfo::new("CHA", 0x9B0000, 0xFFFFFF),
+            Team::Dams2022 => TeamInfo::new("DAM", 0x00AEEF, 0xFFFFFF),
+            Team::Campos2022 => TeamInfo::new("CAM", 0x00205B, 0xFFD200),
+            Team::VanAmersfoortRacing2022 => TeamInfo::new("VAR", 0x003DA5, 0xFFFFFF),
+            Team::Trident2022 => TeamInfo::new("TRI", 0x00205B, 0xC0C0C0),
+            Team::F1CustomTeam | Team::Unknown => return None,
+        };
+        Some(info)
+    }
+}
+
+impl fmt::Display for Team {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Team::Mercedes => "Mercedes",
+            Team::Ferrari => "Ferrari",
+            Team::RedBullRacing => "Red Bull Racing",
+            Team::Williams => "Williams",
+            Team::AstonMartin => "Aston Martin",
+            Team::Alpine => "Alpine",
+            Team::AlphaTauri => "AlphaTauri",
+            Team::Haas => "Haas",
+            Team::McLaren => "McLaren",
+            Team::AlfaRomeo => "Alfa Romeo",
+            Team::Mercedes2020 => "Mercedes 2020",
+            Team::Ferrari2020 => "Ferrari 2020",
+            Team::RedBull2020 => "Red Bull 2020",
+            Team::Williams2020 => "Williams 2020",
+            Team::RacingPoint2020 => "Racing Point 2020",
+            Team::Renault2020 => "Renault 2020",
+            Team::AlphaTauri2020 => "AlphaTauri 2020",
+            Team::Haas2020 => "Haas 2020",
+            Team::McLaren2020 => "McLaren 2020",
+            Team::AlfaRomeo2020 => "Alfa Romeo 2020",
+            Team::AstonMartinDB11V12 => "Aston Martin DB11 V12",
+            Team::AstonMartinVantageF1Edition => "Aston Martin Vantage F1 Edition",
+            Team::AstonMartinVantageSafetyCar => "Aston Martin Vantage Safety Car",
+            Team::FerrariF8Tributo => "Ferrari F8 Tributo",
+            Team::FerrariRoma => "Ferrari Roma",
+            Team::McLaren720S => "McLaren 720S",
+            Team::McLarenArtura => "McLaren Artura",
+            Team::MercedesAMGGTBlackSeriesSafetyCar => "Mercedes-AMG GT Black Series Safety Car",
+            Team::MercedesAMGGTRPro => "Mercedes-AMG GTR Pro",
+            Team::F1CustomTeam => "F1 Custom Team",
+            Team::Prema2021 => "Prema 2021",
+            Team::UniVirtuosi2021 => "UNI-Virtuosi 2021",
+            Team::Carlin2021 => "Carlin 2021",
+            Team::Hitech2021 => "Hitech 2021",
+            Team::ArtGP2021 => "ART GP 2021",
+            Team::MPMotorsport2021 => "MP Motorsport 2021",
+            Team::Charouz2021 => "Charouz 2021",
+            Team::Dams2021 => "DAMS 2021",
+            Team::Campos2021 => "Campos 2021",
+            Team::BWT2021 => "BWT 2021",
+            Team::Trident2021 => "Trident 2021",
+            Team::MercedesAMGGTBlackSeries => "Mercedes-AMG GT Black Series",
+            Team::Prema2022 => "Prema 2022",
+            Team::Virtuosi2022 => "Virtuosi 2022",
+            Team::Carlin2022 => "Carlin 2022",
+            Team::Hitech2022 => "Hitech 2022",
+            Team::ArtGP2022 => "ART GP 2022",
+            Team::MPMotorsport2022 => "MP Motorsport 2022",
+            Team::Charouz2022 => "Charouz 2022",
+            Team::Dams2022 => "DAMS 2022",
+            Team::Campos2022 => "Campos 2022",
+            Team::VanAmersfoortRacing2022 => "Van Amersfoort Racing 2022",
+            Team::Trident2022 => "Trident 2022",
+            Team::Unknown => "Unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Nationality {
     #[default]
@@ -1125,58 +1959,42 @@ pub enum Nationality {
 binread_enum!(Nationality, u8);
 
 // CAR SETUP
-#[derive(Debug, BinRead)]
-pub struct CarSetup {
-    pub header: Header,
-    #[br(count = 22)]
-    pub car_setup_data: Vec<CarSetupData>,
-}
-
 player_data!(CarSetup, CarSetupData, car_setup_data);
-
-#[derive(Debug, Default, BinRead)]
-pub struct CarSetupData {
-    pub wing: FrontRearValue<u8>,              // Wing aero
-    pub on_throttle: u8,                       // Differential adjustment on throttle (percentage)
-    pub off_throttle: u8,                      // Differential adjustment off throttle (percentage)
-    pub camber: FrontRearValue<f32>,           // Camber angle (suspension geometry)
-    pub toe: FrontRearValue<f32>,              // Toe angle (suspension geometry)
-    pub suspension: FrontRearValue<u8>,        // Suspension
-    pub anti_roll_bar: FrontRearValue<u8>,     // Anti-roll bar
-    pub suspension_height: FrontRearValue<u8>, // Ride height
-    pub brake_pressure: u8,                    // Brake pressure (percentage)
-    pub brake_bias: u8,                        // Brake bias (percentage)
-    pub type_pressure: WheelValue<f32>,        // Tyre pressure (PSI)
-    pub ballast: u8,                           // Ballast
-    pub fuel_load: f32,                        // Fuel load
-}
+active_data!(CarSetup, CarSetupData, car_setup_data, active_car_setups);
 
 // CAR TELEMETRY
-#[derive(Debug, BinRead)]
+#[derive(Debug, Clone, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CarTelemetry {
     pub header: Header,
-    #[br(count = 22)]
-    pub car_telemetry_data: Vec<CarTelemetryData>,
+    pub car_telemetry_data: [CarTelemetryData; 22],
     pub mfd_panel: MFDPanel, // Index of MFD panel open - 255 = MFD closed
     // Single player, race – 0 = Car setup, 1 = Pits
     // 2 = Damage, 3 =  Engine, 4 = Temperatures
     // May vary depending on game mode
     pub mfd_panel_secondary_player: MFDPanel, // See above
-    #[br(map = |x: i8| if x == 0 { Gear::Unknown } else { Gear::try_from(x).unwrap() })]
+    #[br(map = |x: i8| if x == 0 { Gear::Unknown } else { Gear::try_from(x).unwrap_or_default() })]
     pub suggested_gear: Gear, // Suggested gear for the player (1-8)
                                               // 0 if no gear suggested
 }
 
 player_data!(CarTelemetry, CarTelemetryData, car_telemetry_data);
+active_data!(
+    CarTelemetry,
+    CarTelemetryData,
+    car_telemetry_data,
+    active_car_telemetry
+);
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CarTelemetryData {
     pub speed: u16,    // Speed of car in kilometres per hour
     pub throttle: f32, // Amount of throttle applied (0.0 to 1.0)
     pub steer: f32,    // Steering (-1.0 (full lock left) to 1.0 (full lock right))
     pub brake: f32,    // Amount of brake applied (0.0 to 1.0)
     pub clutch: u8,    // Amount of clutch applied (0 to 100)
-    #[br(map = |x: i8| Gear::try_from(x).unwrap())]
+    #[br(map = |x: i8| Gear::try_from(x).unwrap_or_default())]
     pub gear: Gear, // Gear selected (1-8, N=0, R=-1)
     pub engine_rpm: u16, // Engine RPM
     #[br(map = |x: u8| x > 0)]
@@ -1192,7 +2010,8 @@ pub struct CarTelemetryData {
     pub surface_type: WheelValue<Surface>, // Driving surface, see appendices
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(i8)]
 pub enum Gear {
     Reverse = -1,
@@ -1211,7 +2030,28 @@ pub enum Gear {
 
 binread_enum!(Gear, i8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl Gear {
+    /// as_number returns the gear as a number, -1 for reverse and 0 for neutral (or unknown)
+    pub fn as_number(&self) -> i8 {
+        match self {
+            Gear::Unknown => 0,
+            gear => *gear as i8,
+        }
+    }
+}
+
+impl fmt::Display for Gear {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Gear::Reverse => f.write_str("R"),
+            Gear::Neutral | Gear::Unknown => f.write_str("N"),
+            gear => write!(f, "{}", gear.as_number()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum Surface {
     Tarmac,
@@ -1248,7 +2088,8 @@ fn surface_type_parser<R: binread::io::Read + binread::io::Seek>(
     })
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum MFDPanel {
     CarSetup,
@@ -1265,16 +2106,11 @@ binread_enum!(MFDPanel, u8);
 
 // CAR STATUS
 
-#[derive(Debug, BinRead)]
-pub struct CarStatus {
-    pub header: Header,
-    #[br(count = 22)]
-    pub car_status_data: Vec<CarStatusData>,
-}
-
 player_data!(CarStatus, CarStatusData, car_status_data);
+active_data!(CarStatus, CarStatusData, car_status_data, active_car_status);
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CarStatusData {
     pub traction_control: u8, // Traction control - 0 = off, 1 = medium, 2 = full
     #[br(map = |x: u8| x > 0)]
@@ -1311,7 +2147,8 @@ pub struct CarStatusData {
     pub network_paused: u8, // Whether the car is paused in a network game
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum FuelMix {
     Lean,
@@ -1324,7 +2161,8 @@ pub enum FuelMix {
 
 binread_enum!(FuelMix, u8);
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u16)]
 pub enum DRSActivationDistance {
     #[default]
@@ -1332,7 +2170,8 @@ pub enum DRSActivationDistance {
     Distance(u16),
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum TyreCompound {
     Inter = 7,
@@ -1355,7 +2194,31 @@ pub enum TyreCompound {
 
 binread_enum!(TyreCompound, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl fmt::Display for TyreCompound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TyreCompound::Inter => "Intermediate",
+            TyreCompound::Wet => "Wet",
+            TyreCompound::F1ClassicDry => "Classic Dry",
+            TyreCompound::F1ClassicWet => "Classic Wet",
+            TyreCompound::F2SuperSoft => "F2 Super Soft",
+            TyreCompound::F2Soft => "F2 Soft",
+            TyreCompound::F2Medium => "F2 Medium",
+            TyreCompound::F2Hard => "F2 Hard",
+            TyreCompound::F2Wet => "F2 Wet",
+            TyreCompound::C5 => "C5",
+            TyreCompound::C4 => "C4",
+            TyreCompound::C3 => "C3",
+            TyreCompound::C2 => "C2",
+            TyreCompound::C1 => "C1",
+            TyreCompound::Unknown => "Unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum TyreVisual {
     Inter = 7,
@@ -1376,7 +2239,29 @@ pub enum TyreVisual {
 
 binread_enum!(TyreVisual, u8);
 
-#[derive(Debug, Default, TryFromPrimitive)]
+impl fmt::Display for TyreVisual {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TyreVisual::Inter => "Intermediate",
+            TyreVisual::Wet => "Wet",
+            TyreVisual::ClassicDry => "Classic Dry",
+            TyreVisual::ClassicWet => "Classic Wet",
+            TyreVisual::F2Wet => "F2 Wet",
+            TyreVisual::Soft => "Soft",
+            TyreVisual::Medium => "Medium",
+            TyreVisual::Hard => "Hard",
+            TyreVisual::F2SuperSoft => "F2 Super Soft",
+            TyreVisual::F2Soft => "F2 Soft",
+            TyreVisual::F2Medium => "F2 Medium",
+            TyreVisual::F2Hard => "F2 Hard",
+            TyreVisual::Unknown => "Unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(i8)]
 pub enum FiaFlag {
     #[default]
@@ -1390,17 +2275,8 @@ pub enum FiaFlag {
 
 binread_enum!(FiaFlag, i8);
 
-#[derive(Debug, Default, BinRead)]
-pub struct ERS {
-    pub stored_energy: f32,         // ERS energy store in Joules
-    pub deploy_mode: ERSDeployMode, // ERS deployment mode, 0 = none, 1 = medium
-    // 2 = hotlap, 3 = overtake
-    pub harvested_this_lap_mguk: f32, // ERS energy harvested this lap by MGU-K
-    pub harvested_this_lap_mguh: f32, // ERS energy harvested this lap by MGU-H
-    pub deployed_this_lap: f32,       // ERS energy deployed this lap
-}
-
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum ERSDeployMode {
     None,
@@ -1414,21 +2290,55 @@ pub enum ERSDeployMode {
 binread_enum!(ERSDeployMode, u8);
 
 // FINAL CLASSIFICATION
-#[derive(Debug, BinRead)]
-pub struct FinalClassification {
-    pub header: Header,
-    pub number_of_cars: u8, // Number of cars in the final classification
-    #[br(count = 22)]
-    pub final_classification_data: Vec<FinalClassificationData>,
-}
-
 player_data!(
     FinalClassification,
     FinalClassificationData,
     final_classification_data
 );
 
-#[derive(Debug, Default, BinRead)]
+impl FinalClassification {
+    /// classified returns the (car index, data) of the classified cars ordered by finishing position
+    pub fn classified(&self) -> Vec<(CarIndex, &FinalClassificationData)> {
+        let number_of_cars = self.number_of_cars as usize;
+        let mut classified: Vec<(CarIndex, &FinalClassificationData)> = self
+            .final_classification_data
+            .iter()
+            .take(number_of_cars)
+            .enumerate()
+            .map(|(index, data)| (CarIndex(index as u8), data))
+            .collect();
+        classified.sort_by_key(|(_, data)| data.position);
+        classified
+    }
+
+    /// car returns the classification of the given car index
+    pub fn car(&self, car_index: CarIndex) -> Option<&FinalClassificationData> {
+        self.final_classification_data.get(car_index.index()?)
+    }
+
+    /// position returns the (car index, data) of the car that finished in the given position
+    pub fn position(&self, position: u8) -> Option<(CarIndex, &FinalClassificationData)> {
+        self.classified()
+            .into_iter()
+            .find(|(_, data)| data.position == position)
+    }
+
+    /// driver returns the classification of the given driver, using participants to find its car
+    pub fn driver(
+        &self,
+        participants: &Participants,
+        driver: Driver,
+    ) -> Option<&FinalClassificationData> {
+        let car_index = participants
+            .participants_data
+            .iter()
+            .position(|participant| participant.driver == driver)?;
+        self.final_classification_data.get(car_index)
+    }
+}
+
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct FinalClassificationData {
     pub position: u8,                // Finishing position
     pub number_of_laps: u8,          // Number of laps completed
@@ -1443,36 +2353,30 @@ pub struct FinalClassificationData {
     pub penalties_time_s: u8,  // Total penalties accumulated in seconds
     pub number_of_penalties: u8, // Number of penalties applied to this driver
     pub number_of_tyre_stints: u8, // Number of tyres stints up to maximum
-    #[br(count = 8)]
-    pub tyre_stints_actual: Vec<TyreCompound>, // Actual tyres used by this driver
-    #[br(count = 8)]
-    pub tyre_stints_visual: Vec<TyreVisual>, // Visual tyres used by this driver
-    #[br(count = 8)]
-    pub tyre_stints_end_laps: Vec<u8>, // The lap number stints end on
+    pub tyre_stints_actual: [TyreCompound; 8], // Actual tyres used by this driver
+    pub tyre_stints_visual: [TyreVisual; 8], // Visual tyres used by this driver
+    pub tyre_stints_end_laps: [u8; 8], // The lap number stints end on
 }
 
 // LOBBY INFO
-#[derive(Debug, BinRead)]
-pub struct LobbyInfo {
-    pub header: Header,
-    pub number_of_players: u8, // Number of players in the lobby data
-    #[br(count = 22)]
-    pub lobby_players: Vec<LobbyInfoData>,
-}
-
 player_data!(LobbyInfo, LobbyInfoData, lobby_players);
 
 impl LobbyInfo {
+    /// active_players returns the players in the lobby (number_of_players)
+    pub fn active_players(&self) -> &[LobbyInfoData] {
+        let count = (self.number_of_players as usize).min(self.lobby_players.len());
+        &self.lobby_players[..count]
+    }
+
     pub fn players(self) -> Vec<LobbyInfoData> {
-        let number_of_players = self.number_of_players as usize;
-        self.lobby_players
-            .into_iter()
-            .take(number_of_players)
-            .collect()
+        let mut players = Vec::from(self.lobby_players);
+        players.truncate(self.number_of_players as usize);
+        players
     }
 }
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct LobbyInfoData {
     #[br(map = |x: u8| x > 0)]
     pub ai_controlled: bool, // Whether the vehicle is AI (1) or Human (0) controlled
@@ -1485,7 +2389,8 @@ pub struct LobbyInfoData {
     pub status: LobbyStatus, // 0 = not ready, 1 = ready, 2 = spectating
 }
 
-#[derive(Debug, Default, TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum LobbyStatus {
     NotReady,
@@ -1498,18 +2403,20 @@ pub enum LobbyStatus {
 binread_enum!(LobbyStatus, u8);
 
 // CAR DAMAGE
-#[derive(Debug, BinRead)]
+#[derive(Debug, Clone, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CarDamage {
     pub header: Header,
-    #[br(count = 22)]
-    pub car_damage_data: Vec<CarDamageData>,
+    pub car_damage_data: [CarDamageData; 22],
 }
 
 player_data!(CarDamage, CarDamageData, car_damage_data);
+active_data!(CarDamage, CarDamageData, car_damage_data, active_car_damage);
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CarDamageData {
-    pub tyres_wear: WheelValue<u8>,    // Tyre wear (percentage)
+    pub tyres_wear: WheelValue<f32>,   // Tyre wear (percentage)
     pub tyres_damage: WheelValue<u8>,  // Tyre damage (percentage)
     pub brakes_damage: WheelValue<u8>, // Brakes damage (percentage)
     pub wing_damage: WingValue<u8>,    // Wing damage (percentage)
@@ -1535,23 +2442,56 @@ pub struct CarDamageData {
 }
 
 // SESSION HISTORY
-#[derive(Debug, BinRead)]
+#[derive(Debug, Clone, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SessionHistory {
     pub header: Header,            // Header
-    pub car_index: u8,             // Index of the car this lap data relates to
+    pub car_index: CarIndex,       // Index of the car this lap data relates to
     pub num_laps: u8,              // Num laps in the data (including current partial lap)
     pub num_tyre_stints: u8,       // Number of tyre stints in the data
     pub best_lap_time_lap_num: u8, // Lap the best lap time was achieved on
     pub best_sector1_lap_num: u8,  // Lap the best Sector 1 time was achieved on
     pub best_sector2_lap_num: u8,  // Lap the best Sector 2 time was achieved on
     pub best_sector3_lap_num: u8,  // Lap the best Sector 3 time was achieved on
-    #[br(count = 100)]
-    pub lap_history_data: Vec<LapHistoryData>, // 100 laps of data max
-    #[br(count = 8)]
-    pub tyre_stints_history_data: Vec<TyreStintHistoryData>,
+    #[br(args(num_laps))]
+    pub lap_history_data: TruncatedArray<LapHistoryData, 100>, // truncated to num_laps
+    #[br(args(num_tyre_stints))]
+    pub tyre_stints_history_data: TruncatedArray<TyreStintHistoryData, 8>, // to num_tyre_stints
 }
 
-#[derive(Debug, Default, BinRead)]
+impl SessionHistory {
+    /// lap returns the history of the given lap number (starting at 1)
+    pub fn lap(&self, lap_number: u8) -> Option<&LapHistoryData> {
+        self.lap_history_data
+            .get(lap_number.checked_sub(1)? as usize)
+    }
+
+    /// valid_laps iterates over the completed laps that were not invalidated
+    /// yields the lap number alongside its history
+    pub fn valid_laps(&self) -> impl Iterator<Item = (u8, &LapHistoryData)> {
+        self.lap_history_data
+            .iter()
+            .enumerate()
+            .filter(|(_, lap)| lap.lap_time_ms > 0 && lap.is_valid())
+            .map(|(index, lap)| (index as u8 + 1, lap))
+    }
+
+    /// best_lap returns the history of the lap the best lap time was achieved on
+    pub fn best_lap(&self) -> Option<&LapHistoryData> {
+        self.lap(self.best_lap_time_lap_num)
+    }
+
+    /// best_theoretical_lap_ms returns the sum of the best sector times in milliseconds
+    pub fn best_theoretical_lap_ms(&self) -> Option<u32> {
+        let sector1 = self.lap(self.best_sector1_lap_num)?.sector_times_ms.0;
+        let sector2 = self.lap(self.best_sector2_lap_num)?.sector_times_ms.1;
+        let sector3 = self.lap(self.best_sector3_lap_num)?.sector_times_ms.2;
+        Some(sector1 as u32 + sector2 as u32 + sector3 as u32)
+    }
+}
+
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct LapHistoryData {
     pub lap_time_ms: u32,                 // Lap time in milliseconds
     pub sector_times_ms: (u16, u16, u16), // Sector times in milliseconds
@@ -1561,8 +2501,16 @@ pub struct LapHistoryData {
                                             // 0x04 bit set-sector 2 valid, 0x08 bit set-sector 3 valid
 }
 
+impl LapHistoryData {
+    /// is_valid returns whether the lap was not invalidated
+    pub fn is_valid(&self) -> bool {
+        self.lap_valid_bit_flags.contains(LapValidFlags::LAP_VALID)
+    }
+}
+
 bitflags! {
-    #[derive(Debug)]
+    #[derive(Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub struct LapValidFlags: u8 {
         const LAP_VALID         = 0x01;
         const SECTOR_1_VALID    = 0x02;
@@ -1588,7 +2536,8 @@ fn lap_valid_flags_aprser<R: binread::io::Read + binread::io::Seek>(
     Ok(LapValidFlags::from_bits(bytes[0]).unwrap_or_default())
 }
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TyreStintHistoryData {
     pub end_lap: u8, // Lap the tyre usage ends on (255 of current tyre)
     pub tyre_actual_compound: TyreCompound, // Actual tyres used by this driver
@@ -1598,62 +2547,87 @@ pub struct TyreStintHistoryData {
 // PARSING
 impl TelemetryEvent for F1_2022 {
     fn from_packet(packet: &TelemetryPacket) -> Result<F1_2022, Box<dyn Error>> {
+        Self::from_packet_with_endianness(packet, Endianness::Little)
+    }
+
+    fn from_packet_with_endianness(
+        packet: &TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<F1_2022, Box<dyn Error>> {
         if packet.len() < 24 {
             return Err(Box::from("Packet is too small to contain a header"));
         }
 
         let packet_id = packet[5]; // packet_id
-        let mut reader = Cursor::new(packet);
+        let endian = binread::Endian::from(endianness);
         match packet_id {
             0 => {
-                let data: Motion = reader.read_le()?;
+                let data: Motion = read_packet(packet, endian)?;
                 Ok(F1_2022::Motion(data))
             }
             1 => {
-                let data: Session = reader.read_le()?;
+                let data: Session = read_packet(packet, endian)?;
                 Ok(F1_2022::Session(data))
             }
             2 => {
-                let data: LapData = reader.read_le()?;
+                let data: LapData = read_packet(packet, endian)?;
                 Ok(F1_2022::LapData(data))
             }
             3 => {
-                let data: Event = reader.read_le()?;
+                let data: Event = read_packet(packet, endian)?;
                 Ok(F1_2022::Event(data))
             }
             4 => {
-                let data: Participants = reader.read_le()?;
+                let data: Participants = read_packet(packet, endian)?;
                 Ok(F1_2022::Participants(data))
             }
             5 => {
-                let data: CarSetup = reader.read_le()?;
+                let data: CarSetup = read_packet(packet, endian)?;
                 Ok(F1_2022::CarSetup(data))
             }
             6 => {
-                let data: CarTelemetry = reader.read_le()?;
+                let data: CarTelemetry = read_packet(packet, endian)?;
                 Ok(F1_2022::CarTelemetry(data))
             }
             7 => {
-                let data: CarStatus = reader.read_le()?;
+                let data: CarStatus = read_packet(packet, endian)?;
                 Ok(F1_2022::CarStatus(data))
             }
             8 => {
-                let data: FinalClassification = reader.read_le()?;
+                let data: FinalClassification = read_packet(packet, endian)?;
                 Ok(F1_2022::FinalClassification(data))
             }
             9 => {
-                let data: LobbyInfo = reader.read_le()?;
+                let data: LobbyInfo = read_packet(packet, endian)?;
                 Ok(F1_2022::LobbyInfo(data))
             }
             10 => {
-                let data: CarDamage = reader.read_le()?;
+                let data: CarDamage = read_packet(packet, endian)?;
                 Ok(F1_2022::CarDamage(data))
             }
             11 => {
-                let data: SessionHistory = reader.read_le()?;
+                let data: SessionHistory = read_packet(packet, endian)?;
                 Ok(F1_2022::SessionHistory(data))
             }
             id => Err(Box::from(format!("Unknown packet type: {}", id))),
         }
     }
 }
+
+impl TelemetryFrame for F1_2022 {
+    fn session_uid(&self) -> u64 {
+        self.header().session_uid
+    }
+
+    fn frame_identifier(&self) -> u32 {
+        self.header().frame_identifier
+    }
+
+    fn packet_id(&self) -> u8 {
+        self.header().packet_id
+    }
+
+    fn session_time(&self) -> f32 {
+        self.header().session_time
+    }
+}
@@ -0,0 +1,125 @@
+use alloc::vec::Vec;
+
+use crate::f1::f1_2022::{SafetyCarStatus, Session, ZoneFlag};
+
+/// TrackState is the overall condition of the track, derived from a Session packet
+/// when several apply the most restrictive one wins (red flag over safety car over yellow)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TrackState {
+    #[default]
+    Green,
+    Yellow,
+    FormationLap,
+    VirtualSafetyCar,
+    SafetyCar,
+    Red,
+}
+
+impl TrackState {
+    /// from_session computes the track state from the safety car status and marshal zone flags
+    pub fn from_session(session: &Session) -> TrackState {
+        let zones = session.active_marshal_zones();
+        if zones.iter().any(|zone| zone.zone_flag == ZoneFlag::Red) {
+            return TrackState::Red;
+        }
+
+        match session.safety_car_status {
+            SafetyCarStatus::FullSafetyCar => TrackState::SafetyCar,
+            SafetyCarStatus::VirtualSafetyCar => TrackState::VirtualSafetyCar,
+            SafetyCarStatus::FormationLap => TrackState::FormationLap,
+            _ if session.active_yellow_sectors().next().is_some() => TrackState::Yellow,
+            _ => TrackState::Green,
+        }
+    }
+}
+
+/// FlagTransition is what changed between two track states
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FlagTransition {
+    GreenFlag,
+    YellowFlag,
+    FormationLap,
+    VirtualSafetyCarDeployed,
+    VirtualSafetyCarEnding,
+    SafetyCarDeployed,
+    SafetyCarEnding,
+    RedFlag,
+}
+
+/// FlagEvent is a transition stamped with the session time of the packet that caused it
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FlagEvent {
+    pub session_time: f32,
+    pub frame_identifier: u32,
+    pub transition: FlagTransition,
+}
+
+/// FlagTracker follows the track state over "F1 22" Session packets
+/// and emits the transitions instead of requiring consumers to diff packets
+#[derive(Debug, Default, Clone)]
+pub struct FlagTracker {
+    state: Option<TrackState>,
+    since: f32,
+}
+
+impl FlagTracker {
+    pub fn new() -> FlagTracker {
+        FlagTracker::default()
+    }
+
+    /// update feeds a Session packet and returns the transitions it caused (oldest first)
+    /// leaving a (virtual) safety car emits its ending before the new state
+    /// the first packet only sets the initial state
+    pub fn update(&mut self, session: &Session) -> Vec<FlagEvent> {
+        let current = TrackState::from_session(session);
+        let previous = self.state.replace(current);
+        if previous == Some(current) {
+            return Vec::new();
+        }
+
+        self.since = session.header.session_time;
+        let previous = match previous {
+            Some(previous) => previous,
+            None => return Vec::new(),
+        };
+
+        let mut transitions = Vec::with_capacity(2);
+        match previous {
+            TrackState::SafetyCar => transitions.push(FlagTransition::SafetyCarEnding),
+            TrackState::VirtualSafetyCar => {
+                transitions.push(FlagTransition::VirtualSafetyCarEnding)
+            }
+            _ => {}
+        }
+        transitions.push(match current {
+            TrackState::Green => FlagTransition::GreenFlag,
+            TrackState::Yellow => FlagTransition::YellowFlag,
+            TrackState::FormationLap => FlagTransition::FormationLap,
+            TrackState::VirtualSafetyCar => FlagTransition::VirtualSafetyCarDeployed,
+            TrackState::SafetyCar => FlagTransition::SafetyCarDeployed,
+            TrackState::Red => FlagTransition::RedFlag,
+        });
+
+        transitions
+            .into_iter()
+            .map(|transition| FlagEvent {
+                session_time: session.header.session_time,
+                frame_identifier: session.header.frame_identifier,
+                transition,
+            })
+            .collect()
+    }
+
+    /// state returns the current track state (None until the first Session packet)
+    pub fn state(&self) -> Option<TrackState> {
+        self.state
+    }
+
+    /// since returns the session time at which the current state started
+    pub fn since(&self) -> f32 {
+        self.since
+    }
+}
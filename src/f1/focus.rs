@@ -0,0 +1,47 @@
+use crate::f1::f1_2022::{Header, F1_2022};
+use crate::f1::util::CarIndex;
+
+/// FocusTracker follows the car on screen over "F1 22" packets: the player car, or when
+/// spectating (only told by Session packets) the spectated car, which replaces player_car_index.
+/// Pass spectated_car to the focused_car_data methods of the packets
+#[derive(Debug, Default, Clone)]
+pub struct FocusTracker {
+    session_uid: Option<u64>,
+    spectated_car: Option<CarIndex>,
+}
+
+impl FocusTracker {
+    pub fn new() -> FocusTracker {
+        FocusTracker::default()
+    }
+
+    /// observe feeds a packet into the tracker, a new session_uid resets it
+    pub fn observe(&mut self, event: &F1_2022) {
+        let session_uid = event.header().session_uid;
+        if self.session_uid != Some(session_uid) {
+            self.session_uid = Some(session_uid);
+            self.spectated_car = None;
+        }
+
+        if let F1_2022::Session(session) = event {
+            self.spectated_car = session.spectated_car();
+        }
+    }
+
+    /// spectated_car returns the index of the spectated car, None when not spectating
+    pub fn spectated_car(&self) -> Option<CarIndex> {
+        self.spectated_car
+    }
+
+    /// is_spectating returns true if the last Session packet was received while spectating
+    pub fn is_spectating(&self) -> bool {
+        self.spectated_car.is_some()
+    }
+
+    /// focused_car returns the index of the car on screen for a packet with the given header
+    /// None when there is no valid index (ie: player_car_index is 255)
+    pub fn focused_car(&self, header: &Header) -> Option<CarIndex> {
+        let index = self.spectated_car.unwrap_or(header.player_car_index);
+        Some(index).filter(|index| index.is_valid())
+    }
+}
@@ -0,0 +1,237 @@
+use alloc::{boxed::Box, format, vec::Vec};
+use core::error::Error;
+
+use binread::BinRead;
+
+use crate::f1::f1_2022::{
+    BrakingAssist, ForecastAccuracy, Formula, GameMode, GearboxAssist, Header, LapHistoryData,
+    MarshalZone, RacingLine, RacingLineType, RuleSet, SafetyCarStatus, Session, SessionHistory,
+    SessionLength, SessionType, Track, TyreStintHistoryData, Weather, WeatherForecastSample,
+};
+use crate::f1::util::{read_packet, CarIndex};
+use crate::{Endianness, TelemetryPacket};
+
+// offsets (and entry sizes) of the variable sections of the "F1 22" Session packet
+const SESSION_SIZE: usize = 632;
+const MARSHAL_ZONES: (usize, usize, usize) = (43, 5, 21); // offset, entry size, capacity
+const WEATHER_FORECAST_SAMPLES: (usize, usize, usize) = (151, 8, 56);
+
+// offsets (and entry sizes) of the sections of the "F1 22" SessionHistory packet
+const SESSION_HISTORY_SIZE: usize = 1155;
+const LAP_HISTORY: (usize, usize, usize) = (31, 11, 100);
+const TYRE_STINTS_HISTORY: (usize, usize, usize) = (1131, 3, 8);
+
+/// lazy_field implements a function named fn_name decoding a field of the given type at offset,
+/// the field is decoded every time the function is called
+macro_rules! lazy_field {
+    ($fn_name:ident, bool, $offset:expr) => {
+        pub fn $fn_name(&self) -> Result<bool, Box<dyn Error>> {
+            self.sections.read_at::<u8>($offset).map(|x| x > 0)
+        }
+    };
+    ($fn_name:ident, $type:ty, $offset:expr) => {
+        pub fn $fn_name(&self) -> Result<$type, Box<dyn Error>> {
+            self.sections.read_at($offset)
+        }
+    };
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sections<'a> {
+    packet: &'a [u8],
+    endian: binread::Endian,
+}
+
+impl<'a> Sections<'a> {
+    fn new(
+        packet: &'a TelemetryPacket,
+        endianness: Endianness,
+        packet_id: u8,
+        size: usize,
+    ) -> Result<(Header, Sections<'a>), Box<dyn Error>> {
+        if packet.len() < size {
+            return Err(Box::from(format!(
+                "Packet is too small: {} bytes, expected {}",
+                packet.len(),
+                size
+            )));
+        }
+        if packet[5] != packet_id {
+            return Err(Box::from(format!(
+                "Unexpected packet type: {}, expected {}",
+                packet[5], packet_id
+            )));
+        }
+
+        let endian = binread::Endian::from(endianness);
+        let header = read_packet(packet, endian)?;
+        Ok((header, Sections { packet, endian }))
+    }
+
+    fn read_at<T: BinRead<Args = ()>>(&self, offset: usize) -> Result<T, Box<dyn Error>> {
+        read_packet(&self.packet[offset..], self.endian)
+    }
+
+    // reads the first len entries of a fixed size array, see util::TruncatedArray
+    fn read_entries<T: BinRead<Args = ()>>(
+        &self,
+        len: u8,
+        (offset, size, capacity): (usize, usize, usize),
+    ) -> Result<Vec<T>, Box<dyn Error>> {
+        (0..(len as usize).min(capacity))
+            .map(|index| self.read_at(offset + index * size))
+            .collect()
+    }
+}
+
+/// LazySession is a view over a "F1 22" Session packet that decodes the header right away and
+/// every other field (or section) when asked, for consumers that only need a few fields
+/// (ie: safety_car_status) and do not want to decode the 21 marshal zones and 56 weather
+/// forecast samples of every packet. Use decode to get the whole Session
+#[derive(Debug, Clone)]
+pub struct LazySession<'a> {
+    pub header: Header,
+    sections: Sections<'a>,
+}
+
+impl<'a> LazySession<'a> {
+    /// new reads the header of a little-endian Session packet
+    pub fn new(packet: &'a TelemetryPacket) -> Result<LazySession<'a>, Box<dyn Error>> {
+        Self::with_endianness(packet, Endianness::Little)
+    }
+
+    /// with_endianness reads the header of a Session packet using the given byte order
+    pub fn with_endianness(
+        packet: &'a TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<LazySession<'a>, Box<dyn Error>> {
+        let (header, sections) = Sections::new(packet, endianness, 1, SESSION_SIZE)?;
+        Ok(LazySession { header, sections })
+    }
+
+    lazy_field!(weather, Weather, 24);
+    lazy_field!(track_temperature, i8, 25);
+    lazy_field!(air_temperature, i8, 26);
+    lazy_field!(total_laps, u8, 27);
+    lazy_field!(track_length, u16, 28);
+    lazy_field!(session_type, SessionType, 30);
+    lazy_field!(track, Track, 31);
+    lazy_field!(formula, Formula, 32);
+    lazy_field!(session_time_left, u16, 33);
+    lazy_field!(session_duration, u16, 35);
+    lazy_field!(pit_speed_limit, u8, 37);
+    lazy_field!(game_paused, u8, 38);
+    lazy_field!(is_spectating, u8, 39);
+    lazy_field!(spectator_car_index, CarIndex, 40);
+    lazy_field!(number_of_marshal_zones, u8, 42);
+    lazy_field!(safety_car_status, SafetyCarStatus, 148);
+    lazy_field!(network_game, bool, 149);
+    lazy_field!(number_of_weather_forecast_samples, u8, 150);
+    lazy_field!(forecast_accuracy, ForecastAccuracy, 599);
+    lazy_field!(ai_difficulty, u8, 600);
+    lazy_field!(season_link_identifier, u32, 601);
+    lazy_field!(weekend_link_identifier, u32, 605);
+    lazy_field!(session_link_identifier, u32, 609);
+    lazy_field!(pit_stop_window_ideal_lap, u8, 613);
+    lazy_field!(pit_stop_window_latest_lap, u8, 614);
+    lazy_field!(pit_stop_rejoin_position, u8, 615);
+    lazy_field!(steering_assist, bool, 616);
+    lazy_field!(braking_assist, BrakingAssist, 617);
+    lazy_field!(gearbox_assist, GearboxAssist, 618);
+    lazy_field!(pit_assist, bool, 619);
+    lazy_field!(pit_release_assist, bool, 620);
+    lazy_field!(ers_assist, bool, 621);
+    lazy_field!(drs_assist, bool, 622);
+    lazy_field!(dynamic_racing_line, RacingLine, 623);
+    lazy_field!(dynamic_racing_line_type, RacingLineType, 624);
+    lazy_field!(game_mode, GameMode, 625);
+    lazy_field!(rule_set, RuleSet, 626);
+    lazy_field!(time_of_day, u32, 627);
+    lazy_field!(session_length, SessionLength, 631);
+
+    /// marshal_zones decodes the marshal zones in use (number_of_marshal_zones)
+    pub fn marshal_zones(&self) -> Result<Vec<MarshalZone>, Box<dyn Error>> {
+        self.sections
+            .read_entries(self.number_of_marshal_zones()?, MARSHAL_ZONES)
+    }
+
+    /// weather_forecast_samples decodes the forecast samples sent
+    /// (number_of_weather_forecast_samples)
+    pub fn weather_forecast_samples(&self) -> Result<Vec<WeatherForecastSample>, Box<dyn Error>> {
+        self.sections.read_entries(
+            self.number_of_weather_forecast_samples()?,
+            WEATHER_FORECAST_SAMPLES,
+        )
+    }
+
+    /// decode decodes the whole packet
+    pub fn decode(&self) -> Result<Session, Box<dyn Error>> {
+        self.sections.read_at(0)
+    }
+}
+
+/// LazySessionHistory is a view over a "F1 22" SessionHistory packet that decodes the header
+/// right away and the laps and tyre stints when asked, a single lap can be decoded without the
+/// other 99 entries. Use decode to get the whole SessionHistory
+#[derive(Debug, Clone)]
+pub struct LazySessionHistory<'a> {
+    pub header: Header,
+    sections: Sections<'a>,
+}
+
+impl<'a> LazySessionHistory<'a> {
+    /// new reads the header of a little-endian SessionHistory packet
+    pub fn new(packet: &'a TelemetryPacket) -> Result<LazySessionHistory<'a>, Box<dyn Error>> {
+        Self::with_endianness(packet, Endianness::Little)
+    }
+
+    /// with_endianness reads the header of a SessionHistory packet using the given byte order
+    pub fn with_endianness(
+        packet: &'a TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<LazySessionHistory<'a>, Box<dyn Error>> {
+        let (header, sections) = Sections::new(packet, endianness, 11, SESSION_HISTORY_SIZE)?;
+        Ok(LazySessionHistory { header, sections })
+    }
+
+    lazy_field!(car_index, CarIndex, 24);
+    lazy_field!(num_laps, u8, 25);
+    lazy_field!(num_tyre_stints, u8, 26);
+    lazy_field!(best_lap_time_lap_num, u8, 27);
+    lazy_field!(best_sector1_lap_num, u8, 28);
+    lazy_field!(best_sector2_lap_num, u8, 29);
+    lazy_field!(best_sector3_lap_num, u8, 30);
+
+    /// lap decodes the history of the given lap number (starting at 1), None past num_laps
+    pub fn lap(&self, lap_number: u8) -> Result<Option<LapHistoryData>, Box<dyn Error>> {
+        let (offset, size, _) = LAP_HISTORY;
+        match lap_number.checked_sub(1) {
+            Some(index) if index < self.num_laps()? && (index as usize) < LAP_HISTORY.2 => self
+                .sections
+                .read_at(offset + index as usize * size)
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// best_lap decodes the history of the lap the best lap time was achieved on
+    pub fn best_lap(&self) -> Result<Option<LapHistoryData>, Box<dyn Error>> {
+        self.lap(self.best_lap_time_lap_num()?)
+    }
+
+    /// lap_history_data decodes the history of every lap (num_laps)
+    pub fn lap_history_data(&self) -> Result<Vec<LapHistoryData>, Box<dyn Error>> {
+        self.sections.read_entries(self.num_laps()?, LAP_HISTORY)
+    }
+
+    /// tyre_stints_history_data decodes the tyre stints (num_tyre_stints)
+    pub fn tyre_stints_history_data(&self) -> Result<Vec<TyreStintHistoryData>, Box<dyn Error>> {
+        self.sections
+            .read_entries(self.num_tyre_stints()?, TYRE_STINTS_HISTORY)
+    }
+
+    /// decode decodes the whole packet
+    pub fn decode(&self) -> Result<SessionHistory, Box<dyn Error>> {
+        self.sections.read_at(0)
+    }
+}
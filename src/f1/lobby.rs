@@ -0,0 +1,112 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::f1::f1_2022::{LobbyInfo, LobbyStatus, Team};
+
+/// LobbyPlayer is a player of the lobby, as last seen in a LobbyInfo packet
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LobbyPlayer {
+    pub name: String,
+    pub team: Team,
+    pub car_number: u8,
+    pub ai_controlled: bool,
+    pub status: LobbyStatus,
+}
+
+/// LobbyEvent is a change of the lobby between two LobbyInfo packets
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum LobbyEvent {
+    Joined {
+        name: String,
+        status: LobbyStatus,
+    },
+    Left {
+        name: String,
+    },
+    StatusChanged {
+        name: String,
+        from: LobbyStatus,
+        to: LobbyStatus,
+    },
+}
+
+/// LobbyTracker follows the players of an "F1 22" lobby over LobbyInfo packets
+/// and emits who joined, left or changed their ready state; players are matched by name
+/// as their position in lobby_players moves when someone leaves
+#[derive(Debug, Default, Clone)]
+pub struct LobbyTracker {
+    players: Vec<LobbyPlayer>,
+}
+
+impl LobbyTracker {
+    pub fn new() -> LobbyTracker {
+        LobbyTracker::default()
+    }
+
+    /// update feeds a LobbyInfo packet and returns the changes it caused,
+    /// every player of the first packet is reported as joined
+    pub fn update(&mut self, lobby: &LobbyInfo) -> Vec<LobbyEvent> {
+        let players: Vec<LobbyPlayer> = lobby
+            .active_players()
+            .iter()
+            .map(|player| LobbyPlayer {
+                name: player.name.clone(),
+                team: player.team,
+                car_number: player.car_number,
+                ai_controlled: player.ai_controlled,
+                status: player.status,
+            })
+            .collect();
+
+        let mut events = Vec::new();
+        for previous in &self.players {
+            if !players.iter().any(|player| player.name == previous.name) {
+                events.push(LobbyEvent::Left {
+                    name: previous.name.clone(),
+                });
+            }
+        }
+
+        for player in &players {
+            match self.player(&player.name) {
+                None => events.push(LobbyEvent::Joined {
+                    name: player.name.clone(),
+                    status: player.status,
+                }),
+                Some(previous) if previous.status != player.status => {
+                    events.push(LobbyEvent::StatusChanged {
+                        name: player.name.clone(),
+                        from: previous.status,
+                        to: player.status,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        self.players = players;
+        events
+    }
+
+    /// players returns the players of the last LobbyInfo packet
+    pub fn players(&self) -> &[LobbyPlayer] {
+        &self.players
+    }
+
+    /// player returns the player with the given name
+    pub fn player(&self, name: &str) -> Option<&LobbyPlayer> {
+        self.players.iter().find(|player| player.name == name)
+    }
+
+    /// all_ready returns true if every human player that is not spectating is ready
+    /// (false for an empty lobby)
+    pub fn all_ready(&self) -> bool {
+        let mut racers = self
+            .players
+            .iter()
+            .filter(|player| !player.ai_controlled && player.status != LobbyStatus::Spectating)
+            .peekable();
+        racers.peek().is_some() && racers.all(|player| player.status == LobbyStatus::Ready)
+    }
+}
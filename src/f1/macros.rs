@@ -1,11 +1,27 @@
-/// player_data implements the "player_data()" function
+/// player_data implements the "player_data()" and "focused_car_data()" functions
 /// for the given impl_type, return_type and data_field
 macro_rules! player_data {
     ($impl_type:ident, $return_type:ident, $data_field:ident) => {
         impl $impl_type {
-            pub fn player_data(&self) -> &$return_type {
-                let player_index = self.header.player_car_index as usize;
-                &self.$data_field[player_index]
+            /// player_data returns the data of the player car, None while spectating
+            /// (player_car_index is 255)
+            pub fn player_data(&self) -> Option<&$return_type> {
+                self.$data_field.get(self.header.player_car_index.index()?)
+            }
+
+            /// focused_car_data returns the data of the car on screen, the spectated car
+            /// (see Session::spectated_car) when spectating and the player car otherwise
+            pub fn focused_car_data(
+                &self,
+                spectated_car: Option<CarIndex>,
+            ) -> Option<&$return_type> {
+                let index = spectated_car.unwrap_or(self.header.player_car_index);
+                self.$data_field.get(index.index()?)
+            }
+
+            /// car_data returns the data of the car at the given index
+            pub fn car_data(&self, car_index: CarIndex) -> Option<&$return_type> {
+                self.$data_field.get(car_index.index()?)
             }
         }
     };
@@ -13,9 +29,27 @@ macro_rules! player_data {
 
 pub(crate) use player_data;
 
+/// active_data implements a function named fn_name returning the entries of data_field
+/// of the cars on track, num_active_cars comes from the Participants packet
+macro_rules! active_data {
+    ($impl_type:ident, $return_type:ident, $data_field:ident, $fn_name:ident) => {
+        impl $impl_type {
+            /// returns the first num_active_cars entries (see Participants::num_active_cars),
+            /// the entries after them are placeholders for empty slots
+            pub fn $fn_name(&self, num_active_cars: u8) -> &[$return_type] {
+                let count = (num_active_cars as usize).min(self.$data_field.len());
+                &self.$data_field[..count]
+            }
+        }
+    };
+}
+
+pub(crate) use active_data;
+
 /// binread_enum implements a default BinRead trait for enums
 /// arguments are the enum to implement and the size of it
 /// note: enum has to implement "Default" and "TryFromPrimitive" traits.
+/// unknown values decode as the default variant and are reported, see f1::unknown
 macro_rules! binread_enum {
     ($type:ident, $repr:ident) => {
         impl binread::BinRead for $type {
@@ -26,10 +60,13 @@ macro_rules! binread_enum {
                 args: Self::Args,
             ) -> binread::BinResult<Self> {
                 let byte = $repr::read_options(reader, options, args)?;
-                Ok($type::try_from(byte).unwrap_or($type::default()))
+                Ok($type::try_from(byte).unwrap_or_else(|_| {
+                    $crate::f1::unknown::report(stringify!($type), byte as i64);
+                    $type::default()
+                }))
             }
         }
     };
 }
 
-pub(crate) use binread_enum;
\ No newline at end of file
+pub(crate) use binread_enum;
@@ -1,5 +1,22 @@
+pub mod anonymize;
+pub mod car_stream;
+pub mod flags;
+pub mod focus;
+pub mod lazy;
+pub mod lobby;
 pub mod macros;
+pub mod motion;
+pub mod names;
+pub mod player;
+pub mod race_control;
+pub mod roster;
+pub mod setup;
+pub mod spec;
+pub mod teams;
+pub mod tracks;
+mod units; // unit conversion accessors of the packets
+pub mod unknown;
 pub mod util;
 
 pub mod f1_2020;
-pub mod f1_2022;
\ No newline at end of file
+pub mod f1_2022;
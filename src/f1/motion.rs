@@ -0,0 +1,62 @@
+use crate::f1::util::{Coordinates, WheelValue};
+use crate::f1::{f1_2020, f1_2022};
+
+/// PlayerMotion is the extended motion data of the player car, sent at the end of the Motion
+/// packet up to "F1 22" (and in its own MotionEx packet since "F1 23"), motion rigs written
+/// against it work with every supported year
+pub trait PlayerMotion {
+    fn suspension_position(&self) -> &WheelValue<f32>;
+    fn suspension_velocity(&self) -> &WheelValue<f32>;
+    fn suspension_acceleration(&self) -> &WheelValue<f32>;
+    fn wheel_speed(&self) -> &WheelValue<f32>;
+    fn wheel_slip(&self) -> &WheelValue<f32>;
+    fn local_velocity(&self) -> &Coordinates<f32>;
+    fn angular_velocity(&self) -> &Coordinates<f32>;
+    fn angular_acceleration(&self) -> &Coordinates<f32>;
+    fn front_wheel_angle(&self) -> f32; // radians
+}
+
+macro_rules! player_motion {
+    ($type:ty) => {
+        impl PlayerMotion for $type {
+            fn suspension_position(&self) -> &WheelValue<f32> {
+                &self.suspension_position
+            }
+
+            fn suspension_velocity(&self) -> &WheelValue<f32> {
+                &self.suspension_velocity
+            }
+
+            fn suspension_acceleration(&self) -> &WheelValue<f32> {
+                &self.suspension_acceleration
+            }
+
+            fn wheel_speed(&self) -> &WheelValue<f32> {
+                &self.wheel_speed
+            }
+
+            fn wheel_slip(&self) -> &WheelValue<f32> {
+                &self.wheel_slip
+            }
+
+            fn local_velocity(&self) -> &Coordinates<f32> {
+                &self.local_velocity
+            }
+
+            fn angular_velocity(&self) -> &Coordinates<f32> {
+                &self.angular_velocity
+            }
+
+            fn angular_acceleration(&self) -> &Coordinates<f32> {
+                &self.angular_acceleration
+            }
+
+            fn front_wheel_angle(&self) -> f32 {
+                self.front_wheel_angle
+            }
+        }
+    };
+}
+
+player_motion!(f1_2020::Motion);
+player_motion!(f1_2022::Motion);
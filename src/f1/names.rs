@@ -0,0 +1,39 @@
+use alloc::{collections::BTreeSet, sync::Arc};
+
+/// NameInterner keeps a single shared copy of every participant name, trackers that hold names
+/// across packets (ie: roster::Roster) intern them so the Participants packets of a long session
+/// do not leave thousands of copies of the same 22 names behind
+#[derive(Debug, Default, Clone)]
+pub struct NameInterner {
+    names: BTreeSet<Arc<str>>,
+}
+
+impl NameInterner {
+    pub fn new() -> NameInterner {
+        NameInterner::default()
+    }
+
+    /// intern returns the shared copy of name, only allocated the first time it is seen
+    pub fn intern(&mut self, name: &str) -> Arc<str> {
+        if let Some(interned) = self.names.get(name) {
+            return Arc::clone(interned);
+        }
+        let interned: Arc<str> = Arc::from(name);
+        self.names.insert(Arc::clone(&interned));
+        interned
+    }
+
+    /// len returns the number of different names seen
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// shrink forgets the names not held anywhere else, ie: between sessions
+    pub fn shrink(&mut self) {
+        self.names.retain(|name| Arc::strong_count(name) > 1);
+    }
+}
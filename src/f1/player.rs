@@ -0,0 +1,214 @@
+use alloc::{boxed::Box, format};
+use core::convert::TryFrom;
+use core::error::Error;
+
+use binread::{io::SeekFrom, BinRead};
+
+use crate::f1::f1_2022::{
+    CarDamageData, CarMotionData, CarSetupData, CarStatusData, CarTelemetryData, Event,
+    FinalClassificationData, Gear, Header, Lap, LobbyInfo, MFDPanel, ParticipantsData, Session,
+    SessionHistory,
+};
+use crate::f1::spec::packet_spec;
+use crate::f1::util::*;
+use crate::{Endianness, TelemetryEvent, TelemetryFrame, TelemetryPacket};
+
+// size of the header, the per car arrays start right after it (or after a count byte)
+const HEADER_SIZE: u64 = 24;
+// number of entries of the per car arrays
+const CARS: u64 = CarIndex::MAX_CARS as u64;
+
+/// F1_2022Player decodes "F1 22" packets keeping only the entries of the player car
+/// (header.player_car_index): the other 21 entries are skipped instead of decoded, for single
+/// driver dashboards. Packets without per car arrays are decoded whole, SessionHistory is sent
+/// for every car, check its car_index
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[allow(clippy::large_enum_variant)] // decoded without heap allocations, see util::TruncatedArray
+pub enum F1_2022Player {
+    Motion(PlayerMotion),
+    Session(Session),
+    LapData(PlayerLapData),
+    Event(Event),
+    Participants(PlayerParticipants),
+    CarSetup(PlayerCarSetup),
+    CarTelemetry(PlayerCarTelemetry),
+    CarStatus(PlayerCarStatus),
+    FinalClassification(PlayerFinalClassification),
+    LobbyInfo(LobbyInfo),
+    CarDamage(PlayerCarDamage),
+    SessionHistory(SessionHistory),
+}
+
+/// player_entry returns where the entry of the player car is, in an array of the given entry size
+/// starting at offset
+fn player_entry(header: &Header, offset: u64, size: u64) -> SeekFrom {
+    SeekFrom::Start(offset + header.player_car_index.0 as u64 * size)
+}
+
+fn suggested_gear(x: i8) -> Gear {
+    if x == 0 {
+        Gear::Unknown
+    } else {
+        Gear::try_from(x).unwrap_or_default()
+    }
+}
+
+// same layouts as the packets of f1_2022, with a single entry instead of the per car arrays
+packet_spec! {
+    PlayerMotion {
+        header: Header,
+        #[br(seek_before = player_entry(&header, HEADER_SIZE, 60))]
+        car_motion_data: CarMotionData,
+        #[br(seek_before = SeekFrom::Start(HEADER_SIZE + CARS * 60))]
+        suspension_position: WheelValue<f32>,
+        suspension_velocity: WheelValue<f32>,
+        suspension_acceleration: WheelValue<f32>,
+        wheel_speed: WheelValue<f32>,
+        wheel_slip: WheelValue<f32>,
+        local_velocity: Coordinates<f32>,
+        angular_velocity: Coordinates<f32>,
+        angular_acceleration: Coordinates<f32>,
+        front_wheel_angle: f32,
+    }
+
+    PlayerLapData {
+        header: Header,
+        #[br(seek_before = player_entry(&header, HEADER_SIZE, 43))]
+        lap: Lap,
+        #[br(seek_before = SeekFrom::Start(HEADER_SIZE + CARS * 43))]
+        time_trial_pb_car_idx: CarIndex,
+        time_trial_rival_car_idx: CarIndex,
+    }
+
+    PlayerParticipants {
+        header: Header,
+        num_active_cars: u8,
+        #[br(seek_before = player_entry(&header, HEADER_SIZE + 1, 56))]
+        participants_data: ParticipantsData,
+    }
+
+    PlayerCarSetup {
+        header: Header,
+        #[br(seek_before = player_entry(&header, HEADER_SIZE, 49))]
+        car_setup_data: CarSetupData,
+    }
+
+    PlayerCarTelemetry {
+        header: Header,
+        #[br(seek_before = player_entry(&header, HEADER_SIZE, 60))]
+        car_telemetry_data: CarTelemetryData,
+        #[br(seek_before = SeekFrom::Start(HEADER_SIZE + CARS * 60))]
+        mfd_panel: MFDPanel,
+        mfd_panel_secondary_player: MFDPanel,
+        #[br(map = suggested_gear)]
+        suggested_gear: Gear,
+    }
+
+    PlayerCarStatus {
+        header: Header,
+        #[br(seek_before = player_entry(&header, HEADER_SIZE, 47))]
+        car_status_data: CarStatusData,
+    }
+
+    PlayerFinalClassification {
+        header: Header,
+        number_of_cars: u8,
+        #[br(seek_before = player_entry(&header, HEADER_SIZE + 1, 45))]
+        final_classification_data: FinalClassificationData,
+    }
+
+    PlayerCarDamage {
+        header: Header,
+        #[br(seek_before = player_entry(&header, HEADER_SIZE, 42))]
+        car_damage_data: CarDamageData,
+    }
+}
+
+impl F1_2022Player {
+    /// header returns the packet header shared by every packet type
+    pub fn header(&self) -> &Header {
+        match self {
+            F1_2022Player::Motion(data) => &data.header,
+            F1_2022Player::Session(data) => &data.header,
+            F1_2022Player::LapData(data) => &data.header,
+            F1_2022Player::Event(data) => &data.header,
+            F1_2022Player::Participants(data) => &data.header,
+            F1_2022Player::CarSetup(data) => &data.header,
+            F1_2022Player::CarTelemetry(data) => &data.header,
+            F1_2022Player::CarStatus(data) => &data.header,
+            F1_2022Player::FinalClassification(data) => &data.header,
+            F1_2022Player::LobbyInfo(data) => &data.header,
+            F1_2022Player::CarDamage(data) => &data.header,
+            F1_2022Player::SessionHistory(data) => &data.header,
+        }
+    }
+}
+
+// packets read through player_entry, the player car index has to be valid
+fn has_player_entry(packet_id: u8) -> bool {
+    matches!(packet_id, 0 | 2 | 4 | 5 | 6 | 7 | 8 | 10)
+}
+
+impl TelemetryEvent for F1_2022Player {
+    fn from_packet(packet: &TelemetryPacket) -> Result<F1_2022Player, Box<dyn Error>> {
+        Self::from_packet_with_endianness(packet, Endianness::Little)
+    }
+
+    fn from_packet_with_endianness(
+        packet: &TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<F1_2022Player, Box<dyn Error>> {
+        if packet.len() < HEADER_SIZE as usize {
+            return Err(Box::from("Packet is too small to contain a header"));
+        }
+
+        let packet_id = packet[5]; // packet_id
+        let endian = binread::Endian::from(endianness);
+        if has_player_entry(packet_id) {
+            let header: Header = read_packet(packet, endian)?;
+            if !header.player_car_index.is_valid() {
+                return Err(Box::from(format!(
+                    "Invalid player car index: {}",
+                    header.player_car_index
+                )));
+            }
+        }
+
+        match packet_id {
+            0 => Ok(F1_2022Player::Motion(read_packet(packet, endian)?)),
+            1 => Ok(F1_2022Player::Session(read_packet(packet, endian)?)),
+            2 => Ok(F1_2022Player::LapData(read_packet(packet, endian)?)),
+            3 => Ok(F1_2022Player::Event(read_packet(packet, endian)?)),
+            4 => Ok(F1_2022Player::Participants(read_packet(packet, endian)?)),
+            5 => Ok(F1_2022Player::CarSetup(read_packet(packet, endian)?)),
+            6 => Ok(F1_2022Player::CarTelemetry(read_packet(packet, endian)?)),
+            7 => Ok(F1_2022Player::CarStatus(read_packet(packet, endian)?)),
+            8 => Ok(F1_2022Player::FinalClassification(read_packet(
+                packet, endian,
+            )?)),
+            9 => Ok(F1_2022Player::LobbyInfo(read_packet(packet, endian)?)),
+            10 => Ok(F1_2022Player::CarDamage(read_packet(packet, endian)?)),
+            11 => Ok(F1_2022Player::SessionHistory(read_packet(packet, endian)?)),
+            id => Err(Box::from(format!("Unknown packet type: {}", id))),
+        }
+    }
+}
+
+impl TelemetryFrame for F1_2022Player {
+    fn session_uid(&self) -> u64 {
+        self.header().session_uid
+    }
+
+    fn frame_identifier(&self) -> u32 {
+        self.header().frame_identifier
+    }
+
+    fn packet_id(&self) -> u8 {
+        self.header().packet_id
+    }
+
+    fn session_time(&self) -> f32 {
+        self.header().session_time
+    }
+}
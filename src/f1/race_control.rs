@@ -0,0 +1,270 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::f1::f1_2022::{
+    EventDataDetail, Header, PenaltyEventDetail, PenaltyType, SafetyCarStatus, F1_2022,
+};
+use crate::f1::util::CarIndex;
+
+/// Severity ranks race control messages, overlays usually hide Info and highlight Critical
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// MessageKind is what triggered a race control message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum MessageKind {
+    SessionStarted,
+    SessionEnded,
+    LightsOut,
+    ChequeredFlag,
+    RaceWinner,
+    FastestLap,
+    Retirement,
+    DRSEnabled,
+    DRSDisabled,
+    Penalty,
+    PenaltyServed,
+    SafetyCar(SafetyCarStatus),
+}
+
+/// RaceControlMessage is a single entry of the race control feed
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RaceControlMessage {
+    pub session_time: f32,
+    pub frame_identifier: u32,
+    pub severity: Severity,
+    pub kind: MessageKind,
+    pub vehicle_index: Option<CarIndex>,
+    pub text: String,
+}
+
+/// RaceControl turns "F1 22" packets into an ordered feed of race control messages
+/// events are taken from Event packets, safety car changes from Session packets
+/// and driver labels from the latest Participants packet
+#[derive(Debug, Default, Clone)]
+pub struct RaceControl {
+    messages: Vec<RaceControlMessage>,
+    labels: Vec<String>,
+    safety_car_status: Option<SafetyCarStatus>,
+}
+
+impl RaceControl {
+    pub fn new() -> RaceControl {
+        RaceControl::default()
+    }
+
+    /// observe feeds a packet into the race control and returns the messages it produced
+    /// a flashback drops the messages after the session time it rewinds to
+    pub fn observe(&mut self, event: &F1_2022) -> &[RaceControlMessage] {
+        let start = self.messages.len();
+
+        match event {
+            F1_2022::Participants(data) => {
+                self.labels = data.participants_data.iter().map(|p| p.label()).collect();
+            }
+            F1_2022::Session(data) => self.observe_safety_car(&data.header, data.safety_car_status),
+            F1_2022::Event(data) => {
+                if let EventDataDetail::Flashback(_, session_time) = data.event_data_details {
+                    self.messages.retain(|m| m.session_time <= session_time);
+                    return &[];
+                }
+                self.observe_event(&data.header, &data.event_data_details);
+            }
+            _ => {}
+        }
+
+        &self.messages[start..]
+    }
+
+    /// feed returns every message since the start of the session, oldest first
+    pub fn feed(&self) -> &[RaceControlMessage] {
+        &self.messages
+    }
+
+    /// at_least returns the messages with the given severity or above, oldest first
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &RaceControlMessage> {
+        self.messages.iter().filter(move |m| m.severity >= severity)
+    }
+
+    /// clear forgets every message and label (ie: when a new session starts)
+    pub fn clear(&mut self) {
+        *self = RaceControl::default();
+    }
+
+    fn observe_safety_car(&mut self, header: &Header, status: SafetyCarStatus) {
+        if status == SafetyCarStatus::Unknown {
+            return;
+        }
+
+        let previous = self.safety_car_status.replace(status);
+        let (severity, text) = match (previous, status) {
+            (None, _) => return,
+            (Some(previous), current) if previous == current => return,
+            (_, SafetyCarStatus::FullSafetyCar) => (Severity::Critical, "Safety car deployed"),
+            (_, SafetyCarStatus::VirtualSafetyCar) => {
+                (Severity::Critical, "Virtual safety car deployed")
+            }
+            (_, SafetyCarStatus::FormationLap) => (Severity::Info, "Formation lap"),
+            (Some(SafetyCarStatus::FullSafetyCar), _) => (Severity::Warning, "Safety car ending"),
+            (Some(SafetyCarStatus::VirtualSafetyCar), _) => {
+                (Severity::Warning, "Virtual safety car ending")
+            }
+            _ => return,
+        };
+
+        self.push(
+            header,
+            severity,
+            MessageKind::SafetyCar(status),
+            None,
+            String::from(text),
+        );
+    }
+
+    fn observe_event(&mut self, header: &Header, detail: &EventDataDetail) {
+        let (severity, kind, vehicle_index, text) = match detail {
+            EventDataDetail::SessionStarted => (
+                Severity::Info,
+                MessageKind::SessionStarted,
+                None,
+                String::from("Session started"),
+            ),
+            EventDataDetail::SessionEnded => (
+                Severity::Info,
+                MessageKind::SessionEnded,
+                None,
+                String::from("Session ended"),
+            ),
+            EventDataDetail::LightsOut => (
+                Severity::Info,
+                MessageKind::LightsOut,
+                None,
+                String::from("Lights out"),
+            ),
+            EventDataDetail::ChequeredFlag => (
+                Severity::Info,
+                MessageKind::ChequeredFlag,
+                None,
+                String::from("Chequered flag"),
+            ),
+            EventDataDetail::RaceWinner(idx) => (
+                Severity::Info,
+                MessageKind::RaceWinner,
+                Some(*idx),
+                format!("{} wins the race", self.label(*idx)),
+            ),
+            EventDataDetail::FastestLap(idx, lap_time) => (
+                Severity::Info,
+                MessageKind::FastestLap,
+                Some(*idx),
+                format!(
+                    "Fastest lap: {} {}",
+                    self.label(*idx),
+                    format_lap_time(*lap_time)
+                ),
+            ),
+            EventDataDetail::Retirement(idx) => (
+                Severity::Warning,
+                MessageKind::Retirement,
+                Some(*idx),
+                format!("{} retired", self.label(*idx)),
+            ),
+            EventDataDetail::DRSEnabled => (
+                Severity::Info,
+                MessageKind::DRSEnabled,
+                None,
+                String::from("DRS enabled"),
+            ),
+            EventDataDetail::DRSDisabled => (
+                Severity::Warning,
+                MessageKind::DRSDisabled,
+                None,
+                String::from("DRS disabled"),
+            ),
+            EventDataDetail::Penalty(penalty) => (
+                penalty_severity(penalty),
+                MessageKind::Penalty,
+                Some(penalty.vehicle_index),
+                format!(
+                    "{}: {}",
+                    self.label(penalty.vehicle_index),
+                    penalty.description()
+                ),
+            ),
+            EventDataDetail::DriveThroughServed(idx) => (
+                Severity::Info,
+                MessageKind::PenaltyServed,
+                Some(*idx),
+                format!("{} served a drive-through penalty", self.label(*idx)),
+            ),
+            EventDataDetail::StopGoServed(idx) => (
+                Severity::Info,
+                MessageKind::PenaltyServed,
+                Some(*idx),
+                format!("{} served a stop-go penalty", self.label(*idx)),
+            ),
+            _ => return,
+        };
+
+        self.push(header, severity, kind, vehicle_index, text);
+    }
+
+    fn push(
+        &mut self,
+        header: &Header,
+        severity: Severity,
+        kind: MessageKind,
+        vehicle_index: Option<CarIndex>,
+        text: String,
+    ) {
+        self.messages.push(RaceControlMessage {
+            session_time: header.session_time,
+            frame_identifier: header.frame_identifier,
+            severity,
+            kind,
+            vehicle_index,
+            text,
+        });
+    }
+
+    fn label(&self, vehicle_index: CarIndex) -> String {
+        match vehicle_index
+            .index()
+            .and_then(|index| self.labels.get(index))
+        {
+            Some(label) if !label.is_empty() => label.clone(),
+            _ => format!("Car {}", vehicle_index),
+        }
+    }
+}
+
+fn penalty_severity(penalty: &PenaltyEventDetail) -> Severity {
+    match penalty.penalty_type {
+        PenaltyType::DriveThrough
+        | PenaltyType::StopGo
+        | PenaltyType::Disqualified
+        | PenaltyType::BlackFlagTimer
+        | PenaltyType::Retired => Severity::Critical,
+        PenaltyType::TimePenalty | PenaltyType::GridPenalty | PenaltyType::Warning => {
+            Severity::Warning
+        }
+        _ => Severity::Info,
+    }
+}
+
+/// format_lap_time formats a lap time in seconds as "m:ss.mmm"
+fn format_lap_time(seconds: f32) -> String {
+    let millis = (seconds * 1000.0) as u32;
+    format!(
+        "{}:{:02}.{:03}",
+        millis / 60_000,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
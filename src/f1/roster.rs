@@ -0,0 +1,151 @@
+use alloc::{sync::Arc, vec, vec::Vec};
+
+use crate::f1::f1_2022::Participants;
+use crate::f1::names::NameInterner;
+use crate::f1::util::CarIndex;
+
+/// Participant is the stable identity of a car: humans are matched on network_id
+/// (so renames are detected) and AI drivers on their name
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Participant {
+    pub network_id: u8,
+    pub name: Arc<str>,
+    pub ai_controlled: bool,
+}
+
+impl Participant {
+    fn is(&self, other: &Participant) -> bool {
+        if self.ai_controlled || other.ai_controlled {
+            self.ai_controlled == other.ai_controlled && self.name == other.name
+        } else {
+            self.network_id == other.network_id
+        }
+    }
+}
+
+/// RosterEvent is a change of the roster between two Participants packets
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum RosterEvent {
+    Joined {
+        car_index: CarIndex,
+        name: Arc<str>,
+    },
+    Renamed {
+        car_index: CarIndex,
+        from: Arc<str>,
+        to: Arc<str>,
+    },
+    Moved {
+        name: Arc<str>,
+        from: CarIndex, // car index in the previous Participants packet
+        to: CarIndex,
+    },
+    Dropped {
+        car_index: CarIndex,
+        name: Arc<str>,
+    },
+}
+
+/// Roster maps the car indices of "F1 22" Participants packets to stable participant identities,
+/// car indices can change between sessions and when players drop, the roster is kept across
+/// sessions and reports who moved, was renamed, joined or dropped
+#[derive(Debug, Default, Clone)]
+pub struct Roster {
+    cars: Vec<Participant>, // by car index
+    names: NameInterner,
+}
+
+impl Roster {
+    pub fn new() -> Roster {
+        Roster::default()
+    }
+
+    /// update feeds a Participants packet and returns the changes it caused,
+    /// every participant of the first packet is reported as joined
+    pub fn update(&mut self, participants: &Participants) -> Vec<RosterEvent> {
+        let cars: Vec<Participant> = participants
+            .active_participants()
+            .iter()
+            .map(|data| Participant {
+                network_id: data.network_id,
+                name: self.names.intern(&data.name),
+                ai_controlled: data.ai_controlled,
+            })
+            .collect();
+
+        let mut events = Vec::new();
+        let mut matched = vec![false; self.cars.len()];
+        for (index, car) in cars.iter().enumerate() {
+            // same car index first, so two AI cars with the same name are not swapped
+            let unmatched = |i: usize| !matched[i] && self.cars[i].is(car);
+            let previous = if index < self.cars.len() && unmatched(index) {
+                Some(index)
+            } else {
+                (0..self.cars.len()).find(|&i| unmatched(i))
+            };
+
+            let previous = match previous {
+                Some(previous) => previous,
+                None => {
+                    events.push(RosterEvent::Joined {
+                        car_index: CarIndex(index as u8),
+                        name: car.name.clone(),
+                    });
+                    continue;
+                }
+            };
+
+            matched[previous] = true;
+            if previous != index {
+                events.push(RosterEvent::Moved {
+                    name: car.name.clone(),
+                    from: CarIndex(previous as u8),
+                    to: CarIndex(index as u8),
+                });
+            }
+            if self.cars[previous].name != car.name {
+                events.push(RosterEvent::Renamed {
+                    car_index: CarIndex(index as u8),
+                    from: self.cars[previous].name.clone(),
+                    to: car.name.clone(),
+                });
+            }
+        }
+
+        for (index, previous) in self.cars.iter().enumerate() {
+            if !matched[index] {
+                events.push(RosterEvent::Dropped {
+                    car_index: CarIndex(index as u8),
+                    name: previous.name.clone(),
+                });
+            }
+        }
+
+        self.cars = cars;
+        self.names.shrink();
+        events
+    }
+
+    /// participant returns the identity of the car at the given index
+    pub fn participant(&self, car_index: CarIndex) -> Option<&Participant> {
+        self.cars.get(car_index.index()?)
+    }
+
+    /// car_index returns the current index of the given participant
+    pub fn car_index(&self, participant: &Participant) -> Option<CarIndex> {
+        self.cars
+            .iter()
+            .position(|car| car.is(participant))
+            .map(|index| CarIndex(index as u8))
+    }
+
+    /// participants iterates over the car indices and identities of the last Participants packet
+    pub fn participants(&self) -> impl Iterator<Item = (CarIndex, &Participant)> {
+        self.cars
+            .iter()
+            .enumerate()
+            .map(|(index, car)| (CarIndex(index as u8), car))
+    }
+}
@@ -0,0 +1,235 @@
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
+use core::error::Error;
+use core::fmt::Write;
+
+use crate::f1::{f1_2020, f1_2022};
+
+/// SetupChange is a parameter that differs between two setups
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SetupChange {
+    pub parameter: &'static str, // ie: "front_wing", see CarSetupData::parameters
+    pub from: f32,
+    pub to: f32,
+}
+
+impl SetupChange {
+    /// delta returns how much the parameter changed (to - from)
+    pub fn delta(&self) -> f32 {
+        self.to - self.from
+    }
+}
+
+/// SetupDiff lists the parameters that changed between two setups, in the order of
+/// CarSetupData::parameters
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SetupDiff {
+    pub changes: Vec<SetupChange>,
+}
+
+impl SetupDiff {
+    /// is_empty returns true if both setups are the same
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// change returns the change of the given parameter, None if it did not change
+    pub fn change(&self, parameter: &str) -> Option<&SetupChange> {
+        self.changes
+            .iter()
+            .find(|change| change.parameter == parameter)
+    }
+}
+
+/// setup_parameters implements the parameters, diff, text and setup sheet functions of a
+/// CarSetupData from the list of its parameter names, setup sheet keys and the field (and type)
+/// each one is stored in
+macro_rules! setup_parameters {
+    ($type:ty, $($name:literal, $key:literal => $($field:ident).+ as $kind:ty),* $(,)?) => {
+        impl $type {
+            /// parameters returns the name and value of every parameter of the setup
+            pub fn parameters(&self) -> Vec<(&'static str, f32)> {
+                vec![$(($name, self.$($field).+ as f32)),*]
+            }
+
+            /// set_parameter changes the parameter with the given name
+            pub fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), Box<dyn Error>> {
+                match name {
+                    $($name => self.$($field).+ = value as $kind,)*
+                    _ => return Err(Box::from(format!("Unknown setup parameter: {}", name))),
+                }
+                Ok(())
+            }
+
+            /// diff returns the parameters that changed from this setup to the other one
+            pub fn diff(&self, other: &Self) -> SetupDiff {
+                let changes = self
+                    .parameters()
+                    .into_iter()
+                    .zip(other.parameters())
+                    .filter(|((_, from), (_, to))| from != to)
+                    .map(|((parameter, from), (_, to))| SetupChange {
+                        parameter,
+                        from,
+                        to,
+                    })
+                    .collect();
+                SetupDiff { changes }
+            }
+
+            /// to_text returns the setup as "parameter = value" lines, to be shared as text
+            pub fn to_text(&self) -> String {
+                let mut text = String::new();
+                for (name, value) in self.parameters() {
+                    let _ = writeln!(text, "{} = {}", name, value);
+                }
+                text
+            }
+
+            /// from_text reads a setup written by to_text, every parameter has to be present
+            /// (empty lines and lines starting with "#" are ignored)
+            pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+                let lines = text
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(|line| {
+                        let (name, value) = line
+                            .split_once('=')
+                            .ok_or_else(|| format!("Invalid setup line: {}", line))?;
+                        Ok((name.trim(), parse_value(name.trim(), value)?))
+                    });
+                Self::from_parameters(lines)
+            }
+
+            /// to_setup_sheet returns the setup as a setup sheet, the flat JSON object of setup
+            /// sharing sites keyed with the names of the UDP specification (ie: "frontWing")
+            pub fn to_setup_sheet(&self) -> String {
+                let keys = [$($key),*];
+                let mut json = String::from("{");
+                for (index, (key, (_, value))) in keys.iter().zip(self.parameters()).enumerate() {
+                    let separator = if index > 0 { "," } else { "" };
+                    let _ = write!(json, "{}\"{}\":{}", separator, key, value);
+                }
+                json.push('}');
+                json
+            }
+
+            /// from_setup_sheet reads a setup sheet, unknown keys (ie: "track" or "notes")
+            /// are ignored and every parameter has to be present
+            pub fn from_setup_sheet(json: &str) -> Result<Self, Box<dyn Error>> {
+                let parameters = json_object(json)?
+                    .into_iter()
+                    .filter_map(|(key, value)| {
+                        let name = match key {
+                            $($key => $name,)*
+                            _ => return None,
+                        };
+                        Some(parse_value(name, value).map(|value| (name, value)))
+                    });
+                Self::from_parameters(parameters)
+            }
+
+            fn from_parameters<'a>(
+                parameters: impl Iterator<Item = Result<(&'a str, f32), Box<dyn Error>>>,
+            ) -> Result<Self, Box<dyn Error>> {
+                let mut setup = Self::default();
+                let mut missing: Vec<&str> = vec![$($name),*];
+                for parameter in parameters {
+                    let (name, value) = parameter?;
+                    setup.set_parameter(name, value)?;
+                    missing.retain(|parameter| *parameter != name);
+                }
+                if !missing.is_empty() {
+                    return Err(Box::from(format!(
+                        "Missing setup parameters: {}",
+                        missing.join(", ")
+                    )));
+                }
+                Ok(setup)
+            }
+        }
+    };
+}
+
+// setup sheets of some sites quote their numbers
+fn parse_value(name: &str, value: &str) -> Result<f32, Box<dyn Error>> {
+    let value = value.trim().trim_matches('"');
+    value
+        .parse()
+        .map_err(|_| Box::from(format!("Invalid value for {}: {}", name, value)))
+}
+
+/// json_object splits a flat JSON object into its keys and raw values,
+/// setup sheets have no nested values (strings are kept with their quotes)
+fn json_object(json: &str) -> Result<Vec<(&str, &str)>, Box<dyn Error>> {
+    let body = json
+        .trim()
+        .strip_prefix('{')
+        .and_then(|json| json.strip_suffix('}'))
+        .ok_or("Invalid setup sheet: not a JSON object")?;
+
+    let mut entries = Vec::new();
+    let mut rest = body.trim();
+    while !rest.is_empty() {
+        let key_end = rest
+            .strip_prefix('"')
+            .and_then(|key| key.find('"'))
+            .ok_or("Invalid setup sheet: expected a key")?;
+        let key = &rest[1..key_end + 1];
+        let value = rest[key_end + 2..]
+            .trim_start()
+            .strip_prefix(':')
+            .ok_or("Invalid setup sheet: expected a ':'")?
+            .trim_start();
+
+        // strings may contain commas, numbers end at the next one
+        let value_end = match value.strip_prefix('"') {
+            Some(string) => string.find('"').map(|end| end + 2),
+            None => Some(value.find(',').unwrap_or(value.len())),
+        }
+        .ok_or("Invalid setup sheet: unterminated string")?;
+        entries.push((key, value[..value_end].trim()));
+
+        rest = value[value_end..].trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+    Ok(entries)
+}
+
+/// car_setup implements setup_parameters for the CarSetupData of a year,
+/// its layout did not change since "F1 2020" (see spec::shared_packets)
+macro_rules! car_setup {
+    ($type:ty) => {
+        setup_parameters!(
+            $type,
+            "front_wing", "frontWing" => wing.front as u8,
+            "rear_wing", "rearWing" => wing.rear as u8,
+            "on_throttle", "onThrottle" => on_throttle as u8,
+            "off_throttle", "offThrottle" => off_throttle as u8,
+            "front_camber", "frontCamber" => camber.front as f32,
+            "rear_camber", "rearCamber" => camber.rear as f32,
+            "front_toe", "frontToe" => toe.front as f32,
+            "rear_toe", "rearToe" => toe.rear as f32,
+            "front_suspension", "frontSuspension" => suspension.front as u8,
+            "rear_suspension", "rearSuspension" => suspension.rear as u8,
+            "front_anti_roll_bar", "frontAntiRollBar" => anti_roll_bar.front as u8,
+            "rear_anti_roll_bar", "rearAntiRollBar" => anti_roll_bar.rear as u8,
+            "front_suspension_height", "frontSuspensionHeight" => suspension_height.front as u8,
+            "rear_suspension_height", "rearSuspensionHeight" => suspension_height.rear as u8,
+            "brake_pressure", "brakePressure" => brake_pressure as u8,
+            "brake_bias", "brakeBias" => brake_bias as u8,
+            "rear_left_tyre_pressure", "rearLeftTyrePressure" => type_pressure.rear_left as f32,
+            "rear_right_tyre_pressure", "rearRightTyrePressure" => type_pressure.rear_right as f32,
+            "front_left_tyre_pressure", "frontLeftTyrePressure" => type_pressure.front_left as f32,
+            "front_right_tyre_pressure", "frontRightTyrePressure" =>
+                type_pressure.front_right as f32,
+            "ballast", "ballast" => ballast as u8,
+            "fuel_load", "fuelLoad" => fuel_load as f32,
+        );
+    };
+}
+
+car_setup!(f1_2020::CarSetupData);
+car_setup!(f1_2022::CarSetupData);
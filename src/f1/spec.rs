@@ -0,0 +1,168 @@
+/// packet_spec declares packet layouts as a list of structs with their fields
+/// every struct gets the Debug, Clone, BinRead and serde derives and public fields, extra derives
+/// and binread attributes (ie: #[br(map = ...)]) are passed through
+macro_rules! packet_spec {
+    ($(
+        $(#[$attr:meta])*
+        $name:ident {
+            $(
+                $(#[$field_attr:meta])*
+                $field:ident: $type:ty
+            ),* $(,)?
+        }
+    )*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone, BinRead)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+            pub struct $name {
+                $(
+                    $(#[$field_attr])*
+                    pub $field: $type,
+                )*
+            }
+        )*
+    };
+}
+
+pub(crate) use packet_spec;
+
+/// shared_packets declares the packets whose layout did not change since "F1 2020"
+/// it expands in the module of each year, so the types it names (ie: ZoneFlag, ERSDeployMode)
+/// are the ones of that year; a layout that changes in a new year moves out of here
+macro_rules! shared_packets {
+    () => {
+        $crate::f1::spec::packet_spec! {
+            // HEADER
+            #[derive(Default)]
+            Header {
+                packet_format: u16,                   // 2020, 2022...
+                game_major_version: u8,               // Game major version - "X.00"
+                game_minor_version: u8,               // Game minor version - "1.XX"
+                packet_version: u8,                   // Version of this packet type, all start from 1
+                packet_id: u8,                        // Identifier for the packet type, see below
+                session_uid: u64,                     // Unique identifier for the session
+                session_time: f32,                    // Session timestamp
+                frame_identifier: u32,                // Identifier for the frame the data was retrieved on
+                player_car_index: CarIndex,           // Index of player's car in the array
+                secondary_player_car_index: CarIndex, // Index of secondary player's car in the array
+                                                      // (splitscreen), 255 if no second player
+            }
+
+            // MOTION
+            Motion {
+                header: Header,
+
+                car_motion_data: [CarMotionData; 22], // Data for all cars on track (22)
+
+                // Extra player car ONLY data
+                suspension_position: WheelValue<f32>, // Note: All wheel arrays have the following order:
+                suspension_velocity: WheelValue<f32>, // RL, RR, FL, FR
+                suspension_acceleration: WheelValue<f32>, // RL, RR, FL, FR
+                wheel_speed: WheelValue<f32>,         // Speed of each wheel
+                wheel_slip: WheelValue<f32>,          // Slip ratio for each wheel
+                local_velocity: Coordinates<f32>,     // Velocity in local space
+                angular_velocity: Coordinates<f32>,   // Angular velocity
+                angular_acceleration: Coordinates<f32>, // Angular acceleration
+                front_wheel_angle: f32,               // Current front wheels angle in radians
+            }
+
+            #[derive(Default)]
+            CarMotionData {
+                world_position: Coordinates<f32>,    // World space position
+                world_velocity: Coordinates<f32>,    // Velocity in world space
+                world_forward_dir: Coordinates<i16>, // World space forward direction (normalised)
+                world_right_dir: Coordinates<i16>,   // World space right direction (normalised)
+                g_force_lateral: f32,                // Lateral G-Force component
+                g_force_longitudinal: f32,           // Longitudinal G-Force component
+                g_force_vertical: f32,               // Vertical G-Force component
+                yaw: f32,                            // Yaw angle in radians
+                pitch: f32,                          // Pitch angle in radians
+                roll: f32,                           // Roll angle in radians
+            }
+
+            // SESSION
+            #[derive(Default)]
+            MarshalZone {
+                zone_start: f32,    // Fraction (0..1) of way through the lap the marshal zone starts
+                zone_flag: ZoneFlag, // -1 = invalid/unknown, 0 = none, 1 = green, 2 = blue
+                                     // 3 = yellow, 4 = red
+            }
+
+            // EVENT
+            #[derive(Default)]
+            PenaltyEventDetail {
+                penalty_type: PenaltyType,          // Penalty type – see Appendices
+                infrigement_type: InfringementType, // Infringement type – see Appendices
+                vehicle_index: CarIndex,            // Vehicle index of the car the penalty is applied to
+                other_vehicle_index: CarIndex,      // Vehicle index of the other car involved
+                time: u8,                           // Time gained, or time spent doing action in seconds
+                lap_number: u8,                     // Lap the penalty occurred on
+                places_gained: u8,                  // Number of places gained by this
+            }
+
+            // PARTICIPANTS
+            Participants {
+                header: Header,
+                num_active_cars: u8, // Number of active cars in the data – should match number of
+                                     // cars on HUD
+                participants_data: [ParticipantsData; 22],
+            }
+
+            // CAR SETUP
+            CarSetup {
+                header: Header,
+                car_setup_data: [CarSetupData; 22],
+            }
+
+            #[derive(Default)]
+            CarSetupData {
+                wing: FrontRearValue<u8>,              // Wing aero
+                on_throttle: u8,                       // Differential adjustment on throttle (percentage)
+                off_throttle: u8,                      // Differential adjustment off throttle (percentage)
+                camber: FrontRearValue<f32>,           // Camber angle (suspension geometry)
+                toe: FrontRearValue<f32>,              // Toe angle (suspension geometry)
+                suspension: FrontRearValue<u8>,        // Suspension
+                anti_roll_bar: FrontRearValue<u8>,     // Anti-roll bar
+                suspension_height: FrontRearValue<u8>, // Ride height
+                brake_pressure: u8,                    // Brake pressure (percentage)
+                brake_bias: u8,                        // Brake bias (percentage)
+                type_pressure: WheelValue<f32>,        // Tyre pressure (PSI)
+                ballast: u8,                           // Ballast
+                fuel_load: f32,                        // Fuel load
+            }
+
+            // CAR STATUS
+            CarStatus {
+                header: Header,
+                car_status_data: [CarStatusData; 22],
+            }
+
+            #[derive(Default)]
+            ERS {
+                stored_energy: f32,           // ERS energy store in Joules
+                deploy_mode: ERSDeployMode,   // ERS deployment mode, 0 = none, 1 = medium
+                                              // 2 = hotlap, 3 = overtake
+                harvested_this_lap_mguk: f32, // ERS energy harvested this lap by MGU-K
+                harvested_this_lap_mguh: f32, // ERS energy harvested this lap by MGU-H
+                deployed_this_lap: f32,       // ERS energy deployed this lap
+            }
+
+            // FINAL CLASSIFICATION
+            FinalClassification {
+                header: Header,
+                number_of_cars: u8, // Number of cars in the final classification
+                final_classification_data: [FinalClassificationData; 22],
+            }
+
+            // LOBBY INFO
+            LobbyInfo {
+                header: Header,
+                number_of_players: u8, // Number of players in the lobby data
+                lobby_players: [LobbyInfoData; 22],
+            }
+        }
+    };
+}
+
+pub(crate) use shared_packets;
@@ -0,0 +1,43 @@
+/// Rgb is a 24 bit color
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    /// from_hex builds a color from its 0xRRGGBB representation
+    pub const fn from_hex(hex: u32) -> Rgb {
+        Rgb {
+            r: (hex >> 16) as u8,
+            g: (hex >> 8) as u8,
+            b: hex as u8,
+        }
+    }
+
+    /// to_hex returns the 0xRRGGBB representation of the color
+    pub const fn to_hex(&self) -> u32 {
+        (self.r as u32) << 16 | (self.g as u32) << 8 | self.b as u32
+    }
+}
+
+/// TeamInfo holds the branding of a team (short code and livery colors) for timing towers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TeamInfo {
+    pub code: &'static str,
+    pub primary: Rgb,
+    pub secondary: Rgb,
+}
+
+impl TeamInfo {
+    pub(crate) const fn new(code: &'static str, primary: u32, secondary: u32) -> TeamInfo {
+        TeamInfo {
+            code,
+            primary: Rgb::from_hex(primary),
+            secondary: Rgb::from_hex(secondary),
+        }
+    }
+}
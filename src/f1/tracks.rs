@@ -0,0 +1,439 @@
+/// TrackInfo holds the real world metadata of a circuit layout, used by overlay displays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TrackInfo {
+    pub name: &'static str,
+    pub grand_prix: &'static str,
+    pub country: &'static str,
+    pub length: u32, // meters
+    pub sectors: u8,
+    pub corners: u8,
+    pub lap_record: Option<LapRecord>, // None for layouts never raced by F1
+}
+
+/// LapRecord is the official race lap record of a circuit at the time of the games
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LapRecord {
+    pub time_ms: u32,
+    pub driver: &'static str,
+    pub year: u16,
+}
+
+pub const MELBOURNE: TrackInfo = TrackInfo {
+    name: "Albert Park Circuit",
+    grand_prix: "Australian Grand Prix",
+    country: "Australia",
+    length: 5278,
+    sectors: 3,
+    corners: 14,
+    lap_record: Some(LapRecord {
+        time_ms: 80260,
+        driver: "Charles Leclerc",
+        year: 2022,
+    }),
+};
+
+pub const PAUL_RICARD: TrackInfo = TrackInfo {
+    name: "Circuit Paul Ricard",
+    grand_prix: "French Grand Prix",
+    country: "France",
+    length: 5842,
+    sectors: 3,
+    corners: 15,
+    lap_record: Some(LapRecord {
+        time_ms: 92740,
+        driver: "Sebastian Vettel",
+        year: 2019,
+    }),
+};
+
+pub const SHANGHAI: TrackInfo = TrackInfo {
+    name: "Shanghai International Circuit",
+    grand_prix: "Chinese Grand Prix",
+    country: "China",
+    length: 5451,
+    sectors: 3,
+    corners: 16,
+    lap_record: Some(LapRecord {
+        time_ms: 92238,
+        driver: "Michael Schumacher",
+        year: 2004,
+    }),
+};
+
+pub const SAKHIR: TrackInfo = TrackInfo {
+    name: "Bahrain International Circuit",
+    grand_prix: "Bahrain Grand Prix",
+    country: "Bahrain",
+    length: 5412,
+    sectors: 3,
+    corners: 15,
+    lap_record: Some(LapRecord {
+        time_ms: 91447,
+        driver: "Pedro de la Rosa",
+        year: 2005,
+    }),
+};
+
+pub const CATALUNYA: TrackInfo = TrackInfo {
+    name: "Circuit de Barcelona-Catalunya",
+    grand_prix: "Spanish Grand Prix",
+    country: "Spain",
+    length: 4675,
+    sectors: 3,
+    corners: 16,
+    lap_record: Some(LapRecord {
+        time_ms: 78149,
+        driver: "Max Verstappen",
+        year: 2021,
+    }),
+};
+
+pub const MONACO: TrackInfo = TrackInfo {
+    name: "Circuit de Monaco",
+    grand_prix: "Monaco Grand Prix",
+    country: "Monaco",
+    length: 3337,
+    sectors: 3,
+    corners: 19,
+    lap_record: Some(LapRecord {
+        time_ms: 72909,
+        driver: "Lewis Hamilton",
+        year: 2021,
+    }),
+};
+
+pub const MONTREAL: TrackInfo = TrackInfo {
+    name: "Circuit Gilles Villeneuve",
+    grand_prix: "Canadian Grand Prix",
+    country: "Canada",
+    length: 4361,
+    sectors: 3,
+    corners: 14,
+    lap_record: Some(LapRecord {
+        time_ms: 73078,
+        driver: "Valtteri Bottas",
+        year: 2019,
+    }),
+};
+
+pub const SILVERSTONE: TrackInfo = TrackInfo {
+    name: "Silverstone Circuit",
+    grand_prix: "British Grand Prix",
+    country: "United Kingdom",
+    length: 5891,
+    sectors: 3,
+    corners: 18,
+    lap_record: Some(LapRecord {
+        time_ms: 87097,
+        driver: "Max Verstappen",
+        year: 2020,
+    }),
+};
+
+pub const HOCKENHEIM: TrackInfo = TrackInfo {
+    name: "Hockenheimring",
+    grand_prix: "German Grand Prix",
+    country: "Germany",
+    length: 4574,
+    sectors: 3,
+    corners: 17,
+    lap_record: Some(LapRecord {
+        time_ms: 73780,
+        driver: "Kimi Räikkönen",
+        year: 2004,
+    }),
+};
+
+pub const HUNGARORING: TrackInfo = TrackInfo {
+    name: "Hungaroring",
+    grand_prix: "Hungarian Grand Prix",
+    country: "Hungary",
+    length: 4381,
+    sectors: 3,
+    corners: 14,
+    lap_record: Some(LapRecord {
+        time_ms: 76627,
+        driver: "Lewis Hamilton",
+        year: 2020,
+    }),
+};
+
+pub const SPA: TrackInfo = TrackInfo {
+    name: "Circuit de Spa-Francorchamps",
+    grand_prix: "Belgian Grand Prix",
+    country: "Belgium",
+    length: 7004,
+    sectors: 3,
+    corners: 19,
+    lap_record: Some(LapRecord {
+        time_ms: 106286,
+        driver: "Valtteri Bottas",
+        year: 2018,
+    }),
+};
+
+pub const MONZA: TrackInfo = TrackInfo {
+    name: "Autodromo Nazionale Monza",
+    grand_prix: "Italian Grand Prix",
+    country: "Italy",
+    length: 5793,
+    sectors: 3,
+    corners: 11,
+    lap_record: Some(LapRecord {
+        time_ms: 81046,
+        driver: "Rubens Barrichello",
+        year: 2004,
+    }),
+};
+
+pub const SINGAPORE: TrackInfo = TrackInfo {
+    name: "Marina Bay Street Circuit",
+    grand_prix: "Singapore Grand Prix",
+    country: "Singapore",
+    length: 5063,
+    sectors: 3,
+    corners: 23,
+    lap_record: Some(LapRecord {
+        time_ms: 101905,
+        driver: "Kevin Magnussen",
+        year: 2018,
+    }),
+};
+
+pub const SUZUKA: TrackInfo = TrackInfo {
+    name: "Suzuka International Racing Course",
+    grand_prix: "Japanese Grand Prix",
+    country: "Japan",
+    length: 5807,
+    sectors: 3,
+    corners: 18,
+    lap_record: Some(LapRecord {
+        time_ms: 90983,
+        driver: "Lewis Hamilton",
+        year: 2019,
+    }),
+};
+
+pub const ABU_DHABI: TrackInfo = TrackInfo {
+    name: "Yas Marina Circuit",
+    grand_prix: "Abu Dhabi Grand Prix",
+    country: "United Arab Emirates",
+    length: 5281,
+    sectors: 3,
+    corners: 16,
+    lap_record: Some(LapRecord {
+        time_ms: 86103,
+        driver: "Max Verstappen",
+        year: 2021,
+    }),
+};
+
+pub const TEXAS: TrackInfo = TrackInfo {
+    name: "Circuit of the Americas",
+    grand_prix: "United States Grand Prix",
+    country: "United States",
+    length: 5513,
+    sectors: 3,
+    corners: 20,
+    lap_record: Some(LapRecord {
+        time_ms: 96169,
+        driver: "Charles Leclerc",
+        year: 2019,
+    }),
+};
+
+pub const BRAZIL: TrackInfo = TrackInfo {
+    name: "Autódromo José Carlos Pace",
+    grand_prix: "São Paulo Grand Prix",
+    country: "Brazil",
+    length: 4309,
+    sectors: 3,
+    corners: 15,
+    lap_record: Some(LapRecord {
+        time_ms: 70540,
+        driver: "Valtteri Bottas",
+        year: 2018,
+    }),
+};
+
+pub const AUSTRIA: TrackInfo = TrackInfo {
+    name: "Red Bull Ring",
+    grand_prix: "Austrian Grand Prix",
+    country: "Austria",
+    length: 4318,
+    sectors: 3,
+    corners: 10,
+    lap_record: Some(LapRecord {
+        time_ms: 65619,
+        driver: "Carlos Sainz",
+        year: 2020,
+    }),
+};
+
+pub const SOCHI: TrackInfo = TrackInfo {
+    name: "Sochi Autodrom",
+    grand_prix: "Russian Grand Prix",
+    country: "Russia",
+    length: 5848,
+    sectors: 3,
+    corners: 18,
+    lap_record: Some(LapRecord {
+        time_ms: 95761,
+        driver: "Lewis Hamilton",
+        year: 2019,
+    }),
+};
+
+pub const MEXICO: TrackInfo = TrackInfo {
+    name: "Autódromo Hermanos Rodríguez",
+    grand_prix: "Mexico City Grand Prix",
+    country: "Mexico",
+    length: 4304,
+    sectors: 3,
+    corners: 17,
+    lap_record: Some(LapRecord {
+        time_ms: 77774,
+        driver: "Valtteri Bottas",
+        year: 2021,
+    }),
+};
+
+pub const BAKU: TrackInfo = TrackInfo {
+    name: "Baku City Circuit",
+    grand_prix: "Azerbaijan Grand Prix",
+    country: "Azerbaijan",
+    length: 6003,
+    sectors: 3,
+    corners: 20,
+    lap_record: Some(LapRecord {
+        time_ms: 103009,
+        driver: "Charles Leclerc",
+        year: 2019,
+    }),
+};
+
+pub const SAKHIR_SHORT: TrackInfo = TrackInfo {
+    name: "Bahrain International Circuit (Outer)",
+    grand_prix: "Sakhir Grand Prix",
+    country: "Bahrain",
+    length: 3543,
+    sectors: 3,
+    corners: 11,
+    lap_record: Some(LapRecord {
+        time_ms: 55404,
+        driver: "George Russell",
+        year: 2020,
+    }),
+};
+
+pub const SILVERSTONE_SHORT: TrackInfo = TrackInfo {
+    name: "Silverstone Circuit (National)",
+    grand_prix: "British Grand Prix",
+    country: "United Kingdom",
+    length: 2639,
+    sectors: 3,
+    corners: 6,
+    lap_record: None,
+};
+
+pub const TEXAS_SHORT: TrackInfo = TrackInfo {
+    name: "Circuit of the Americas (National)",
+    grand_prix: "United States Grand Prix",
+    country: "United States",
+    length: 3702,
+    sectors: 3,
+    corners: 11,
+    lap_record: None,
+};
+
+pub const SUZUKA_SHORT: TrackInfo = TrackInfo {
+    name: "Suzuka International Racing Course (East)",
+    grand_prix: "Japanese Grand Prix",
+    country: "Japan",
+    length: 2243,
+    sectors: 3,
+    corners: 9,
+    lap_record: None,
+};
+
+pub const HANOI: TrackInfo = TrackInfo {
+    name: "Hanoi Circuit",
+    grand_prix: "Vietnamese Grand Prix",
+    country: "Vietnam",
+    length: 5607,
+    sectors: 3,
+    corners: 23,
+    lap_record: None,
+};
+
+pub const ZANDVOORT: TrackInfo = TrackInfo {
+    name: "Circuit Zandvoort",
+    grand_prix: "Dutch Grand Prix",
+    country: "Netherlands",
+    length: 4259,
+    sectors: 3,
+    corners: 14,
+    lap_record: Some(LapRecord {
+        time_ms: 71097,
+        driver: "Lewis Hamilton",
+        year: 2021,
+    }),
+};
+
+pub const IMOLA: TrackInfo = TrackInfo {
+    name: "Autodromo Enzo e Dino Ferrari",
+    grand_prix: "Emilia Romagna Grand Prix",
+    country: "Italy",
+    length: 4909,
+    sectors: 3,
+    corners: 19,
+    lap_record: Some(LapRecord {
+        time_ms: 75484,
+        driver: "Lewis Hamilton",
+        year: 2020,
+    }),
+};
+
+pub const PORTIMAO: TrackInfo = TrackInfo {
+    name: "Autódromo Internacional do Algarve",
+    grand_prix: "Portuguese Grand Prix",
+    country: "Portugal",
+    length: 4653,
+    sectors: 3,
+    corners: 15,
+    lap_record: Some(LapRecord {
+        time_ms: 78750,
+        driver: "Lewis Hamilton",
+        year: 2020,
+    }),
+};
+
+pub const JEDDAH: TrackInfo = TrackInfo {
+    name: "Jeddah Corniche Circuit",
+    grand_prix: "Saudi Arabian Grand Prix",
+    country: "Saudi Arabia",
+    length: 6174,
+    sectors: 3,
+    corners: 27,
+    lap_record: Some(LapRecord {
+        time_ms: 90734,
+        driver: "Lewis Hamilton",
+        year: 2021,
+    }),
+};
+
+pub const MIAMI: TrackInfo = TrackInfo {
+    name: "Miami International Autodrome",
+    grand_prix: "Miami Grand Prix",
+    country: "United States",
+    length: 5412,
+    sectors: 3,
+    corners: 19,
+    lap_record: Some(LapRecord {
+        time_ms: 91361,
+        driver: "Max Verstappen",
+        year: 2022,
+    }),
+};
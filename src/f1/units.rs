@@ -0,0 +1,65 @@
+use crate::f1::util::WheelValue;
+use crate::f1::{f1_2020, f1_2022};
+use crate::units;
+
+// the games send speeds in kph, temperatures in celsius, tyre pressures in PSI
+// and fuel in kg, these accessors return the other units dashboards show
+macro_rules! car_telemetry_units {
+    ($type:ty) => {
+        impl $type {
+            /// speed_mph returns the speed of the car in miles per hour
+            pub fn speed_mph(&self) -> f32 {
+                units::kph_to_mph(self.speed as f32)
+            }
+
+            /// brake_temp_fahrenheit returns the brakes temperature in fahrenheit
+            pub fn brake_temp_fahrenheit(&self) -> WheelValue<f32> {
+                self.brake_temp
+                    .map(|&celsius| units::celsius_to_fahrenheit(celsius as f32))
+            }
+
+            /// tyres_surface_temp_fahrenheit returns the tyres surface temperature in fahrenheit
+            pub fn tyres_surface_temp_fahrenheit(&self) -> WheelValue<f32> {
+                self.tyres_surface_temp
+                    .map(|&celsius| units::celsius_to_fahrenheit(celsius as f32))
+            }
+
+            /// tyres_inner_temp_fahrenheit returns the tyres inner temperature in fahrenheit
+            pub fn tyres_inner_temp_fahrenheit(&self) -> WheelValue<f32> {
+                self.tyres_inner_temp
+                    .map(|&celsius| units::celsius_to_fahrenheit(celsius as f32))
+            }
+
+            /// engine_temp_fahrenheit returns the engine temperature in fahrenheit
+            pub fn engine_temp_fahrenheit(&self) -> f32 {
+                units::celsius_to_fahrenheit(self.engine_temp as f32)
+            }
+
+            /// tyres_pressure_bar returns the tyres pressure in bar
+            pub fn tyres_pressure_bar(&self) -> WheelValue<f32> {
+                self.tyres_pressure.map(|&psi| units::psi_to_bar(psi))
+            }
+        }
+    };
+}
+
+macro_rules! car_status_units {
+    ($type:ty) => {
+        impl $type {
+            /// fuel_in_tank_litres returns the fuel in the tank in litres (fuel_in_tank is in kg)
+            pub fn fuel_in_tank_litres(&self) -> f32 {
+                units::fuel_kg_to_litres(self.fuel_in_tank)
+            }
+
+            /// fuel_capacity_litres returns the capacity of the tank in litres
+            pub fn fuel_capacity_litres(&self) -> f32 {
+                units::fuel_kg_to_litres(self.fuel_capacity)
+            }
+        }
+    };
+}
+
+car_telemetry_units!(f1_2020::CarTelemetryData);
+car_telemetry_units!(f1_2022::CarTelemetryData);
+car_status_units!(f1_2020::CarStatusData);
+car_status_units!(f1_2022::CarStatusData);
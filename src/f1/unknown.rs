@@ -0,0 +1,50 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use cfg_if::cfg_if;
+
+#[cfg(feature = "std")]
+use std::sync::RwLock;
+
+/// UnknownValue is a raw value that did not match any variant of an enum,
+/// the field was decoded as the default variant (usually Unknown) instead.
+/// They point at values added by a game patch that this crate does not know yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UnknownValue {
+    pub type_name: &'static str, // ie: "Weather"
+    pub raw: i64,
+}
+
+#[cfg(feature = "std")]
+type Hook = alloc::boxed::Box<dyn Fn(UnknownValue) + Send + Sync>;
+
+static UNKNOWN_VALUES: AtomicU32 = AtomicU32::new(0);
+
+#[cfg(feature = "std")]
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+/// set_unknown_value_hook registers a function called with every unknown value decoded
+/// (from any thread), None removes it
+#[cfg(feature = "std")]
+pub fn set_unknown_value_hook(hook: Option<Hook>) {
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = hook;
+}
+
+/// unknown_values returns how many unknown values were decoded since the program started
+pub fn unknown_values() -> u32 {
+    UNKNOWN_VALUES.load(Ordering::Relaxed)
+}
+
+pub(crate) fn report(type_name: &'static str, raw: i64) {
+    UNKNOWN_VALUES.fetch_add(1, Ordering::Relaxed);
+
+    cfg_if! {
+        if #[cfg(feature = "std")] {
+            if let Some(hook) = HOOK.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+                hook(UnknownValue { type_name, raw });
+            }
+        } else {
+            let _ = (type_name, raw); // only counted without std, hooks need a lock
+        }
+    }
+}
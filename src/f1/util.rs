@@ -1,15 +1,23 @@
-use binread::BinRead;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::error::Error;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
 
+use binread::{io::Cursor, BinRead, BinReaderExt};
+use bitflags::bitflags;
+use cfg_if::cfg_if;
 use num::Num;
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Coordinates<T: Num + binread::BinRead<Args = ()>> {
     pub x: T,
     pub y: T,
     pub z: T,
 }
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct WheelValue<T: binread::BinRead<Args = ()>> {
     pub rear_left: T,
     pub rear_right: T,
@@ -17,15 +25,253 @@ pub struct WheelValue<T: binread::BinRead<Args = ()>> {
     pub front_right: T,
 }
 
-#[derive(Debug, Default, BinRead)]
+impl<T: binread::BinRead<Args = ()>> WheelValue<T> {
+    /// map returns the values of every wheel converted by f (ie: to other units)
+    pub fn map<U: binread::BinRead<Args = ()>>(&self, f: impl Fn(&T) -> U) -> WheelValue<U> {
+        WheelValue {
+            rear_left: f(&self.rear_left),
+            rear_right: f(&self.rear_right),
+            front_left: f(&self.front_left),
+            front_right: f(&self.front_right),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct FrontRearValue<T: Num + binread::BinRead<Args = ()>> {
     pub front: T,
     pub rear: T,
 }
 
-#[derive(Debug, Default, BinRead)]
+#[derive(Debug, Clone, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct WingValue<T: binread::BinRead<Args = ()>> {
     pub front_left: T,
     pub front_right: T,
     pub rear: T,
-}
\ No newline at end of file
+}
+
+bitflags! {
+    /// ButtonFlags are the buttons pressed on the controller (or wheel) of the player,
+    /// sent in the CarTelemetry packet of "F1 2020" and the BUTN event of "F1 22"
+    #[derive(Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    pub struct ButtonFlags: u32 {
+        const CROSS_OR_A        = 0x00000001;
+        const TRIANGLE_OR_Y     = 0x00000002;
+        const CIRCLE_OR_B       = 0x00000004;
+        const SQUARE_OR_X       = 0x00000008;
+        const D_PAD_LEFT        = 0x00000010;
+        const D_PAD_RIGHT       = 0x00000020;
+        const D_PAD_UP          = 0x00000040;
+        const D_PAD_DOWN        = 0x00000080;
+        const OPTIONS_OR_MENU   = 0x00000100;
+        const L1_OR_LB          = 0x00000200;
+        const R1_OR_RB          = 0x00000400;
+        const L2_OR_LT          = 0x00000800;
+        const R2_OR_RT          = 0x00001000;
+        const LEFT_STICK_CLICK  = 0x00002000;
+        const RIGHT_STICK_CLICK = 0x00004000;
+        const RIGHT_STICK_LEFT  = 0x00008000;
+        const RIGHT_STICK_RIGHT = 0x00010000;
+        const RIGHT_STICK_UP    = 0x00020000;
+        const RIGHT_STICK_DOWN  = 0x00040000;
+        const SPECIAL           = 0x00080000;
+        const UDP_ACTION_1      = 0x00100000;
+        const UDP_ACTION_2      = 0x00200000;
+        const UDP_ACTION_3      = 0x00400000;
+        const UDP_ACTION_4      = 0x00800000;
+        const UDP_ACTION_5      = 0x01000000;
+        const UDP_ACTION_6      = 0x02000000;
+        const UDP_ACTION_7      = 0x04000000;
+        const UDP_ACTION_8      = 0x08000000;
+        const UDP_ACTION_9      = 0x10000000;
+        const UDP_ACTION_10     = 0x20000000;
+        const UDP_ACTION_11     = 0x40000000;
+        const UDP_ACTION_12     = 0x80000000;
+    }
+}
+
+impl Default for ButtonFlags {
+    fn default() -> Self {
+        ButtonFlags::empty()
+    }
+}
+
+/// CarIndex is the index of a car in the per car arrays of a packet (ie: car_telemetry_data),
+/// not its position in the race; 255 (or any index past the 22 cars) means no car
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CarIndex(pub u8);
+
+impl CarIndex {
+    /// MAX_CARS is the size of the per car arrays
+    pub const MAX_CARS: usize = 22;
+
+    /// NONE is the index sent when there is no car (ie: no secondary player)
+    pub const NONE: CarIndex = CarIndex(255);
+
+    /// get returns the index, None when it does not point to a car
+    pub fn get(self) -> Option<u8> {
+        if self.is_valid() {
+            Some(self.0)
+        } else {
+            None
+        }
+    }
+
+    /// index returns the index into the per car arrays, None when it does not point to a car
+    pub fn index(self) -> Option<usize> {
+        self.get().map(usize::from)
+    }
+
+    pub fn is_valid(self) -> bool {
+        (self.0 as usize) < CarIndex::MAX_CARS
+    }
+}
+
+impl From<u8> for CarIndex {
+    fn from(index: u8) -> Self {
+        CarIndex(index)
+    }
+}
+
+impl fmt::Display for CarIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// format_lap_time formats a lap time in milliseconds as "m:ss.mmm", "-" when there is none
+pub fn format_lap_time(millis: u32) -> String {
+    if millis == 0 {
+        return String::from("-");
+    }
+    alloc::format!(
+        "{}:{:02}.{:03}",
+        millis / 60_000,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+/// read_packet decodes a T from the start of the packet using the given byte order
+pub(crate) fn read_packet<T: BinRead<Args = ()>>(
+    packet: &[u8],
+    endian: binread::Endian,
+) -> Result<T, Box<dyn Error>> {
+    let mut reader = Cursor::new(packet);
+    cfg_if! {
+        if #[cfg(feature = "std")] {
+            Ok(reader.read_type(endian)?)
+        } else {
+            // binread errors only implement Error with std, keep their message instead
+            reader
+                .read_type(endian)
+                .map_err(|e| Box::from(alloc::format!("{}", e)))
+        }
+    }
+}
+
+/// read_remaining returns every byte left in the reader (ie: the payload of an unknown event)
+pub(crate) fn read_remaining<R: binread::io::Read>(reader: &mut R) -> binread::BinResult<Vec<u8>> {
+    let mut remaining = Vec::new();
+    let mut buf = [0; 64];
+    loop {
+        let number = reader.read(&mut buf)?;
+        if number == 0 {
+            return Ok(remaining);
+        }
+        remaining.extend_from_slice(&buf[..number]);
+    }
+}
+
+/// TruncatedArray is a fixed size array of which only the first entries are in use,
+/// ie: the 21 marshal zones of a Session packet truncated to number_of_marshal_zones.
+/// It dereferences to the slice of the entries in use, and is decoded without heap allocations
+#[derive(Clone)]
+pub struct TruncatedArray<T, const N: usize> {
+    entries: [T; N],
+    len: usize,
+}
+
+impl<T, const N: usize> TruncatedArray<T, N> {
+    /// new keeps the first len entries (at most N) of the array
+    pub fn new(entries: [T; N], len: usize) -> Self {
+        TruncatedArray {
+            entries,
+            len: len.min(N),
+        }
+    }
+
+    /// capacity returns the size of the array, including the entries not in use
+    pub fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T: Default, const N: usize> Default for TruncatedArray<T, N> {
+    fn default() -> Self {
+        TruncatedArray::new(core::array::from_fn(|_| T::default()), 0)
+    }
+}
+
+impl<T, const N: usize> Deref for TruncatedArray<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.entries[..self.len]
+    }
+}
+
+impl<T, const N: usize> DerefMut for TruncatedArray<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.entries[..self.len]
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a TruncatedArray<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for TruncatedArray<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for TruncatedArray<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, const N: usize> serde::Serialize for TruncatedArray<T, N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+/// reads the N entries of the array, args are the number of entries in use
+impl<T: BinRead<Args = ()> + Default, const N: usize> BinRead for TruncatedArray<T, N> {
+    type Args = (u8,);
+
+    fn read_options<R: binread::io::Read + binread::io::Seek>(
+        reader: &mut R,
+        options: &binread::ReadOptions,
+        (len,): Self::Args,
+    ) -> binread::BinResult<Self> {
+        let mut entries: [T; N] = core::array::from_fn(|_| T::default());
+        for entry in entries.iter_mut() {
+            *entry = T::read_options(reader, options, ())?;
+        }
+        Ok(TruncatedArray::new(entries, len as usize))
+    }
+}
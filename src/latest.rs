@@ -0,0 +1,116 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use arc_swap::ArcSwapOption;
+
+use crate::TelemetryFrame;
+
+struct Shared<T> {
+    packets: Box<[ArcSwapOption<T>]>, // latest packet, indexed by packet id
+    version: AtomicU64,
+    running: AtomicBool,
+}
+
+/// LatestState keeps only the most recent packet of each type, for game loops that sample the
+/// telemetry at their own rate instead of consuming every packet. Handles are cheap to clone and
+/// shared between threads; every packet id has its own atomically swapped Arc, readers never
+/// lock nor wait for the receiver
+pub struct LatestState<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Clone for LatestState<T> {
+    fn clone(&self) -> Self {
+        LatestState {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Default for LatestState<T> {
+    fn default() -> Self {
+        LatestState {
+            shared: Arc::new(Shared {
+                packets: (0..=u8::MAX).map(|_| ArcSwapOption::empty()).collect(),
+                version: AtomicU64::new(0),
+                running: AtomicBool::new(false),
+            }),
+        }
+    }
+}
+
+impl<T: TelemetryFrame> LatestState<T> {
+    pub fn new() -> LatestState<T> {
+        LatestState::default()
+    }
+
+    /// publish replaces the latest packet of the same type (packet_id)
+    pub fn publish(&self, event: T) {
+        let packet_id = event.packet_id();
+        self.shared.packets[packet_id as usize].store(Some(Arc::new(event)));
+        self.shared.version.fetch_add(1, Ordering::Release);
+    }
+
+    /// latest returns the most recent packet with the given packet_id
+    pub fn latest(&self, packet_id: u8) -> Option<Arc<T>> {
+        self.shared.packets[packet_id as usize].load_full()
+    }
+
+    /// snapshot returns the most recent packet of every type received, by packet_id
+    /// each packet is read atomically, a packet published meanwhile may or may not be included
+    pub fn snapshot(&self) -> Vec<Arc<T>> {
+        self.shared
+            .packets
+            .iter()
+            .filter_map(ArcSwapOption::load_full)
+            .collect()
+    }
+
+    /// version returns the number of packets published so far,
+    /// a reader can skip its work when it did not change since the last frame
+    pub fn version(&self) -> u64 {
+        self.shared.version.load(Ordering::Acquire)
+    }
+
+    /// is_running returns true while a background receiver (see TelemetryServer::spawn_latest)
+    /// publishes into this state
+    pub fn is_running(&self) -> bool {
+        self.shared.running.load(Ordering::Acquire)
+    }
+
+    /// clear forgets every packet, ie: when a new session starts
+    pub fn clear(&self) {
+        for packet in self.shared.packets.iter() {
+            packet.store(None);
+        }
+    }
+}
+
+#[cfg(feature = "net")]
+impl<T> crate::TelemetryServer<T>
+where
+    T: crate::TelemetryEvent + TelemetryFrame + Send + Sync + 'static,
+{
+    /// spawn_latest moves the server to a background thread publishing every packet into the
+    /// returned LatestState. Packets that do not decode are skipped, the thread stops on a
+    /// transport error or on the next packet once every handle was dropped
+    pub fn spawn_latest(self) -> LatestState<T> {
+        let state = LatestState::new();
+        state.shared.running.store(true, Ordering::Release);
+
+        let publisher = state.clone();
+        std::thread::spawn(move || {
+            while Arc::strong_count(&publisher.shared) > 1 {
+                let packet = match self.srv.recv() {
+                    Ok(packet) => packet,
+                    Err(_) => break,
+                };
+                if let Ok(event) = T::from_packet_with_endianness(&packet, self.endianness) {
+                    publisher.publish(event);
+                }
+            }
+            publisher.shared.running.store(false, Ordering::Release);
+        });
+        state
+    }
+}
@@ -1,40 +1,169 @@
-mod net;
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+#[cfg(feature = "net")]
+pub mod net;
+
+pub mod analysis;
+#[cfg(feature = "auth")]
+pub mod auth;
+pub mod availability;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod channels;
+pub mod dedupe;
+#[cfg(feature = "net")]
+pub mod demux;
 pub mod dirt;
+#[cfg(feature = "discord")]
+pub mod discord;
+pub mod export;
 pub mod f1;
+#[cfg(feature = "std")]
+pub mod latest;
+pub mod loss;
+pub mod merge;
+pub mod pcars;
+pub mod pipeline;
+pub mod schema;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "ui")]
+pub mod ui;
+pub mod units;
+#[cfg(feature = "upnp")]
+pub mod upnp;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use alloc::boxed::Box;
+use core::error::Error;
 
 /// TelemetryPacket is an alias for a vector of bytes
 pub type TelemetryPacket = [u8];
 
+/// Endianness specifies the byte order used to decode a TelemetryPacket
+/// games emit little-endian packets, big-endian is for relays that re-serialize in network order
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big,
+}
+
+impl From<Endianness> for binread::Endian {
+    fn from(endianness: Endianness) -> Self {
+        match endianness {
+            Endianness::Little => binread::Endian::Little,
+            Endianness::Big => binread::Endian::Big,
+        }
+    }
+}
+
 /// TelemetryEvent specifies a way to serialize itself from a Packet
 pub trait TelemetryEvent {
-    fn from_packet(packet: &TelemetryPacket) -> Result<Self, Box<dyn std::error::Error>>
+    fn from_packet(packet: &TelemetryPacket) -> Result<Self, Box<dyn Error>>
     where
         Self: Sized;
+
+    /// from_packet_with_endianness serializes itself from a Packet using the given byte order
+    /// defaults to from_packet for little-endian packets and rejects anything else
+    fn from_packet_with_endianness(
+        packet: &TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<Self, Box<dyn Error>>
+    where
+        Self: Sized,
+    {
+        match endianness {
+            Endianness::Little => Self::from_packet(packet),
+            Endianness::Big => Err(Box::from(
+                "Big-endian packets are not supported by this event",
+            )),
+        }
+    }
+}
+
+/// TelemetryFrame is implemented by events whose packets carry a frame based header
+/// it exposes the identifiers needed to order packets from the same session
+pub trait TelemetryFrame {
+    fn session_uid(&self) -> u64;
+    fn frame_identifier(&self) -> u32;
+    fn packet_id(&self) -> u8;
+    fn session_time(&self) -> f32; // seconds since the session started
 }
 
 /// TelemetryServer implements a generic server that can bind and recv packets
 /// exposes the next_event method that returns an unpacked "TelemetryEvent"
+#[cfg(feature = "net")]
 pub struct TelemetryServer<T: TelemetryEvent> {
-    srv: net::Server,
+    srv: Box<dyn net::Transport + Send + Sync>,
+    endianness: Endianness,
     phantom: std::marker::PhantomData<T>, // needed to avoid "type unused" compile error
 }
 
+#[cfg(feature = "net")]
 impl<T: TelemetryEvent> TelemetryServer<T> {
-    /// new initializes a Server with the given address
+    /// new initializes a Server listening for UDP packets on the given address
     pub fn new(address: &str) -> Result<TelemetryServer<T>, std::io::Error> {
-        let srv = net::Server::new(address)?;
-        Ok(TelemetryServer {
-            srv,
+        let transport = net::UdpTransport::new(address)?;
+        Ok(TelemetryServer::with_transport(transport))
+    }
+
+    /// new_multi initializes a Server listening for UDP packets on every given address,
+    /// use next_on to know which address received each event
+    pub fn new_multi(addresses: &[&str]) -> Result<TelemetryServer<T>, std::io::Error> {
+        let transport = net::MultiUdpTransport::new(addresses)?;
+        Ok(TelemetryServer::with_transport(transport))
+    }
+
+    /// with_transport initializes a Server that receives packets from the given transport
+    pub fn with_transport<R: net::Transport + Send + Sync + 'static>(
+        transport: R,
+    ) -> TelemetryServer<T> {
+        TelemetryServer {
+            srv: Box::new(transport),
+            endianness: Endianness::default(),
             phantom: std::marker::PhantomData,
-        })
+        }
     }
 
-    /// next will call recv on the inner UDP server (this blocks)
+    /// with_endianness sets the byte order used to decode incoming packets (defaults to little-endian)
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// next will call recv on the inner transport (this blocks)
     /// and will call from_packet from the given T
-    pub fn next(&self) -> Result<T, Box<dyn std::error::Error>> {
+    pub fn next(&self) -> Result<T, Box<dyn Error>> {
         let packet = self.srv.recv()?;
-        T::from_packet(&packet)
+        T::from_packet_with_endianness(&packet, self.endianness)
+    }
+
+    /// next_from is like next but also returns the address that sent the packet
+    /// (when the transport knows it)
+    pub fn next_from(&self) -> Result<(T, Option<std::net::SocketAddr>), Box<dyn Error>> {
+        let (packet, source) = self.srv.recv_from()?;
+        let event = T::from_packet_with_endianness(&packet, self.endianness)?;
+        Ok((event, source))
+    }
+
+    /// next_on is like next but also returns the local address that received the packet,
+    /// ie: to tell apart rigs sending to different ports of a server built with new_multi
+    pub fn next_on(&self) -> Result<(T, Option<std::net::SocketAddr>), Box<dyn Error>> {
+        let (packet, local_addr) = self.srv.recv_on()?;
+        let event = T::from_packet_with_endianness(&packet, self.endianness)?;
+        Ok((event, local_addr))
+    }
+
+    /// local_addr returns the address the server is bound to
+    /// (ie: the port assigned when binding to port 0)
+    /// None when the transport has no socket address (or several, see net::MultiUdpTransport)
+    pub fn local_addr(&self) -> Option<std::net::SocketAddr> {
+        self.srv.local_addr()
     }
 }
 
@@ -43,6 +172,7 @@ impl<T: TelemetryEvent> TelemetryServer<T> {
 #[cfg(feature = "async")]
 pub struct AsyncTelemetryServer<T: TelemetryEvent> {
     srv: net::AsyncServer,
+    endianness: Endianness,
     phantom: std::marker::PhantomData<T>, // needed to avoid "type unused" compile error
 }
 
@@ -53,14 +183,27 @@ impl<T: TelemetryEvent> AsyncTelemetryServer<T> {
         let srv = net::AsyncServer::new(address).await?;
         Ok(AsyncTelemetryServer {
             srv,
+            endianness: Endianness::default(),
             phantom: std::marker::PhantomData,
         })
     }
 
+    /// with_endianness sets the byte order used to decode incoming packets (defaults to little-endian)
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
     /// next will call recv on the inner UDP async server
     /// and will call from_packet from the given T
-    pub async fn next(&self) -> Result<T, Box<dyn std::error::Error>> {
+    pub async fn next(&self) -> Result<T, Box<dyn Error>> {
         let packet = self.srv.recv().await?;
-        T::from_packet(&packet)
+        T::from_packet_with_endianness(&packet, self.endianness)
+    }
+
+    /// local_addr returns the address the server is bound to
+    /// (ie: the port assigned when binding to port 0)
+    pub fn local_addr(&self) -> Option<std::net::SocketAddr> {
+        self.srv.local_addr()
     }
 }
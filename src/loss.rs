@@ -0,0 +1,133 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use crate::dedupe::ON_DEMAND_PACKETS;
+use crate::TelemetryFrame;
+
+/// PacketLoss is reported when the frame_identifier of a packet jumped further
+/// than the usual cadence of its packet type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacketLoss {
+    pub session_uid: u64,
+    pub packet_id: u8,
+    pub frame_identifier: u32, // frame of the packet received after the gap
+    pub missed: u32,           // estimated number of packets that never arrived
+}
+
+#[derive(Debug, Default)]
+struct Cadence {
+    last_frame: u32,
+    step: Option<u32>, // smallest frame step seen so far between two packets
+}
+
+/// FrameLossDetector estimates lost packets from gaps in frame_identifier
+/// games send each packet type at a fixed rate (ie: Motion every 3 frames at 60fps and 20Hz),
+/// the detector learns that cadence per (session_uid, packet_id) and reports gaps bigger than it.
+/// Packets sent on demand (see ON_DEMAND_PACKETS) have no cadence and are ignored by default
+#[derive(Debug)]
+pub struct FrameLossDetector {
+    ignored: BTreeSet<u8>,
+    cadences: BTreeMap<(u64, u8), Cadence>,
+    missed: BTreeMap<u8, u64>,
+    received: BTreeMap<u8, u64>,
+}
+
+impl Default for FrameLossDetector {
+    fn default() -> Self {
+        FrameLossDetector::new()
+    }
+}
+
+impl FrameLossDetector {
+    pub fn new() -> FrameLossDetector {
+        FrameLossDetector {
+            ignored: ON_DEMAND_PACKETS.iter().copied().collect(),
+            cadences: BTreeMap::new(),
+            missed: BTreeMap::new(),
+            received: BTreeMap::new(),
+        }
+    }
+
+    /// with_ignored_packets replaces the packet ids skipped by loss detection
+    /// (ON_DEMAND_PACKETS by default)
+    pub fn with_ignored_packets(mut self, packet_ids: &[u8]) -> FrameLossDetector {
+        self.ignored = packet_ids.iter().copied().collect();
+        self
+    }
+
+    /// observe records the event and returns a PacketLoss if packets went missing before it
+    pub fn observe<T: TelemetryFrame>(&mut self, event: &T) -> Option<PacketLoss> {
+        let packet_id = event.packet_id();
+        if self.ignored.contains(&packet_id) {
+            return None;
+        }
+
+        *self.received.entry(packet_id).or_insert(0) += 1;
+
+        let frame = event.frame_identifier();
+        let key = (event.session_uid(), packet_id);
+        let cadence = match self.cadences.get_mut(&key) {
+            Some(cadence) => cadence,
+            None => {
+                self.cadences.insert(
+                    key,
+                    Cadence {
+                        last_frame: frame,
+                        step: None,
+                    },
+                );
+                return None;
+            }
+        };
+
+        // duplicates and frame counter resets (ie: flashbacks) are not losses
+        if frame <= cadence.last_frame {
+            cadence.last_frame = frame;
+            return None;
+        }
+
+        let gap = frame - cadence.last_frame;
+        cadence.last_frame = frame;
+
+        let step = match cadence.step {
+            Some(step) if step <= gap => step,
+            _ => {
+                cadence.step = Some(gap);
+                return None;
+            }
+        };
+
+        // round to the nearest multiple of the cadence to absorb frame time jitter
+        let missed = (gap + step / 2) / step - 1;
+        if missed == 0 {
+            return None;
+        }
+
+        *self.missed.entry(packet_id).or_insert(0) += u64::from(missed);
+        Some(PacketLoss {
+            session_uid: key.0,
+            packet_id,
+            frame_identifier: frame,
+            missed,
+        })
+    }
+
+    /// missed returns the estimated number of lost packets for the given packet id
+    pub fn missed(&self, packet_id: u8) -> u64 {
+        self.missed.get(&packet_id).copied().unwrap_or(0)
+    }
+
+    /// received returns the number of observed packets for the given packet id
+    pub fn received(&self, packet_id: u8) -> u64 {
+        self.received.get(&packet_id).copied().unwrap_or(0)
+    }
+
+    /// loss_ratio returns the estimated fraction (0..1) of lost packets for the given packet id
+    pub fn loss_ratio(&self, packet_id: u8) -> f64 {
+        let missed = self.missed(packet_id) as f64;
+        let expected = missed + self.received(packet_id) as f64;
+        if expected == 0.0 {
+            return 0.0;
+        }
+        missed / expected
+    }
+}
@@ -0,0 +1,95 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::TelemetryFrame;
+
+/// MergedEvent is an event of one of the merged recordings
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MergedEvent<T> {
+    pub recording: usize, // index of the recording, in the order they were added
+    pub event: T,
+}
+
+/// Timeline merges recordings made by several players of the same online session into a single
+/// stream, ie: for league-wide analysis when each driver only recorded their own data.
+/// Sessions are matched on session_uid (shared by everyone in an online session), their events are
+/// ordered by session_time, then recording and frame_identifier; sessions follow each other
+/// in the order they were first seen. Flashbacks are disabled online so session_time never rewinds
+#[derive(Debug)]
+pub struct Timeline<T> {
+    events: Vec<MergedEvent<T>>,
+    sessions: BTreeMap<u64, usize>, // session_uid: order of appearance
+    recordings: usize,
+}
+
+impl<T> Default for Timeline<T> {
+    fn default() -> Self {
+        Timeline {
+            events: Vec::new(),
+            sessions: BTreeMap::new(),
+            recordings: 0,
+        }
+    }
+}
+
+impl<T: TelemetryFrame> Timeline<T> {
+    pub fn new() -> Timeline<T> {
+        Timeline::default()
+    }
+
+    /// add merges the events of a recording into the timeline and returns its index
+    pub fn add<I: IntoIterator<Item = T>>(&mut self, recording: I) -> usize {
+        let index = self.recordings;
+        self.recordings += 1;
+
+        for event in recording {
+            let order = self.sessions.len();
+            self.sessions.entry(event.session_uid()).or_insert(order);
+            self.events.push(MergedEvent {
+                recording: index,
+                event,
+            });
+        }
+
+        // stable, so packets of the same frame keep the order they were recorded in
+        let sessions = &self.sessions;
+        self.events.sort_by(|a, b| {
+            sessions[&a.event.session_uid()]
+                .cmp(&sessions[&b.event.session_uid()])
+                .then(a.event.session_time().total_cmp(&b.event.session_time()))
+                .then(a.recording.cmp(&b.recording))
+                .then(a.event.frame_identifier().cmp(&b.event.frame_identifier()))
+        });
+        index
+    }
+
+    /// recordings returns how many recordings were added
+    pub fn recordings(&self) -> usize {
+        self.recordings
+    }
+
+    /// sessions returns the session_uid of every session, in the order they were first seen
+    pub fn sessions(&self) -> Vec<u64> {
+        let mut sessions: Vec<(&u64, &usize)> = self.sessions.iter().collect();
+        sessions.sort_by_key(|(_, order)| **order);
+        sessions.into_iter().map(|(uid, _)| *uid).collect()
+    }
+
+    /// events returns every merged event, in timeline order
+    pub fn events(&self) -> &[MergedEvent<T>] {
+        &self.events
+    }
+
+    /// session returns the merged events of the session with the given uid
+    pub fn session(&self, session_uid: u64) -> impl Iterator<Item = &MergedEvent<T>> {
+        self.events
+            .iter()
+            .filter(move |merged| merged.event.session_uid() == session_uid)
+    }
+
+    /// into_events consumes the timeline and returns every merged event, in timeline order
+    pub fn into_events(self) -> Vec<MergedEvent<T>> {
+        self.events
+    }
+}
@@ -1,31 +1,294 @@
-use std::net::UdpSocket;
+use std::io::Read;
+use std::net::{SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Mutex;
+use std::thread;
+
+#[cfg(feature = "tls")]
+use std::sync::Arc;
+
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+#[cfg(unix)]
+use std::path::Path;
+
+#[cfg(feature = "tls")]
+use rustls::{ServerConfig, ServerConnection, StreamOwned};
 
 #[cfg(feature = "async")]
 use tokio::net::UdpSocket as AsyncUdpSocket;
 
 const MAX_PACKET_SIZE: usize = 2048;
 
-pub struct Server {
+/// Transport specifies a way to receive raw telemetry packets
+/// one call to recv returns exactly one packet (this blocks)
+pub trait Transport {
+    /// recv_from returns the next packet and, when the transport knows it, the address that sent it
+    fn recv_from(&self) -> Result<(Vec<u8>, Option<SocketAddr>), std::io::Error>;
+
+    fn recv(&self) -> Result<Vec<u8>, std::io::Error> {
+        let (packet, _) = self.recv_from()?;
+        Ok(packet)
+    }
+
+    /// local_addr returns the address the transport is bound to
+    /// None when it has no socket address (ie: unix sockets)
+    fn local_addr(&self) -> Option<SocketAddr> {
+        None
+    }
+
+    /// recv_on returns the next packet and the local address it was received on
+    fn recv_on(&self) -> Result<(Vec<u8>, Option<SocketAddr>), std::io::Error> {
+        let packet = self.recv()?;
+        Ok((packet, self.local_addr()))
+    }
+}
+
+/// bind_error adds the attempted address to the error returned when binding fails
+fn bind_error(addr: &str, e: std::io::Error) -> std::io::Error {
+    std::io::Error::new(e.kind(), format!("failed to bind to {}: {}", addr, e))
+}
+
+/// UdpTransport receives packets as sent by the games, one datagram per packet
+pub struct UdpTransport {
     srv: UdpSocket,
 }
 
-impl Server {
-    pub fn new(addr: &str) -> Result<Server, std::io::Error> {
+impl UdpTransport {
+    pub fn new(addr: &str) -> Result<UdpTransport, std::io::Error> {
         match UdpSocket::bind(addr) {
-            Ok(udp_server) => Ok(Server { srv: udp_server }),
-            Err(e) => Err(e),
+            Ok(udp_server) => Ok(UdpTransport { srv: udp_server }),
+            Err(e) => Err(bind_error(addr, e)),
         }
     }
+}
 
-    pub fn recv(&self) -> Result<Vec<u8>, std::io::Error> {
+impl Transport for UdpTransport {
+    fn recv_from(&self) -> Result<(Vec<u8>, Option<SocketAddr>), std::io::Error> {
         let mut buf = [0; MAX_PACKET_SIZE];
-        let (number, _) = self.srv.recv_from(&mut buf)?;
-        Ok(buf[..number].to_vec())
+        let (number, source) = self.srv.recv_from(&mut buf)?;
+        Ok((buf[..number].to_vec(), Some(source)))
+    }
+
+    fn local_addr(&self) -> Option<SocketAddr> {
+        self.srv.local_addr().ok()
+    }
+}
+
+// a received datagram: packet, source and local address
+type Datagram = Result<(Vec<u8>, SocketAddr, SocketAddr), std::io::Error>;
+
+/// MultiUdpTransport receives packets on several UDP sockets at once (ie: 20777 and 20778
+/// for two rigs), merged in the order they arrive; use recv_on to know the receiving socket.
+/// every socket is read by its own thread, which exits on the next datagram once dropped
+pub struct MultiUdpTransport {
+    local_addrs: Vec<SocketAddr>,
+    packets: Mutex<Receiver<Datagram>>,
+}
+
+impl MultiUdpTransport {
+    pub fn new(addrs: &[&str]) -> Result<MultiUdpTransport, std::io::Error> {
+        if addrs.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "no address to bind to",
+            ));
+        }
+
+        let mut sockets = Vec::with_capacity(addrs.len());
+        for addr in addrs {
+            sockets.push(UdpSocket::bind(addr).map_err(|e| bind_error(addr, e))?);
+        }
+
+        let mut local_addrs = Vec::with_capacity(sockets.len());
+        let (sender, packets) = channel();
+        for socket in sockets {
+            let local_addr = socket.local_addr()?;
+            local_addrs.push(local_addr);
+
+            let sender = sender.clone();
+            thread::spawn(move || loop {
+                let mut buf = [0; MAX_PACKET_SIZE];
+                let datagram = socket
+                    .recv_from(&mut buf)
+                    .map(|(number, source)| (buf[..number].to_vec(), source, local_addr));
+                if sender.send(datagram).is_err() {
+                    return; // the transport was dropped
+                }
+            });
+        }
+
+        Ok(MultiUdpTransport {
+            local_addrs,
+            packets: Mutex::new(packets),
+        })
+    }
+
+    /// local_addrs returns the addresses of every socket, in the order they were given
+    pub fn local_addrs(&self) -> &[SocketAddr] {
+        &self.local_addrs
+    }
+
+    fn next_datagram(&self) -> Datagram {
+        let packets = self.packets.lock().unwrap_or_else(|e| e.into_inner());
+        // the threads only stop once the receiver is dropped, so the channel stays connected
+        packets.recv().map_err(std::io::Error::other)?
+    }
+}
+
+impl Transport for MultiUdpTransport {
+    fn recv_from(&self) -> Result<(Vec<u8>, Option<SocketAddr>), std::io::Error> {
+        let (packet, source, _) = self.next_datagram()?;
+        Ok((packet, Some(source)))
+    }
+
+    /// local_addr is None as there are several, see local_addrs
+    fn local_addr(&self) -> Option<SocketAddr> {
+        None
+    }
+
+    fn recv_on(&self) -> Result<(Vec<u8>, Option<SocketAddr>), std::io::Error> {
+        let (packet, _, local_addr) = self.next_datagram()?;
+        Ok((packet, Some(local_addr)))
+    }
+}
+
+/// TcpTransport receives packets tunneled over TCP by a relay
+/// every packet is framed with its length as a big-endian u32,
+/// when a relay disconnects the next recv waits for a new connection
+pub struct TcpTransport {
+    listener: TcpListener,
+    stream: Mutex<Option<(TcpStream, SocketAddr)>>,
+}
+
+impl TcpTransport {
+    pub fn new(addr: &str) -> Result<TcpTransport, std::io::Error> {
+        Ok(TcpTransport {
+            listener: TcpListener::bind(addr).map_err(|e| bind_error(addr, e))?,
+            stream: Mutex::new(None),
+        })
+    }
+}
+
+impl Transport for TcpTransport {
+    fn recv_from(&self) -> Result<(Vec<u8>, Option<SocketAddr>), std::io::Error> {
+        let mut stream = self.stream.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if stream.is_none() {
+                *stream = Some(self.listener.accept()?);
+            }
+
+            let (reader, peer) = stream.as_mut().unwrap();
+            match read_frame(reader) {
+                Ok(packet) => return Ok((packet, Some(*peer))),
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => *stream = None,
+                Err(e) => {
+                    *stream = None;
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr().ok()
+    }
+}
+
+/// TlsTransport receives packets from a relay over TCP wrapped in TLS
+/// framing is the same as TcpTransport, the given rustls config decides
+/// which certificate is presented and whether relays must authenticate with a client certificate
+#[cfg(feature = "tls")]
+pub struct TlsTransport {
+    listener: TcpListener,
+    config: Arc<ServerConfig>,
+    stream: Mutex<Option<(StreamOwned<ServerConnection, TcpStream>, SocketAddr)>>,
+}
+
+#[cfg(feature = "tls")]
+impl TlsTransport {
+    pub fn new(addr: &str, config: Arc<ServerConfig>) -> Result<TlsTransport, std::io::Error> {
+        Ok(TlsTransport {
+            listener: TcpListener::bind(addr).map_err(|e| bind_error(addr, e))?,
+            config,
+            stream: Mutex::new(None),
+        })
+    }
+}
+
+#[cfg(feature = "tls")]
+impl Transport for TlsTransport {
+    fn recv_from(&self) -> Result<(Vec<u8>, Option<SocketAddr>), std::io::Error> {
+        let mut stream = self.stream.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if stream.is_none() {
+                let (accepted, peer) = self.listener.accept()?;
+                let connection =
+                    ServerConnection::new(self.config.clone()).map_err(std::io::Error::other)?;
+                *stream = Some((StreamOwned::new(connection, accepted), peer));
+            }
+
+            let (reader, peer) = stream.as_mut().unwrap();
+            match read_frame(reader) {
+                Ok(packet) => return Ok((packet, Some(*peer))),
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => *stream = None,
+                Err(e) => {
+                    *stream = None;
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr().ok()
+    }
+}
+
+fn read_frame<R: Read>(reader: &mut R) -> Result<Vec<u8>, std::io::Error> {
+    let mut length = [0; 4];
+    reader.read_exact(&mut length)?;
+
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_PACKET_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Frame of {} bytes exceeds the maximum packet size", length),
+        ));
+    }
+
+    let mut buf = vec![0; length];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// UnixTransport receives packets forwarded over a local unix datagram socket
+#[cfg(unix)]
+pub struct UnixTransport {
+    srv: UnixDatagram,
+}
+
+#[cfg(unix)]
+impl UnixTransport {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<UnixTransport, std::io::Error> {
+        Ok(UnixTransport {
+            srv: UnixDatagram::bind(&path)
+                .map_err(|e| bind_error(&path.as_ref().display().to_string(), e))?,
+        })
+    }
+}
+
+#[cfg(unix)]
+impl Transport for UnixTransport {
+    fn recv_from(&self) -> Result<(Vec<u8>, Option<SocketAddr>), std::io::Error> {
+        let mut buf = [0; MAX_PACKET_SIZE];
+        let number = self.srv.recv(&mut buf)?;
+        Ok((buf[..number].to_vec(), None))
     }
 }
 
 #[cfg(feature = "async")]
-pub struct AsyncServer {
+pub(crate) struct AsyncServer {
     srv: AsyncUdpSocket,
 }
 
@@ -34,10 +297,14 @@ impl AsyncServer {
     pub async fn new(addr: &str) -> Result<AsyncServer, std::io::Error> {
         match AsyncUdpSocket::bind(addr).await {
             Ok(udp_server) => Ok(AsyncServer { srv: udp_server }),
-            Err(e) => Err(e),
+            Err(e) => Err(bind_error(addr, e)),
         }
     }
 
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.srv.local_addr().ok()
+    }
+
     pub async fn recv(&self) -> Result<Vec<u8>, std::io::Error> {
         let mut buf = [0; MAX_PACKET_SIZE];
         let (number, _) = self.srv.recv_from(&mut buf).await?;
@@ -0,0 +1,378 @@
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::convert::TryFrom;
+use core::error::Error;
+
+use crate::{
+    f1::util::{read_packet, Coordinates},
+    Endianness, TelemetryEvent, TelemetryPacket,
+};
+
+use binread::BinRead;
+use num_enum::TryFromPrimitive;
+
+/// ProjectCars2 implements the UDP telemetry protocol of "Project CARS 2" ("Patch 5" format),
+/// also sent by "Automobilista 2". Packet types 0 to 4 are decoded, TimeStats (7) and
+/// ParticipantVehicleNames / VehicleClassNames (8, told apart by their size) are sent as well but
+/// not decoded yet: they are errors, like WeatherState (5) and VehicleNames (6) that are not sent
+/// See: SMS_UDP_Definitions.hpp shipped with the game
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[allow(clippy::large_enum_variant)] // CarPhysics is most of the traffic, not worth boxing
+pub enum ProjectCars2 {
+    CarPhysics(CarPhysics),
+    RaceDefinition(RaceDefinition),
+    Participants(Participants),
+    Timings(Timings),
+    GameState(GameState),
+}
+
+impl ProjectCars2 {
+    pub fn header(&self) -> &Header {
+        match self {
+            ProjectCars2::CarPhysics(data) => &data.header,
+            ProjectCars2::RaceDefinition(data) => &data.header,
+            ProjectCars2::Participants(data) => &data.header,
+            ProjectCars2::Timings(data) => &data.header,
+            ProjectCars2::GameState(data) => &data.header,
+        }
+    }
+}
+
+#[derive(Debug, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Header {
+    pub packet_number: u32, // Counter reflecting all the packets that have been sent
+    pub category_packet_number: u32, // Counter of the packets of this packet_type
+    pub partial_packet_index: u8, // Index of the part of a packet split in several (from 1)
+    pub partial_packet_number: u8, // Number of parts of a packet split in several
+    pub packet_type: u8,    // Identifier for the packet type, see ProjectCars2
+    pub packet_version: u8, // Version of this packet type
+}
+
+/// Tyres are values for each wheel, in the order of the game (fronts first)
+#[derive(Debug, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Tyres<T: BinRead<Args = ()>> {
+    pub front_left: T,
+    pub front_right: T,
+    pub rear_left: T,
+    pub rear_right: T,
+}
+
+// CAR PHYSICS
+// the controller state and what follows it are not decoded
+#[derive(Debug, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CarPhysics {
+    pub header: Header,
+
+    pub viewed_participant_index: i8, // Index of the participant on screen, -1 if none
+    pub unfiltered_throttle: u8,      // 0..255
+    pub unfiltered_brake: u8,         // 0..255
+    pub unfiltered_steering: i8,      // -127..127
+    pub unfiltered_clutch: u8,        // 0..255
+
+    pub car_flags: u8, // Headlight, engine on, engine warning, speed limiter, abs, handbrake
+    pub oil_temperature: i16, // Celsius
+    pub oil_pressure: u16, // KPa
+    pub water_temperature: i16, // Celsius
+    pub water_pressure: u16, // KPa
+    pub fuel_pressure: u16, // KPa
+    pub fuel_capacity: u8, // Litres
+    pub brake: u8,     // 0..255
+    pub throttle: u8,  // 0..255
+    pub clutch: u8,    // 0..255
+    pub fuel_level: f32, // 0..1 of fuel_capacity
+    pub speed: f32,    // Metres per second
+    pub rpm: u16,
+    pub max_rpm: u16,
+    pub steering: i8,       // -127..127
+    pub gear_num_gears: u8, // Gear in the low 4 bits (15 = reverse), number of gears in the high 4
+    pub boost_amount: u8,   // 0..100
+    pub crash_state: u8,    // 0 = none, 1 = off track, 2 = large prop, 3 = spinning, 4 = rolling
+    pub odometer: f32,      // Kilometres
+
+    pub orientation: Coordinates<f32>,      // Euler angles, radians
+    pub local_velocity: Coordinates<f32>,   // Metres per second
+    pub world_velocity: Coordinates<f32>,   // Metres per second
+    pub angular_velocity: Coordinates<f32>, // Radians per second
+    pub local_acceleration: Coordinates<f32>, // Metres per second squared
+    pub world_acceleration: Coordinates<f32>, // Metres per second squared
+    pub extents_centre: Coordinates<f32>,   // Metres
+
+    pub tyre_flags: Tyres<u8>,       // Attached, inflated, is on ground
+    pub terrain: Tyres<u8>,          // Material under each tyre
+    pub tyre_y: Tyres<f32>,          // Metres
+    pub tyre_rps: Tyres<f32>,        // Revolutions per second
+    pub tyre_temperature: Tyres<u8>, // Celsius
+    pub tyre_height_above_ground: Tyres<f32>, // Metres
+    pub tyre_wear: Tyres<u8>,        // 0..255
+    pub brake_damage: Tyres<u8>,     // 0..255
+    pub suspension_damage: Tyres<u8>, // 0..255
+    pub brake_temperature: Tyres<i16>, // Celsius
+    pub tyre_tread_temperature: Tyres<u16>, // Kelvin
+    pub tyre_layer_temperature: Tyres<u16>, // Kelvin
+    pub tyre_carcass_temperature: Tyres<u16>, // Kelvin
+    pub tyre_rim_temperature: Tyres<u16>, // Kelvin
+    pub tyre_internal_air_temperature: Tyres<u16>, // Kelvin
+    pub tyre_temperature_left: Tyres<u16>, // Kelvin
+    pub tyre_temperature_center: Tyres<u16>, // Kelvin
+    pub tyre_temperature_right: Tyres<u16>, // Kelvin
+    pub wheel_local_position_y: Tyres<f32>, // Metres
+    pub ride_height: Tyres<f32>,     // Centimetres
+    pub suspension_travel: Tyres<f32>, // Metres
+    pub suspension_velocity: Tyres<f32>, // Metres per second
+    pub suspension_ride_height: Tyres<u16>, // Millimetres
+    pub air_pressure: Tyres<u16>,    // Bar * 100
+
+    pub engine_speed: f32,  // Radians per second
+    pub engine_torque: f32, // Newton metres
+    pub wings: [u8; 2],     // Front and rear, 0..255
+    pub handbrake: u8,      // 0..255
+
+    pub aero_damage: u8,   // 0..255
+    pub engine_damage: u8, // 0..255
+}
+
+impl CarPhysics {
+    /// gear returns the current gear, -1 for reverse and 0 for neutral
+    pub fn gear(&self) -> i8 {
+        match self.gear_num_gears & 0x0f {
+            15 => -1,
+            gear => gear as i8,
+        }
+    }
+
+    /// num_gears returns the number of forward gears of the car
+    pub fn num_gears(&self) -> u8 {
+        self.gear_num_gears >> 4
+    }
+}
+
+// RACE DEFINITION
+#[derive(Debug, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RaceDefinition {
+    pub header: Header,
+
+    pub world_fastest_lap_time: f32,             // Seconds
+    pub personal_fastest_lap_time: f32,          // Seconds
+    pub personal_fastest_sector_times: [f32; 3], // Seconds
+    pub world_fastest_sector_times: [f32; 3],    // Seconds
+
+    pub track_length: f32, // Metres
+    #[br(parse_with = string_parser, args(64))]
+    pub track_location: String,
+    #[br(parse_with = string_parser, args(64))]
+    pub track_variation: String,
+    #[br(parse_with = string_parser, args(64))]
+    pub translated_track_location: String,
+    #[br(parse_with = string_parser, args(64))]
+    pub translated_track_variation: String,
+
+    pub laps_time_in_event: u16, // Laps, or minutes when the top bit is set (timed event)
+    pub enforced_pit_stop_lap: i8, // -1 when there is none
+}
+
+impl RaceDefinition {
+    /// is_timed returns true if the event lasts a duration instead of a number of laps
+    pub fn is_timed(&self) -> bool {
+        self.laps_time_in_event & 0x8000 != 0
+    }
+
+    /// laps returns the number of laps of the event, None for timed events
+    pub fn laps(&self) -> Option<u16> {
+        Some(self.laps_time_in_event).filter(|_| !self.is_timed())
+    }
+
+    /// duration returns the duration of timed events in minutes, None for the others
+    pub fn duration(&self) -> Option<u16> {
+        Some(self.laps_time_in_event & 0x7fff).filter(|_| self.is_timed())
+    }
+}
+
+// PARTICIPANTS
+#[derive(Debug, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Participants {
+    pub header: Header, // 32 participants are sent as 2 parts of 16, see partial_packet_index
+
+    pub participants_changed_timestamp: u32,
+    #[br(count = 16, parse_with = names_parser)]
+    pub names: Vec<String>,
+    pub nationalities: [u32; 16],
+    pub indices: [u16; 16], // Index of each participant in the Timings packet
+}
+
+// TIMINGS
+#[derive(Debug, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Timings {
+    pub header: Header,
+
+    pub num_participants: i8,
+    pub participants_changed_timestamp: u32,
+    pub event_time_remaining: f32, // Seconds
+    pub split_time_ahead: f32,     // Seconds
+    pub split_time_behind: f32,    // Seconds
+    pub split_time: f32,           // Seconds
+    #[br(count = 32)]
+    pub participants: Vec<ParticipantInfo>,
+    pub local_participant_index: u16,
+    pub tick_count: u32,
+}
+
+impl Timings {
+    /// active_participants returns the entries of the participants on track
+    pub fn active_participants(&self) -> impl Iterator<Item = &ParticipantInfo> {
+        self.participants
+            .iter()
+            .take(self.num_participants.max(0) as usize)
+            .filter(|participant| participant.is_active())
+    }
+}
+
+#[derive(Debug, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParticipantInfo {
+    pub world_position: Coordinates<i16>, // Metres
+    pub orientation: Coordinates<i16>,    // Quantized euler angles
+    pub current_lap_distance: u16,        // Metres
+    pub race_position: u8,                // Top bit set when the participant is active
+    pub sector: u8,
+    pub highest_flag: u8,
+    pub pit_mode_schedule: u8,
+    pub car_index: u16, // Top bit set when the participant is human
+    pub race_state: u8,
+    pub current_lap: u8,
+    pub current_time: f32,        // Seconds
+    pub current_sector_time: f32, // Seconds
+    pub mp_participant_index: u16,
+}
+
+impl ParticipantInfo {
+    /// is_active returns true if the participant is on track
+    pub fn is_active(&self) -> bool {
+        self.race_position & 0x80 != 0
+    }
+
+    /// position returns the race position of the participant, starting at 1
+    pub fn position(&self) -> u8 {
+        self.race_position & 0x7f
+    }
+
+    /// is_human returns true if the participant is a human player
+    pub fn is_human(&self) -> bool {
+        self.car_index & 0x8000 != 0
+    }
+}
+
+// GAME STATE
+#[derive(Debug, Default, BinRead)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GameState {
+    pub header: Header,
+
+    pub build_version_number: u16,
+    pub game_session_state: u8, // Game state in the low 3 bits, session state in the next 3
+    pub ambient_temperature: i8, // Celsius
+    pub track_temperature: i8,  // Celsius
+    pub rain_density: u8,       // 0..255
+    pub snow_density: u8,       // 0..255
+    pub wind_speed: i8,
+    pub wind_direction_x: i8,
+    pub wind_direction_y: i8,
+}
+
+impl GameState {
+    pub fn game_state(&self) -> Game {
+        Game::try_from(self.game_session_state & 0x07).unwrap_or_default()
+    }
+
+    pub fn session_state(&self) -> SessionState {
+        SessionState::try_from((self.game_session_state >> 3) & 0x07).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[repr(u8)]
+pub enum Game {
+    #[default]
+    Exited,
+    FrontEnd,
+    Playing,
+    Paused,
+    InMenuTimeTicking,
+    Restarting,
+    Replay,
+    FrontEndReplay,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[repr(u8)]
+pub enum SessionState {
+    #[default]
+    Invalid,
+    Practice,
+    Test,
+    Qualify,
+    FormationLap,
+    Race,
+    TimeAttack,
+}
+
+fn string_parser<R: binread::io::Read + binread::io::Seek>(
+    reader: &mut R,
+    _: &binread::ReadOptions,
+    (size,): (usize,),
+) -> binread::BinResult<String> {
+    let mut bytes = [0; 64];
+    let bytes = &mut bytes[..size];
+    reader.read_exact(bytes)?;
+
+    // strings are null terminated, what follows the terminator is garbage
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(size);
+    Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+fn names_parser<R: binread::io::Read + binread::io::Seek>(
+    reader: &mut R,
+    options: &binread::ReadOptions,
+    _: (),
+) -> binread::BinResult<Vec<String>> {
+    let count = options.count.unwrap_or(0);
+    let mut names = Vec::with_capacity(count);
+    for _ in 0..count {
+        names.push(string_parser(reader, options, (64,))?);
+    }
+    Ok(names)
+}
+
+// PARSING
+impl TelemetryEvent for ProjectCars2 {
+    fn from_packet(packet: &TelemetryPacket) -> Result<ProjectCars2, Box<dyn Error>> {
+        Self::from_packet_with_endianness(packet, Endianness::Little)
+    }
+
+    fn from_packet_with_endianness(
+        packet: &TelemetryPacket,
+        endianness: Endianness,
+    ) -> Result<ProjectCars2, Box<dyn Error>> {
+        if packet.len() < 12 {
+            return Err(Box::from("Packet is too small to contain a header"));
+        }
+
+        let packet_type = packet[10]; // packet_type
+        let endian = binread::Endian::from(endianness);
+        match packet_type {
+            0 => Ok(ProjectCars2::CarPhysics(read_packet(packet, endian)?)),
+            1 => Ok(ProjectCars2::RaceDefinition(read_packet(packet, endian)?)),
+            2 => Ok(ProjectCars2::Participants(read_packet(packet, endian)?)),
+            3 => Ok(ProjectCars2::Timings(read_packet(packet, endian)?)),
+            4 => Ok(ProjectCars2::GameState(read_packet(packet, endian)?)),
+            id => Err(Box::from(format!("Unknown packet type: {}", id))),
+        }
+    }
+}
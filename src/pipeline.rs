@@ -0,0 +1,124 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::TelemetryFrame;
+
+/// DEFAULT_TICK_INTERVAL is how often (session seconds) processors are ticked
+pub const DEFAULT_TICK_INTERVAL: f32 = 1.0;
+
+/// TelemetryProcessor is implemented by extensions (analysis, exporters...) that plug into a
+/// Pipeline, so they can be shipped by other crates and run along the processors of this one
+pub trait TelemetryProcessor<T> {
+    /// on_event is called for every event, in the order received
+    fn on_event(&mut self, event: &T);
+
+    /// on_tick is called once every tick interval of session time, after on_event
+    fn on_tick(&mut self, _session_time: f32) {}
+
+    /// on_session_end is called when an event of another session arrives (before its on_event)
+    /// and when the pipeline is finished
+    fn on_session_end(&mut self, _session_uid: u64) {}
+}
+
+/// Pipeline feeds every event to the registered processors, in registration order,
+/// and keeps track of the session and ticks they are notified of
+pub struct Pipeline<T> {
+    processors: Vec<Box<dyn TelemetryProcessor<T>>>,
+    tick_interval: f32,
+    session_uid: Option<u64>,
+    last_tick: Option<f32>, // session_time of the last tick
+}
+
+impl<T> Default for Pipeline<T> {
+    fn default() -> Self {
+        Pipeline {
+            processors: Vec::new(),
+            tick_interval: DEFAULT_TICK_INTERVAL,
+            session_uid: None,
+            last_tick: None,
+        }
+    }
+}
+
+impl<T: TelemetryFrame> Pipeline<T> {
+    pub fn new() -> Pipeline<T> {
+        Pipeline::default()
+    }
+
+    /// with_tick_interval sets how often (session seconds) processors are ticked
+    pub fn with_tick_interval(mut self, interval: f32) -> Self {
+        self.tick_interval = interval;
+        self
+    }
+
+    /// register adds a processor, it receives the events processed from now on
+    pub fn register<P: TelemetryProcessor<T> + 'static>(&mut self, processor: P) -> &mut Self {
+        self.processors.push(Box::new(processor));
+        self
+    }
+
+    /// len returns the number of registered processors
+    pub fn len(&self) -> usize {
+        self.processors.len()
+    }
+
+    /// is_empty returns true when no processor is registered
+    pub fn is_empty(&self) -> bool {
+        self.processors.is_empty()
+    }
+
+    /// process feeds an event to every processor, ending the previous session first
+    /// when the event belongs to another one
+    pub fn process(&mut self, event: &T) {
+        let session_uid = event.session_uid();
+        if self.session_uid != Some(session_uid) {
+            self.finish();
+            self.session_uid = Some(session_uid);
+        }
+
+        for processor in self.processors.iter_mut() {
+            processor.on_event(event);
+        }
+
+        let session_time = event.session_time();
+        let due = match self.last_tick {
+            Some(last) => session_time - last >= self.tick_interval || session_time < last,
+            None => true,
+        };
+        if due {
+            self.last_tick = Some(session_time);
+            for processor in self.processors.iter_mut() {
+                processor.on_tick(session_time);
+            }
+        }
+    }
+
+    /// finish ends the current session (if any), ie: when the recording or the server is done
+    pub fn finish(&mut self) {
+        if let Some(session_uid) = self.session_uid.take() {
+            for processor in self.processors.iter_mut() {
+                processor.on_session_end(session_uid);
+            }
+        }
+        self.last_tick = None;
+    }
+}
+
+#[cfg(feature = "net")]
+impl<T: crate::TelemetryEvent + TelemetryFrame> crate::TelemetryServer<T> {
+    /// run feeds every packet received to the pipeline, packets that do not decode are skipped.
+    /// It only returns on a transport error, after finishing the pipeline
+    pub fn run(&self, pipeline: &mut Pipeline<T>) -> Result<(), Box<dyn core::error::Error>> {
+        loop {
+            let packet = match self.srv.recv() {
+                Ok(packet) => packet,
+                Err(e) => {
+                    pipeline.finish();
+                    return Err(Box::new(e));
+                }
+            };
+            if let Ok(event) = T::from_packet_with_endianness(&packet, self.endianness) {
+                pipeline.process(&event);
+            }
+        }
+    }
+}
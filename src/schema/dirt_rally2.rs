@@ -0,0 +1,86 @@
+use super::{field, nested, FieldSchema, PacketSchema};
+
+const COORDINATE_METRES: [FieldSchema; 3] = [
+    field("0", "f32", "m"),
+    field("1", "f32", "m"),
+    field("2", "f32", "m"),
+];
+
+const COORDINATE_METRES_PER_SECOND: [FieldSchema; 3] = [
+    field("0", "f32", "m/s"),
+    field("1", "f32", "m/s"),
+    field("2", "f32", "m/s"),
+];
+
+const COORDINATE: [FieldSchema; 3] = [
+    field("0", "f32", ""),
+    field("1", "f32", ""),
+    field("2", "f32", ""),
+];
+
+const WHEEL: [FieldSchema; 4] = [
+    field("suspension_position", "f32", "mm"),
+    field("suspension_velocity", "f32", "mm/s"),
+    field("wheel_velocity", "f32", "m/s"),
+    field("brake_temperature", "f32", "C"),
+];
+
+const WHEELS: [FieldSchema; 4] = [
+    nested("rear_left", "Wheel", &WHEEL),
+    nested("rear_right", "Wheel", &WHEEL),
+    nested("front_left", "Wheel", &WHEEL),
+    nested("front_right", "Wheel", &WHEEL),
+];
+
+const CAR: [FieldSchema; 8] = [
+    field("speed", "f32", "m/s"),
+    field("gear", "Gear", ""),
+    nested("wheels", "Wheels", &WHEELS),
+    field("throttle", "f32", ""),
+    field("steer", "f32", ""),
+    field("brake", "f32", ""),
+    field("clutch", "f32", ""),
+    field("rpms", "f32", "rpm/10"),
+];
+
+const TRACK: [FieldSchema; 3] = [
+    field("time", "f32", "s"),
+    field("distance", "f32", "m"),
+    field("length", "f32", "m"),
+];
+
+const LAP: [FieldSchema; 5] = [
+    field("current_lap", "f32", ""),
+    field("total_laps", "f32", ""),
+    field("last_lap_time", "f32", "s"),
+    field("current_lap_time", "f32", "s"),
+    field("current_lap_distance", "f32", "m"),
+];
+
+const SESSION: [FieldSchema; 4] = [
+    field("position", "f32", ""),
+    nested("location", "(f32, f32, f32)", &COORDINATE_METRES),
+    nested("track", "Track", &TRACK),
+    nested("lap_info", "Lap", &LAP),
+];
+
+const MOTION: [FieldSchema; 5] = [
+    nested("velocity", "(f32, f32, f32)", &COORDINATE_METRES_PER_SECOND),
+    nested("roll_vector", "(f32, f32, f32)", &COORDINATE),
+    nested("pitch_vector", "(f32, f32, f32)", &COORDINATE),
+    field("g_force_lateral", "f32", "G"),
+    field("g_force_longitudinal", "f32", "G"),
+];
+
+const DIRT_RALLY_2: [FieldSchema; 3] = [
+    nested("car", "Car", &CAR),
+    nested("session", "Session", &SESSION),
+    nested("motion", "Motion", &MOTION),
+];
+
+// the game sends a single packet type, without a header
+pub(super) const PACKETS: [PacketSchema; 1] = [PacketSchema {
+    id: 0,
+    name: "DirtRally2",
+    fields: &DIRT_RALLY_2,
+}];
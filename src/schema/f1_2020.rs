@@ -0,0 +1,454 @@
+use super::{array, field, nested, nested_array, FieldSchema, PacketSchema};
+
+const HEADER: [FieldSchema; 10] = [
+    field("packet_format", "u16", ""),
+    field("game_major_version", "u8", ""),
+    field("game_minor_version", "u8", ""),
+    field("packet_version", "u8", ""),
+    field("packet_id", "u8", ""),
+    field("session_uid", "u64", ""),
+    field("session_time", "f32", ""),
+    field("frame_identifier", "u32", ""),
+    field("player_car_index", "CarIndex", ""),
+    field("secondary_player_car_index", "CarIndex", ""),
+];
+
+const COORDINATES_F32_METRES: [FieldSchema; 3] = [
+    field("x", "f32", "m"),
+    field("y", "f32", "m"),
+    field("z", "f32", "m"),
+];
+
+const COORDINATES_F32_METRES_PER_SECOND: [FieldSchema; 3] = [
+    field("x", "f32", "m/s"),
+    field("y", "f32", "m/s"),
+    field("z", "f32", "m/s"),
+];
+
+const COORDINATES_I16: [FieldSchema; 3] = [
+    field("x", "i16", ""),
+    field("y", "i16", ""),
+    field("z", "i16", ""),
+];
+
+const CAR_MOTION_DATA: [FieldSchema; 10] = [
+    nested(
+        "world_position",
+        "Coordinates<f32>",
+        &COORDINATES_F32_METRES,
+    ),
+    nested(
+        "world_velocity",
+        "Coordinates<f32>",
+        &COORDINATES_F32_METRES_PER_SECOND,
+    ),
+    nested("world_forward_dir", "Coordinates<i16>", &COORDINATES_I16),
+    nested("world_right_dir", "Coordinates<i16>", &COORDINATES_I16),
+    field("g_force_lateral", "f32", "G"),
+    field("g_force_longitudinal", "f32", "G"),
+    field("g_force_vertical", "f32", "G"),
+    field("yaw", "f32", "rad"),
+    field("pitch", "f32", "rad"),
+    field("roll", "f32", "rad"),
+];
+
+const WHEEL_VALUE_F32: [FieldSchema; 4] = [
+    field("rear_left", "f32", ""),
+    field("rear_right", "f32", ""),
+    field("front_left", "f32", ""),
+    field("front_right", "f32", ""),
+];
+
+const WHEEL_VALUE_F32_METRES_PER_SECOND: [FieldSchema; 4] = [
+    field("rear_left", "f32", "m/s"),
+    field("rear_right", "f32", "m/s"),
+    field("front_left", "f32", "m/s"),
+    field("front_right", "f32", "m/s"),
+];
+
+const COORDINATES_F32_RADIANS_PER_SECOND: [FieldSchema; 3] = [
+    field("x", "f32", "rad/s"),
+    field("y", "f32", "rad/s"),
+    field("z", "f32", "rad/s"),
+];
+
+const COORDINATES_F32_RADIANS_PER_SECOND2: [FieldSchema; 3] = [
+    field("x", "f32", "rad/s2"),
+    field("y", "f32", "rad/s2"),
+    field("z", "f32", "rad/s2"),
+];
+
+const MOTION: [FieldSchema; 11] = [
+    nested("header", "Header", &HEADER),
+    nested_array(
+        "car_motion_data",
+        "[CarMotionData; 22]",
+        22,
+        &CAR_MOTION_DATA,
+    ),
+    nested("suspension_position", "WheelValue<f32>", &WHEEL_VALUE_F32),
+    nested("suspension_velocity", "WheelValue<f32>", &WHEEL_VALUE_F32),
+    nested(
+        "suspension_acceleration",
+        "WheelValue<f32>",
+        &WHEEL_VALUE_F32,
+    ),
+    nested(
+        "wheel_speed",
+        "WheelValue<f32>",
+        &WHEEL_VALUE_F32_METRES_PER_SECOND,
+    ),
+    nested("wheel_slip", "WheelValue<f32>", &WHEEL_VALUE_F32),
+    nested(
+        "local_velocity",
+        "Coordinates<f32>",
+        &COORDINATES_F32_METRES_PER_SECOND,
+    ),
+    nested(
+        "angular_velocity",
+        "Coordinates<f32>",
+        &COORDINATES_F32_RADIANS_PER_SECOND,
+    ),
+    nested(
+        "angular_acceleration",
+        "Coordinates<f32>",
+        &COORDINATES_F32_RADIANS_PER_SECOND2,
+    ),
+    field("front_wheel_angle", "f32", "rad"),
+];
+
+const SESSION: [FieldSchema; 22] = [
+    nested("header", "Header", &HEADER),
+    field("weather", "Weather", ""),
+    field("track_temperature", "i8", "C"),
+    field("air_temperature", "i8", "C"),
+    field("total_laps", "i8", ""),
+    field("track_length", "i16", "m"),
+    field("session_type", "SessionType", ""),
+    field("track", "Track", ""),
+    field("formula", "Formula", ""),
+    field("session_time_left", "u16", "s"),
+    field("session_duration", "u16", "s"),
+    field("pit_speed_limit", "u8", "km/h"),
+    field("game_paused", "u8", ""),
+    field("is_spectating", "u8", ""),
+    field("spectator_car_index", "CarIndex", ""),
+    field("sli_pro_native_support", "u8", ""),
+    field("number_of_marshal_zones", "u8", ""),
+    field("marshal_zones", "[MarshalZone; 21]", ""),
+    field("safety_car_status", "SafetyCarStatus", ""),
+    field("network_game", "bool", ""),
+    field("number_of_weather_forecast_samples", "u8", ""),
+    field(
+        "weather_forecast_samples",
+        "[WeatherForecastSample; 20]",
+        "",
+    ),
+];
+
+const LAP: [FieldSchema; 19] = [
+    field("last_lap_time", "f32", ""),
+    field("current_lap_time", "f32", ""),
+    field("sector_time_ms", "(u16, u16)", "ms"),
+    field("best_lap_time", "f32", ""),
+    field("best_lap_number", "u8", ""),
+    field("best_lap_sector_time", "(u16, u16, u16)", ""),
+    field(
+        "best_overall_sector_time",
+        "(BestOverallSectorTime, BestOverallSectorTime, BestOverallSectorTime)",
+        "",
+    ),
+    field("lap_distance", "f32", "m"),
+    field("total_distance", "f32", "m"),
+    field("safety_car_delta", "f32", "s"),
+    field("car_position", "u8", ""),
+    field("current_lap_number", "u8", ""),
+    field("pit_status", "PitStatus", ""),
+    field("sector", "Sector", ""),
+    field("current_lap_invalid", "bool", ""),
+    field("penalties", "u8", "s"),
+    field("grid_position", "u8", ""),
+    field("driver_status", "DriverStatus", ""),
+    field("result_status", "ResultStatus", ""),
+];
+
+const LAP_DATA: [FieldSchema; 2] = [
+    nested("header", "Header", &HEADER),
+    nested_array("laps", "[Lap; 22]", 22, &LAP),
+];
+
+const EVENT: [FieldSchema; 2] = [
+    nested("header", "Header", &HEADER),
+    field("event_data_details", "EventDataDetail", ""),
+];
+
+const PARTICIPANTS_DATA: [FieldSchema; 7] = [
+    field("ai_controlled", "bool", ""),
+    field("driver", "Driver", ""),
+    field("team", "Team", ""),
+    field("race_number", "u8", ""),
+    field("nationality", "Nationality", ""),
+    field("name", "String", ""),
+    field("your_telemetry_restricted", "bool", ""),
+];
+
+const PARTICIPANTS: [FieldSchema; 3] = [
+    nested("header", "Header", &HEADER),
+    field("num_active_cars", "u8", ""),
+    nested_array(
+        "participants_data",
+        "[ParticipantsData; 22]",
+        22,
+        &PARTICIPANTS_DATA,
+    ),
+];
+
+const FRONT_REAR_VALUE_U8: [FieldSchema; 2] = [field("front", "u8", ""), field("rear", "u8", "")];
+
+const FRONT_REAR_VALUE_F32: [FieldSchema; 2] =
+    [field("front", "f32", ""), field("rear", "f32", "")];
+
+const WHEEL_VALUE_F32_PSI: [FieldSchema; 4] = [
+    field("rear_left", "f32", "psi"),
+    field("rear_right", "f32", "psi"),
+    field("front_left", "f32", "psi"),
+    field("front_right", "f32", "psi"),
+];
+
+const CAR_SETUP_DATA: [FieldSchema; 13] = [
+    nested("wing", "FrontRearValue<u8>", &FRONT_REAR_VALUE_U8),
+    field("on_throttle", "u8", "%"),
+    field("off_throttle", "u8", "%"),
+    nested("camber", "FrontRearValue<f32>", &FRONT_REAR_VALUE_F32),
+    nested("toe", "FrontRearValue<f32>", &FRONT_REAR_VALUE_F32),
+    nested("suspension", "FrontRearValue<u8>", &FRONT_REAR_VALUE_U8),
+    nested("anti_roll_bar", "FrontRearValue<u8>", &FRONT_REAR_VALUE_U8),
+    nested(
+        "suspension_height",
+        "FrontRearValue<u8>",
+        &FRONT_REAR_VALUE_U8,
+    ),
+    field("brake_pressure", "u8", "%"),
+    field("brake_bias", "u8", "%"),
+    nested("type_pressure", "WheelValue<f32>", &WHEEL_VALUE_F32_PSI),
+    field("ballast", "u8", ""),
+    field("fuel_load", "f32", "kg"),
+];
+
+const CAR_SETUP: [FieldSchema; 2] = [
+    nested("header", "Header", &HEADER),
+    nested_array("car_setup_data", "[CarSetupData; 22]", 22, &CAR_SETUP_DATA),
+];
+
+const WHEEL_VALUE_U16_CELSIUS: [FieldSchema; 4] = [
+    field("rear_left", "u16", "C"),
+    field("rear_right", "u16", "C"),
+    field("front_left", "u16", "C"),
+    field("front_right", "u16", "C"),
+];
+
+const WHEEL_VALUE_U8_CELSIUS: [FieldSchema; 4] = [
+    field("rear_left", "u8", "C"),
+    field("rear_right", "u8", "C"),
+    field("front_left", "u8", "C"),
+    field("front_right", "u8", "C"),
+];
+
+const WHEEL_VALUE_SURFACE: [FieldSchema; 4] = [
+    field("rear_left", "Surface", ""),
+    field("rear_right", "Surface", ""),
+    field("front_left", "Surface", ""),
+    field("front_right", "Surface", ""),
+];
+
+const CAR_TELEMETRY_DATA: [FieldSchema; 15] = [
+    field("speed", "u16", "km/h"),
+    field("throttle", "f32", ""),
+    field("steer", "f32", ""),
+    field("brake", "f32", ""),
+    field("clutch", "u8", ""),
+    field("gear", "Gear", ""),
+    field("engine_rpm", "u16", "rpm"),
+    field("drs", "bool", ""),
+    field("rev_lights_percent", "u8", "%"),
+    nested("brake_temp", "WheelValue<u16>", &WHEEL_VALUE_U16_CELSIUS),
+    nested(
+        "tyres_surface_temp",
+        "WheelValue<u8>",
+        &WHEEL_VALUE_U8_CELSIUS,
+    ),
+    nested(
+        "tyres_inner_temp",
+        "WheelValue<u8>",
+        &WHEEL_VALUE_U8_CELSIUS,
+    ),
+    field("engine_temp", "u16", "C"),
+    nested("tyres_pressure", "WheelValue<f32>", &WHEEL_VALUE_F32_PSI),
+    nested("surface_type", "WheelValue<Surface>", &WHEEL_VALUE_SURFACE),
+];
+
+const CAR_TELEMETRY: [FieldSchema; 6] = [
+    nested("header", "Header", &HEADER),
+    nested_array(
+        "car_telemetry_data",
+        "[CarTelemetryData; 22]",
+        22,
+        &CAR_TELEMETRY_DATA,
+    ),
+    field("button_status", "ButtonFlags", ""),
+    field("mfd_panel", "MFDPanel", ""),
+    field("mfd_panel_secondary_player", "MFDPanel", ""),
+    field("suggested_gear", "Gear", ""),
+];
+
+const ERS: [FieldSchema; 5] = [
+    field("stored_energy", "f32", "J"),
+    field("deploy_mode", "ERSDeployMode", ""),
+    field("harvested_this_lap_mguk", "f32", "J"),
+    field("harvested_this_lap_mguh", "f32", "J"),
+    field("deployed_this_lap", "f32", "J"),
+];
+
+const WHEEL_VALUE_U8_PERCENT: [FieldSchema; 4] = [
+    field("rear_left", "u8", "%"),
+    field("rear_right", "u8", "%"),
+    field("front_left", "u8", "%"),
+    field("front_right", "u8", "%"),
+];
+
+const WING_VALUE_U8_PERCENT: [FieldSchema; 3] = [
+    field("front_left", "u8", "%"),
+    field("front_right", "u8", "%"),
+    field("rear", "u8", "%"),
+];
+
+const CAR_STATUS_DATA: [FieldSchema; 24] = [
+    field("traction_control", "u8", ""),
+    field("anti_lock_brakes", "bool", ""),
+    field("fuel_mix", "FuelMix", ""),
+    field("front_brake_bias", "u8", "%"),
+    field("pit_limiter_status", "bool", ""),
+    field("fuel_in_tank", "f32", "kg"),
+    field("fuel_capacity", "f32", "kg"),
+    field("fuel_remaining_laps", "f32", ""),
+    field("max_rpm", "u16", "rpm"),
+    field("idle_rpm", "u16", "rpm"),
+    field("max_gears", "u8", ""),
+    field("drs_allowed", "DRSAllowed", ""),
+    field("drs_activation_distance", "DRSActivationDistance", "m"),
+    nested("tyres_wear", "WheelValue<u8>", &WHEEL_VALUE_U8_PERCENT),
+    field("tyres_compound", "TyreCompound", ""),
+    field("tyres_visual", "TyreVisual", ""),
+    field("tyres_ages_lap", "u8", ""),
+    nested("tyres_damage", "WheelValue<u8>", &WHEEL_VALUE_U8_PERCENT),
+    nested("wing_damage", "WingValue<u8>", &WING_VALUE_U8_PERCENT),
+    field("drs_fault", "bool", ""),
+    field("engine_damage", "u8", "%"),
+    field("gearbox_damage", "u8", "%"),
+    field("vehicle_fia_flag", "FiaFlag", ""),
+    nested("ers_data", "ERS", &ERS),
+];
+
+const CAR_STATUS: [FieldSchema; 2] = [
+    nested("header", "Header", &HEADER),
+    nested_array(
+        "car_status_data",
+        "[CarStatusData; 22]",
+        22,
+        &CAR_STATUS_DATA,
+    ),
+];
+
+const FINAL_CLASSIFICATION_DATA: [FieldSchema; 13] = [
+    field("position", "u8", ""),
+    field("number_of_laps", "u8", ""),
+    field("grid_position", "u8", ""),
+    field("points", "u8", ""),
+    field("number_of_pit_stops", "u8", ""),
+    field("result_status", "ResultStatus", ""),
+    field("best_lap_time", "f32", ""),
+    field("total_race_time", "f64", "s"),
+    field("penalties_time", "u8", ""),
+    field("number_of_penalties", "u8", ""),
+    field("number_of_tyre_stints", "u8", ""),
+    array("tyre_stints_actual", "[TyreCompound; 8]", "", 8),
+    array("tyre_stints_visual", "[TyreVisual; 8]", "", 8),
+];
+
+const FINAL_CLASSIFICATION: [FieldSchema; 3] = [
+    nested("header", "Header", &HEADER),
+    field("number_of_cars", "u8", ""),
+    nested_array(
+        "final_classification_data",
+        "[FinalClassificationData; 22]",
+        22,
+        &FINAL_CLASSIFICATION_DATA,
+    ),
+];
+
+const LOBBY_INFO_DATA: [FieldSchema; 5] = [
+    field("ai_controlled", "bool", ""),
+    field("team", "Team", ""),
+    field("nationality", "Nationality", ""),
+    field("name", "String", ""),
+    field("status", "LobbyStatus", ""),
+];
+
+const LOBBY_INFO: [FieldSchema; 3] = [
+    nested("header", "Header", &HEADER),
+    field("number_of_players", "u8", ""),
+    nested_array("lobby_players", "[LobbyInfoData; 22]", 22, &LOBBY_INFO_DATA),
+];
+
+pub(super) const PACKETS: [PacketSchema; 10] = [
+    PacketSchema {
+        id: 0,
+        name: "Motion",
+        fields: &MOTION,
+    },
+    PacketSchema {
+        id: 1,
+        name: "Session",
+        fields: &SESSION,
+    },
+    PacketSchema {
+        id: 2,
+        name: "LapData",
+        fields: &LAP_DATA,
+    },
+    PacketSchema {
+        id: 3,
+        name: "Event",
+        fields: &EVENT,
+    },
+    PacketSchema {
+        id: 4,
+        name: "Participants",
+        fields: &PARTICIPANTS,
+    },
+    PacketSchema {
+        id: 5,
+        name: "CarSetup",
+        fields: &CAR_SETUP,
+    },
+    PacketSchema {
+        id: 6,
+        name: "CarTelemetry",
+        fields: &CAR_TELEMETRY,
+    },
+    PacketSchema {
+        id: 7,
+        name: "CarStatus",
+        fields: &CAR_STATUS,
+    },
+    PacketSchema {
+        id: 8,
+        name: "FinalClassification",
+        fields: &FINAL_CLASSIFICATION,
+    },
+    PacketSchema {
+        id: 9,
+        name: "LobbyInfo",
+        fields: &LOBBY_INFO,
+    },
+];
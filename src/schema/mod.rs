@@ -0,0 +1,180 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::dirt::rally2::DirtRally2;
+use crate::f1::f1_2020::F1_2020;
+use crate::f1::f1_2022::F1_2022;
+
+mod dirt_rally2;
+mod f1_2020;
+mod f1_2022;
+
+/// FieldSchema describes a field of a packet as decoded by this crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FieldSchema {
+    pub name: &'static str,
+    pub type_name: &'static str, // rust type, ie: "f32", "WheelValue<u16>", "[Lap; 22]"
+    pub unit: &'static str,      // empty when unitless (ie: enums, counters, 0..1 ratios)
+    pub count: usize,            // array entries (at most, when partially filled), 1 otherwise
+    pub fields: &'static [FieldSchema], // fields of structs, empty for values and enums
+}
+
+/// PacketSchema describes a packet type of a format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PacketSchema {
+    pub id: u8, // packet_id of the header
+    pub name: &'static str,
+    pub fields: &'static [FieldSchema],
+}
+
+/// Column is a leaf value of a packet, ie: a column of a table of packets
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Column {
+    pub path: String, // dotted path, arrays marked with "[]" (ie: "car_telemetry_data[].speed")
+    pub type_name: &'static str,
+    pub unit: &'static str,
+}
+
+impl PacketSchema {
+    /// field returns the top level field with the given name
+    pub fn field(&self, name: &str) -> Option<&FieldSchema> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// columns flattens the fields of the packet into their leaf values
+    pub fn columns(&self) -> Vec<Column> {
+        let mut columns = Vec::new();
+        flatten("", self.fields, &mut columns);
+        columns
+    }
+}
+
+fn flatten(prefix: &str, fields: &[FieldSchema], columns: &mut Vec<Column>) {
+    for field in fields {
+        let array = if field.count > 1 { "[]" } else { "" };
+        let path = format!("{}{}{}", prefix, field.name, array);
+        if field.fields.is_empty() {
+            columns.push(Column {
+                path,
+                type_name: field.type_name,
+                unit: field.unit,
+            });
+        } else {
+            flatten(&format!("{}.", path), field.fields, columns);
+        }
+    }
+}
+
+/// Schema describes every packet type of a supported format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Schema {
+    pub format: &'static str,
+    pub packets: &'static [PacketSchema],
+}
+
+impl Schema {
+    /// packet returns the schema of the packet with the given packet_id
+    pub fn packet(&self, id: u8) -> Option<&PacketSchema> {
+        self.packets.iter().find(|packet| packet.id == id)
+    }
+}
+
+/// TelemetrySchema is implemented by events that can describe their own layout
+pub trait TelemetrySchema {
+    fn schema() -> &'static Schema;
+}
+
+static F1_2020_SCHEMA: Schema = Schema {
+    format: "F1 2020",
+    packets: &f1_2020::PACKETS,
+};
+
+static F1_2022_SCHEMA: Schema = Schema {
+    format: "F1 22",
+    packets: &f1_2022::PACKETS,
+};
+
+static DIRT_RALLY_2_SCHEMA: Schema = Schema {
+    format: "Dirt Rally 2.0",
+    packets: &dirt_rally2::PACKETS,
+};
+
+impl TelemetrySchema for F1_2020 {
+    fn schema() -> &'static Schema {
+        &F1_2020_SCHEMA
+    }
+}
+
+impl TelemetrySchema for F1_2022 {
+    fn schema() -> &'static Schema {
+        &F1_2022_SCHEMA
+    }
+}
+
+impl TelemetrySchema for DirtRally2 {
+    fn schema() -> &'static Schema {
+        &DIRT_RALLY_2_SCHEMA
+    }
+}
+
+/// schemas returns the schema of every supported format
+pub fn schemas() -> [&'static Schema; 3] {
+    [&F1_2020_SCHEMA, &F1_2022_SCHEMA, &DIRT_RALLY_2_SCHEMA]
+}
+
+const fn field(name: &'static str, type_name: &'static str, unit: &'static str) -> FieldSchema {
+    FieldSchema {
+        name,
+        type_name,
+        unit,
+        count: 1,
+        fields: &[],
+    }
+}
+
+const fn array(
+    name: &'static str,
+    type_name: &'static str,
+    unit: &'static str,
+    count: usize,
+) -> FieldSchema {
+    FieldSchema {
+        name,
+        type_name,
+        unit,
+        count,
+        fields: &[],
+    }
+}
+
+const fn nested(
+    name: &'static str,
+    type_name: &'static str,
+    fields: &'static [FieldSchema],
+) -> FieldSchema {
+    FieldSchema {
+        name,
+        type_name,
+        unit: "",
+        count: 1,
+        fields,
+    }
+}
+
+const fn nested_array(
+    name: &'static str,
+    type_name: &'static str,
+    count: usize,
+    fields: &'static [FieldSchema],
+) -> FieldSchema {
+    FieldSchema {
+        name,
+        type_name,
+        unit: "",
+        count,
+        fields,
+    }
+}
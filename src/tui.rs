@@ -0,0 +1,129 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Row, Table, Widget};
+
+use crate::f1::f1_2022::{LapData, Participants, PitStatus, Session, F1_2022};
+use crate::f1::util::format_lap_time;
+
+/// LiveTimingScreen is a ratatui widget showing the standings of a "F1 22" session (position,
+/// driver, lap, last and best lap times, pit stops) for terminals without a browser or a GPU,
+/// ie: headless boxes at LAN events. Feed it every packet and render it on each frame
+#[derive(Debug, Default, Clone)]
+pub struct LiveTimingScreen {
+    session: Option<Session>,
+    laps: Option<LapData>,
+    participants: Option<Participants>,
+    session_uid: u64,
+    best_laps: Vec<u32>, // best lap time in milliseconds by car index, 0 before the first lap
+}
+
+impl LiveTimingScreen {
+    pub fn new() -> LiveTimingScreen {
+        LiveTimingScreen::default()
+    }
+
+    /// observe feeds a packet, a new session_uid resets the best laps
+    pub fn observe(&mut self, event: &F1_2022) {
+        let session_uid = event.header().session_uid;
+        if session_uid != self.session_uid {
+            *self = LiveTimingScreen::new();
+            self.session_uid = session_uid;
+        }
+
+        match event {
+            F1_2022::Session(data) => self.session = Some(data.clone()),
+            F1_2022::Participants(data) => self.participants = Some(data.clone()),
+            F1_2022::LapData(data) => {
+                self.best_laps.resize(data.laps.len(), 0);
+                for (best, lap) in self.best_laps.iter_mut().zip(data.laps.iter()) {
+                    let last = lap.last_lap_time_ms;
+                    if last > 0 && (*best == 0 || last < *best) {
+                        *best = last;
+                    }
+                }
+                self.laps = Some(data.clone());
+            }
+            _ => {}
+        }
+    }
+
+    fn title(&self) -> String {
+        let session = match &self.session {
+            Some(session) => session,
+            None => return String::from(" Live Timing "),
+        };
+        let lap = self
+            .laps
+            .as_ref()
+            .and_then(|laps| laps.car_data(laps.car_at(1)?))
+            .map(|lap| lap.current_lap_number)
+            .unwrap_or_default();
+        format!(" {} - Lap {}/{} ", session.track, lap, session.total_laps)
+    }
+
+    fn rows(&self) -> Vec<Row<'static>> {
+        let laps = match &self.laps {
+            Some(laps) => laps,
+            None => return Vec::new(),
+        };
+
+        laps.running_order()
+            .into_iter()
+            .filter_map(|car| {
+                let lap = laps.car_data(car)?;
+                let driver = self
+                    .participants
+                    .as_ref()
+                    .and_then(|data| data.car_data(car))
+                    .map(|participant| participant.label())
+                    .unwrap_or_else(|| format!("Car {}", car));
+                let pit = match lap.pit_status {
+                    PitStatus::Pitting | PitStatus::InPitArea => "PIT",
+                    _ => "",
+                };
+                let best = self
+                    .best_laps
+                    .get(car.index()?)
+                    .copied()
+                    .unwrap_or_default();
+
+                let row = Row::new([
+                    lap.car_position.to_string(),
+                    driver,
+                    lap.current_lap_number.to_string(),
+                    format_lap_time(lap.last_lap_time_ms),
+                    format_lap_time(best),
+                    lap.num_pit_stops.to_string(),
+                    String::from(pit),
+                ]);
+                Some(if car == laps.header.player_car_index {
+                    row.style(Style::new().add_modifier(Modifier::BOLD))
+                } else {
+                    row
+                })
+            })
+            .collect()
+    }
+}
+
+impl Widget for &LiveTimingScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let widths = [
+            Constraint::Length(3), // position
+            Constraint::Min(12),   // driver
+            Constraint::Length(3), // lap
+            Constraint::Length(9), // last lap
+            Constraint::Length(9), // best lap
+            Constraint::Length(4), // pit stops
+            Constraint::Length(3), // in the pits
+        ];
+        let header = Row::new(["Pos", "Driver", "Lap", "Last", "Best", "Pits", ""])
+            .style(Style::new().add_modifier(Modifier::REVERSED));
+
+        Table::new(self.rows(), widths)
+            .header(header)
+            .block(Block::bordered().title(self.title()))
+            .render(area, buf);
+    }
+}
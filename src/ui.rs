@@ -0,0 +1,196 @@
+use std::collections::VecDeque;
+
+use egui::{pos2, vec2, Color32, Grid, Sense, Shape, Stroke, Ui};
+
+use crate::f1::f1_2022::{CarTelemetryData, LapData, Participants, F1_2022};
+use crate::f1::util::{format_lap_time, CarIndex};
+
+// number of samples shown by the input trace, about 5 seconds at 60 packets per second
+const TRACE_SAMPLES: usize = 300;
+// tyre surface temperatures (celsius) of the working window
+const TYRE_COLD: u8 = 80;
+const TYRE_HOT: u8 = 105;
+
+/// timing_tower shows the running order of a LapData packet with the labels of the participants
+/// (see ParticipantsData::label), the player car is highlighted
+pub fn timing_tower(ui: &mut Ui, laps: &LapData, participants: Option<&Participants>) {
+    let mut order: Vec<_> = laps
+        .active_laps()
+        .filter(|(_, lap)| lap.car_position > 0)
+        .collect();
+    order.sort_by_key(|(_, lap)| lap.car_position);
+
+    Grid::new("timing_tower").striped(true).show(ui, |ui| {
+        ui.strong("Pos");
+        ui.strong("Driver");
+        ui.strong("Lap");
+        ui.strong("Last lap");
+        ui.end_row();
+
+        for (car, lap) in order {
+            let label = participants
+                .and_then(|data| data.participants_data.get(car.index()?))
+                .map(|participant| participant.label())
+                .unwrap_or_else(|| format!("Car {}", car));
+            ui.label(lap.car_position.to_string());
+            if car == laps.header.player_car_index {
+                ui.strong(label);
+            } else {
+                ui.label(label);
+            }
+            ui.label(lap.current_lap_number.to_string());
+            ui.label(format_lap_time(lap.last_lap_time_ms));
+            ui.end_row();
+        }
+    });
+}
+
+/// tyre_temperatures shows the surface and inner temperature of every tyre laid out like the car,
+/// colored blue under the working window and red over it
+pub fn tyre_temperatures(ui: &mut Ui, telemetry: &CarTelemetryData) {
+    let surface = &telemetry.tyres_surface_temp;
+    let inner = &telemetry.tyres_inner_temp;
+    let tyre = |ui: &mut Ui, surface: u8, inner: u8| {
+        ui.colored_label(tyre_color(surface), format!("{}°C ({}°C)", surface, inner));
+    };
+
+    Grid::new("tyre_temperatures").show(ui, |ui| {
+        tyre(ui, surface.front_left, inner.front_left);
+        tyre(ui, surface.front_right, inner.front_right);
+        ui.end_row();
+        tyre(ui, surface.rear_left, inner.rear_left);
+        tyre(ui, surface.rear_right, inner.rear_right);
+        ui.end_row();
+    });
+}
+
+fn tyre_color(surface: u8) -> Color32 {
+    if surface < TYRE_COLD {
+        Color32::LIGHT_BLUE
+    } else if surface > TYRE_HOT {
+        Color32::LIGHT_RED
+    } else {
+        Color32::LIGHT_GREEN
+    }
+}
+
+/// InputTrace keeps the last seconds of throttle and brake inputs of a car
+/// and draws them as two lines (throttle green, brake red)
+#[derive(Debug, Clone)]
+pub struct InputTrace {
+    car: Option<CarIndex>,
+    samples: VecDeque<(f32, f32)>, // throttle, brake
+}
+
+impl Default for InputTrace {
+    fn default() -> Self {
+        InputTrace {
+            car: None,
+            samples: VecDeque::with_capacity(TRACE_SAMPLES),
+        }
+    }
+}
+
+impl InputTrace {
+    /// new traces the inputs of the player car
+    pub fn new() -> InputTrace {
+        InputTrace::default()
+    }
+
+    /// with_car traces the inputs of the given car instead of the player car
+    pub fn with_car(mut self, car: CarIndex) -> Self {
+        self.car = Some(car);
+        self
+    }
+
+    /// observe feeds a packet, only CarTelemetry packets are used
+    pub fn observe(&mut self, event: &F1_2022) {
+        if let F1_2022::CarTelemetry(data) = event {
+            let car = self.car.unwrap_or(data.header.player_car_index);
+            if let Some(telemetry) = data.car_data(car) {
+                if self.samples.len() == TRACE_SAMPLES {
+                    self.samples.pop_front();
+                }
+                self.samples
+                    .push_back((telemetry.throttle, telemetry.brake));
+            }
+        }
+    }
+
+    /// show draws the trace over the available width
+    pub fn show(&self, ui: &mut Ui, height: f32) {
+        let (response, painter) =
+            ui.allocate_painter(vec2(ui.available_width(), height), Sense::hover());
+        let rect = response.rect;
+        let step = rect.width() / (TRACE_SAMPLES - 1) as f32;
+        let line = |value: fn(&(f32, f32)) -> f32| {
+            self.samples
+                .iter()
+                .enumerate()
+                .map(|(index, sample)| {
+                    let y = rect.bottom() - value(sample).clamp(0.0, 1.0) * rect.height();
+                    pos2(rect.left() + index as f32 * step, y)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        painter.rect_filled(rect, 0.0, Color32::from_gray(24));
+        painter.add(Shape::line(
+            line(|sample| sample.0),
+            Stroke::new(1.5, Color32::GREEN),
+        ));
+        painter.add(Shape::line(
+            line(|sample| sample.1),
+            Stroke::new(1.5, Color32::RED),
+        ));
+    }
+}
+
+/// Dashboard keeps the packets its widgets need and shows them together: the timing tower,
+/// the tyre temperatures and the input trace of the player car
+#[derive(Debug, Default, Clone)]
+pub struct Dashboard {
+    laps: Option<LapData>,
+    participants: Option<Participants>,
+    telemetry: Option<CarTelemetryData>,
+    trace: InputTrace,
+}
+
+impl Dashboard {
+    pub fn new() -> Dashboard {
+        Dashboard::default()
+    }
+
+    /// observe feeds a packet, call it for every packet received
+    pub fn observe(&mut self, event: &F1_2022) {
+        self.trace.observe(event);
+        match event {
+            F1_2022::LapData(data) => self.laps = Some(data.clone()),
+            F1_2022::Participants(data) => self.participants = Some(data.clone()),
+            F1_2022::CarTelemetry(data) => {
+                if let Some(telemetry) = data.focused_car_data(None) {
+                    self.telemetry = Some(telemetry.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// show draws every widget that received its packets
+    pub fn show(&self, ui: &mut Ui) {
+        ui.horizontal_top(|ui| {
+            ui.vertical(|ui| match &self.laps {
+                Some(laps) => timing_tower(ui, laps, self.participants.as_ref()),
+                None => {
+                    ui.label("Waiting for LapData packets...");
+                }
+            });
+            ui.vertical(|ui| {
+                if let Some(telemetry) = &self.telemetry {
+                    tyre_temperatures(ui, telemetry);
+                }
+                self.trace.show(ui, 80.0);
+            });
+        });
+    }
+}
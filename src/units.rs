@@ -0,0 +1,74 @@
+// conversions between the units sent by the games and the ones shown to users,
+// games send metric values (m/s or kph, radians) and PSI for tyre pressures
+
+const KPH_PER_MPS: f32 = 3.6;
+const KPH_PER_MPH: f32 = 1.609_344;
+const PSI_PER_BAR: f32 = 14.503_774;
+
+/// FUEL_DENSITY is the density (kg per litre) used for fuel, F1 fuel is around 0.75
+pub const FUEL_DENSITY: f32 = 0.75;
+
+/// mps_to_kph converts metres per second to kilometres per hour
+pub fn mps_to_kph(mps: f32) -> f32 {
+    mps * KPH_PER_MPS
+}
+
+/// kph_to_mps converts kilometres per hour to metres per second
+pub fn kph_to_mps(kph: f32) -> f32 {
+    kph / KPH_PER_MPS
+}
+
+/// mps_to_mph converts metres per second to miles per hour
+pub fn mps_to_mph(mps: f32) -> f32 {
+    kph_to_mph(mps_to_kph(mps))
+}
+
+/// kph_to_mph converts kilometres per hour to miles per hour
+pub fn kph_to_mph(kph: f32) -> f32 {
+    kph / KPH_PER_MPH
+}
+
+/// mph_to_kph converts miles per hour to kilometres per hour
+pub fn mph_to_kph(mph: f32) -> f32 {
+    mph * KPH_PER_MPH
+}
+
+/// radians_to_degrees converts an angle in radians to degrees
+pub fn radians_to_degrees(radians: f32) -> f32 {
+    radians.to_degrees()
+}
+
+/// degrees_to_radians converts an angle in degrees to radians
+pub fn degrees_to_radians(degrees: f32) -> f32 {
+    degrees.to_radians()
+}
+
+/// psi_to_bar converts a pressure in pounds per square inch to bar
+pub fn psi_to_bar(psi: f32) -> f32 {
+    psi / PSI_PER_BAR
+}
+
+/// bar_to_psi converts a pressure in bar to pounds per square inch
+pub fn bar_to_psi(bar: f32) -> f32 {
+    bar * PSI_PER_BAR
+}
+
+/// celsius_to_fahrenheit converts a temperature in degrees celsius to fahrenheit
+pub fn celsius_to_fahrenheit(celsius: f32) -> f32 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+/// fahrenheit_to_celsius converts a temperature in degrees fahrenheit to celsius
+pub fn fahrenheit_to_celsius(fahrenheit: f32) -> f32 {
+    (fahrenheit - 32.0) * 5.0 / 9.0
+}
+
+/// fuel_kg_to_litres converts a mass of fuel to its volume, see FUEL_DENSITY
+pub fn fuel_kg_to_litres(kg: f32) -> f32 {
+    kg / FUEL_DENSITY
+}
+
+/// fuel_litres_to_kg converts a volume of fuel to its mass, see FUEL_DENSITY
+pub fn fuel_litres_to_kg(litres: f32) -> f32 {
+    litres * FUEL_DENSITY
+}
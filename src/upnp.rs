@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+
+use igd_next::{Gateway, PortMappingProtocol, SearchOptions};
+
+/// Protocol of a port mapping, Udp for games sending straight to the server
+/// and Tcp for relays using net::TcpTransport or net::TlsTransport
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Udp,
+    Tcp,
+}
+
+impl From<Protocol> for PortMappingProtocol {
+    fn from(protocol: Protocol) -> Self {
+        match protocol {
+            Protocol::Udp => PortMappingProtocol::UDP,
+            Protocol::Tcp => PortMappingProtocol::TCP,
+        }
+    }
+}
+
+/// PortMapping forwards a port of the router (found with UPnP) to a local server,
+/// so a console on another network can send its telemetry to external_addr.
+/// The mapping is removed when dropped
+pub struct PortMapping {
+    gateway: Gateway,
+    protocol: Protocol,
+    local_addr: SocketAddr,
+    external_addr: SocketAddr,
+    removed: bool,
+}
+
+impl PortMapping {
+    /// new maps the same port of the router to the given local address (ie: server.local_addr()),
+    /// an unspecified ip (0.0.0.0) is replaced by the address of this host in the router network
+    /// lease_duration is in seconds, 0 keeps the mapping until it is removed
+    pub fn new(
+        protocol: Protocol,
+        local_addr: SocketAddr,
+        lease_duration: u32,
+    ) -> Result<PortMapping, Box<dyn Error>> {
+        let gateway = igd_next::search_gateway(SearchOptions::default())?;
+
+        let mut local_addr = local_addr;
+        if local_addr.ip().is_unspecified() {
+            local_addr.set_ip(route_to(gateway.addr)?);
+        }
+
+        let external_ip = gateway.get_external_ip()?;
+        gateway.add_port(
+            protocol.into(),
+            local_addr.port(),
+            local_addr,
+            lease_duration,
+            "cm-telemetry",
+        )?;
+
+        Ok(PortMapping {
+            gateway,
+            protocol,
+            local_addr,
+            external_addr: SocketAddr::new(external_ip, local_addr.port()),
+            removed: false,
+        })
+    }
+
+    /// external_addr returns the address consoles on the internet should send their telemetry to
+    pub fn external_addr(&self) -> SocketAddr {
+        self.external_addr
+    }
+
+    /// local_addr returns the address the router forwards the traffic to
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    /// remove deletes the mapping from the router, reporting the error that drop ignores
+    pub fn remove(mut self) -> Result<(), Box<dyn Error>> {
+        self.removed = true;
+        self.gateway
+            .remove_port(self.protocol.into(), self.external_addr.port())?;
+        Ok(())
+    }
+}
+
+impl Drop for PortMapping {
+    fn drop(&mut self) {
+        if self.removed {
+            return;
+        }
+        let _ = self
+            .gateway
+            .remove_port(self.protocol.into(), self.external_addr.port());
+    }
+}
+
+// route_to returns the local ip used to reach the given address (no packet is sent)
+fn route_to(addr: SocketAddr) -> Result<IpAddr, std::io::Error> {
+    let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+    socket.connect(addr)?;
+    Ok(socket.local_addr()?.ip())
+}
@@ -0,0 +1,29 @@
+use crate::dirt::rally2::DirtRally2;
+use crate::f1::{f1_2020::F1_2020, f1_2022::F1_2022};
+use crate::{TelemetryEvent, TelemetryPacket};
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// decode_f1_2020 decodes a "F1 2020" packet into a plain javascript object
+#[wasm_bindgen]
+pub fn decode_f1_2020(bytes: &[u8]) -> Result<JsValue, JsValue> {
+    decode::<F1_2020>(bytes)
+}
+
+/// decode_f1_2022 decodes a "F1 22" packet into a plain javascript object
+#[wasm_bindgen]
+pub fn decode_f1_2022(bytes: &[u8]) -> Result<JsValue, JsValue> {
+    decode::<F1_2022>(bytes)
+}
+
+/// decode_dirt_rally2 decodes a "Dirt Rally 2.0" packet into a plain javascript object
+#[wasm_bindgen]
+pub fn decode_dirt_rally2(bytes: &[u8]) -> Result<JsValue, JsValue> {
+    decode::<DirtRally2>(bytes)
+}
+
+fn decode<T: TelemetryEvent + Serialize>(packet: &TelemetryPacket) -> Result<JsValue, JsValue> {
+    let event = T::from_packet(packet).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(serde_wasm_bindgen::to_value(&event)?)
+}
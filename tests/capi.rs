@@ -0,0 +1,25 @@
+#![cfg(feature = "capi")]
+
+use std::ffi::CString;
+
+use cm_telemetry::capi::{cm_telemetry_close, cm_telemetry_open, CmGame};
+
+#[test]
+fn open_with_an_unknown_game_returns_null() {
+    let address = CString::new("127.0.0.1:0").unwrap();
+
+    let handle = unsafe { cm_telemetry_open(address.as_ptr(), 7) };
+    assert!(handle.is_null());
+
+    let handle = unsafe { cm_telemetry_open(address.as_ptr(), u32::MAX) };
+    assert!(handle.is_null());
+}
+
+#[test]
+fn open_with_a_known_game_returns_a_handle() {
+    let address = CString::new("127.0.0.1:0").unwrap();
+
+    let handle = unsafe { cm_telemetry_open(address.as_ptr(), CmGame::F1_2022 as u32) };
+    assert!(!handle.is_null());
+    unsafe { cm_telemetry_close(handle) };
+}
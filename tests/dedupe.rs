@@ -0,0 +1,150 @@
+use cm_telemetry::dedupe::DuplicateGuard;
+use cm_telemetry::f1::f1_2022::F1_2022;
+use cm_telemetry::{TelemetryEvent, TelemetryFrame};
+
+#[derive(Debug)]
+struct Frame {
+    session_uid: u64,
+    packet_id: u8,
+    frame_identifier: u32,
+    #[allow(dead_code)] // only read through Debug, which DuplicateGuard hashes
+    content: &'static str,
+}
+
+impl TelemetryFrame for Frame {
+    fn session_uid(&self) -> u64 {
+        self.session_uid
+    }
+
+    fn frame_identifier(&self) -> u32 {
+        self.frame_identifier
+    }
+
+    fn packet_id(&self) -> u8 {
+        self.packet_id
+    }
+
+    fn session_time(&self) -> f32 {
+        self.frame_identifier as f32 / 60.0
+    }
+}
+
+fn frame(packet_id: u8, frame_identifier: u32) -> Frame {
+    Frame {
+        session_uid: 1,
+        packet_id,
+        frame_identifier,
+        content: "",
+    }
+}
+
+fn on_demand(packet_id: u8, frame_identifier: u32, content: &'static str) -> Frame {
+    Frame {
+        content,
+        ..frame(packet_id, frame_identifier)
+    }
+}
+
+// f1_2022_penalty_event builds an Event packet with a PENA (penalty) code
+fn f1_2022_penalty_event(frame_identifier: u32, vehicle_index: u8) -> Vec<u8> {
+    let mut bytes = vec![0; 40];
+    bytes[0..2].copy_from_slice(&2022u16.to_le_bytes());
+    bytes[4] = 1; // packet_version
+    bytes[5] = 3; // packet_id
+    bytes[6..14].copy_from_slice(&42u64.to_le_bytes());
+    bytes[18..22].copy_from_slice(&frame_identifier.to_le_bytes());
+    bytes[24..28].copy_from_slice(b"PENA");
+    bytes[28] = 0; // penalty_type: drive through
+    bytes[29] = 2; // infringement_type
+    bytes[30] = vehicle_index;
+    bytes[31] = 255; // other_vehicle_index
+    bytes
+}
+
+#[test]
+fn periodic_duplicates_are_dropped() {
+    let mut guard = DuplicateGuard::default();
+    assert!(guard.accept(&frame(0, 10)));
+    assert!(!guard.accept(&frame(0, 10)));
+    assert!(guard.accept(&frame(0, 11)));
+    assert_eq!(guard.duplicates(), 1);
+    assert_eq!(guard.stale(), 0);
+}
+
+#[test]
+fn late_periodic_packets_are_dropped_within_the_reorder_window() {
+    let mut guard = DuplicateGuard::new(5);
+    assert!(guard.accept(&frame(6, 100)));
+    assert!(!guard.accept(&frame(6, 97)));
+    assert_eq!(guard.stale(), 1);
+
+    // further back than the window: the frame counter restarted (ie: a flashback)
+    assert!(guard.accept(&frame(6, 20)));
+    assert!(guard.accept(&frame(6, 21)));
+}
+
+#[test]
+fn packet_types_and_sessions_are_tracked_separately() {
+    let mut guard = DuplicateGuard::default();
+    assert!(guard.accept(&frame(0, 10)));
+    assert!(guard.accept(&frame(2, 10)));
+    assert!(guard.accept(&Frame {
+        session_uid: 2,
+        packet_id: 0,
+        frame_identifier: 10,
+        content: "",
+    }));
+    assert_eq!(guard.duplicates(), 0);
+}
+
+#[test]
+fn events_sharing_a_frame_are_accepted() {
+    let mut guard = DuplicateGuard::default();
+    // ie: a BUTN event and a PENA event sent on the same frame
+    assert!(guard.accept(&on_demand(3, 10, "BUTN")));
+    assert!(guard.accept(&on_demand(3, 10, "PENA")));
+    assert!(guard.accept(&on_demand(3, 9, "BUTN")));
+    // FinalClassification and LobbyInfo are sent on demand too
+    assert!(guard.accept(&on_demand(8, 10, "first")));
+    assert!(guard.accept(&on_demand(8, 10, "second")));
+    assert!(guard.accept(&on_demand(9, 10, "first")));
+    assert!(guard.accept(&on_demand(9, 10, "second")));
+    assert_eq!(guard.duplicates(), 0);
+    assert_eq!(guard.stale(), 0);
+}
+
+#[test]
+fn duplicated_on_demand_packets_are_dropped() {
+    let mut guard = DuplicateGuard::default();
+    assert!(guard.accept(&on_demand(3, 10, "BUTN")));
+    assert!(guard.accept(&on_demand(3, 11, "PENA")));
+    assert!(!guard.accept(&on_demand(3, 10, "BUTN")));
+    assert!(!guard.accept(&on_demand(3, 11, "PENA")));
+    assert_eq!(guard.duplicates(), 2);
+
+    // forgotten once it is further back than the reorder window
+    assert!(guard.accept(&on_demand(3, 100, "BUTN")));
+    assert!(guard.accept(&on_demand(3, 10, "BUTN")));
+}
+
+#[test]
+fn the_same_f1_2022_penalty_event_is_accepted_once() {
+    let mut guard = DuplicateGuard::default();
+    let penalty = F1_2022::from_packet(&f1_2022_penalty_event(10, 4)).unwrap();
+    let duplicate = F1_2022::from_packet(&f1_2022_penalty_event(10, 4)).unwrap();
+    let other_car = F1_2022::from_packet(&f1_2022_penalty_event(10, 5)).unwrap();
+
+    assert!(guard.accept(&penalty));
+    assert!(!guard.accept(&duplicate));
+    assert!(guard.accept(&other_car));
+    assert_eq!(guard.duplicates(), 1);
+}
+
+#[test]
+fn on_demand_packets_can_be_replaced() {
+    let mut guard = DuplicateGuard::default().with_on_demand_packets(&[7]);
+    assert!(guard.accept(&on_demand(7, 10, "first")));
+    assert!(guard.accept(&on_demand(7, 10, "second")));
+    assert!(guard.accept(&on_demand(3, 10, "BUTN")));
+    assert!(!guard.accept(&on_demand(3, 10, "PENA")));
+}
@@ -0,0 +1,470 @@
+// Golden packet tests: decodes the captured datagrams of tests/data and checks their fields,
+// see tests/data/README.md to add captures.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cm_telemetry::dirt::rally2::{self, DirtRally2};
+use cm_telemetry::f1::util::CarIndex;
+use cm_telemetry::f1::{f1_2020, f1_2020::F1_2020, f1_2022, f1_2022::F1_2022};
+use cm_telemetry::schema::TelemetrySchema;
+use cm_telemetry::TelemetryEvent;
+
+// every F1 capture shares the same header, only packet_format and packet_id change
+const SESSION_UID: u64 = 0x1122_3344_5566_7788;
+const SESSION_TIME: f32 = 12.5;
+const FRAME_IDENTIFIER: u32 = 750;
+
+fn data_directory() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data")
+}
+
+/// decode reads and decodes a capture of tests/data (ie: "f1_2022/06_car_telemetry.bin")
+fn decode<T: TelemetryEvent>(capture: &str) -> T {
+    let path = data_directory().join(capture);
+    let packet =
+        fs::read(&path).unwrap_or_else(|e| panic!("{}: failed to read: {}", path.display(), e));
+    T::from_packet(&packet).unwrap_or_else(|e| panic!("{}: failed to decode: {}", capture, e))
+}
+
+/// f1_capture_names lists the capture expected for every packet id of the schema,
+/// named as examples/capture.rs saves them (ie: "06_car_telemetry.bin")
+fn f1_capture_names<T: TelemetrySchema>() -> Vec<(u8, String)> {
+    T::schema()
+        .packets
+        .iter()
+        .map(|packet| {
+            let mut name = format!("{:02}_", packet.id);
+            for (i, c) in packet.name.chars().enumerate() {
+                if c.is_ascii_uppercase() && i > 0 {
+                    name.push('_');
+                }
+                name.push(c.to_ascii_lowercase());
+            }
+            (packet.id, name + ".bin")
+        })
+        .collect()
+}
+
+macro_rules! assert_f1_header {
+    ($header:expr, $packet_format:expr, $packet_id:expr) => {{
+        let header = &$header;
+        assert_eq!(header.packet_format, $packet_format);
+        assert_eq!(header.packet_id, $packet_id);
+        assert_eq!(header.session_uid, SESSION_UID);
+        assert_eq!(header.session_time, SESSION_TIME);
+        assert_eq!(header.frame_identifier, FRAME_IDENTIFIER);
+        assert_eq!(header.player_car_index, CarIndex(0));
+        assert_eq!(header.secondary_player_car_index, CarIndex::NONE);
+    }};
+}
+
+#[test]
+fn f1_2020_every_packet_id_has_a_capture() {
+    for (id, name) in f1_capture_names::<F1_2020>() {
+        let event: F1_2020 = decode(&format!("f1_2020/{}", name));
+        assert_f1_header!(event.header(), 2020, id);
+    }
+}
+
+#[test]
+fn f1_2022_every_packet_id_has_a_capture() {
+    for (id, name) in f1_capture_names::<F1_2022>() {
+        let event: F1_2022 = decode(&format!("f1_2022/{}", name));
+        assert_f1_header!(event.header(), 2022, id);
+    }
+}
+
+#[test]
+fn captures_are_named_after_a_packet_id() {
+    for (format, names) in [
+        ("f1_2020", f1_capture_names::<F1_2020>()),
+        ("f1_2022", f1_capture_names::<F1_2022>()),
+    ] {
+        for entry in fs::read_dir(data_directory().join(format)).expect("failed to list captures") {
+            let file_name = entry.expect("failed to list captures").file_name();
+            let file_name = file_name.to_string_lossy();
+            assert!(
+                names.iter().any(|(_, name)| *name == file_name),
+                "{}/{}: not named after a packet id",
+                format,
+                file_name
+            );
+        }
+    }
+}
+
+// F1 2020
+
+#[test]
+fn f1_2020_motion() {
+    match decode("f1_2020/00_motion.bin") {
+        F1_2020::Motion(data) => {
+            let car = data.player_data().unwrap();
+            assert_eq!(car.world_position.x, 120.5);
+            assert_eq!(car.world_position.y, 3.25);
+            assert_eq!(car.world_position.z, -48.0);
+            assert_eq!(car.g_force_lateral, 1.5);
+        }
+        event => panic!("expected a Motion packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2020_session() {
+    match decode("f1_2020/01_session.bin") {
+        F1_2020::Session(data) => {
+            assert_eq!(data.weather, f1_2020::Weather::LigthCloud);
+            assert_eq!(data.track_temperature, 32);
+            assert_eq!(data.air_temperature, 24);
+            assert_eq!(data.total_laps, 5);
+            assert_eq!(data.track_length, 7004);
+            assert_eq!(data.session_type, f1_2020::SessionType::Race);
+            assert_eq!(data.track, f1_2020::Track::Spa);
+        }
+        event => panic!("expected a Session packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2020_lap_data() {
+    match decode("f1_2020/02_lap_data.bin") {
+        F1_2020::LapData(data) => {
+            let lap = data.player_data().unwrap();
+            assert_eq!(lap.last_lap_time, 92.5);
+            assert_eq!(lap.current_lap_time, 12.25);
+        }
+        event => panic!("expected a LapData packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2020_event() {
+    match decode("f1_2020/03_event.bin") {
+        F1_2020::Event(data) => match data.event_data_details {
+            f1_2020::EventDataDetail::FastestLap(car_index, lap_time) => {
+                assert_eq!(car_index, CarIndex(3));
+                assert_eq!(lap_time, 91.25);
+            }
+            details => panic!("expected a FastestLap event, got {:?}", details),
+        },
+        event => panic!("expected an Event packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2020_participants() {
+    match decode("f1_2020/04_participants.bin") {
+        F1_2020::Participants(data) => {
+            assert_eq!(data.num_active_cars, 20);
+            let participant = data.player_data().unwrap();
+            assert!(!participant.ai_controlled);
+            assert_eq!(participant.driver, f1_2020::Driver::MaxVerstappen);
+            assert_eq!(participant.team, f1_2020::Team::RedBullRacing);
+            assert_eq!(participant.race_number, 33);
+            assert_eq!(participant.nationality, f1_2020::Nationality::British);
+            assert_eq!(participant.name, "VERSTAPPEN");
+            assert!(!participant.your_telemetry_restricted);
+        }
+        event => panic!("expected a Participants packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2020_car_setup() {
+    match decode("f1_2020/05_car_setup.bin") {
+        F1_2020::CarSetup(data) => {
+            let setup = data.player_data().unwrap();
+            assert_eq!(setup.wing.front, 7);
+            assert_eq!(setup.wing.rear, 9);
+        }
+        event => panic!("expected a CarSetup packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2020_car_telemetry() {
+    match decode("f1_2020/06_car_telemetry.bin") {
+        F1_2020::CarTelemetry(data) => {
+            let telemetry = data.player_data().unwrap();
+            assert_eq!(telemetry.speed, 212);
+            assert_eq!(telemetry.gear, f1_2020::Gear::Fourth);
+            assert_eq!(telemetry.engine_rpm, 11000);
+            assert!(telemetry.drs);
+            assert_eq!(telemetry.engine_temp, 110);
+            assert_eq!(data.suggested_gear, f1_2020::Gear::Fifth);
+        }
+        event => panic!("expected a CarTelemetry packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2020_car_status() {
+    match decode("f1_2020/07_car_status.bin") {
+        F1_2020::CarStatus(data) => {
+            let status = data.player_data().unwrap();
+            assert_eq!(status.traction_control, 2);
+            assert!(status.anti_lock_brakes);
+            assert_eq!(status.fuel_mix, f1_2020::FuelMix::Rich);
+            assert_eq!(status.front_brake_bias, 55);
+            assert!(!status.pit_limiter_status);
+            assert_eq!(status.fuel_in_tank, 40.5);
+            assert_eq!(status.fuel_capacity, 110.0);
+            assert_eq!(status.fuel_remaining_laps, 12.5);
+        }
+        event => panic!("expected a CarStatus packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2020_final_classification() {
+    match decode("f1_2020/08_final_classification.bin") {
+        F1_2020::FinalClassification(data) => {
+            assert_eq!(data.number_of_cars, 20);
+            let classification = &data.final_classification_data[0];
+            assert_eq!(classification.position, 1);
+            assert_eq!(classification.number_of_laps, 5);
+            assert_eq!(classification.grid_position, 2);
+            assert_eq!(classification.points, 25);
+            assert_eq!(classification.number_of_pit_stops, 1);
+            assert_eq!(
+                classification.result_status,
+                f1_2020::ResultStatus::Finished
+            );
+            assert_eq!(classification.best_lap_time, 91.25);
+            assert_eq!(classification.total_race_time, 470.5);
+        }
+        event => panic!("expected a FinalClassification packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2020_lobby_info() {
+    match decode("f1_2020/09_lobby_info.bin") {
+        F1_2020::LobbyInfo(data) => {
+            assert_eq!(data.number_of_players, 2);
+            let player = &data.active_players()[0];
+            assert!(!player.ai_controlled);
+            assert_eq!(player.team, f1_2020::Team::Ferrari);
+            assert_eq!(player.nationality, f1_2020::Nationality::British);
+            assert_eq!(player.name, "PLAYER");
+            assert_eq!(player.status, f1_2020::LobbyStatus::Ready);
+        }
+        event => panic!("expected a LobbyInfo packet, got {:?}", event),
+    }
+}
+
+// F1 22
+
+#[test]
+fn f1_2022_motion() {
+    match decode("f1_2022/00_motion.bin") {
+        F1_2022::Motion(data) => {
+            let car = data.player_data().unwrap();
+            assert_eq!(car.world_position.x, 120.5);
+            assert_eq!(car.world_position.y, 3.25);
+            assert_eq!(car.world_position.z, -48.0);
+            assert_eq!(car.g_force_lateral, 1.5);
+        }
+        event => panic!("expected a Motion packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2022_session() {
+    match decode("f1_2022/01_session.bin") {
+        F1_2022::Session(data) => {
+            assert_eq!(data.weather, f1_2022::Weather::LigthCloud);
+            assert_eq!(data.track_temperature, 32);
+            assert_eq!(data.air_temperature, 24);
+            assert_eq!(data.total_laps, 5);
+            assert_eq!(data.track_length, 7004);
+            assert_eq!(data.session_type, f1_2022::SessionType::Race);
+            assert_eq!(data.track, f1_2022::Track::Spa);
+        }
+        event => panic!("expected a Session packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2022_lap_data() {
+    match decode("f1_2022/02_lap_data.bin") {
+        F1_2022::LapData(data) => {
+            let lap = data.player_data().unwrap();
+            assert_eq!(lap.last_lap_time_ms, 92500);
+            assert_eq!(lap.current_lap_time_ms, 12250);
+            assert_eq!(lap.car_position, 3);
+            assert_eq!(lap.current_lap_number, 4);
+        }
+        event => panic!("expected a LapData packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2022_event() {
+    match decode("f1_2022/03_event.bin") {
+        F1_2022::Event(data) => match data.event_data_details {
+            f1_2022::EventDataDetail::FastestLap(car_index, lap_time) => {
+                assert_eq!(car_index, CarIndex(3));
+                assert_eq!(lap_time, 91.25);
+            }
+            details => panic!("expected a FastestLap event, got {:?}", details),
+        },
+        event => panic!("expected an Event packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2022_participants() {
+    match decode("f1_2022/04_participants.bin") {
+        F1_2022::Participants(data) => {
+            assert_eq!(data.num_active_cars, 20);
+            let participant = data.player_data().unwrap();
+            assert!(!participant.ai_controlled);
+            assert_eq!(participant.driver, f1_2022::Driver::MaxVerstappen);
+            assert_eq!(participant.network_id, 4);
+            assert_eq!(participant.team, f1_2022::Team::RedBullRacing);
+            assert!(participant.my_team);
+            assert_eq!(participant.race_number, 1);
+            assert_eq!(participant.nationality, f1_2022::Nationality::British);
+            assert_eq!(participant.name, "VERSTAPPEN");
+            assert!(participant.your_telemetry_public);
+        }
+        event => panic!("expected a Participants packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2022_car_setup() {
+    match decode("f1_2022/05_car_setup.bin") {
+        F1_2022::CarSetup(data) => {
+            let setup = data.player_data().unwrap();
+            assert_eq!(setup.wing.front, 7);
+            assert_eq!(setup.wing.rear, 9);
+        }
+        event => panic!("expected a CarSetup packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2022_car_telemetry() {
+    match decode("f1_2022/06_car_telemetry.bin") {
+        F1_2022::CarTelemetry(data) => {
+            let telemetry = data.player_data().unwrap();
+            assert_eq!(telemetry.speed, 212);
+            assert_eq!(telemetry.gear, f1_2022::Gear::Fourth);
+            assert_eq!(telemetry.engine_rpm, 11000);
+            assert!(telemetry.drs);
+            assert_eq!(telemetry.engine_temp, 110);
+            assert_eq!(data.suggested_gear, f1_2022::Gear::Fifth);
+        }
+        event => panic!("expected a CarTelemetry packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2022_car_status() {
+    match decode("f1_2022/07_car_status.bin") {
+        F1_2022::CarStatus(data) => {
+            let status = data.player_data().unwrap();
+            assert_eq!(status.traction_control, 2);
+            assert!(status.anti_lock_brakes);
+            assert_eq!(status.fuel_mix, f1_2022::FuelMix::Rich);
+            assert_eq!(status.front_brake_bias, 55);
+            assert!(!status.pit_limiter_status);
+            assert_eq!(status.fuel_in_tank, 40.5);
+            assert_eq!(status.fuel_capacity, 110.0);
+            assert_eq!(status.fuel_remaining_laps, 12.5);
+        }
+        event => panic!("expected a CarStatus packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2022_final_classification() {
+    match decode("f1_2022/08_final_classification.bin") {
+        F1_2022::FinalClassification(data) => {
+            assert_eq!(data.number_of_cars, 20);
+            let classification = &data.final_classification_data[0];
+            assert_eq!(classification.position, 1);
+            assert_eq!(classification.number_of_laps, 5);
+            assert_eq!(classification.grid_position, 2);
+            assert_eq!(classification.points, 25);
+            assert_eq!(classification.number_of_pit_stops, 1);
+            assert_eq!(
+                classification.result_status,
+                f1_2022::ResultStatus::Finished
+            );
+            assert_eq!(classification.best_lap_time_ms, 91250);
+            assert_eq!(classification.total_race_time, 470.5);
+        }
+        event => panic!("expected a FinalClassification packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2022_lobby_info() {
+    match decode("f1_2022/09_lobby_info.bin") {
+        F1_2022::LobbyInfo(data) => {
+            assert_eq!(data.number_of_players, 2);
+            let player = &data.active_players()[0];
+            assert!(!player.ai_controlled);
+            assert_eq!(player.team, f1_2022::Team::Ferrari);
+            assert_eq!(player.nationality, f1_2022::Nationality::British);
+            assert_eq!(player.name, "PLAYER");
+            assert_eq!(player.car_number, 16);
+            assert_eq!(player.status, f1_2022::LobbyStatus::Ready);
+        }
+        event => panic!("expected a LobbyInfo packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2022_car_damage() {
+    match decode("f1_2022/10_car_damage.bin") {
+        F1_2022::CarDamage(data) => {
+            let damage = data.player_data().unwrap();
+            assert_eq!(damage.tyres_wear.rear_left, 10.5);
+            assert_eq!(damage.tyres_wear.rear_right, 11.0);
+            assert_eq!(damage.tyres_wear.front_left, 8.25);
+            assert_eq!(damage.tyres_wear.front_right, 8.5);
+            assert_eq!(damage.wing_damage.front_left, 15);
+            assert_eq!(damage.wing_damage.front_right, 0);
+            assert!(!damage.engine_blown);
+        }
+        event => panic!("expected a CarDamage packet, got {:?}", event),
+    }
+}
+
+#[test]
+fn f1_2022_session_history() {
+    match decode("f1_2022/11_session_history.bin") {
+        F1_2022::SessionHistory(data) => {
+            assert_eq!(data.car_index, CarIndex(0));
+            assert_eq!(data.lap_history_data.len(), 2);
+            let lap = &data.lap_history_data[0];
+            assert_eq!(lap.lap_time_ms, 91250);
+            assert_eq!(lap.sector_times_ms, (30100, 31050, 30100));
+            assert!(lap.is_valid());
+            assert_eq!(data.tyre_stints_history_data.len(), 1);
+            let stint = &data.tyre_stints_history_data[0];
+            assert_eq!(stint.end_lap, 255);
+            assert_eq!(stint.tyre_actual_compound, f1_2022::TyreCompound::C5);
+            assert_eq!(stint.tyre_visual_compound, f1_2022::TyreVisual::Soft);
+        }
+        event => panic!("expected a SessionHistory packet, got {:?}", event),
+    }
+}
+
+// Dirt Rally 2.0
+
+#[test]
+fn dirt_rally2_stage() {
+    let event: DirtRally2 = decode("dirt_rally2/stage.bin");
+    assert_eq!(event.car.speed, 27.5);
+    assert_eq!(event.car.gear, rally2::Gear::Third);
+    assert_eq!(event.car.throttle, 1.0);
+    assert_eq!(event.car.rpms, 6000.0);
+    assert_eq!(event.session.track.distance, 1520.0);
+    assert_eq!(event.session.track.time, 95.5);
+}
@@ -0,0 +1,93 @@
+use cm_telemetry::dirt::rally2;
+use cm_telemetry::f1::{f1_2020, f1_2022};
+use cm_telemetry::TelemetryEvent;
+
+const HEADER_SIZE: usize = 24;
+
+fn header(packet_format: u16, packet_id: u8) -> Vec<u8> {
+    let mut bytes = vec![0; HEADER_SIZE];
+    bytes[0..2].copy_from_slice(&packet_format.to_le_bytes());
+    bytes[5] = packet_id;
+    bytes
+}
+
+#[test]
+fn f1_2020_car_telemetry_invalid_gear_falls_back_to_unknown() {
+    const CAR_SIZE: usize = 58;
+    const GEAR_OFFSET: usize = 15;
+
+    let mut packet = header(2020, 6);
+    packet.resize(HEADER_SIZE + 22 * CAR_SIZE + 4 + 3, 0);
+    packet[HEADER_SIZE + GEAR_OFFSET] = 100;
+    *packet.last_mut().unwrap() = 0x80; // suggested gear -128
+
+    match f1_2020::F1_2020::from_packet(&packet).expect("packet should decode") {
+        f1_2020::F1_2020::CarTelemetry(data) => {
+            assert!(matches!(
+                data.player_data().expect("player car").gear,
+                f1_2020::Gear::Unknown
+            ));
+            assert!(matches!(data.suggested_gear, f1_2020::Gear::Unknown));
+        }
+        _ => panic!("expected a CarTelemetry packet"),
+    }
+}
+
+#[test]
+fn f1_2022_car_telemetry_invalid_gear_falls_back_to_unknown() {
+    const CAR_SIZE: usize = 60;
+    const GEAR_OFFSET: usize = 15;
+
+    let mut packet = header(2022, 6);
+    packet.resize(HEADER_SIZE + 22 * CAR_SIZE + 3, 0);
+    packet[HEADER_SIZE + GEAR_OFFSET] = 100;
+    *packet.last_mut().unwrap() = 42;
+
+    match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+        f1_2022::F1_2022::CarTelemetry(data) => {
+            assert!(matches!(
+                data.player_data().expect("player car").gear,
+                f1_2022::Gear::Unknown
+            ));
+            assert!(matches!(data.suggested_gear, f1_2022::Gear::Unknown));
+        }
+        _ => panic!("expected a CarTelemetry packet"),
+    }
+}
+
+#[test]
+fn f1_2022_player_data_is_none_while_spectating() {
+    let mut packet = header(2022, 6);
+    packet[22] = 255; // player_car_index
+    packet.resize(HEADER_SIZE + 22 * 60 + 3, 0);
+
+    match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+        f1_2022::F1_2022::CarTelemetry(data) => assert!(data.player_data().is_none()),
+        _ => panic!("expected a CarTelemetry packet"),
+    }
+}
+
+#[test]
+fn f1_2022_truncated_packet_is_an_error() {
+    let mut packet = header(2022, 6);
+    packet.resize(HEADER_SIZE + 10, 0);
+
+    assert!(f1_2022::F1_2022::from_packet(&packet).is_err());
+}
+
+#[test]
+fn dirt_rally2_gear_is_rounded_and_invalid_gears_are_errors() {
+    const GEAR_OFFSET: usize = 132;
+
+    let decode = |gear: f32| {
+        let mut packet = vec![0; 264];
+        packet[GEAR_OFFSET..GEAR_OFFSET + 4].copy_from_slice(&gear.to_le_bytes());
+        rally2::DirtRally2::from_packet(&packet).map(|event| event.car.gear)
+    };
+
+    assert_eq!(decode(2.9999).unwrap(), rally2::Gear::Third);
+    assert_eq!(decode(10.0).unwrap(), rally2::Gear::Reverse);
+    assert_eq!(decode(3.0).unwrap().to_string(), "3");
+    assert!(decode(f32::NAN).is_err());
+    assert!(decode(42.0).is_err());
+}
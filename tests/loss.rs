@@ -0,0 +1,70 @@
+use cm_telemetry::loss::{FrameLossDetector, PacketLoss};
+use cm_telemetry::TelemetryFrame;
+
+struct Frame {
+    packet_id: u8,
+    frame_identifier: u32,
+}
+
+impl TelemetryFrame for Frame {
+    fn session_uid(&self) -> u64 {
+        1
+    }
+
+    fn frame_identifier(&self) -> u32 {
+        self.frame_identifier
+    }
+
+    fn packet_id(&self) -> u8 {
+        self.packet_id
+    }
+
+    fn session_time(&self) -> f32 {
+        self.frame_identifier as f32 / 60.0
+    }
+}
+
+fn frame(packet_id: u8, frame_identifier: u32) -> Frame {
+    Frame {
+        packet_id,
+        frame_identifier,
+    }
+}
+
+#[test]
+fn gaps_in_the_cadence_are_reported() {
+    let mut detector = FrameLossDetector::new();
+    assert_eq!(detector.observe(&frame(0, 0)), None);
+    assert_eq!(detector.observe(&frame(0, 3)), None);
+    assert_eq!(
+        detector.observe(&frame(0, 12)),
+        Some(PacketLoss {
+            session_uid: 1,
+            packet_id: 0,
+            frame_identifier: 12,
+            missed: 2,
+        })
+    );
+    assert_eq!(detector.missed(0), 2);
+}
+
+#[test]
+fn event_packets_between_motion_frames_are_not_losses() {
+    let mut detector = FrameLossDetector::new();
+    for (packet_id, frame_identifier) in [(0, 0), (3, 1), (0, 3), (3, 4), (3, 5), (0, 6), (3, 40)] {
+        assert_eq!(detector.observe(&frame(packet_id, frame_identifier)), None);
+    }
+    assert_eq!(detector.missed(3), 0);
+    assert_eq!(detector.received(3), 0);
+}
+
+#[test]
+fn ignored_packets_can_be_replaced() {
+    let mut detector = FrameLossDetector::new().with_ignored_packets(&[]);
+    detector.observe(&frame(3, 1));
+    detector.observe(&frame(3, 2));
+    assert_eq!(
+        detector.observe(&frame(3, 10)).map(|loss| loss.missed),
+        Some(7)
+    );
+}
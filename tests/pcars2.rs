@@ -0,0 +1,96 @@
+use cm_telemetry::pcars::pcars2::ProjectCars2;
+use cm_telemetry::TelemetryEvent;
+
+const HEADER_SIZE: usize = 12;
+
+// packet sizes of the "Patch 5" format, see SMS_UDP_Definitions.hpp
+const CAR_PHYSICS_SIZE: usize = 559;
+const CAR_PHYSICS_DECODED_SIZE: usize = 373; // the controller state and what follows are skipped
+const RACE_DEFINITION_SIZE: usize = 307;
+const PARTICIPANTS_SIZE: usize = 1136;
+const TIMINGS_SIZE: usize = 1063;
+const GAME_STATE_SIZE: usize = 22;
+
+fn packet(packet_type: u8, size: usize) -> Vec<u8> {
+    let mut bytes = vec![0; size];
+    bytes[10] = packet_type;
+    bytes
+}
+
+#[test]
+fn packets_decode_at_their_documented_size() {
+    // packet type, size sent by the game, bytes read by the decoder
+    let sizes = [
+        (0, CAR_PHYSICS_SIZE, CAR_PHYSICS_DECODED_SIZE),
+        (1, RACE_DEFINITION_SIZE, RACE_DEFINITION_SIZE),
+        (2, PARTICIPANTS_SIZE, PARTICIPANTS_SIZE),
+        (3, TIMINGS_SIZE, TIMINGS_SIZE),
+        (4, GAME_STATE_SIZE, GAME_STATE_SIZE),
+    ];
+    for (packet_type, size, decoded_size) in sizes {
+        for size in [size, decoded_size] {
+            let event = ProjectCars2::from_packet(&packet(packet_type, size))
+                .unwrap_or_else(|e| panic!("packet type {} should decode: {}", packet_type, e));
+            assert_eq!(event.header().packet_type, packet_type);
+        }
+
+        let truncated = packet(packet_type, decoded_size - 1);
+        assert!(
+            ProjectCars2::from_packet(&truncated).is_err(),
+            "packet type {} should not decode with {} bytes",
+            packet_type,
+            decoded_size - 1
+        );
+    }
+}
+
+#[test]
+fn car_physics_fields_are_at_their_offsets() {
+    const SPEED_OFFSET: usize = 36;
+    const GEAR_OFFSET: usize = 45;
+    const ENGINE_DAMAGE_OFFSET: usize = CAR_PHYSICS_DECODED_SIZE - 1;
+
+    let mut bytes = packet(0, CAR_PHYSICS_SIZE);
+    bytes[SPEED_OFFSET..SPEED_OFFSET + 4].copy_from_slice(&42.5f32.to_le_bytes());
+    bytes[GEAR_OFFSET] = 0x6f; // reverse, 6 gears
+    bytes[ENGINE_DAMAGE_OFFSET] = 200;
+
+    match ProjectCars2::from_packet(&bytes).expect("packet should decode") {
+        ProjectCars2::CarPhysics(data) => {
+            assert_eq!(data.speed, 42.5);
+            assert_eq!(data.gear(), -1);
+            assert_eq!(data.num_gears(), 6);
+            assert_eq!(data.engine_damage, 200);
+        }
+        _ => panic!("expected a CarPhysics packet"),
+    }
+}
+
+#[test]
+fn timings_participants_are_at_their_offsets() {
+    const PARTICIPANTS_OFFSET: usize = HEADER_SIZE + 1 + 4 * 5;
+    const PARTICIPANT_SIZE: usize = 32;
+
+    let mut bytes = packet(3, TIMINGS_SIZE);
+    bytes[HEADER_SIZE] = 2; // num_participants
+    let second = PARTICIPANTS_OFFSET + PARTICIPANT_SIZE;
+    bytes[second + 14] = 0x80 | 3; // race_position, active
+    bytes[TIMINGS_SIZE - 6..TIMINGS_SIZE - 4].copy_from_slice(&1u16.to_le_bytes());
+
+    match ProjectCars2::from_packet(&bytes).expect("packet should decode") {
+        ProjectCars2::Timings(data) => {
+            let active: Vec<_> = data.active_participants().collect();
+            assert_eq!(active.len(), 1);
+            assert_eq!(active[0].position(), 3);
+            assert_eq!(data.local_participant_index, 1);
+        }
+        _ => panic!("expected a Timings packet"),
+    }
+}
+
+#[test]
+fn packets_that_are_not_decoded_are_errors() {
+    for packet_type in [5, 6, 7, 8] {
+        assert!(ProjectCars2::from_packet(&packet(packet_type, 1500)).is_err());
+    }
+}
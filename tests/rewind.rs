@@ -0,0 +1,130 @@
+use cm_telemetry::analysis::damage::{DamageComponent, DamageEvent, DamageTracker};
+use cm_telemetry::analysis::rewind::{Rewindable, Rewound};
+use cm_telemetry::f1::f1_2022::F1_2022;
+use cm_telemetry::f1::util::CarIndex;
+use cm_telemetry::TelemetryEvent;
+
+// event packet of "F1 22" (packet_id 3) sent on frame_identifier of session_uid
+fn event(session_uid: u64, frame_identifier: u32, code: &[u8; 4], details: &[u8]) -> F1_2022 {
+    let mut packet = vec![0; 24];
+    packet[0..2].copy_from_slice(&2022u16.to_le_bytes());
+    packet[5] = 3;
+    packet[6..14].copy_from_slice(&session_uid.to_le_bytes());
+    packet[18..22].copy_from_slice(&frame_identifier.to_le_bytes());
+    packet.extend_from_slice(code);
+    packet.extend_from_slice(details);
+    packet.resize(40, 0);
+    F1_2022::from_packet(&packet).expect("packet should decode")
+}
+
+fn flashback(session_uid: u64, frame_identifier: u32, session_time: f32) -> F1_2022 {
+    let mut details = frame_identifier.to_le_bytes().to_vec();
+    details.extend_from_slice(&session_time.to_le_bytes());
+    // sent on a later frame than the ones flashed back over
+    event(session_uid, 1000, b"FLBK", &details)
+}
+
+// count is the state: the number of packets applied
+fn count(state: &mut u32, _: &F1_2022) {
+    *state += 1;
+}
+
+#[test]
+fn flashback_restores_the_snapshot_before_the_target_frame() {
+    let mut rewindable = Rewindable::new(0u32).with_snapshots(10, 10);
+    for frame in 0..50 {
+        assert_eq!(
+            rewindable.observe(&event(1, frame, b"SSTA", &[]), count),
+            None
+        );
+    }
+    assert_eq!(*rewindable.state(), 50);
+    assert_eq!(rewindable.snapshots(), 5); // frames 0, 10, 20, 30 and 40
+
+    let rewound = rewindable.observe(&flashback(1, 25, 0.5), count);
+    assert_eq!(
+        rewound,
+        Some(Rewound {
+            frame_identifier: 25,
+            session_time: 0.5,
+            snapshot_frame: Some(20),
+        })
+    );
+    // the state before the packets of frame 20
+    assert_eq!(*rewindable.state(), 20);
+    assert_eq!(rewindable.snapshots(), 3);
+}
+
+#[test]
+fn flashback_older_than_the_oldest_snapshot_keeps_the_state() {
+    let mut rewindable = Rewindable::new(0u32).with_snapshots(2, 10);
+    for frame in 0..50 {
+        rewindable.observe(&event(1, frame, b"SSTA", &[]), count);
+    }
+    assert_eq!(rewindable.snapshots(), 2); // frames 30 and 40
+
+    let rewound = rewindable.observe(&flashback(1, 25, 0.5), count);
+    assert_eq!(rewound.map(|rewound| rewound.snapshot_frame), Some(None));
+    assert_eq!(*rewindable.state(), 50);
+}
+
+#[test]
+fn new_session_clears_the_snapshots() {
+    let mut rewindable = Rewindable::new(0u32).with_snapshots(10, 10);
+    for frame in 0..30 {
+        rewindable.observe(&event(1, frame, b"SSTA", &[]), count);
+    }
+    assert_eq!(rewindable.snapshots(), 3);
+
+    rewindable.observe(&event(2, 0, b"SSTA", &[]), count);
+    assert_eq!(rewindable.snapshots(), 1);
+
+    // the snapshots of session 1 are gone: frame 20 rewinds to frame 0 of session 2
+    let rewound = rewindable.observe(&flashback(2, 20, 0.5), count);
+    assert_eq!(rewound.map(|rewound| rewound.snapshot_frame), Some(Some(0)));
+    assert_eq!(*rewindable.state(), 30);
+}
+
+// CarDamage packet of "F1 22" (packet_id 10) where the first car has front_left_wing damage
+fn car_damage(session_uid: u64, frame_identifier: u32, front_left_wing: u8) -> F1_2022 {
+    let mut packet = vec![0; 24];
+    packet[0..2].copy_from_slice(&2022u16.to_le_bytes());
+    packet[5] = 10;
+    packet[6..14].copy_from_slice(&session_uid.to_le_bytes());
+    packet[18..22].copy_from_slice(&frame_identifier.to_le_bytes());
+    packet.resize(24 + 22 * 42, 0);
+    packet[24 + 24] = front_left_wing; // after the tyres wear (f32), tyres and brakes damage
+    F1_2022::from_packet(&packet).expect("packet should decode")
+}
+
+#[test]
+fn flashback_rewinds_a_damage_tracker() {
+    let mut rewindable = Rewindable::new(DamageTracker::new(1)).with_snapshots(10, 10);
+    let mut events = Vec::new();
+    let mut observe = |rewindable: &mut Rewindable<DamageTracker>, event: &F1_2022| {
+        rewindable.observe(event, |tracker, event| {
+            events.extend(tracker.observe(event))
+        })
+    };
+
+    for frame in 0..30 {
+        let wing = if frame < 20 { 0 } else { 40 };
+        observe(&mut rewindable, &car_damage(1, frame, wing));
+    }
+    let rewound = observe(&mut rewindable, &flashback(1, 15, 0.5));
+    assert_eq!(
+        rewound.map(|rewound| rewound.snapshot_frame),
+        Some(Some(10))
+    );
+
+    // the wing was damaged again after the flashback, the tracker reports it a second time
+    observe(&mut rewindable, &car_damage(1, 16, 0));
+    observe(&mut rewindable, &car_damage(1, 20, 40));
+    let damage = DamageEvent::Damage {
+        car: CarIndex(0),
+        component: DamageComponent::FrontLeftWing,
+        from: 0,
+        to: 40,
+    };
+    assert_eq!(events, vec![damage, damage]);
+}
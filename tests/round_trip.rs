@@ -0,0 +1,2228 @@
+// Round-trip tests: random field values are encoded with the layout of the UDP specification,
+// decoded with the crate and compared back, so any drift of a packet layout shows up as a
+// mismatch on the first field after it.
+// Every F1 2020 and 2022 packet type is covered, SessionHistory only for 2022 (the packet was
+// added by "F1 21"). There is no encoder in the crate yet, the Writer below encodes them.
+
+use cm_telemetry::dirt::rally2;
+use cm_telemetry::f1::util::ButtonFlags;
+use cm_telemetry::f1::{f1_2020, f1_2022, player};
+use cm_telemetry::TelemetryEvent;
+use proptest::prelude::*;
+
+const CARS: usize = 22;
+
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u8(&mut self, value: u8) -> &mut Self {
+        self.0.push(value);
+        self
+    }
+
+    fn i8(&mut self, value: i8) -> &mut Self {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn u16(&mut self, value: u16) -> &mut Self {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn i16(&mut self, value: i16) -> &mut Self {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn u32(&mut self, value: u32) -> &mut Self {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn u64(&mut self, value: u64) -> &mut Self {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn f32(&mut self, value: f32) -> &mut Self {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn f64(&mut self, value: f64) -> &mut Self {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn bytes(&mut self, values: &[u8]) -> &mut Self {
+        self.0.extend_from_slice(values);
+        self
+    }
+
+    // name writes a null terminated string padded to 48 bytes
+    fn name(&mut self, name: &[u8]) -> &mut Self {
+        let mut bytes = [0; 48];
+        bytes[..name.len()].copy_from_slice(name);
+        self.bytes(&bytes)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Header {
+    packet_format: u16,
+    game_major_version: u8,
+    game_minor_version: u8,
+    packet_version: u8,
+    packet_id: u8,
+    session_uid: u64,
+    session_time: f32,
+    frame_identifier: u32,
+    player_car_index: u8,
+    secondary_player_car_index: u8,
+}
+
+impl Header {
+    fn write(&self, writer: &mut Writer) {
+        writer
+            .u16(self.packet_format)
+            .u8(self.game_major_version)
+            .u8(self.game_minor_version)
+            .u8(self.packet_version)
+            .u8(self.packet_id)
+            .u64(self.session_uid)
+            .f32(self.session_time)
+            .u32(self.frame_identifier)
+            .u8(self.player_car_index)
+            .u8(self.secondary_player_car_index);
+    }
+}
+
+// both years share the header layout, f1_2020::Header is the same struct shape
+macro_rules! assert_header {
+    ($decoded:expr, $header:expr) => {
+        let (decoded, header) = (&$decoded, &$header);
+        prop_assert_eq!(decoded.packet_format, header.packet_format);
+        prop_assert_eq!(decoded.game_major_version, header.game_major_version);
+        prop_assert_eq!(decoded.game_minor_version, header.game_minor_version);
+        prop_assert_eq!(decoded.packet_version, header.packet_version);
+        prop_assert_eq!(decoded.packet_id, header.packet_id);
+        prop_assert_eq!(decoded.session_uid, header.session_uid);
+        prop_assert_eq!(
+            decoded.session_time.to_bits(),
+            header.session_time.to_bits()
+        );
+        prop_assert_eq!(decoded.frame_identifier, header.frame_identifier);
+        prop_assert_eq!(decoded.player_car_index.0, header.player_car_index);
+        prop_assert_eq!(
+            decoded.secondary_player_car_index.0,
+            header.secondary_player_car_index
+        );
+    };
+}
+
+prop_compose! {
+    fn header(packet_format: u16, packet_id: u8)(
+        game_major_version: u8,
+        game_minor_version: u8,
+        packet_version: u8,
+        session_uid: u64,
+        session_time: f32,
+        frame_identifier: u32,
+        player_car_index in 0..CARS as u8,
+        secondary_player_car_index: u8,
+    ) -> Header {
+        Header {
+            packet_format,
+            game_major_version,
+            game_minor_version,
+            packet_version,
+            packet_id,
+            session_uid,
+            session_time,
+            frame_identifier,
+            player_car_index,
+            secondary_player_car_index,
+        }
+    }
+}
+
+fn cars<T: core::fmt::Debug>(car: impl Strategy<Value = T>) -> impl Strategy<Value = Vec<T>> {
+    prop::collection::vec(car, CARS)
+}
+
+// MOTION (same layout in 2020 and 2022)
+
+#[derive(Debug, Clone)]
+struct CarMotion {
+    world_position: [f32; 3],
+    world_velocity: [f32; 3],
+    world_forward_dir: [i16; 3],
+    world_right_dir: [i16; 3],
+    g_force: [f32; 3], // lateral, longitudinal, vertical
+    angles: [f32; 3],  // yaw, pitch, roll
+}
+
+fn car_motion() -> impl Strategy<Value = CarMotion> {
+    (
+        any::<[f32; 3]>(),
+        any::<[f32; 3]>(),
+        any::<[i16; 3]>(),
+        any::<[i16; 3]>(),
+        any::<[f32; 3]>(),
+        any::<[f32; 3]>(),
+    )
+        .prop_map(
+            |(
+                world_position,
+                world_velocity,
+                world_forward_dir,
+                world_right_dir,
+                g_force,
+                angles,
+            )| {
+                CarMotion {
+                    world_position,
+                    world_velocity,
+                    world_forward_dir,
+                    world_right_dir,
+                    g_force,
+                    angles,
+                }
+            },
+        )
+}
+
+fn motion_packet(header: &Header, cars: &[CarMotion], extra: &[f32; 30]) -> Vec<u8> {
+    let mut writer = Writer::default();
+    header.write(&mut writer);
+    for car in cars {
+        for v in &car.world_position {
+            writer.f32(*v);
+        }
+        for v in &car.world_velocity {
+            writer.f32(*v);
+        }
+        for v in &car.world_forward_dir {
+            writer.i16(*v);
+        }
+        for v in &car.world_right_dir {
+            writer.i16(*v);
+        }
+        for v in &car.g_force {
+            writer.f32(*v);
+        }
+        for v in &car.angles {
+            writer.f32(*v);
+        }
+    }
+    for v in extra {
+        writer.f32(*v);
+    }
+    writer.0
+}
+
+macro_rules! assert_motion {
+    ($data:expr, $cars:expr, $extra:expr) => {
+        let (data, cars, extra) = (&$data, &$cars, &$extra);
+        for (decoded, car) in data.car_motion_data.iter().zip(cars.iter()) {
+            let position = &decoded.world_position;
+            let velocity = &decoded.world_velocity;
+            prop_assert_eq!(
+                [position.x, position.y, position.z].map(f32::to_bits),
+                car.world_position.map(f32::to_bits)
+            );
+            prop_assert_eq!(
+                [velocity.x, velocity.y, velocity.z].map(f32::to_bits),
+                car.world_velocity.map(f32::to_bits)
+            );
+            let forward = &decoded.world_forward_dir;
+            let right = &decoded.world_right_dir;
+            prop_assert_eq!([forward.x, forward.y, forward.z], car.world_forward_dir);
+            prop_assert_eq!([right.x, right.y, right.z], car.world_right_dir);
+            prop_assert_eq!(
+                [
+                    decoded.g_force_lateral,
+                    decoded.g_force_longitudinal,
+                    decoded.g_force_vertical
+                ]
+                .map(f32::to_bits),
+                car.g_force.map(f32::to_bits)
+            );
+            prop_assert_eq!(
+                [decoded.yaw, decoded.pitch, decoded.roll].map(f32::to_bits),
+                car.angles.map(f32::to_bits)
+            );
+        }
+        let wheel_speed = &data.wheel_speed;
+        prop_assert_eq!(
+            [
+                wheel_speed.rear_left,
+                wheel_speed.rear_right,
+                wheel_speed.front_left,
+                wheel_speed.front_right
+            ]
+            .map(f32::to_bits),
+            [extra[12], extra[13], extra[14], extra[15]].map(f32::to_bits)
+        );
+        prop_assert_eq!(data.front_wheel_angle.to_bits(), extra[29].to_bits());
+    };
+}
+
+// CAR TELEMETRY
+
+#[derive(Debug, Clone)]
+struct CarTelemetry {
+    speed: u16,
+    pedals: [f32; 3], // throttle, steer, brake
+    clutch: u8,
+    gear: i8,
+    engine_rpm: u16,
+    drs: u8,
+    rev_lights_percent: u8,
+    rev_lights_bit_value: u16, // 2022 only
+    brake_temp: [u16; 4],
+    tyres_surface_temp: [u8; 4],
+    tyres_inner_temp: [u8; 4],
+    engine_temp: u16,
+    tyres_pressure: [f32; 4],
+    surface_type: [u8; 4],
+}
+
+fn car_telemetry() -> impl Strategy<Value = CarTelemetry> {
+    (
+        (any::<u16>(), any::<[f32; 3]>(), any::<u8>(), -1i8..=8),
+        (any::<u16>(), any::<u8>(), any::<u8>(), any::<u16>()),
+        (any::<[u16; 4]>(), any::<[u8; 4]>(), any::<[u8; 4]>()),
+        (
+            any::<u16>(),
+            any::<[f32; 4]>(),
+            prop::array::uniform4(0u8..=11),
+        ),
+    )
+        .prop_map(|(a, b, c, d)| CarTelemetry {
+            speed: a.0,
+            pedals: a.1,
+            clutch: a.2,
+            gear: a.3,
+            engine_rpm: b.0,
+            drs: b.1,
+            rev_lights_percent: b.2,
+            rev_lights_bit_value: b.3,
+            brake_temp: c.0,
+            tyres_surface_temp: c.1,
+            tyres_inner_temp: c.2,
+            engine_temp: d.0,
+            tyres_pressure: d.1,
+            surface_type: d.2,
+        })
+}
+
+fn car_telemetry_packet(header: &Header, cars: &[CarTelemetry], year: u16) -> Writer {
+    let mut writer = Writer::default();
+    header.write(&mut writer);
+    for car in cars {
+        writer.u16(car.speed);
+        for v in &car.pedals {
+            writer.f32(*v);
+        }
+        writer
+            .u8(car.clutch)
+            .i8(car.gear)
+            .u16(car.engine_rpm)
+            .u8(car.drs)
+            .u8(car.rev_lights_percent);
+        if year == 2022 {
+            writer.u16(car.rev_lights_bit_value);
+        }
+        for v in &car.brake_temp {
+            writer.u16(*v);
+        }
+        for v in &car.tyres_surface_temp {
+            writer.u8(*v);
+        }
+        for v in &car.tyres_inner_temp {
+            writer.u8(*v);
+        }
+        writer.u16(car.engine_temp);
+        for v in &car.tyres_pressure {
+            writer.f32(*v);
+        }
+        for v in &car.surface_type {
+            writer.u8(*v);
+        }
+    }
+    writer
+}
+
+macro_rules! assert_car_telemetry {
+    ($decoded:expr, $car:expr) => {
+        let (decoded, car) = (&$decoded, &$car);
+        prop_assert_eq!(decoded.speed, car.speed);
+        prop_assert_eq!(
+            [decoded.throttle, decoded.steer, decoded.brake].map(f32::to_bits),
+            car.pedals.map(f32::to_bits)
+        );
+        prop_assert_eq!(decoded.clutch, car.clutch);
+        prop_assert_eq!(decoded.gear as i8, car.gear);
+        prop_assert_eq!(decoded.engine_rpm, car.engine_rpm);
+        prop_assert_eq!(decoded.drs, car.drs > 0);
+        prop_assert_eq!(decoded.rev_lights_percent, car.rev_lights_percent);
+        let brakes = &decoded.brake_temp;
+        prop_assert_eq!(
+            [
+                brakes.rear_left,
+                brakes.rear_right,
+                brakes.front_left,
+                brakes.front_right
+            ],
+            car.brake_temp
+        );
+        let surface = &decoded.tyres_surface_temp;
+        prop_assert_eq!(
+            [
+                surface.rear_left,
+                surface.rear_right,
+                surface.front_left,
+                surface.front_right
+            ],
+            car.tyres_surface_temp
+        );
+        let inner = &decoded.tyres_inner_temp;
+        prop_assert_eq!(
+            [
+                inner.rear_left,
+                inner.rear_right,
+                inner.front_left,
+                inner.front_right
+            ],
+            car.tyres_inner_temp
+        );
+        prop_assert_eq!(decoded.engine_temp, car.engine_temp);
+        let pressure = &decoded.tyres_pressure;
+        prop_assert_eq!(
+            [
+                pressure.rear_left,
+                pressure.rear_right,
+                pressure.front_left,
+                pressure.front_right
+            ]
+            .map(f32::to_bits),
+            car.tyres_pressure.map(f32::to_bits)
+        );
+        let surfaces = &decoded.surface_type;
+        prop_assert_eq!(
+            [
+                surfaces.rear_left as u8,
+                surfaces.rear_right as u8,
+                surfaces.front_left as u8,
+                surfaces.front_right as u8
+            ],
+            car.surface_type
+        );
+    };
+}
+
+// CAR DAMAGE (2022)
+
+// tyres wear, then every other damage value
+fn car_damage_packet(header: &Header, cars: &[([f32; 4], [u8; 26])]) -> Vec<u8> {
+    let mut writer = Writer::default();
+    header.write(&mut writer);
+    for (tyres_wear, damage) in cars {
+        for wear in tyres_wear {
+            writer.f32(*wear);
+        }
+        writer.bytes(damage);
+    }
+    writer.0
+}
+
+// names are sent null terminated in 48 bytes
+fn name() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(b'a'..=b'z', 1..48)
+}
+
+// valid values of enums whose discriminants are not contiguous
+const DRIVERS: &[u8] = &[0, 1, 2, 6];
+const TYRE_VISUALS: &[u8] = &[7, 8, 16, 17, 18];
+
+// SESSION
+
+#[derive(Debug, Clone)]
+struct ForecastSample {
+    session_type: u8,
+    time_offset: u8,
+    weather: u8,
+    temperatures: [i8; 2], // track, air
+    changes: [i8; 2],      // track, air (2022 only)
+    rain_percentage: u8,   // 2022 only
+}
+
+prop_compose! {
+    fn forecast_sample()(
+        session_type in 0u8..=12,
+        time_offset: u8,
+        weather in 0u8..=5,
+        temperatures: [i8; 2],
+        changes in prop::array::uniform2(0i8..=2),
+        rain_percentage in 0u8..=100,
+    ) -> ForecastSample {
+        ForecastSample {
+            session_type,
+            time_offset,
+            weather,
+            temperatures,
+            changes,
+            rain_percentage,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Session {
+    weather: u8,
+    temperatures: [i8; 2], // track, air
+    total_laps: u8,
+    track_length: u16,
+    session_type: u8,
+    track: i8,
+    formula: u8,
+    times: [u16; 2], // session_time_left, session_duration
+    // pit_speed_limit, game_paused, is_spectating, spectator_car_index, sli_pro_native_support
+    settings: [u8; 5],
+    marshal_zones: (u8, Vec<(f32, i8)>), // number in use, the 21 zones (start, flag)
+    safety_car_status: u8,
+    network_game: u8,
+    forecast: (u8, Vec<ForecastSample>), // number in use, the 56 samples (20 sent in 2020)
+}
+
+prop_compose! {
+    fn session()(
+        (weather, temperatures, total_laps, track_length) in
+            (0u8..=5, any::<[i8; 2]>(), 0u8..=127, 0u16..=i16::MAX as u16),
+        (session_type, track, formula) in (0u8..=12, -1i8..=12, 0u8..=3),
+        times: [u16; 2],
+        settings: [u8; 5],
+        marshal_zones in (0u8..=21, prop::collection::vec((any::<f32>(), -1i8..=4), 21)),
+        safety_car_status in 0u8..=2,
+        network_game: u8,
+        forecast in (0u8..=20, prop::collection::vec(forecast_sample(), 56)),
+    ) -> Session {
+        Session {
+            weather,
+            temperatures,
+            total_laps,
+            track_length,
+            session_type,
+            track,
+            formula,
+            times,
+            settings,
+            marshal_zones,
+            safety_car_status,
+            network_game,
+            forecast,
+        }
+    }
+}
+
+// fields sent after the weather forecast since "F1 21"
+#[derive(Debug, Clone)]
+struct SessionSettings {
+    forecast_accuracy: u8,
+    ai_difficulty: u8,
+    link_identifiers: [u32; 3], // season, weekend, session
+    pit_stop_window: [u8; 3],   // ideal lap, latest lap, rejoin position
+    assists: [u8; 5],           // steering, pit, pit release, ers, drs
+    braking_assist: u8,
+    gearbox_assist: u8,
+    racing_line: [u8; 2], // dynamic_racing_line, dynamic_racing_line_type
+    game_mode: u8,
+    rule_set: u8,
+    time_of_day: u32,
+    session_length: u8,
+}
+
+prop_compose! {
+    fn session_settings()(
+        (forecast_accuracy, ai_difficulty) in (0u8..=1, 0u8..=110),
+        link_identifiers: [u32; 3],
+        pit_stop_window: [u8; 3],
+        assists in prop::array::uniform5(0u8..=1),
+        (braking_assist, gearbox_assist) in (0u8..=3, 1u8..=3),
+        racing_line in (0u8..=2, 0u8..=1),
+        (game_mode, rule_set) in (prop::sample::select(&[0u8, 3, 5][..]), 0u8..=2),
+        time_of_day: u32,
+        session_length in prop::sample::select(&[0u8, 2, 3, 4][..]),
+    ) -> SessionSettings {
+        SessionSettings {
+            forecast_accuracy,
+            ai_difficulty,
+            link_identifiers,
+            pit_stop_window,
+            assists,
+            braking_assist,
+            gearbox_assist,
+            racing_line: [racing_line.0, racing_line.1],
+            game_mode,
+            rule_set,
+            time_of_day,
+            session_length,
+        }
+    }
+}
+
+fn session_packet(header: &Header, session: &Session, year: u16) -> Writer {
+    let mut writer = Writer::default();
+    header.write(&mut writer);
+    writer
+        .u8(session.weather)
+        .i8(session.temperatures[0])
+        .i8(session.temperatures[1])
+        .u8(session.total_laps)
+        .u16(session.track_length)
+        .u8(session.session_type)
+        .i8(session.track)
+        .u8(session.formula)
+        .u16(session.times[0])
+        .u16(session.times[1])
+        .bytes(&session.settings)
+        .u8(session.marshal_zones.0);
+    for (zone_start, zone_flag) in &session.marshal_zones.1 {
+        writer.f32(*zone_start).i8(*zone_flag);
+    }
+    writer
+        .u8(session.safety_car_status)
+        .u8(session.network_game)
+        .u8(session.forecast.0);
+    let samples = if year == 2022 { 56 } else { 20 };
+    for sample in &session.forecast.1[..samples] {
+        writer
+            .u8(sample.session_type)
+            .u8(sample.time_offset)
+            .u8(sample.weather)
+            .i8(sample.temperatures[0]);
+        if year == 2022 {
+            writer.i8(sample.changes[0]);
+        }
+        writer.i8(sample.temperatures[1]);
+        if year == 2022 {
+            writer.i8(sample.changes[1]).u8(sample.rain_percentage);
+        }
+    }
+    writer
+}
+
+// marshal zones and forecast samples are only compared up to the number in use in 2022
+macro_rules! assert_session {
+    ($data:expr, $session:expr) => {
+        let (data, session) = (&$data, &$session);
+        prop_assert_eq!(data.weather as u8, session.weather);
+        prop_assert_eq!(
+            [data.track_temperature, data.air_temperature],
+            session.temperatures
+        );
+        prop_assert_eq!(data.total_laps as u8, session.total_laps);
+        prop_assert_eq!(data.track_length as u16, session.track_length);
+        prop_assert_eq!(data.session_type as u8, session.session_type);
+        prop_assert_eq!(data.track as i8, session.track);
+        prop_assert_eq!(data.formula as u8, session.formula);
+        prop_assert_eq!(
+            [data.session_time_left, data.session_duration],
+            session.times
+        );
+        prop_assert_eq!(
+            [
+                data.pit_speed_limit,
+                data.game_paused,
+                data.is_spectating,
+                data.spectator_car_index.0,
+                data.sli_pro_native_support
+            ],
+            session.settings
+        );
+        prop_assert_eq!(data.number_of_marshal_zones, session.marshal_zones.0);
+        for (decoded, (zone_start, zone_flag)) in
+            data.marshal_zones.iter().zip(&session.marshal_zones.1)
+        {
+            prop_assert_eq!(decoded.zone_start.to_bits(), zone_start.to_bits());
+            prop_assert_eq!(decoded.zone_flag as i8, *zone_flag);
+        }
+        prop_assert_eq!(data.safety_car_status as u8, session.safety_car_status);
+        prop_assert_eq!(data.network_game, session.network_game > 0);
+        prop_assert_eq!(data.number_of_weather_forecast_samples, session.forecast.0);
+        for (decoded, sample) in data
+            .weather_forecast_samples
+            .iter()
+            .zip(&session.forecast.1)
+        {
+            prop_assert_eq!(decoded.session_type as u8, sample.session_type);
+            prop_assert_eq!(decoded.time_offset, sample.time_offset);
+            prop_assert_eq!(decoded.weather as u8, sample.weather);
+            prop_assert_eq!(
+                [decoded.track_temperature, decoded.air_temperature],
+                sample.temperatures
+            );
+        }
+    };
+}
+
+// LAP DATA
+
+#[derive(Debug, Clone)]
+struct Lap2020 {
+    times: [f32; 3], // last_lap_time, current_lap_time, best_lap_time
+    sector_times: [u16; 2],
+    best_lap_number: u8,
+    best_lap_sector_times: [u16; 3],
+    best_overall_sector_times: [(u16, u8); 3], // time, lap number
+    distances: [f32; 3],                       // lap_distance, total_distance, safety_car_delta
+    positions: [u8; 2],                        // car_position, current_lap_number
+    statuses: (u8, u8, u8, u8),                // pit_status, sector, driver_status, result_status
+    counters: [u8; 3],                         // current_lap_invalid, penalties, grid_position
+}
+
+prop_compose! {
+    fn lap_2020()(
+        times: [f32; 3],
+        sector_times: [u16; 2],
+        best_lap_number: u8,
+        best_lap_sector_times: [u16; 3],
+        best_overall_sector_times: [(u16, u8); 3],
+        distances: [f32; 3],
+        positions: [u8; 2],
+        statuses in (0u8..=2, 0u8..=2, 0u8..=4, 0u8..=7),
+        counters: [u8; 3],
+    ) -> Lap2020 {
+        Lap2020 {
+            times,
+            sector_times,
+            best_lap_number,
+            best_lap_sector_times,
+            best_overall_sector_times,
+            distances,
+            positions,
+            statuses,
+            counters,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Lap2022 {
+    times: [u32; 2], // last_lap_time_ms, current_lap_time_ms
+    sector_times: [u16; 2],
+    distances: [f32; 3],        // lap_distance, total_distance, safety_car_delta
+    positions: [u8; 2],         // car_position, current_lap_number
+    statuses: (u8, u8, u8, u8), // pit_status, sector, driver_status, result_status
+    // num_pit_stops, current_lap_invalid, penalties, warnings, num_unserved_drive_through,
+    // num_unserved_stop_go, grid_position
+    counters: [u8; 7],
+    pit_lane_timer_active: u8,
+    pit_timers: [u16; 2], // pit_lane_time_in_lane_ms, pit_stop_timer_ms
+    pit_stop_should_serve_penalty: u8,
+}
+
+prop_compose! {
+    fn lap_2022()(
+        times: [u32; 2],
+        sector_times: [u16; 2],
+        distances: [f32; 3],
+        positions: [u8; 2],
+        statuses in (0u8..=2, 0u8..=2, 0u8..=4, 0u8..=7),
+        counters: [u8; 7],
+        pit_lane_timer_active: u8,
+        pit_timers: [u16; 2],
+        pit_stop_should_serve_penalty: u8,
+    ) -> Lap2022 {
+        Lap2022 {
+            times,
+            sector_times,
+            distances,
+            positions,
+            statuses,
+            counters,
+            pit_lane_timer_active,
+            pit_timers,
+            pit_stop_should_serve_penalty,
+        }
+    }
+}
+
+// EVENT
+
+fn event_packet(header: &Header, code: &[u8; 4], details: &Writer, size: usize) -> Vec<u8> {
+    let mut writer = Writer::default();
+    header.write(&mut writer);
+    writer.bytes(code).bytes(&details.0);
+    writer.0.resize(size, 0); // event details are a padded union
+    writer.0
+}
+
+prop_compose! {
+    // penalty_type, infringement_type, vehicle_index, other_vehicle_index, time, lap_number,
+    // places_gained
+    fn penalty()(
+        (penalty_type, infringement_type) in (0u8..=5, 0u8..=5),
+        rest: [u8; 5],
+    ) -> [u8; 7] {
+        [penalty_type, infringement_type, rest[0], rest[1], rest[2], rest[3], rest[4]]
+    }
+}
+
+macro_rules! assert_penalty {
+    ($decoded:expr, $penalty:expr) => {
+        let (decoded, penalty) = (&$decoded, &$penalty);
+        prop_assert_eq!(
+            [
+                decoded.penalty_type as u8,
+                decoded.infrigement_type as u8,
+                decoded.vehicle_index.0,
+                decoded.other_vehicle_index.0,
+                decoded.time,
+                decoded.lap_number,
+                decoded.places_gained
+            ],
+            *penalty
+        );
+    };
+}
+
+// PARTICIPANTS AND LOBBY INFO
+
+#[derive(Debug, Clone)]
+struct Participant {
+    ai_controlled: u8,
+    driver: u8,
+    network_id: u8, // 2022 only
+    team: u8,
+    my_team: u8, // 2022 only
+    race_number: u8,
+    nationality: u8,
+    name: Vec<u8>,
+    telemetry: u8, // your_telemetry_restricted in 2020, your_telemetry_public in 2022
+    status: u8,    // lobby status
+}
+
+prop_compose! {
+    fn participant()(
+        ai_controlled in 0u8..=1,
+        driver in prop::sample::select(DRIVERS),
+        network_id: u8,
+        (team, my_team) in (0u8..=5, 0u8..=1),
+        race_number: u8,
+        nationality in 0u8..=4,
+        name in name(),
+        telemetry in 0u8..=1,
+        status in 0u8..=2,
+    ) -> Participant {
+        Participant {
+            ai_controlled,
+            driver,
+            network_id,
+            team,
+            my_team,
+            race_number,
+            nationality,
+            name,
+            telemetry,
+            status,
+        }
+    }
+}
+
+// CAR SETUP (same layout in 2020 and 2022)
+
+#[derive(Debug, Clone)]
+struct CarSetup {
+    wing: [u8; 2],     // front, rear
+    throttle: [u8; 2], // on, off
+    camber: [f32; 2],
+    toe: [f32; 2],
+    suspension: [u8; 2],
+    anti_roll_bar: [u8; 2],
+    suspension_height: [u8; 2],
+    brake: [u8; 2], // pressure, bias
+    tyre_pressure: [f32; 4],
+    ballast: u8,
+    fuel_load: f32,
+}
+
+prop_compose! {
+    fn car_setup()(
+        (wing, throttle) in (any::<[u8; 2]>(), any::<[u8; 2]>()),
+        (camber, toe) in (any::<[f32; 2]>(), any::<[f32; 2]>()),
+        suspension: [u8; 2],
+        anti_roll_bar: [u8; 2],
+        suspension_height: [u8; 2],
+        brake: [u8; 2],
+        tyre_pressure: [f32; 4],
+        ballast: u8,
+        fuel_load: f32,
+    ) -> CarSetup {
+        CarSetup {
+            wing,
+            throttle,
+            camber,
+            toe,
+            suspension,
+            anti_roll_bar,
+            suspension_height,
+            brake,
+            tyre_pressure,
+            ballast,
+            fuel_load,
+        }
+    }
+}
+
+fn car_setup_packet(header: &Header, cars: &[CarSetup]) -> Vec<u8> {
+    let mut writer = Writer::default();
+    header.write(&mut writer);
+    for car in cars {
+        writer
+            .bytes(&car.wing)
+            .bytes(&car.throttle)
+            .f32(car.camber[0])
+            .f32(car.camber[1])
+            .f32(car.toe[0])
+            .f32(car.toe[1])
+            .bytes(&car.suspension)
+            .bytes(&car.anti_roll_bar)
+            .bytes(&car.suspension_height)
+            .bytes(&car.brake);
+        for v in &car.tyre_pressure {
+            writer.f32(*v);
+        }
+        writer.u8(car.ballast).f32(car.fuel_load);
+    }
+    writer.0
+}
+
+macro_rules! assert_car_setup {
+    ($data:expr, $cars:expr) => {
+        let (data, cars) = (&$data, &$cars);
+        for (decoded, car) in data.car_setup_data.iter().zip(cars.iter()) {
+            prop_assert_eq!([decoded.wing.front, decoded.wing.rear], car.wing);
+            prop_assert_eq!([decoded.on_throttle, decoded.off_throttle], car.throttle);
+            prop_assert_eq!(
+                [
+                    decoded.camber.front,
+                    decoded.camber.rear,
+                    decoded.toe.front,
+                    decoded.toe.rear
+                ]
+                .map(f32::to_bits),
+                [car.camber[0], car.camber[1], car.toe[0], car.toe[1]].map(f32::to_bits)
+            );
+            prop_assert_eq!(
+                [
+                    decoded.suspension.front,
+                    decoded.suspension.rear,
+                    decoded.anti_roll_bar.front,
+                    decoded.anti_roll_bar.rear,
+                    decoded.suspension_height.front,
+                    decoded.suspension_height.rear,
+                    decoded.brake_pressure,
+                    decoded.brake_bias
+                ],
+                [
+                    car.suspension[0],
+                    car.suspension[1],
+                    car.anti_roll_bar[0],
+                    car.anti_roll_bar[1],
+                    car.suspension_height[0],
+                    car.suspension_height[1],
+                    car.brake[0],
+                    car.brake[1]
+                ]
+            );
+            let pressure = &decoded.type_pressure;
+            prop_assert_eq!(
+                [
+                    pressure.rear_left,
+                    pressure.rear_right,
+                    pressure.front_left,
+                    pressure.front_right
+                ]
+                .map(f32::to_bits),
+                car.tyre_pressure.map(f32::to_bits)
+            );
+            prop_assert_eq!(decoded.ballast, car.ballast);
+            prop_assert_eq!(decoded.fuel_load.to_bits(), car.fuel_load.to_bits());
+        }
+    };
+}
+
+// CAR STATUS
+
+#[derive(Debug, Clone)]
+struct CarStatus {
+    assists: (u8, u8, u8, u8, u8), // traction_control, anti_lock_brakes, fuel_mix, bias, limiter
+    fuel: [f32; 3],                // in tank, capacity, remaining laps
+    rpm: [u16; 2],                 // max, idle
+    max_gears: u8,
+    drs: (u8, u16),      // allowed, activation distance
+    tyres: (u8, u8, u8), // compound, visual, age in laps
+    vehicle_fia_flag: i8,
+    ers: (f32, u8, [f32; 3]), // stored energy, deploy mode, harvested mguk, mguh, deployed
+    // 2020 only: tyres wear, tyres damage, wing damage, drs fault, engine and gearbox damage
+    damage: ([u8; 4], [u8; 4], [u8; 3], u8, [u8; 2]),
+    network_paused: u8, // 2022 only
+}
+
+prop_compose! {
+    fn car_status()(
+        assists in (any::<u8>(), 0u8..=1, 0u8..=3, any::<u8>(), 0u8..=1),
+        fuel: [f32; 3],
+        rpm: [u16; 2],
+        max_gears: u8,
+        drs in (0u8..=1, any::<u16>()),
+        tyres in (7u8..=12, prop::sample::select(TYRE_VISUALS), any::<u8>()),
+        vehicle_fia_flag in -1i8..=3,
+        ers in (any::<f32>(), 0u8..=3, any::<[f32; 3]>()),
+        damage: ([u8; 4], [u8; 4], [u8; 3], u8, [u8; 2]),
+        network_paused: u8,
+    ) -> CarStatus {
+        CarStatus {
+            assists,
+            fuel,
+            rpm,
+            max_gears,
+            drs,
+            tyres,
+            vehicle_fia_flag,
+            ers,
+            damage,
+            network_paused,
+        }
+    }
+}
+
+fn car_status_packet(header: &Header, cars: &[CarStatus], year: u16) -> Vec<u8> {
+    let mut writer = Writer::default();
+    header.write(&mut writer);
+    for car in cars {
+        let (traction_control, anti_lock_brakes, fuel_mix, bias, limiter) = car.assists;
+        writer
+            .u8(traction_control)
+            .u8(anti_lock_brakes)
+            .u8(fuel_mix)
+            .u8(bias)
+            .u8(limiter)
+            .f32(car.fuel[0])
+            .f32(car.fuel[1])
+            .f32(car.fuel[2])
+            .u16(car.rpm[0])
+            .u16(car.rpm[1])
+            .u8(car.max_gears)
+            .u8(car.drs.0)
+            .u16(car.drs.1);
+        let (tyres_wear, tyres_damage, wing_damage, drs_fault, engine_gearbox) = &car.damage;
+        if year == 2020 {
+            writer.bytes(tyres_wear);
+        }
+        writer.u8(car.tyres.0).u8(car.tyres.1).u8(car.tyres.2);
+        if year == 2020 {
+            writer
+                .bytes(tyres_damage)
+                .bytes(wing_damage)
+                .u8(*drs_fault)
+                .bytes(engine_gearbox);
+        }
+        writer.i8(car.vehicle_fia_flag);
+        let (stored_energy, deploy_mode, energies) = car.ers;
+        writer
+            .f32(stored_energy)
+            .u8(deploy_mode)
+            .f32(energies[0])
+            .f32(energies[1])
+            .f32(energies[2]);
+        if year == 2022 {
+            writer.u8(car.network_paused);
+        }
+    }
+    writer.0
+}
+
+macro_rules! assert_car_status {
+    ($year:ident, $decoded:expr, $car:expr) => {
+        let (decoded, car) = (&$decoded, &$car);
+        let (traction_control, anti_lock_brakes, fuel_mix, bias, limiter) = car.assists;
+        prop_assert_eq!(decoded.traction_control, traction_control);
+        prop_assert_eq!(decoded.anti_lock_brakes, anti_lock_brakes > 0);
+        prop_assert_eq!(decoded.fuel_mix as u8, fuel_mix);
+        prop_assert_eq!(decoded.front_brake_bias, bias);
+        prop_assert_eq!(decoded.pit_limiter_status, limiter > 0);
+        prop_assert_eq!(
+            [
+                decoded.fuel_in_tank,
+                decoded.fuel_capacity,
+                decoded.fuel_remaining_laps
+            ]
+            .map(f32::to_bits),
+            car.fuel.map(f32::to_bits)
+        );
+        prop_assert_eq!([decoded.max_rpm, decoded.idle_rpm], car.rpm);
+        prop_assert_eq!(decoded.max_gears, car.max_gears);
+        let distance = match decoded.drs_activation_distance {
+            $year::DRSActivationDistance::Distance(distance) => distance,
+            $year::DRSActivationDistance::NotAvailable => 0,
+        };
+        prop_assert_eq!(distance, car.drs.1);
+        prop_assert_eq!(
+            (
+                decoded.tyres_compound as u8,
+                decoded.tyres_visual as u8,
+                decoded.tyres_ages_lap
+            ),
+            car.tyres
+        );
+        prop_assert_eq!(decoded.vehicle_fia_flag as i8, car.vehicle_fia_flag);
+        let ers = &decoded.ers_data;
+        prop_assert_eq!(ers.stored_energy.to_bits(), car.ers.0.to_bits());
+        prop_assert_eq!(ers.deploy_mode as u8, car.ers.1);
+        prop_assert_eq!(
+            [
+                ers.harvested_this_lap_mguk,
+                ers.harvested_this_lap_mguh,
+                ers.deployed_this_lap
+            ]
+            .map(f32::to_bits),
+            car.ers.2.map(f32::to_bits)
+        );
+    };
+}
+
+// FINAL CLASSIFICATION
+
+#[derive(Debug, Clone)]
+struct Classification {
+    results: [u8; 5], // position, number_of_laps, grid_position, points, number_of_pit_stops
+    result_status: u8,
+    best_lap_time: u32, // milliseconds in 2022, the bits of the time in seconds in 2020
+    total_race_time: f64,
+    penalties: [u8; 2], // time, number
+    number_of_tyre_stints: u8,
+    tyre_stints_actual: [u8; 8],
+    tyre_stints_visual: [u8; 8],
+    tyre_stints_end_laps: [u8; 8], // 2022 only
+}
+
+prop_compose! {
+    fn classification()(
+        results: [u8; 5],
+        result_status in 0u8..=7,
+        best_lap_time: u32,
+        total_race_time: f64,
+        penalties: [u8; 2],
+        number_of_tyre_stints: u8,
+        tyre_stints_actual in prop::array::uniform8(7u8..=12),
+        tyre_stints_visual in prop::array::uniform8(prop::sample::select(TYRE_VISUALS)),
+        tyre_stints_end_laps: [u8; 8],
+    ) -> Classification {
+        Classification {
+            results,
+            result_status,
+            best_lap_time,
+            total_race_time,
+            penalties,
+            number_of_tyre_stints,
+            tyre_stints_actual,
+            tyre_stints_visual,
+            tyre_stints_end_laps,
+        }
+    }
+}
+
+fn final_classification_packet(
+    header: &Header,
+    number_of_cars: u8,
+    cars: &[Classification],
+    year: u16,
+) -> Vec<u8> {
+    let mut writer = Writer::default();
+    header.write(&mut writer);
+    writer.u8(number_of_cars);
+    for car in cars {
+        writer
+            .bytes(&car.results)
+            .u8(car.result_status)
+            .u32(car.best_lap_time)
+            .f64(car.total_race_time)
+            .bytes(&car.penalties)
+            .u8(car.number_of_tyre_stints)
+            .bytes(&car.tyre_stints_actual)
+            .bytes(&car.tyre_stints_visual);
+        if year == 2022 {
+            writer.bytes(&car.tyre_stints_end_laps);
+        }
+    }
+    writer.0
+}
+
+macro_rules! assert_classification {
+    ($decoded:expr, $car:expr) => {
+        let (decoded, car) = (&$decoded, &$car);
+        prop_assert_eq!(
+            [
+                decoded.position,
+                decoded.number_of_laps,
+                decoded.grid_position,
+                decoded.points,
+                decoded.number_of_pit_stops
+            ],
+            car.results
+        );
+        prop_assert_eq!(decoded.result_status as u8, car.result_status);
+        prop_assert_eq!(
+            decoded.total_race_time.to_bits(),
+            car.total_race_time.to_bits()
+        );
+        prop_assert_eq!(decoded.number_of_tyre_stints, car.number_of_tyre_stints);
+        prop_assert_eq!(
+            decoded.tyre_stints_actual.map(|tyre| tyre as u8),
+            car.tyre_stints_actual
+        );
+        prop_assert_eq!(
+            decoded.tyre_stints_visual.map(|tyre| tyre as u8),
+            car.tyre_stints_visual
+        );
+    };
+}
+
+fn lobby_info_packet(header: &Header, players: u8, cars: &[Participant], year: u16) -> Vec<u8> {
+    let mut writer = Writer::default();
+    header.write(&mut writer);
+    writer.u8(players);
+    for car in cars {
+        writer
+            .u8(car.ai_controlled)
+            .u8(car.team)
+            .u8(car.nationality)
+            .name(&car.name);
+        if year == 2022 {
+            writer.u8(car.race_number);
+        }
+        writer.u8(car.status);
+    }
+    writer.0
+}
+
+// SESSION HISTORY (2022, the packet was added by "F1 21")
+
+#[derive(Debug, Clone)]
+struct SessionHistory {
+    car_index: u8,
+    num_laps: u8,
+    num_tyre_stints: u8,
+    best_lap_numbers: [u8; 4], // lap time, sector 1, sector 2, sector 3
+    laps: Vec<(u32, [u16; 3], u8)>, // the 100 laps: time, sector times, valid flags
+    tyre_stints: Vec<(u8, u8, u8)>, // the 8 stints: end lap, actual and visual compound
+}
+
+prop_compose! {
+    fn session_history()(
+        car_index in 0..CARS as u8,
+        num_laps in 0u8..=100,
+        num_tyre_stints in 0u8..=8,
+        best_lap_numbers: [u8; 4],
+        laps in prop::collection::vec((any::<u32>(), any::<[u16; 3]>(), 0u8..=15), 100),
+        tyre_stints in prop::collection::vec(
+            (any::<u8>(), 7u8..=12, prop::sample::select(TYRE_VISUALS)),
+            8,
+        ),
+    ) -> SessionHistory {
+        SessionHistory {
+            car_index,
+            num_laps,
+            num_tyre_stints,
+            best_lap_numbers,
+            laps,
+            tyre_stints,
+        }
+    }
+}
+
+fn session_history_packet(header: &Header, history: &SessionHistory) -> Vec<u8> {
+    let mut writer = Writer::default();
+    header.write(&mut writer);
+    writer
+        .u8(history.car_index)
+        .u8(history.num_laps)
+        .u8(history.num_tyre_stints)
+        .bytes(&history.best_lap_numbers);
+    for (lap_time, sector_times, valid_flags) in &history.laps {
+        writer.u32(*lap_time);
+        for v in sector_times {
+            writer.u16(*v);
+        }
+        writer.u8(*valid_flags);
+    }
+    for (end_lap, actual, visual) in &history.tyre_stints {
+        writer.u8(*end_lap).u8(*actual).u8(*visual);
+    }
+    writer.0
+}
+
+proptest! {
+    #[test]
+    fn f1_2022_motion_round_trip(
+        header in header(2022, 0),
+        cars in cars(car_motion()),
+        extra in any::<[f32; 30]>(),
+    ) {
+        let packet = motion_packet(&header, &cars, &extra);
+        match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+            f1_2022::F1_2022::Motion(data) => {
+                assert_header!(data.header, header);
+                assert_motion!(data, cars, extra);
+            }
+            _ => prop_assert!(false, "expected a Motion packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2020_motion_round_trip(
+        header in header(2020, 0),
+        cars in cars(car_motion()),
+        extra in any::<[f32; 30]>(),
+    ) {
+        let packet = motion_packet(&header, &cars, &extra);
+        match f1_2020::F1_2020::from_packet(&packet).expect("packet should decode") {
+            f1_2020::F1_2020::Motion(data) => {
+                assert_header!(data.header, header);
+                assert_motion!(data, cars, extra);
+            }
+            _ => prop_assert!(false, "expected a Motion packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_car_telemetry_round_trip(
+        header in header(2022, 6),
+        cars in cars(car_telemetry()),
+        mfd_panel in 0u8..=4,
+        suggested_gear in 1i8..=8,
+    ) {
+        let mut writer = car_telemetry_packet(&header, &cars, 2022);
+        writer.u8(mfd_panel).u8(255).i8(suggested_gear);
+        match f1_2022::F1_2022::from_packet(&writer.0).expect("packet should decode") {
+            f1_2022::F1_2022::CarTelemetry(data) => {
+                assert_header!(data.header, header);
+                for (decoded, car) in data.car_telemetry_data.iter().zip(&cars) {
+                    assert_car_telemetry!(decoded, car);
+                    prop_assert_eq!(decoded.rev_lights_bit_value, car.rev_lights_bit_value);
+                }
+                prop_assert_eq!(data.mfd_panel as u8, mfd_panel);
+                prop_assert_eq!(data.mfd_panel_secondary_player, f1_2022::MFDPanel::Closed);
+                prop_assert_eq!(data.suggested_gear as i8, suggested_gear);
+            }
+            _ => prop_assert!(false, "expected a CarTelemetry packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2020_car_telemetry_round_trip(
+        header in header(2020, 6),
+        cars in cars(car_telemetry()),
+        button_status: u32,
+        suggested_gear in 1i8..=8,
+    ) {
+        let mut writer = car_telemetry_packet(&header, &cars, 2020);
+        writer.u32(button_status).u8(255).u8(255).i8(suggested_gear);
+        match f1_2020::F1_2020::from_packet(&writer.0).expect("packet should decode") {
+            f1_2020::F1_2020::CarTelemetry(data) => {
+                assert_header!(data.header, header);
+                for (decoded, car) in data.car_telemetry_data.iter().zip(&cars) {
+                    assert_car_telemetry!(decoded, car);
+                }
+                prop_assert_eq!(data.button_status.bits(), button_status);
+                prop_assert_eq!(data.suggested_gear as i8, suggested_gear);
+            }
+            _ => prop_assert!(false, "expected a CarTelemetry packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_car_damage_round_trip(
+        header in header(2022, 10),
+        cars in cars((prop::array::uniform4(0f32..100.0), any::<[u8; 26]>())),
+    ) {
+        let packet = car_damage_packet(&header, &cars);
+        match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+            f1_2022::F1_2022::CarDamage(data) => {
+                assert_header!(data.header, header);
+                for (decoded, (tyres_wear, car)) in data.car_damage_data.iter().zip(&cars) {
+                    let wear = &decoded.tyres_wear;
+                    prop_assert_eq!(
+                        [wear.rear_left, wear.rear_right, wear.front_left, wear.front_right],
+                        *tyres_wear
+                    );
+                    let wheels = |value: &cm_telemetry::f1::util::WheelValue<u8>| {
+                        [value.rear_left, value.rear_right, value.front_left, value.front_right]
+                    };
+                    let wings = &decoded.wing_damage;
+                    let decoded_bytes = [
+                        &wheels(&decoded.tyres_damage)[..],
+                        &wheels(&decoded.brakes_damage)[..],
+                        &[wings.front_left, wings.front_right, wings.rear],
+                        &[decoded.floor_damage, decoded.diffuser_damage, decoded.sidepod_damage],
+                    ]
+                    .concat();
+                    prop_assert_eq!(&decoded_bytes[..], &car[..14]);
+                    prop_assert_eq!(decoded.drs_fault, car[14] > 0);
+                    prop_assert_eq!(decoded.ers_fault, car[15] > 0);
+                    prop_assert_eq!(
+                        [
+                            decoded.gear_box_damage,
+                            decoded.engine_damage,
+                            decoded.engine_mguh_wear,
+                            decoded.engine_es_wear,
+                            decoded.engine_ce_wear,
+                            decoded.engine_ice_wear,
+                            decoded.engine_mguk_wear,
+                            decoded.engine_tc_wear,
+                        ],
+                        &car[16..24]
+                    );
+                    prop_assert_eq!(decoded.engine_blown, car[24] > 0);
+                    prop_assert_eq!(decoded.engine_seized, car[25] > 0);
+                }
+            }
+            _ => prop_assert!(false, "expected a CarDamage packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_session_round_trip(
+        header in header(2022, 1),
+        session in session(),
+        number_of_forecast_samples in 0u8..=56,
+        settings in session_settings(),
+    ) {
+        let mut session = session;
+        session.forecast.0 = number_of_forecast_samples;
+        let mut writer = session_packet(&header, &session, 2022);
+        writer
+            .u8(settings.forecast_accuracy)
+            .u8(settings.ai_difficulty)
+            .u32(settings.link_identifiers[0])
+            .u32(settings.link_identifiers[1])
+            .u32(settings.link_identifiers[2])
+            .bytes(&settings.pit_stop_window)
+            .u8(settings.assists[0])
+            .u8(settings.braking_assist)
+            .u8(settings.gearbox_assist)
+            .bytes(&settings.assists[1..])
+            .bytes(&settings.racing_line)
+            .u8(settings.game_mode)
+            .u8(settings.rule_set)
+            .u32(settings.time_of_day)
+            .u8(settings.session_length);
+        match f1_2022::F1_2022::from_packet(&writer.0).expect("packet should decode") {
+            f1_2022::F1_2022::Session(data) => {
+                assert_header!(data.header, header);
+                assert_session!(data, session);
+                prop_assert_eq!(data.marshal_zones.len(), session.marshal_zones.0 as usize);
+                prop_assert_eq!(
+                    data.weather_forecast_samples.len(),
+                    number_of_forecast_samples as usize
+                );
+                for (decoded, sample) in data.weather_forecast_samples.iter().zip(&session.forecast.1) {
+                    prop_assert_eq!(
+                        [
+                            decoded.track_temperature_change as i8,
+                            decoded.air_temperature_change as i8
+                        ],
+                        sample.changes
+                    );
+                    prop_assert_eq!(decoded.rain_percentage, sample.rain_percentage);
+                }
+                prop_assert_eq!(data.forecast_accuracy as u8, settings.forecast_accuracy);
+                prop_assert_eq!(data.ai_difficulty, settings.ai_difficulty);
+                prop_assert_eq!(
+                    [
+                        data.season_link_identifier,
+                        data.weekend_link_identifier,
+                        data.session_link_identifier
+                    ],
+                    settings.link_identifiers
+                );
+                prop_assert_eq!(
+                    [
+                        data.pit_stop_window_ideal_lap,
+                        data.pit_stop_window_latest_lap,
+                        data.pit_stop_rejoin_position
+                    ],
+                    settings.pit_stop_window
+                );
+                prop_assert_eq!(
+                    [
+                        data.steering_assist,
+                        data.pit_assist,
+                        data.pit_release_assist,
+                        data.ers_assist,
+                        data.drs_assist
+                    ],
+                    settings.assists.map(|assist| assist > 0)
+                );
+                prop_assert_eq!(data.braking_assist as u8, settings.braking_assist);
+                prop_assert_eq!(data.gearbox_assist as u8, settings.gearbox_assist);
+                prop_assert_eq!(
+                    [
+                        data.dynamic_racing_line as u8,
+                        data.dynamic_racing_line_type as u8
+                    ],
+                    settings.racing_line
+                );
+                prop_assert_eq!(data.game_mode as u8, settings.game_mode);
+                prop_assert_eq!(data.rule_set as u8, settings.rule_set);
+                prop_assert_eq!(data.time_of_day, settings.time_of_day);
+                prop_assert_eq!(data.session_length as u8, settings.session_length);
+            }
+            _ => prop_assert!(false, "expected a Session packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2020_session_round_trip(header in header(2020, 1), session in session()) {
+        let writer = session_packet(&header, &session, 2020);
+        match f1_2020::F1_2020::from_packet(&writer.0).expect("packet should decode") {
+            f1_2020::F1_2020::Session(data) => {
+                assert_header!(data.header, header);
+                assert_session!(data, session);
+            }
+            _ => prop_assert!(false, "expected a Session packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_lap_data_round_trip(
+        header in header(2022, 2),
+        cars in cars(lap_2022()),
+        time_trial_car_indices: [u8; 2],
+    ) {
+        let mut writer = Writer::default();
+        header.write(&mut writer);
+        for car in &cars {
+            let (pit_status, sector, driver_status, result_status) = car.statuses;
+            let counters = &car.counters;
+            writer
+                .u32(car.times[0])
+                .u32(car.times[1])
+                .u16(car.sector_times[0])
+                .u16(car.sector_times[1])
+                .f32(car.distances[0])
+                .f32(car.distances[1])
+                .f32(car.distances[2])
+                .bytes(&car.positions)
+                .u8(pit_status)
+                .u8(counters[0])
+                .u8(sector)
+                .bytes(&counters[1..])
+                .u8(driver_status)
+                .u8(result_status)
+                .u8(car.pit_lane_timer_active)
+                .u16(car.pit_timers[0])
+                .u16(car.pit_timers[1])
+                .u8(car.pit_stop_should_serve_penalty);
+        }
+        writer.bytes(&time_trial_car_indices);
+        match f1_2022::F1_2022::from_packet(&writer.0).expect("packet should decode") {
+            f1_2022::F1_2022::LapData(data) => {
+                assert_header!(data.header, header);
+                for (decoded, car) in data.laps.iter().zip(&cars) {
+                    prop_assert_eq!(
+                        [decoded.last_lap_time_ms, decoded.current_lap_time_ms],
+                        car.times
+                    );
+                    prop_assert_eq!(
+                        [decoded.sector_time_ms.0, decoded.sector_time_ms.1],
+                        car.sector_times
+                    );
+                    prop_assert_eq!(
+                        [
+                            decoded.lap_distance,
+                            decoded.total_distance,
+                            decoded.safety_car_delta
+                        ]
+                        .map(f32::to_bits),
+                        car.distances.map(f32::to_bits)
+                    );
+                    prop_assert_eq!(
+                        [decoded.car_position, decoded.current_lap_number],
+                        car.positions
+                    );
+                    prop_assert_eq!(
+                        (
+                            decoded.pit_status as u8,
+                            decoded.sector as u8,
+                            decoded.driver_status as u8,
+                            decoded.result_status as u8
+                        ),
+                        car.statuses
+                    );
+                    prop_assert_eq!(decoded.num_pit_stops, car.counters[0]);
+                    prop_assert_eq!(decoded.current_lap_invalid, car.counters[1] > 0);
+                    prop_assert_eq!(
+                        [
+                            decoded.penalties,
+                            decoded.warnings,
+                            decoded.num_unserved_drive_through_penalties,
+                            decoded.num_unserved_stop_go_penalties,
+                            decoded.grid_position
+                        ],
+                        &car.counters[2..]
+                    );
+                    prop_assert_eq!(decoded.pit_lane_timer_active, car.pit_lane_timer_active > 0);
+                    prop_assert_eq!(
+                        [decoded.pit_lane_time_in_lane_ms, decoded.pit_stop_timer_ms],
+                        car.pit_timers
+                    );
+                    prop_assert_eq!(
+                        decoded.pit_stop_should_serve_penalty,
+                        car.pit_stop_should_serve_penalty
+                    );
+                }
+                prop_assert_eq!(
+                    [data.time_trial_pb_car_idx.0, data.time_trial_rival_car_idx.0],
+                    time_trial_car_indices
+                );
+            }
+            _ => prop_assert!(false, "expected a LapData packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2020_lap_data_round_trip(header in header(2020, 2), cars in cars(lap_2020())) {
+        let mut writer = Writer::default();
+        header.write(&mut writer);
+        for car in &cars {
+            let (pit_status, sector, driver_status, result_status) = car.statuses;
+            writer
+                .f32(car.times[0])
+                .f32(car.times[1])
+                .u16(car.sector_times[0])
+                .u16(car.sector_times[1])
+                .f32(car.times[2])
+                .u8(car.best_lap_number);
+            for v in &car.best_lap_sector_times {
+                writer.u16(*v);
+            }
+            for (sector_time, lap_number) in &car.best_overall_sector_times {
+                writer.u16(*sector_time).u8(*lap_number);
+            }
+            writer
+                .f32(car.distances[0])
+                .f32(car.distances[1])
+                .f32(car.distances[2])
+                .bytes(&car.positions)
+                .u8(pit_status)
+                .u8(sector)
+                .u8(car.counters[0])
+                .u8(car.counters[1])
+                .u8(car.counters[2])
+                .u8(driver_status)
+                .u8(result_status);
+        }
+        match f1_2020::F1_2020::from_packet(&writer.0).expect("packet should decode") {
+            f1_2020::F1_2020::LapData(data) => {
+                assert_header!(data.header, header);
+                for (decoded, car) in data.laps.iter().zip(&cars) {
+                    prop_assert_eq!(
+                        [
+                            decoded.last_lap_time,
+                            decoded.current_lap_time,
+                            decoded.best_lap_time
+                        ]
+                        .map(f32::to_bits),
+                        car.times.map(f32::to_bits)
+                    );
+                    prop_assert_eq!(
+                        [decoded.sector_time_ms.0, decoded.sector_time_ms.1],
+                        car.sector_times
+                    );
+                    prop_assert_eq!(decoded.best_lap_number, car.best_lap_number);
+                    let best = decoded.best_lap_sector_time;
+                    prop_assert_eq!([best.0, best.1, best.2], car.best_lap_sector_times);
+                    let overall = &decoded.best_overall_sector_time;
+                    prop_assert_eq!(
+                        [&overall.0, &overall.1, &overall.2]
+                            .map(|sector| (sector.sector_time, sector.lap_number)),
+                        car.best_overall_sector_times
+                    );
+                    prop_assert_eq!(
+                        [
+                            decoded.lap_distance,
+                            decoded.total_distance,
+                            decoded.safety_car_delta
+                        ]
+                        .map(f32::to_bits),
+                        car.distances.map(f32::to_bits)
+                    );
+                    prop_assert_eq!(
+                        [decoded.car_position, decoded.current_lap_number],
+                        car.positions
+                    );
+                    prop_assert_eq!(
+                        (
+                            decoded.pit_status as u8,
+                            decoded.sector as u8,
+                            decoded.driver_status as u8,
+                            decoded.result_status as u8
+                        ),
+                        car.statuses
+                    );
+                    prop_assert_eq!(decoded.current_lap_invalid, car.counters[0] > 0);
+                    prop_assert_eq!(
+                        [decoded.penalties, decoded.grid_position],
+                        [car.counters[1], car.counters[2]]
+                    );
+                }
+            }
+            _ => prop_assert!(false, "expected a LapData packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_event_round_trip(
+        header in header(2022, 3),
+        car: u8,
+        other_car: u8,
+        value: f32,
+        fastest_speed: f32,
+        frame_identifier: u32,
+        penalty in penalty(),
+        speed_trap_flags in prop::array::uniform2(0u8..=1),
+        button_status: u32,
+    ) {
+        const SIZE: usize = 40;
+        let decode = |code: &[u8; 4], details: &Writer| {
+            let packet = event_packet(&header, code, details, SIZE);
+            match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+                f1_2022::F1_2022::Event(data) => data,
+                _ => panic!("expected an Event packet"),
+            }
+        };
+
+        let mut details = Writer::default();
+        details.u8(car).f32(value);
+        let event = decode(b"FTLP", &details);
+        assert_header!(event.header, header);
+        match event.event_data_details {
+            f1_2022::EventDataDetail::FastestLap(index, lap_time) => {
+                prop_assert_eq!(index.0, car);
+                prop_assert_eq!(lap_time.to_bits(), value.to_bits());
+            }
+            other => prop_assert!(false, "unexpected {:?}", other),
+        }
+
+        let mut details = Writer::default();
+        details.bytes(&penalty);
+        match decode(b"PENA", &details).event_data_details {
+            f1_2022::EventDataDetail::Penalty(decoded) => {
+                assert_penalty!(decoded, penalty);
+            }
+            other => prop_assert!(false, "unexpected {:?}", other),
+        }
+
+        let mut details = Writer::default();
+        details
+            .u8(car)
+            .f32(value)
+            .bytes(&speed_trap_flags)
+            .u8(other_car)
+            .f32(fastest_speed);
+        match decode(b"SPTP", &details).event_data_details {
+            f1_2022::EventDataDetail::SpeedTrap(decoded) => {
+                prop_assert_eq!(decoded.vehicle_index.0, car);
+                prop_assert_eq!(decoded.speed.to_bits(), value.to_bits());
+                prop_assert_eq!(
+                    [
+                        decoded.is_overall_fastest_in_session,
+                        decoded.is_driver_fastest_in_session
+                    ],
+                    speed_trap_flags.map(|flag| flag > 0)
+                );
+                prop_assert_eq!(decoded.fastest_vehicle_index_in_session.0, other_car);
+                prop_assert_eq!(
+                    decoded.fastest_speed_in_session.to_bits(),
+                    fastest_speed.to_bits()
+                );
+            }
+            other => prop_assert!(false, "unexpected {:?}", other),
+        }
+
+        let mut details = Writer::default();
+        details.u32(frame_identifier).f32(value);
+        match decode(b"FLBK", &details).event_data_details {
+            f1_2022::EventDataDetail::Flashback(frame, session_time) => {
+                prop_assert_eq!(frame, frame_identifier);
+                prop_assert_eq!(session_time.to_bits(), value.to_bits());
+            }
+            other => prop_assert!(false, "unexpected {:?}", other),
+        }
+
+        let button_status = button_status & ButtonFlags::all().bits();
+        let mut details = Writer::default();
+        details.u32(button_status);
+        match decode(b"BUTN", &details).event_data_details {
+            f1_2022::EventDataDetail::ButtonStatus(buttons) => {
+                prop_assert_eq!(buttons.bits(), button_status);
+            }
+            other => prop_assert!(false, "unexpected {:?}", other),
+        }
+    }
+
+    // OVTK and RDFL come from "F1 23", decoded when it is set to the "2022" UDP format
+
+    #[test]
+    fn f1_2022_overtake_event_round_trip(header in header(2022, 3), cars: [u8; 2]) {
+        let mut details = Writer::default();
+        details.bytes(&cars);
+        let packet = event_packet(&header, b"OVTK", &details, 40);
+        match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+            f1_2022::F1_2022::Event(data) => match data.event_data_details {
+                f1_2022::EventDataDetail::Overtake(overtaking, overtaken) => {
+                    prop_assert_eq!([overtaking.0, overtaken.0], cars);
+                }
+                other => prop_assert!(false, "unexpected {:?}", other),
+            },
+            _ => prop_assert!(false, "expected an Event packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_red_flag_event_round_trip(header in header(2022, 3)) {
+        let packet = event_packet(&header, b"RDFL", &Writer::default(), 40);
+        match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+            f1_2022::F1_2022::Event(data) => {
+                assert_header!(data.header, header);
+                prop_assert!(matches!(
+                    data.event_data_details,
+                    f1_2022::EventDataDetail::RedFlag
+                ));
+            }
+            _ => prop_assert!(false, "expected an Event packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_unknown_f1_24_event_round_trip(
+        header in header(2022, 3),
+        code in prop_oneof![Just(*b"COLL"), Just(*b"SCAR")],
+        payload: [u8; 2],
+    ) {
+        let mut details = Writer::default();
+        details.bytes(&payload);
+        let packet = event_packet(&header, &code, &details, 40);
+        match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+            f1_2022::F1_2022::Event(data) => match data.event_data_details {
+                f1_2022::EventDataDetail::Unknown(decoded_code, decoded_payload) => {
+                    prop_assert_eq!(decoded_code, code);
+                    prop_assert_eq!(&decoded_payload[..2], &payload[..]);
+                }
+                other => prop_assert!(false, "unexpected {:?}", other),
+            },
+            _ => prop_assert!(false, "expected an Event packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2020_event_round_trip(
+        header in header(2020, 3),
+        car: u8,
+        value: f32,
+        penalty in penalty(),
+    ) {
+        const SIZE: usize = 35;
+        let decode = |code: &[u8; 4], details: &Writer| {
+            let packet = event_packet(&header, code, details, SIZE);
+            match f1_2020::F1_2020::from_packet(&packet).expect("packet should decode") {
+                f1_2020::F1_2020::Event(data) => data,
+                _ => panic!("expected an Event packet"),
+            }
+        };
+
+        let mut details = Writer::default();
+        details.u8(car).f32(value);
+        let event = decode(b"FTLP", &details);
+        assert_header!(event.header, header);
+        match event.event_data_details {
+            f1_2020::EventDataDetail::FastestLap(index, lap_time) => {
+                prop_assert_eq!(index.0, car);
+                prop_assert_eq!(lap_time.to_bits(), value.to_bits());
+            }
+            other => prop_assert!(false, "unexpected {:?}", other),
+        }
+
+        let mut details = Writer::default();
+        details.bytes(&penalty);
+        match decode(b"PENA", &details).event_data_details {
+            f1_2020::EventDataDetail::Penalty(decoded) => {
+                assert_penalty!(decoded, penalty);
+            }
+            other => prop_assert!(false, "unexpected {:?}", other),
+        }
+
+        let mut details = Writer::default();
+        details.u8(car).f32(value);
+        match decode(b"SPTP", &details).event_data_details {
+            f1_2020::EventDataDetail::SpeedTrap(index, speed) => {
+                prop_assert_eq!(index.0, car);
+                prop_assert_eq!(speed.to_bits(), value.to_bits());
+            }
+            other => prop_assert!(false, "unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn f1_2022_participants_round_trip(
+        header in header(2022, 4),
+        num_active_cars in 0..=CARS as u8,
+        cars in cars(participant()),
+    ) {
+        let mut writer = Writer::default();
+        header.write(&mut writer);
+        writer.u8(num_active_cars);
+        for car in &cars {
+            writer
+                .u8(car.ai_controlled)
+                .u8(car.driver)
+                .u8(car.network_id)
+                .u8(car.team)
+                .u8(car.my_team)
+                .u8(car.race_number)
+                .u8(car.nationality)
+                .name(&car.name)
+                .u8(car.telemetry);
+        }
+        match f1_2022::F1_2022::from_packet(&writer.0).expect("packet should decode") {
+            f1_2022::F1_2022::Participants(data) => {
+                assert_header!(data.header, header);
+                prop_assert_eq!(data.num_active_cars, num_active_cars);
+                for (decoded, car) in data.participants_data.iter().zip(&cars) {
+                    prop_assert_eq!(decoded.ai_controlled, car.ai_controlled > 0);
+                    prop_assert_eq!(decoded.driver as u8, car.driver);
+                    prop_assert_eq!(decoded.network_id, car.network_id);
+                    prop_assert_eq!(decoded.team as u8, car.team);
+                    prop_assert_eq!(decoded.my_team, car.my_team == 1);
+                    prop_assert_eq!(decoded.race_number, car.race_number);
+                    prop_assert_eq!(decoded.nationality as u8, car.nationality);
+                    prop_assert_eq!(decoded.name.as_bytes(), &car.name[..]);
+                    prop_assert_eq!(decoded.your_telemetry_public, car.telemetry == 1);
+                }
+            }
+            _ => prop_assert!(false, "expected a Participants packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2020_participants_round_trip(
+        header in header(2020, 4),
+        num_active_cars in 0..=CARS as u8,
+        cars in cars(participant()),
+    ) {
+        let mut writer = Writer::default();
+        header.write(&mut writer);
+        writer.u8(num_active_cars);
+        for car in &cars {
+            writer
+                .u8(car.ai_controlled)
+                .u8(car.driver)
+                .u8(car.team)
+                .u8(car.race_number)
+                .u8(car.nationality)
+                .name(&car.name)
+                .u8(car.telemetry);
+        }
+        match f1_2020::F1_2020::from_packet(&writer.0).expect("packet should decode") {
+            f1_2020::F1_2020::Participants(data) => {
+                assert_header!(data.header, header);
+                prop_assert_eq!(data.num_active_cars, num_active_cars);
+                for (decoded, car) in data.participants_data.iter().zip(&cars) {
+                    prop_assert_eq!(decoded.ai_controlled, car.ai_controlled > 0);
+                    prop_assert_eq!(decoded.driver as u8, car.driver);
+                    prop_assert_eq!(decoded.team as u8, car.team);
+                    prop_assert_eq!(decoded.race_number, car.race_number);
+                    prop_assert_eq!(decoded.nationality as u8, car.nationality);
+                    prop_assert_eq!(decoded.name.as_bytes(), &car.name[..]);
+                    prop_assert_eq!(decoded.your_telemetry_restricted, car.telemetry == 0);
+                }
+            }
+            _ => prop_assert!(false, "expected a Participants packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_car_setup_round_trip(header in header(2022, 5), cars in cars(car_setup())) {
+        let packet = car_setup_packet(&header, &cars);
+        match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+            f1_2022::F1_2022::CarSetup(data) => {
+                assert_header!(data.header, header);
+                assert_car_setup!(data, cars);
+            }
+            _ => prop_assert!(false, "expected a CarSetup packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2020_car_setup_round_trip(header in header(2020, 5), cars in cars(car_setup())) {
+        let packet = car_setup_packet(&header, &cars);
+        match f1_2020::F1_2020::from_packet(&packet).expect("packet should decode") {
+            f1_2020::F1_2020::CarSetup(data) => {
+                assert_header!(data.header, header);
+                assert_car_setup!(data, cars);
+            }
+            _ => prop_assert!(false, "expected a CarSetup packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_car_status_round_trip(header in header(2022, 7), cars in cars(car_status())) {
+        let packet = car_status_packet(&header, &cars, 2022);
+        match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+            f1_2022::F1_2022::CarStatus(data) => {
+                assert_header!(data.header, header);
+                for (decoded, car) in data.car_status_data.iter().zip(&cars) {
+                    assert_car_status!(f1_2022, decoded, car);
+                    prop_assert_eq!(decoded.drs_allowed, car.drs.0 > 0);
+                    prop_assert_eq!(decoded.network_paused, car.network_paused);
+                }
+            }
+            _ => prop_assert!(false, "expected a CarStatus packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2020_car_status_round_trip(header in header(2020, 7), cars in cars(car_status())) {
+        let packet = car_status_packet(&header, &cars, 2020);
+        match f1_2020::F1_2020::from_packet(&packet).expect("packet should decode") {
+            f1_2020::F1_2020::CarStatus(data) => {
+                assert_header!(data.header, header);
+                for (decoded, car) in data.car_status_data.iter().zip(&cars) {
+                    assert_car_status!(f1_2020, decoded, car);
+                    prop_assert_eq!(decoded.drs_allowed as u8, car.drs.0);
+                    let (tyres_wear, tyres_damage, wing_damage, drs_fault, engine_gearbox) =
+                        &car.damage;
+                    let wheels = |value: &cm_telemetry::f1::util::WheelValue<u8>| {
+                        [value.rear_left, value.rear_right, value.front_left, value.front_right]
+                    };
+                    prop_assert_eq!(&wheels(&decoded.tyres_wear), tyres_wear);
+                    prop_assert_eq!(&wheels(&decoded.tyres_damage), tyres_damage);
+                    let wings = &decoded.wing_damage;
+                    prop_assert_eq!(
+                        &[wings.front_left, wings.front_right, wings.rear],
+                        wing_damage
+                    );
+                    prop_assert_eq!(decoded.drs_fault, *drs_fault > 0);
+                    prop_assert_eq!(
+                        &[decoded.engine_damage, decoded.gearbox_damage],
+                        engine_gearbox
+                    );
+                }
+            }
+            _ => prop_assert!(false, "expected a CarStatus packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_final_classification_round_trip(
+        header in header(2022, 8),
+        number_of_cars in 0..=CARS as u8,
+        cars in cars(classification()),
+    ) {
+        let packet = final_classification_packet(&header, number_of_cars, &cars, 2022);
+        match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+            f1_2022::F1_2022::FinalClassification(data) => {
+                assert_header!(data.header, header);
+                prop_assert_eq!(data.number_of_cars, number_of_cars);
+                for (decoded, car) in data.final_classification_data.iter().zip(&cars) {
+                    assert_classification!(decoded, car);
+                    prop_assert_eq!(decoded.best_lap_time_ms, car.best_lap_time);
+                    prop_assert_eq!(
+                        [decoded.penalties_time_s, decoded.number_of_penalties],
+                        car.penalties
+                    );
+                    prop_assert_eq!(decoded.tyre_stints_end_laps, car.tyre_stints_end_laps);
+                }
+            }
+            _ => prop_assert!(false, "expected a FinalClassification packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2020_final_classification_round_trip(
+        header in header(2020, 8),
+        number_of_cars in 0..=CARS as u8,
+        cars in cars(classification()),
+    ) {
+        let packet = final_classification_packet(&header, number_of_cars, &cars, 2020);
+        match f1_2020::F1_2020::from_packet(&packet).expect("packet should decode") {
+            f1_2020::F1_2020::FinalClassification(data) => {
+                assert_header!(data.header, header);
+                prop_assert_eq!(data.number_of_cars, number_of_cars);
+                for (decoded, car) in data.final_classification_data.iter().zip(&cars) {
+                    assert_classification!(decoded, car);
+                    prop_assert_eq!(decoded.best_lap_time.to_bits(), car.best_lap_time);
+                    prop_assert_eq!(
+                        [decoded.penalties_time, decoded.number_of_penalties],
+                        car.penalties
+                    );
+                }
+            }
+            _ => prop_assert!(false, "expected a FinalClassification packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_lobby_info_round_trip(
+        header in header(2022, 9),
+        number_of_players in 0..=CARS as u8,
+        cars in cars(participant()),
+    ) {
+        let packet = lobby_info_packet(&header, number_of_players, &cars, 2022);
+        match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+            f1_2022::F1_2022::LobbyInfo(data) => {
+                assert_header!(data.header, header);
+                prop_assert_eq!(data.number_of_players, number_of_players);
+                for (decoded, car) in data.lobby_players.iter().zip(&cars) {
+                    prop_assert_eq!(decoded.ai_controlled, car.ai_controlled > 0);
+                    prop_assert_eq!(decoded.team as u8, car.team);
+                    prop_assert_eq!(decoded.nationality as u8, car.nationality);
+                    prop_assert_eq!(decoded.name.as_bytes(), &car.name[..]);
+                    prop_assert_eq!(decoded.car_number, car.race_number);
+                    prop_assert_eq!(decoded.status as u8, car.status);
+                }
+            }
+            _ => prop_assert!(false, "expected a LobbyInfo packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2020_lobby_info_round_trip(
+        header in header(2020, 9),
+        number_of_players in 0..=CARS as u8,
+        cars in cars(participant()),
+    ) {
+        let packet = lobby_info_packet(&header, number_of_players, &cars, 2020);
+        match f1_2020::F1_2020::from_packet(&packet).expect("packet should decode") {
+            f1_2020::F1_2020::LobbyInfo(data) => {
+                assert_header!(data.header, header);
+                prop_assert_eq!(data.number_of_players, number_of_players);
+                for (decoded, car) in data.lobby_players.iter().zip(&cars) {
+                    prop_assert_eq!(decoded.ai_controlled, car.ai_controlled > 0);
+                    prop_assert_eq!(decoded.team as u8, car.team);
+                    prop_assert_eq!(decoded.nationality as u8, car.nationality);
+                    prop_assert_eq!(decoded.name.as_bytes(), &car.name[..]);
+                    prop_assert_eq!(decoded.status as u8, car.status);
+                }
+            }
+            _ => prop_assert!(false, "expected a LobbyInfo packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_session_history_round_trip(
+        header in header(2022, 11),
+        history in session_history(),
+    ) {
+        let packet = session_history_packet(&header, &history);
+        match f1_2022::F1_2022::from_packet(&packet).expect("packet should decode") {
+            f1_2022::F1_2022::SessionHistory(data) => {
+                assert_header!(data.header, header);
+                prop_assert_eq!(data.car_index.0, history.car_index);
+                prop_assert_eq!(data.num_laps, history.num_laps);
+                prop_assert_eq!(data.num_tyre_stints, history.num_tyre_stints);
+                prop_assert_eq!(
+                    [
+                        data.best_lap_time_lap_num,
+                        data.best_sector1_lap_num,
+                        data.best_sector2_lap_num,
+                        data.best_sector3_lap_num
+                    ],
+                    history.best_lap_numbers
+                );
+                prop_assert_eq!(data.lap_history_data.len(), history.num_laps as usize);
+                for (decoded, (lap_time, sector_times, valid_flags)) in
+                    data.lap_history_data.iter().zip(&history.laps)
+                {
+                    prop_assert_eq!(decoded.lap_time_ms, *lap_time);
+                    let sectors = decoded.sector_times_ms;
+                    prop_assert_eq!([sectors.0, sectors.1, sectors.2], *sector_times);
+                    prop_assert_eq!(decoded.lap_valid_bit_flags.bits(), *valid_flags);
+                }
+                prop_assert_eq!(
+                    data.tyre_stints_history_data.len(),
+                    history.num_tyre_stints as usize
+                );
+                for (decoded, stint) in data.tyre_stints_history_data.iter().zip(&history.tyre_stints) {
+                    prop_assert_eq!(
+                        (
+                            decoded.end_lap,
+                            decoded.tyre_actual_compound as u8,
+                            decoded.tyre_visual_compound as u8
+                        ),
+                        *stint
+                    );
+                }
+            }
+            _ => prop_assert!(false, "expected a SessionHistory packet"),
+        }
+    }
+
+    #[test]
+    fn f1_2022_player_car_telemetry_round_trip(
+        header in header(2022, 6),
+        cars in cars(car_telemetry()),
+        suggested_gear in 1i8..=8,
+    ) {
+        let mut writer = car_telemetry_packet(&header, &cars, 2022);
+        writer.u8(255).u8(255).i8(suggested_gear);
+        match player::F1_2022Player::from_packet(&writer.0).expect("packet should decode") {
+            player::F1_2022Player::CarTelemetry(data) => {
+                assert_header!(data.header, header);
+                let car = &cars[header.player_car_index as usize];
+                assert_car_telemetry!(data.car_telemetry_data, car);
+                prop_assert_eq!(data.mfd_panel, f1_2022::MFDPanel::Closed);
+                prop_assert_eq!(data.suggested_gear as i8, suggested_gear);
+            }
+            _ => prop_assert!(false, "expected a CarTelemetry packet"),
+        }
+    }
+
+    #[test]
+    fn dirt_rally2_wheels_round_trip(
+        // suspension position, suspension velocity, wheel velocity and brake temperature,
+        // each sent rear left, rear right, front left then front right
+        values in prop::array::uniform4(prop::array::uniform4(-1000f32..1000f32)),
+    ) {
+        let mut packet = vec![0; 264];
+        for (offset, wheels) in [68, 84, 100, 204].iter().zip(&values) {
+            for (index, value) in wheels.iter().enumerate() {
+                let offset = offset + index * 4;
+                packet[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        let event = rally2::DirtRally2::from_packet(&packet).expect("packet should decode");
+        let wheels = &event.car.wheels;
+        for (index, wheel) in [
+            &wheels.rear_left,
+            &wheels.rear_right,
+            &wheels.front_left,
+            &wheels.front_right,
+        ]
+        .iter()
+        .enumerate()
+        {
+            prop_assert_eq!(wheel.suspension_position, values[0][index]);
+            prop_assert_eq!(wheel.suspension_velocity, values[1][index]);
+            prop_assert_eq!(wheel.wheel_velocity, values[2][index]);
+            prop_assert_eq!(wheel.brake_temperature, values[3][index]);
+        }
+    }
+}